@@ -18,6 +18,11 @@ define_error! {
             [ TraceError<JoinError> ]
             |e| { format_args!("Error when joining task: {}", e.task) },
 
+        IoError
+            { path: String }
+            [ TraceError<std::io::Error> ]
+            |e| { format_args!("I/O error when accessing: {}", e.path) },
+
         JsonParseError
             { chain_name: String }
             [ TraceError <serde_json::Error> ]
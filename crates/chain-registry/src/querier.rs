@@ -1,23 +1,38 @@
 //! Contains traits to query nodes of a given chain from their APIs.
 //! Contains struct to perform a health check on a gRPC/WebSocket endpoint and
 //! to retrieve the `max_block_size` from a RPC endpoint.
+//! Endpoints can be probed all at once, and ranked from the best to the worst.
 
 use std::fmt::Debug;
 use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use futures::{stream::FuturesUnordered, StreamExt};
 use http::Uri;
+use tendermint::node::info::TxIndexStatus;
 use tendermint_rpc::HttpClient;
 use tendermint_rpc::HttpClientUrl;
-use tracing::{debug, info};
+use tendermint_rpc::{SubscriptionClient, WebSocketClient};
+use tokio::time::timeout;
+use tracing::{debug, info, warn};
 
 use ibc_proto::cosmos::bank::v1beta1::query_client::QueryClient;
+use ibc_relayer::config::compat_mode::CompatMode;
 use ibc_relayer::util::create_grpc_client;
 use ibc_relayer::HERMES_VERSION;
 use tendermint_rpc::{Client, Url};
 
 use crate::error::RegistryError;
+use crate::formatter::{SimpleWebSocketFormatter, UriFormatter};
+
+/// Time after which an endpoint which did not answer is considered unhealthy when probing
+/// all the endpoints of a chain.
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of blocks a node can be behind the highest of the probed nodes without being
+/// considered as lagging.
+pub const MAX_HEIGHT_LAG: u64 = 5;
 
 /// `QueryTypes` represents the basic types required to query a node
 pub trait QueryTypes {
@@ -70,12 +85,78 @@ pub trait QueryContext: QueryTypes {
 
         Err(Self::query_error(chain_name))
     }
+
+    /// Order the outputs of the endpoints which answered from the best to the worst endpoint.
+    /// Keeps the order in which the endpoints answered by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `outputs` - The outputs of the endpoints which answered
+    fn rank(_outputs: &mut [Self::QueryOutput]) {}
+
+    /// Query every endpoint from a list of urls concurrently and return the outputs of all
+    /// the endpoints which answered within `PROBE_TIMEOUT`, ranked from the best to the worst.
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_name` - A string that holds the name of a chain
+    /// * `urls` - A vector of urls to query
+    async fn query_ranked(
+        chain_name: String,
+        urls: Vec<Self::QueryInput>,
+    ) -> Result<Vec<Self::QueryOutput>, Self::QueryError>
+    where
+        Self::QueryOutput: Send,
+    {
+        info!("Probing {} endpoints for chain {chain_name}", urls.len());
+        debug!("Probing the following endpoints: {urls:?}");
+
+        let futures: FuturesUnordered<_> = urls
+            .into_iter()
+            .map(|url| async move {
+                let description = format!("{url:?}");
+
+                match timeout(PROBE_TIMEOUT, Self::query(url)).await {
+                    Ok(Ok(output)) => Some(output),
+                    Ok(Err(_)) => {
+                        debug!("endpoint {description} failed to answer");
+                        None
+                    }
+                    Err(_) => {
+                        debug!("endpoint {description} did not answer within {PROBE_TIMEOUT:?}");
+                        None
+                    }
+                }
+            })
+            .collect();
+
+        let mut outputs: Vec<_> = futures
+            .filter_map(|output| async { output })
+            .collect()
+            .await;
+
+        if outputs.is_empty() {
+            return Err(Self::query_error(chain_name));
+        }
+
+        Self::rank(&mut outputs);
+
+        Ok(outputs)
+    }
 }
 
 // ----------------- RPC ------------------
 
 /// `SimpleHermesRpcQuerier` retrieves `HermesConfigData` by querying a list of RPC endpoints
 /// through their RPC API and returns the result of the first endpoint to answer.
+/// When probing all the endpoints, they are ranked by:
+///
+/// 1. whether the CometBFT version of the node is supported by Hermes,
+/// 2. whether transaction indexing is enabled,
+/// 3. whether the node is in sync with the other nodes,
+/// 4. whether the node is an archive node,
+/// 5. whether the WebSocket endpoint of the node is reachable,
+/// 6. the latency of the node.
 pub struct SimpleHermesRpcQuerier;
 
 /// Data which must be retrieved from RPC endpoints for Hermes
@@ -85,6 +166,26 @@ pub struct HermesConfigData {
     pub max_block_size: u64,
     // max_block_time should also be retrieved from the RPC
     // however it looks like it is not in the genesis file anymore
+    /// Time taken by the node to answer the status query
+    pub latency: Duration,
+    pub latest_height: u64,
+    pub earliest_height: u64,
+    pub catching_up: bool,
+    pub tx_indexing: bool,
+    /// The CometBFT version of the node
+    pub version: String,
+    /// The compatibility mode matching the version of the node, if it is supported
+    pub compat_mode: Option<CompatMode>,
+    /// Time taken to open a WebSocket connection to the node, if it could be opened
+    pub websocket_latency: Option<Duration>,
+}
+
+impl HermesConfigData {
+    /// Whether the node stores every block since height 1.
+    /// Nodes of chains restarted from a later height are never reported as archive nodes.
+    pub fn is_archive(&self) -> bool {
+        self.earliest_height <= 1
+    }
 }
 
 /// Expected Input, Output and Error to query an RPC endpoint
@@ -113,6 +214,13 @@ impl QueryContext for SimpleHermesRpcQuerier {
             .build()
             .map_err(|e| RegistryError::rpc_connect_error(rpc_url.clone(), e))?;
 
+        let start = Instant::now();
+        let status = client
+            .status()
+            .await
+            .map_err(|e| RegistryError::rpc_status_error(rpc_url.clone(), e))?;
+        let latency = start.elapsed();
+
         let latest_consensus_params = match client.latest_consensus_params().await {
             Ok(response) => response.consensus_params.block.max_bytes,
             Err(e) => {
@@ -123,24 +231,105 @@ impl QueryContext for SimpleHermesRpcQuerier {
             }
         };
 
+        let websocket_latency = match websocket_latency(&rpc_url).await {
+            Ok(latency) => Some(latency),
+            Err(e) => {
+                debug!("WebSocket endpoint of {rpc_url} is unreachable: {e}");
+                None
+            }
+        };
+
         Ok(HermesConfigData {
             rpc_address: Url::from_str(&rpc_url)
                 .map_err(|e| RegistryError::tendermint_url_parse_error(rpc_url, e))?,
             max_block_size: latest_consensus_params,
+            latency,
+            latest_height: status.sync_info.latest_block_height.value(),
+            earliest_height: status.sync_info.earliest_block_height.value(),
+            catching_up: status.sync_info.catching_up,
+            tx_indexing: status.node_info.other.tx_index == TxIndexStatus::On,
+            version: status.node_info.version.to_string(),
+            compat_mode: CompatMode::from_version(status.node_info.version).ok(),
+            websocket_latency,
         })
     }
+
+    fn rank(outputs: &mut [HermesConfigData]) {
+        let highest = outputs
+            .iter()
+            .map(|data| data.latest_height)
+            .max()
+            .unwrap_or_default();
+
+        outputs.sort_by_key(|data| {
+            let lagging =
+                data.catching_up || data.latest_height.saturating_add(MAX_HEIGHT_LAG) < highest;
+
+            (
+                data.compat_mode.is_none(),
+                !data.tx_indexing,
+                lagging,
+                !data.is_archive(),
+                data.websocket_latency.is_none(),
+                data.latency,
+            )
+        });
+
+        for data in outputs.iter() {
+            if data.compat_mode.is_none() {
+                warn!(
+                    "RPC endpoint {} runs unsupported CometBFT version {}",
+                    data.rpc_address, data.version
+                );
+            }
+        }
+    }
+}
+
+/// Time taken to open a WebSocket connection to the node at the given RPC URL.
+async fn websocket_latency(rpc_url: &str) -> Result<Duration, RegistryError> {
+    let url = SimpleWebSocketFormatter::parse_or_build_address(rpc_url)?;
+
+    let start = Instant::now();
+    let (client, driver) = timeout(PROBE_TIMEOUT, WebSocketClient::new(url.clone()))
+        .await
+        .map_err(|e| RegistryError::websocket_time_out_error(url.to_string(), e))?
+        .map_err(|e| RegistryError::websocket_connect_error(url.to_string(), e))?;
+    let latency = start.elapsed();
+
+    let driver_handle = tokio::spawn(driver.run());
+
+    client
+        .close()
+        .map_err(|e| RegistryError::websocket_conn_close_error(url.to_string(), e))?;
+
+    driver_handle
+        .await
+        .map_err(|e| RegistryError::join_error("websocket_driver".to_string(), e))?
+        .map_err(|e| RegistryError::websocket_driver_error(url.to_string(), e))?;
+
+    Ok(latency)
 }
 
 // ----------------- GRPC ------------------
 
 /// `GrpcHealthCheckQuerier` connects to a list of gRPC endpoints
 /// and returns the URL of the first one to answer.
+/// When probing all the endpoints, they are ranked by latency.
 pub struct GrpcHealthCheckQuerier;
 
+/// Data retrieved when connecting to a gRPC endpoint
+#[derive(Clone, Debug)]
+pub struct GrpcEndpointData {
+    pub grpc_address: Url,
+    /// Time taken to connect to the endpoint
+    pub latency: Duration,
+}
+
 /// Expected Input and Output to query a GRPC endpoint
 impl QueryTypes for GrpcHealthCheckQuerier {
     type QueryInput = Uri;
-    type QueryOutput = Url;
+    type QueryOutput = GrpcEndpointData;
     type QueryError = RegistryError;
 }
 
@@ -160,10 +349,104 @@ impl QueryContext for GrpcHealthCheckQuerier {
 
         info!("Querying gRPC server at {tendermint_url}");
 
+        let start = Instant::now();
         create_grpc_client(&uri, QueryClient::new)
             .await
             .map_err(|_| RegistryError::unable_to_connect_with_grpc())?;
 
-        Ok(tendermint_url)
+        Ok(GrpcEndpointData {
+            grpc_address: tendermint_url,
+            latency: start.elapsed(),
+        })
+    }
+
+    fn rank(outputs: &mut [GrpcEndpointData]) {
+        outputs.sort_by_key(|data| data.latency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_data(address: &str, latency_ms: u64) -> HermesConfigData {
+        HermesConfigData {
+            rpc_address: Url::from_str(address).unwrap(),
+            max_block_size: 22020096,
+            latency: Duration::from_millis(latency_ms),
+            latest_height: 1000,
+            earliest_height: 1,
+            catching_up: false,
+            tx_indexing: true,
+            version: "0.37.2".to_string(),
+            compat_mode: Some(CompatMode::V0_37),
+            websocket_latency: Some(Duration::from_millis(latency_ms)),
+        }
+    }
+
+    fn ranked(mut outputs: Vec<HermesConfigData>) -> Vec<String> {
+        SimpleHermesRpcQuerier::rank(&mut outputs);
+        outputs
+            .into_iter()
+            .map(|data| data.rpc_address.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rank_rpc_by_latency() {
+        let outputs = vec![
+            rpc_data("http://slow:26657", 300),
+            rpc_data("http://fast:26657", 50),
+            rpc_data("http://medium:26657", 100),
+        ];
+
+        assert_eq!(
+            ranked(outputs),
+            [
+                "http://fast:26657/",
+                "http://medium:26657/",
+                "http://slow:26657/"
+            ]
+        );
+    }
+
+    #[test]
+    fn rank_rpc_by_capabilities() {
+        let unsupported = HermesConfigData {
+            version: "0.33.0".to_string(),
+            compat_mode: None,
+            ..rpc_data("http://unsupported:26657", 10)
+        };
+        let no_index = HermesConfigData {
+            tx_indexing: false,
+            ..rpc_data("http://no-index:26657", 20)
+        };
+        let lagging = HermesConfigData {
+            latest_height: 1000 - MAX_HEIGHT_LAG - 1,
+            ..rpc_data("http://lagging:26657", 30)
+        };
+        let pruned = HermesConfigData {
+            earliest_height: 900,
+            ..rpc_data("http://pruned:26657", 40)
+        };
+        let no_websocket = HermesConfigData {
+            websocket_latency: None,
+            ..rpc_data("http://no-websocket:26657", 50)
+        };
+        let best = rpc_data("http://best:26657", 60);
+
+        let outputs = vec![unsupported, no_index, lagging, pruned, no_websocket, best];
+
+        assert_eq!(
+            ranked(outputs),
+            [
+                "http://best:26657/",
+                "http://no-websocket:26657/",
+                "http://pruned:26657/",
+                "http://lagging:26657/",
+                "http://no-index:26657/",
+                "http://unsupported:26657/",
+            ]
+        );
     }
 }
@@ -0,0 +1,386 @@
+//! Contains the sources from which the data of the chain registry can be read
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use flate2::read::GzDecoder;
+use tokio::sync::OnceCell;
+use tracing::{debug, info, warn};
+
+use crate::constants::{DEFAULT_REF, HOST, PROTOCOL, REGISTRY_PATH};
+use crate::error::RegistryError;
+
+/// Where the files of the chain registry are located.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum RegistryLocation {
+    /// The GitHub repository of the chain registry
+    #[default]
+    GitHub,
+    /// A mirror of the GitHub raw content server, serving the file at `<path>` of
+    /// the repository at `<ref>` under `<url>/<ref>/<path>`
+    Mirror(String),
+    /// A local clone of the repository, the requested commit is ignored
+    Directory(PathBuf),
+    /// A `.tar` or `.tar.gz` archive of the repository, such as the ones produced by GitHub.
+    /// The requested commit is ignored.
+    Tarball(PathBuf),
+}
+
+/// A source of chain registry data, optionally backed by an on-disk cache.
+///
+/// Files fetched from a remote location at a given commit never change, so they are
+/// stored in the cache directory under `<cache_dir>/<commit>/<path>` and read from there
+/// afterwards. Files fetched at the default branch, or at any other ref which is not a
+/// full commit hash, such as a branch or a tag, are never cached. Tarballs are unpacked
+/// once per modification, under `<cache_dir>/tarballs/`, or in the temporary directory if
+/// no cache is set.
+#[derive(Clone, Debug, Default)]
+pub struct RegistrySource {
+    location: RegistryLocation,
+    cache_dir: Option<PathBuf>,
+    /// The root of the unpacked tarball, shared between clones
+    unpacked: Arc<OnceCell<PathBuf>>,
+}
+
+impl RegistrySource {
+    pub fn new(location: RegistryLocation) -> Self {
+        Self {
+            location,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: PathBuf) -> Self {
+        self.cache_dir = Some(cache_dir);
+        self
+    }
+
+    pub fn location(&self) -> &RegistryLocation {
+        &self.location
+    }
+
+    /// Reads the file at the given path of the chain registry, at the given commit
+    /// or at the default branch if unset.
+    pub async fn read(&self, path: &Path, commit: Option<String>) -> Result<String, RegistryError> {
+        match &self.location {
+            RegistryLocation::GitHub => {
+                let base = format!("{PROTOCOL}://{HOST}{REGISTRY_PATH}");
+                self.read_remote(&base, path, commit).await
+            }
+            RegistryLocation::Mirror(url) => {
+                self.read_remote(url.trim_end_matches('/'), path, commit)
+                    .await
+            }
+            RegistryLocation::Directory(dir) => {
+                if let Some(commit) = commit {
+                    debug!(
+                        "ignoring commit {commit}, reading {} from local directory {}",
+                        path.display(),
+                        dir.display()
+                    );
+                }
+                read_file(&dir.join(path)).await
+            }
+            RegistryLocation::Tarball(tarball) => {
+                let root = self
+                    .unpacked
+                    .get_or_try_init(|| unpack(tarball.clone(), self.cache_dir.clone()))
+                    .await?;
+                read_file(&root.join(path)).await
+            }
+        }
+    }
+
+    async fn read_remote(
+        &self,
+        base: &str,
+        path: &Path,
+        commit: Option<String>,
+    ) -> Result<String, RegistryError> {
+        // Branches and tags move, only the files at a given commit can be cached
+        let cached = self
+            .cache_dir
+            .as_ref()
+            .zip(commit.as_ref().filter(|commit| is_commit_hash(commit)))
+            .map(|(cache_dir, commit)| cache_dir.join(commit).join(path));
+
+        if let Some(cached) = &cached {
+            if cached.exists() {
+                debug!("reading {} from cache", cached.display());
+                return read_file(cached).await;
+            }
+        }
+
+        let url = format!(
+            "{base}/{}/{}",
+            commit.as_deref().unwrap_or(DEFAULT_REF),
+            path.to_str()
+                .ok_or_else(|| RegistryError::path_error(path.to_path_buf()))?,
+        );
+        url.parse::<http::Uri>()
+            .map_err(|e| RegistryError::uri_parse_error(url.clone(), e))?;
+
+        let response = reqwest::get(&url)
+            .await
+            .map_err(|e| RegistryError::request_error(url.clone(), e))?;
+
+        if !response.status().is_success() {
+            return Err(RegistryError::status_error(url, response.status().as_u16()));
+        }
+
+        let body = response
+            .text()
+            .await
+            .map_err(|e| RegistryError::request_error(url, e))?;
+
+        if let Some(cached) = cached {
+            // Failing to populate the cache only costs a new request next time
+            if let Err(e) = write_file(&cached, &body).await {
+                warn!("failed to cache {}: {e}", cached.display());
+            }
+        }
+
+        Ok(body)
+    }
+}
+
+/// Whether the given ref is a full commit hash, rather than a branch, a tag or an abbreviated hash.
+fn is_commit_hash(git_ref: &str) -> bool {
+    git_ref.len() == 40 && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+async fn read_file(path: &Path) -> Result<String, RegistryError> {
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|e| RegistryError::io_error(path.display().to_string(), e))
+}
+
+async fn write_file(path: &Path, contents: &str) -> Result<(), RegistryError> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| RegistryError::io_error(parent.display().to_string(), e))?;
+    }
+
+    tokio::fs::write(path, contents)
+        .await
+        .map_err(|e| RegistryError::io_error(path.display().to_string(), e))
+}
+
+/// Unpacks the JSON files of the given tarball and returns the root of the repository.
+async fn unpack(tarball: PathBuf, cache_dir: Option<PathBuf>) -> Result<PathBuf, RegistryError> {
+    tokio::task::spawn_blocking(move || unpack_blocking(&tarball, cache_dir))
+        .await
+        .map_err(|e| RegistryError::join_error("unpack_tarball".to_string(), e))?
+}
+
+fn unpack_blocking(tarball: &Path, cache_dir: Option<PathBuf>) -> Result<PathBuf, RegistryError> {
+    let io_error = |path: &Path| {
+        let path = path.display().to_string();
+        move |e| RegistryError::io_error(path, e)
+    };
+
+    let name = tarball
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| RegistryError::path_error(tarball.to_path_buf()))?;
+
+    // Key the unpacked files by modification time, so that an updated tarball is unpacked again
+    let modified = fs::metadata(tarball)
+        .and_then(|metadata| metadata.modified())
+        .map_err(io_error(tarball))?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let target = cache_dir
+        .unwrap_or_else(|| std::env::temp_dir().join("hermes-chain-registry"))
+        .join("tarballs")
+        .join(format!("{name}-{modified}"));
+
+    // Only written once the tarball is fully unpacked
+    let marker = target.join(".unpacked");
+
+    if marker.exists() {
+        debug!("using tarball already unpacked in {}", target.display());
+    } else {
+        info!("unpacking {} into {}", tarball.display(), target.display());
+
+        let file = File::open(tarball).map_err(io_error(tarball))?;
+        let reader: Box<dyn Read> = match tarball.extension().and_then(|ext| ext.to_str()) {
+            Some("gz" | "tgz") => Box::new(GzDecoder::new(file)),
+            _ => Box::new(file),
+        };
+
+        fs::create_dir_all(&target).map_err(io_error(&target))?;
+
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries().map_err(io_error(tarball))? {
+            let mut entry = entry.map_err(io_error(tarball))?;
+
+            let is_json = entry
+                .path()
+                .map_err(io_error(tarball))?
+                .extension()
+                .is_some_and(|ext| ext == "json");
+
+            if is_json {
+                entry.unpack_in(&target).map_err(io_error(&target))?;
+            }
+        }
+
+        fs::write(&marker, "").map_err(io_error(&marker))?;
+    }
+
+    repository_root(&target)
+}
+
+/// Archives usually contain the repository in a single top-level directory,
+/// such as `chain-registry-<ref>/` for the archives produced by GitHub.
+fn repository_root(target: &Path) -> Result<PathBuf, RegistryError> {
+    let io_error = |e| RegistryError::io_error(target.display().to_string(), e);
+
+    let directories = fs::read_dir(target)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?
+        .into_iter()
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+
+    match directories.as_slice() {
+        [single] if single.file_name() != Some(OsStr::new("_IBC")) => Ok(single.clone()),
+        _ => Ok(target.to_path_buf()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn read_from_directory() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().to_path_buf();
+        fs::create_dir_all(dir.join("osmosis")).unwrap();
+        fs::write(dir.join("osmosis").join("chain.json"), "{}").unwrap();
+
+        let source = RegistrySource::new(RegistryLocation::Directory(dir.clone()));
+        let body = source
+            .read(
+                &PathBuf::from("osmosis/chain.json"),
+                Some("abc".to_string()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(body, "{}");
+        assert!(source
+            .read(&PathBuf::from("cosmoshub/chain.json"), None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn read_from_tarball() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().to_path_buf();
+        let tarball = dir.join("chain-registry.tar.gz");
+
+        let file = File::create(&tarball).unwrap();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            file,
+            flate2::Compression::default(),
+        ));
+        for (path, contents) in [
+            (
+                "chain-registry-master/osmosis/chain.json",
+                "{\"chain_name\":\"osmosis\"}",
+            ),
+            ("chain-registry-master/osmosis/images/logo.png", "png"),
+            ("chain-registry-master/_IBC/cosmoshub-osmosis.json", "{}"),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, path, contents.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let cache_dir = dir.join("cache");
+        let source = RegistrySource::new(RegistryLocation::Tarball(tarball))
+            .with_cache_dir(cache_dir.clone());
+
+        let body = source
+            .read(&PathBuf::from("osmosis/chain.json"), None)
+            .await
+            .unwrap();
+        assert_eq!(body, "{\"chain_name\":\"osmosis\"}");
+
+        let body = source
+            .read(
+                &["_IBC", "cosmoshub-osmosis.json"]
+                    .iter()
+                    .collect::<PathBuf>(),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(body, "{}");
+
+        // Only the JSON files are unpacked
+        let unpacked = fs::read_dir(cache_dir.join("tarballs"))
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path()
+            .join("chain-registry-master");
+        assert!(unpacked.join("osmosis").join("chain.json").exists());
+        assert!(!unpacked.join("osmosis").join("images").exists());
+    }
+
+    #[test]
+    fn commit_hashes() {
+        assert!(is_commit_hash("dbd5c4b2d47d4fdd9ac0c0fb52bfe4e14bb7b4f1"));
+        assert!(!is_commit_hash("dbd5c4b"));
+        assert!(!is_commit_hash("master"));
+        assert!(!is_commit_hash("v1.0.0"));
+        assert!(!is_commit_hash("refs/heads/dbd5c4b2d47d4fdd9ac0c0fb52bfe4"));
+    }
+
+    #[tokio::test]
+    async fn read_remote_from_cache() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path().to_path_buf();
+        let commit = "dbd5c4b2d47d4fdd9ac0c0fb52bfe4e14bb7b4f1";
+
+        for git_ref in [commit, "master"] {
+            let cached = dir.join(git_ref).join("osmosis").join("chain.json");
+            fs::create_dir_all(cached.parent().unwrap()).unwrap();
+            fs::write(cached, "{}").unwrap();
+        }
+
+        // Nothing listens on the mirror, so that reading from it fails
+        let source =
+            RegistrySource::new(RegistryLocation::Mirror("http://127.0.0.1:1".to_string()))
+                .with_cache_dir(dir.clone());
+        let path = PathBuf::from("osmosis/chain.json");
+
+        let body = source.read(&path, Some(commit.to_string())).await.unwrap();
+        assert_eq!(body, "{}");
+
+        // A branch is never read from the cache
+        assert!(source
+            .read(&path, Some("master".to_string()))
+            .await
+            .is_err());
+    }
+}
@@ -1,6 +1,9 @@
 //! Definition of the application, based on the Abscissa framework
 
-use std::{path::PathBuf, thread};
+use std::{
+    path::{Path, PathBuf},
+    thread,
+};
 
 use abscissa_core::{
     application::{self, AppCell},
@@ -116,6 +119,13 @@ impl Application for CliApp {
         &self.state
     }
 
+    /// Load the configuration from the given path, resolving its
+    /// environment variables and includes.
+    fn load_config(&mut self, path: &Path) -> Result<Config, FrameworkError> {
+        ibc_relayer::config::load(path)
+            .map_err(|e| FrameworkErrorKind::ConfigError.context(e).into())
+    }
+
     /// Register all components used by this application.
     ///
     /// If you would like to add additional components to your application
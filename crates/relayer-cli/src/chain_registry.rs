@@ -6,9 +6,8 @@ use std::fmt::Display;
 
 use futures::future::join_all;
 use http::Uri;
-use tendermint_rpc::Url;
 use tokio::task::{JoinError, JoinHandle};
-use tracing::{error, trace};
+use tracing::{error, info, trace};
 
 use ibc_chain_registry::asset_list::AssetList;
 use ibc_chain_registry::chain::ChainData;
@@ -17,6 +16,7 @@ use ibc_chain_registry::fetchable::Fetchable;
 use ibc_chain_registry::formatter::{SimpleGrpcFormatter, UriFormatter};
 use ibc_chain_registry::paths::IBCPath;
 use ibc_chain_registry::querier::*;
+use ibc_chain_registry::source::RegistrySource;
 use ibc_relayer::chain::cosmos::config::CosmosSdkConfig;
 use ibc_relayer::config::dynamic_gas::DynamicGasPrice;
 use ibc_relayer::config::filter::{FilterPattern, PacketFilter};
@@ -25,6 +25,7 @@ use ibc_relayer::config::types::{MaxMsgNum, MaxTxSize, Memo, TrustThreshold};
 use ibc_relayer::config::{default, AddressType, ChainConfig, EventSourceMode, GasPrice};
 use ibc_relayer::keyring::Store;
 use ibc_relayer::util::excluded_sequences::ExcludedSequences;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 const MAX_HEALTHY_QUERY_RETRIES: u8 = 5;
 
@@ -60,14 +61,17 @@ fn construct_packet_filters(ibc_paths: Vec<IBCPath>) -> HashMap<String, PacketFi
 }
 
 /// Generates a ChainConfig for a given chain from ChainData, AssetList, and an optional PacketFilter.
+/// The best ranked RPC and gRPC endpoints are used, and up to `backup_endpoints` of the next
+/// best ones are kept as backup addresses.
 async fn hermes_config<GrpcQuerier, RpcQuerier, GrpcFormatter>(
     chain_data: ChainData,
     assets: AssetList,
     packet_filter: Option<PacketFilter>,
+    backup_endpoints: usize,
 ) -> Result<ChainConfig, RegistryError>
 where
-    GrpcQuerier:
-        QueryContext<QueryInput = Uri, QueryOutput = Url, QueryError = RegistryError> + Send,
+    GrpcQuerier: QueryContext<QueryInput = Uri, QueryOutput = GrpcEndpointData, QueryError = RegistryError>
+        + Send,
     RpcQuerier: QueryContext<
             QueryInput = String,
             QueryOutput = HermesConfigData,
@@ -96,20 +100,38 @@ where
         .map(|rpc| rpc.address.to_owned())
         .collect();
 
-    let rpc_data = query_healthy_retry::<RpcQuerier>(
+    let rpc_data = query_ranked_retry::<RpcQuerier>(
         chain_name.to_string(),
         rpc_endpoints,
         MAX_HEALTHY_QUERY_RETRIES,
     )
     .await?;
 
-    let grpc_address = query_healthy_retry::<GrpcQuerier>(
+    let grpc_data = query_ranked_retry::<GrpcQuerier>(
         chain_name.to_string(),
         grpc_endpoints,
         MAX_HEALTHY_QUERY_RETRIES,
     )
     .await?;
 
+    for (rank, data) in rpc_data.iter().enumerate() {
+        info!(
+            "{chain_name}: RPC endpoint #{} {} (latency: {:?}, height: {}, earliest height: {}, tx indexing: {}, version: {}, websocket: {})",
+            rank + 1,
+            data.rpc_address,
+            data.latency,
+            data.latest_height,
+            data.earliest_height,
+            data.tx_indexing,
+            data.version,
+            data.websocket_latency.is_some(),
+        );
+    }
+
+    // Ranking returns at least one endpoint
+    let (best_rpc, backup_rpcs) = rpc_data.split_first().unwrap();
+    let (best_grpc, backup_grpcs) = grpc_data.split_first().unwrap();
+
     let avg_gas_price = if let Some(fee_token) = chain_data.fees.fee_tokens.first() {
         fee_token.average_gas_price
     } else {
@@ -125,8 +147,18 @@ where
 
     Ok(ChainConfig::CosmosSdk(CosmosSdkConfig {
         id: chain_data.chain_id,
-        rpc_addr: rpc_data.rpc_address,
-        grpc_addr: grpc_address,
+        rpc_addr: best_rpc.rpc_address.clone(),
+        grpc_addr: best_grpc.grpc_address.clone(),
+        backup_rpc_addrs: backup_rpcs
+            .iter()
+            .take(backup_endpoints)
+            .map(|data| data.rpc_address.clone())
+            .collect(),
+        backup_grpc_addrs: backup_grpcs
+            .iter()
+            .take(backup_endpoints)
+            .map(|data| data.grpc_address.clone())
+            .collect(),
         event_source: EventSourceMode::Pull {
             interval: default::poll_interval(),
             max_retries: default::max_retries(),
@@ -144,6 +176,7 @@ where
         gas_adjustment: None,
         gas_multiplier: Some(GasMultiplier::new(1.1).unwrap()),
         dynamic_gas_price,
+        fee_bump: Default::default(),
         fee_granter: None,
         max_msg_num: MaxMsgNum::default(),
         max_tx_size: MaxTxSize::default(),
@@ -165,6 +198,11 @@ where
         packet_filter: packet_filter.unwrap_or_default(),
         address_type: AddressType::default(),
         sequential_batch_tx: false,
+        pipeline_txs: false,
+        max_in_flight_txs: default::max_in_flight_txs(),
+        dry_run: false,
+        dry_run_output: None,
+        generate_only: None,
         extension_options: Vec::new(),
         compat_mode: None,
         clear_interval: None,
@@ -173,21 +211,22 @@ where
     }))
 }
 
-/// Concurrent `query_healthy` might fail, this is a helper function which will retry a failed query a fixed
+/// Concurrent `query_ranked` might fail, this is a helper function which will retry a failed query a fixed
 /// amount of times in order to avoid failure with healthy endpoints.
-async fn query_healthy_retry<QuerierType>(
+async fn query_ranked_retry<QuerierType>(
     chain_name: String,
     endpoints: Vec<QuerierType::QueryInput>,
     retries: u8,
-) -> Result<QuerierType::QueryOutput, RegistryError>
+) -> Result<Vec<QuerierType::QueryOutput>, RegistryError>
 where
     QuerierType: QueryContext + Send,
     QuerierType::QueryInput: Clone + Display,
+    QuerierType::QueryOutput: Send,
     QuerierType: QueryContext<QueryError = RegistryError>,
 {
     for i in 0..retries {
         let query_response =
-            QuerierType::query_healthy(chain_name.to_string(), endpoints.clone()).await;
+            QuerierType::query_ranked(chain_name.to_string(), endpoints.clone()).await;
 
         match query_response {
             Ok(r) => {
@@ -207,23 +246,25 @@ where
     Err(RegistryError::unhealthy_endpoints(endpoints, retries))
 }
 
-/// Fetches the specified resources from the Cosmos chain registry, using the specified commit hash
-/// if it is provided. Fetching is done in a concurrent fashion by spawning a task for each resource.
+/// Fetches the specified resources from the given source of the Cosmos chain registry, using the
+/// specified commit hash if it is provided. Fetching is done in a concurrent fashion by spawning a task for each resource.
 /// Returns a vector of handles that need to be awaited in order to access the fetched data, or the
 /// error that occurred while fetching.
 async fn get_handles<T: Fetchable + Send + 'static>(
     chain_ids: &[String],
     commit: &Option<String>,
+    source: &RegistrySource,
 ) -> Vec<(String, JoinHandle<Result<T, RegistryError>>)> {
     let handles = chain_ids
         .iter()
         .map(|chain_id| {
             let commit = commit.clone();
+            let source = source.clone();
             let handle = {
                 let chain_id = chain_id.to_string();
                 tokio::spawn(async move {
                     tracing::info!("{chain_id}: Fetching {}...", T::DESC);
-                    T::fetch(chain_id, commit).await
+                    T::fetch_from(&source, chain_id, commit).await
                 })
             };
             (chain_id.to_string(), handle)
@@ -249,8 +290,93 @@ async fn get_data_from_handles<T>(
     Ok(names.into_iter().zip(results).collect())
 }
 
+/// Spawns a task fetching the IBC path between every pair of the given chains,
+/// whose names must be sorted.
+fn get_path_handles(
+    chains: &[String],
+    commit: &Option<String>,
+    source: &RegistrySource,
+) -> Vec<JoinHandle<Result<IBCPath, RegistryError>>> {
+    let n = chains.len();
+
+    let mut path_handles = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+
+    for i in 0..n {
+        for chain_j in &chains[i + 1..] {
+            let chain_i = &chains[i];
+            let resource = format!("{chain_i}-{chain_j}.json").to_string();
+            let commit_clone = commit.clone();
+            let source = source.clone();
+            path_handles.push(tokio::spawn(async move {
+                IBCPath::fetch_from(&source, resource, commit_clone).await
+            }));
+        }
+    }
+
+    path_handles
+}
+
+/// Awaits the given IBC path handles. Paths which could not be fetched are skipped,
+/// since there is no path in the chain registry between most pairs of chains.
+async fn get_paths_from_handles(
+    path_handles: Vec<JoinHandle<Result<IBCPath, RegistryError>>>,
+) -> Result<Vec<IBCPath>, RegistryError> {
+    let path_data: Result<Vec<_>, JoinError> = join_all(path_handles).await.into_iter().collect();
+    let path_data = path_data
+        .map_err(|e| RegistryError::join_error("path_handle_join".to_string(), e))?
+        .into_iter()
+        .filter_map(|path| match path {
+            Ok(path) => Some(path),
+            Err(e) => {
+                error!("Error while fetching path data: {e}");
+                None
+            }
+        })
+        .collect();
+
+    Ok(path_data)
+}
+
+/// Fetches the IBC paths between every pair of the given chains from the given source of
+/// <https://github.com/cosmos/chain-registry>, along with the chain identifier of every
+/// chain. Chains whose data could not be fetched are left out of the returned identifiers.
+///
+/// # Arguments
+///
+/// * `chains` - A slice of strings that holds the name of the chains.
+/// * `commit` - An optional String representing the commit hash from which the data will be fetched. If it's None, the latest commit will be used.
+/// * `source` - The source from which the chain registry data is read.
+pub async fn get_paths(
+    chains: &[String],
+    commit: Option<String>,
+    source: &RegistrySource,
+) -> Result<(HashMap<String, ChainId>, Vec<IBCPath>), RegistryError> {
+    let mut chains = chains.to_vec();
+    chains.sort();
+    chains.dedup();
+
+    let chain_data_handle = get_handles::<ChainData>(&chains, &commit, source).await;
+    let path_handles = get_path_handles(&chains, &commit, source);
+
+    let chain_ids = get_data_from_handles::<ChainData>(chain_data_handle, "chain_data_join")
+        .await?
+        .into_iter()
+        .filter_map(|(name, data)| match data {
+            Ok(data) => Some((name, data.chain_id)),
+            Err(e) => {
+                error!("Error while fetching chain data for chain {name}: {e}");
+                None
+            }
+        })
+        .collect();
+
+    let paths = get_paths_from_handles(path_handles).await?;
+
+    Ok((chain_ids, paths))
+}
+
 /// Fetches a list of ChainConfigs specified by the given slice of chain names. These
-/// configs are fetched from <https://github.com/cosmos/chain-registry>. The `default_gas`
+/// configs are fetched from the given source of <https://github.com/cosmos/chain-registry>. The `default_gas`
 /// and `max_gas` parameters set to default values. The `gas_price` parameter is set to
 /// the average gas price for the chain listed in the chain registry.
 ///
@@ -258,40 +384,33 @@ async fn get_data_from_handles<T>(
 ///
 /// * `chains` - A slice of strings that holds the name of the chains for which a `ChainConfig` will be generated. It must be sorted.
 /// * `commit` - An optional String representing the commit hash from which the chain configs will be generated. If it's None, the latest commit will be used.
+/// * `source` - The source from which the chain registry data is read.
+/// * `backup_endpoints` - The maximum number of backup RPC and gRPC addresses written in each `ChainConfig`.
 ///
 /// # Example
 ///
 /// ```
+/// use ibc_chain_registry::source::RegistrySource;
 /// use ibc_relayer_cli::chain_registry::get_configs;
 /// let chains = &vec!["cosmoshub".to_string(), "osmosis".to_string()];
-/// let configs = get_configs(chains, None);
+/// let source = RegistrySource::default();
+/// let configs = get_configs(chains, None, &source, 0);
 /// ```
 pub async fn get_configs(
     chains: &[String],
     commit: Option<String>,
+    source: &RegistrySource,
+    backup_endpoints: usize,
 ) -> Result<HashMap<String, Result<ChainConfig, RegistryError>>, RegistryError> {
     if chains.is_empty() {
         return Ok(HashMap::new());
     }
 
     // Spawn tasks to fetch data from the chain-registry
-    let chain_data_handle = get_handles::<ChainData>(chains, &commit).await;
-    let asset_lists_handle = get_handles::<AssetList>(chains, &commit).await;
+    let chain_data_handle = get_handles::<ChainData>(chains, &commit, source).await;
+    let asset_lists_handle = get_handles::<AssetList>(chains, &commit, source).await;
 
-    let n = chains.len();
-
-    let mut path_handles = Vec::with_capacity(n * (n - 1) / 2);
-
-    for i in 0..n {
-        for chain_j in &chains[i + 1..] {
-            let chain_i = &chains[i];
-            let resource = format!("{chain_i}-{chain_j}.json").to_string();
-            let commit_clone = commit.clone();
-            path_handles.push(tokio::spawn(async move {
-                IBCPath::fetch(resource, commit_clone).await
-            }));
-        }
-    }
+    let path_handles = get_path_handles(chains, &commit, source);
 
     // Collect data from the spawned tasks
     let chain_data_results =
@@ -321,18 +440,7 @@ pub async fn get_configs(
         })
         .collect();
 
-    let path_data: Result<Vec<_>, JoinError> = join_all(path_handles).await.into_iter().collect();
-    let path_data: Vec<_> = path_data
-        .map_err(|e| RegistryError::join_error("path_handle_join".to_string(), e))?
-        .into_iter()
-        .filter_map(|path| match path {
-            Ok(path) => Some(path),
-            Err(e) => {
-                error!("Error while fetching path data: {e}");
-                None
-            }
-        })
-        .collect();
+    let path_data = get_paths_from_handles(path_handles).await?;
 
     let mut packet_filters = construct_packet_filters(path_data);
 
@@ -346,7 +454,9 @@ pub async fn get_configs(
                 GrpcHealthCheckQuerier,
                 SimpleHermesRpcQuerier,
                 SimpleGrpcFormatter,
-            >(chain_data, assets, packet_filter));
+            >(
+                chain_data, assets, packet_filter, backup_endpoints
+            ));
 
             (chain_name, handle)
         })
@@ -379,7 +489,13 @@ mod tests {
     // if there is no `{chain-a}-{chain-b}.json` file in the `_IBC/` directory of the
     // chain-registry repository: https://github.com/cosmos/chain-registry/tree/master/_IBC
     async fn should_have_no_filter(test_chains: &[String]) -> Result<(), RegistryError> {
-        let configs = get_configs(test_chains, Some(TEST_COMMIT.to_owned())).await?;
+        let configs = get_configs(
+            test_chains,
+            Some(TEST_COMMIT.to_owned()),
+            &RegistrySource::default(),
+            0,
+        )
+        .await?;
 
         for (_name, config) in configs {
             match config {
@@ -406,7 +522,13 @@ mod tests {
             "osmosis".to_string(),
         ]; // Must be sorted
 
-        let configs = get_configs(test_chains, Some(TEST_COMMIT.to_owned())).await?;
+        let configs = get_configs(
+            test_chains,
+            Some(TEST_COMMIT.to_owned()),
+            &RegistrySource::default(),
+            0,
+        )
+        .await?;
 
         for (_name, config) in configs {
             match config {
@@ -511,7 +633,13 @@ mod tests {
     #[ignore]
     async fn fetch_no_chain() -> Result<(), RegistryError> {
         let test_chains: &[String] = &[];
-        let configs = get_configs(test_chains, Some(TEST_COMMIT.to_owned())).await?;
+        let configs = get_configs(
+            test_chains,
+            Some(TEST_COMMIT.to_owned()),
+            &RegistrySource::default(),
+            0,
+        )
+        .await?;
 
         assert_eq!(configs.len(), 0);
 
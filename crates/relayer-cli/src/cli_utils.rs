@@ -28,13 +28,20 @@ pub struct ChainHandlePair<Chain: ChainHandle = BaseChainHandle> {
 impl<Chain: ChainHandle> ChainHandlePair<Chain> {
     /// Spawn the source and destination chain runtime from the configuration and chain identifiers,
     /// and return the pair of associated handles.
+    ///
+    /// If both identifiers are the same, eg. for channels on the localhost connection,
+    /// a single runtime is spawned and its handle is used for both ends.
     pub fn spawn_generic(
         config: &Config,
         src_chain_id: &ChainId,
         dst_chain_id: &ChainId,
     ) -> Result<Self, Error> {
-        let src = spawn_chain_runtime_generic(config, src_chain_id)?;
-        let dst = spawn_chain_runtime_generic(config, dst_chain_id)?;
+        let src: Chain = spawn_chain_runtime_generic(config, src_chain_id)?;
+        let dst = if src_chain_id == dst_chain_id {
+            src.clone()
+        } else {
+            spawn_chain_runtime_generic(config, dst_chain_id)?
+        };
 
         Ok(ChainHandlePair { src, dst })
     }
@@ -89,7 +96,13 @@ pub fn spawn_chain_counterparty<Chain: ChainHandle>(
         channel_connection_client(&chain, port_id, channel_id).map_err(Error::supervisor)?;
     let counterparty_chain = {
         let counterparty_chain_id = channel_connection_client.client.client_state.chain_id();
-        spawn_chain_runtime_generic::<Chain>(config, &counterparty_chain_id)?
+
+        // Channels on the localhost connection have both ends on the same chain
+        if &counterparty_chain_id == chain_id {
+            chain.clone()
+        } else {
+            spawn_chain_runtime_generic::<Chain>(config, &counterparty_chain_id)?
+        }
     };
 
     Ok((
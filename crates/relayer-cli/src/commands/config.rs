@@ -4,6 +4,9 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
 mod auto;
+mod filters;
+mod registry;
+mod schema;
 mod validate;
 
 /// `config` subcommand
@@ -14,4 +17,11 @@ pub enum ConfigCmd {
 
     /// Automatically generate a config.toml for the specified chain(s)
     Auto(auto::AutoCmd),
+
+    /// Manage the packet filters of the configured chains
+    #[clap(subcommand)]
+    Filters(filters::FiltersCmd),
+
+    /// Print the JSON Schema of the configuration file
+    Schema(schema::SchemaCmd),
 }
@@ -3,6 +3,7 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 use itertools::Itertools;
 
+use crate::commands::config::registry::RegistrySourceArgs;
 use crate::conclude::Output;
 
 use ibc_relayer::config::{store, ChainConfig, Config};
@@ -24,6 +25,11 @@ fn find_key(chain_config: &ChainConfig) -> Option<String> {
 ///
 /// If no key is specified, the first key stored in the KEYSTORE_DEFAULT_FOLDER, if it exists, will be used otherwise the field `key_name` will be left empty.
 /// If a is specified then it will be used without verifying that it exists.
+///
+/// The chain registry is fetched from GitHub, unless another source is selected with the `--registry-*` options.
+///
+/// All the RPC and gRPC endpoints listed in the chain registry are probed and ranked, and the best ones are used.
+/// With `--backup-endpoints <COUNT>`, up to `COUNT` of the next best endpoints are written as backup addresses.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 #[clap(
     override_usage = "hermes config auto [OPTIONS] --output <PATH> --chain <CHAIN1_NAME:OPTIONAL_KEY_NAME> --chain <CHAIN2_NAME:OPTIONAL_KEY_NAME>"
@@ -57,6 +63,18 @@ pub struct AutoCmd {
         help = "Commit hash from which the chain configs will be generated. If it's not set, the latest commit will be used."
     )]
     commit: Option<String>,
+
+    #[clap(
+        long = "backup-endpoints",
+        value_name = "COUNT",
+        default_value = "0",
+        help = "Maximum number of backup RPC and gRPC addresses to write for every chain, \
+                taken from the best ranked endpoints after the selected ones"
+    )]
+    backup_endpoints: usize,
+
+    #[clap(flatten)]
+    registry: RegistrySourceArgs,
 }
 
 fn extract_chains_and_keys(chain_names: &[String]) -> Vec<(String, Option<String>)> {
@@ -91,7 +109,12 @@ impl Runnable for AutoCmd {
         let commit = self.commit.clone();
 
         // Fetch chain configs from the chain registry
-        let config_results = runtime.block_on(get_configs(&chain_names, commit));
+        let config_results = runtime.block_on(get_configs(
+            &chain_names,
+            commit,
+            &self.registry.source(),
+            self.backup_endpoints,
+        ));
 
         if let Err(e) = config_results {
             let config = Config::default();
@@ -190,6 +213,7 @@ impl Runnable for AutoCmd {
 #[cfg(test)]
 mod tests {
     use super::AutoCmd;
+    use crate::commands::config::registry::RegistrySourceArgs;
     use abscissa_core::clap::Parser;
     use std::path::PathBuf;
 
@@ -200,6 +224,8 @@ mod tests {
                 path: PathBuf::from("./example.toml"),
                 chain_names: vec!["chain1:key1".to_string(), "chain2".to_string()],
                 commit: None,
+                backup_endpoints: 0,
+                registry: RegistrySourceArgs::default(),
             },
             AutoCmd::parse_from([
                 "test",
@@ -219,6 +245,8 @@ mod tests {
                 path: PathBuf::from("./example.toml"),
                 chain_names: vec!["chain1:key1".to_string(), "chain2".to_string()],
                 commit: Some("test_commit".to_string()),
+                backup_endpoints: 0,
+                registry: RegistrySourceArgs::default(),
             },
             AutoCmd::parse_from([
                 "test",
@@ -232,4 +260,58 @@ mod tests {
             ])
         )
     }
+
+    #[test]
+    fn auto_config_with_backup_endpoints() {
+        assert_eq!(
+            AutoCmd {
+                path: PathBuf::from("./example.toml"),
+                chain_names: vec!["chain1".to_string()],
+                commit: None,
+                backup_endpoints: 2,
+                registry: RegistrySourceArgs::default(),
+            },
+            AutoCmd::parse_from([
+                "test",
+                "--output",
+                "./example.toml",
+                "--chains",
+                "chain1",
+                "--backup-endpoints",
+                "2"
+            ])
+        )
+    }
+
+    #[test]
+    fn auto_config_from_registry_tarball() {
+        assert!(AutoCmd::try_parse_from([
+            "test",
+            "--output",
+            "./example.toml",
+            "--chains",
+            "chain1",
+            "--registry-tarball",
+            "./chain-registry.tar.gz",
+            "--registry-cache",
+            "./cache"
+        ])
+        .is_ok())
+    }
+
+    #[test]
+    fn auto_config_conflicting_registry_sources() {
+        assert!(AutoCmd::try_parse_from([
+            "test",
+            "--output",
+            "./example.toml",
+            "--chains",
+            "chain1",
+            "--registry-dir",
+            "./chain-registry",
+            "--registry-mirror",
+            "https://mirror.example.com/chain-registry"
+        ])
+        .is_err())
+    }
 }
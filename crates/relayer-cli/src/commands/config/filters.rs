@@ -0,0 +1,13 @@
+//! `config filters` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod generate;
+
+/// `config filters` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum FiltersCmd {
+    /// Generate the packet filters of the configured chains from their channels or the chain registry
+    Generate(generate::GenerateFiltersCmd),
+}
@@ -0,0 +1,664 @@
+use core::str::FromStr;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use serde::Serialize;
+use toml_edit::{Array, DocumentMut, InlineTable, Item, TableLike, Value};
+
+use ibc_chain_registry::paths::IBCPath;
+use ibc_relayer::config::filter::{ChannelFilters, ChannelPolicy};
+use ibc_relayer::config::Config;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::chain_registry::get_paths;
+use crate::commands::config::registry::RegistrySourceArgs;
+use crate::commands::query::topology::{discover_topology, Topology};
+use crate::conclude::Output;
+use crate::config::config_path;
+use crate::prelude::*;
+
+/// Where the channels to allow are discovered from.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FilterSource {
+    /// The open channels between the configured chains
+    #[default]
+    Channels,
+    /// The IBC paths of the chain registry
+    Registry,
+}
+
+impl FromStr for FilterSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "channels" => Ok(Self::Channels),
+            "registry" => Ok(Self::Registry),
+            _ => Err(format!(
+                "unknown source '{s}', expected either 'channels' or 'registry'"
+            )),
+        }
+    }
+}
+
+/// The data structure that represents the arguments when invoking the `config filters generate` CLI command.
+///
+/// The command has the following format:
+///
+/// `config filters generate [--source <SOURCE>] [--registry-chains <CHAIN_NAME>...] [--preferred-only] [--commit <COMMIT_HASH>] [--output <PATH>]`
+///
+/// The allowed channels of every configured chain are merged into its packet filter: the filters
+/// already present are kept, and a chain without packet filter gets an `allow` policy listing
+/// the discovered channels. Chains with a `deny` policy are left untouched.
+///
+/// Only the packet filters are edited in the configuration file, the rest of the file, including
+/// its comments and environment variables, is written back as is. Chains included from other
+/// files are not updated.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct GenerateFiltersCmd {
+    #[clap(
+        long = "source",
+        value_name = "SOURCE",
+        default_value = "channels",
+        help = "Where to discover the channels to allow from: 'channels' for the open channels between \
+                the configured chains, or 'registry' for the IBC paths of the chain registry"
+    )]
+    source: FilterSource,
+
+    #[clap(
+        long = "registry-chains",
+        multiple = true,
+        value_name = "CHAIN_NAME",
+        help = "Names, in the chain registry, of the configured chains. \
+                Required with '--source registry', paths are only looked up between these chains."
+    )]
+    registry_chains: Vec<String>,
+
+    #[clap(
+        long = "preferred-only",
+        help = "Only allow the channels tagged as preferred in the chain registry"
+    )]
+    preferred_only: bool,
+
+    #[clap(
+        long = "commit",
+        value_name = "COMMIT_HASH",
+        help = "Commit hash of the chain registry from which the paths are fetched. If it's not set, the latest commit will be used."
+    )]
+    commit: Option<String>,
+
+    #[clap(
+        long = "output",
+        value_name = "PATH",
+        help = "Path to write the updated configuration to. Defaults to the configuration file in use."
+    )]
+    output: Option<PathBuf>,
+
+    #[clap(flatten)]
+    registry: RegistrySourceArgs,
+}
+
+/// The filters generated for a chain.
+#[derive(Debug, Serialize)]
+struct GeneratedFilters {
+    chain_id: ChainId,
+    /// The channels added to the packet filter of the chain
+    added: Vec<(PortId, ChannelId)>,
+    /// Set if the packet filter of the chain was left untouched
+    skipped: Option<String>,
+}
+
+impl Runnable for GenerateFiltersCmd {
+    fn run(&self) {
+        if self.source == FilterSource::Channels {
+            if !self.registry_chains.is_empty()
+                || self.preferred_only
+                || self.commit.is_some()
+                || self.registry != RegistrySourceArgs::default()
+            {
+                Output::error(
+                    "'--registry-chains', '--preferred-only', '--commit' and the '--registry-*' options require '--source registry'",
+                )
+                .exit();
+            }
+        } else if self.registry_chains.is_empty() {
+            Output::error("'--source registry' requires '--registry-chains'").exit();
+        }
+
+        let Some(config_path) = config_path() else {
+            Output::error("no configuration file found").exit()
+        };
+        let path = self.output.clone().unwrap_or_else(|| config_path.clone());
+
+        // The configuration file is edited as is, rather than the configuration in use,
+        // in which the environment variables and includes are resolved
+        let mut document = match fs::read_to_string(&config_path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| contents.parse::<DocumentMut>().map_err(|e| e.to_string()))
+        {
+            Ok(document) => document,
+            Err(e) => Output::error(format!(
+                "failed to read the configuration file '{}': {e}",
+                config_path.display()
+            ))
+            .exit(),
+        };
+
+        let config = app_config();
+
+        let channels = match self.source {
+            FilterSource::Channels => channels_from_topology(&discover_topology(&config)),
+            FilterSource::Registry => {
+                let runtime = tokio::runtime::Runtime::new().unwrap();
+
+                match runtime.block_on(get_paths(
+                    &self.registry_chains,
+                    self.commit.clone(),
+                    &self.registry.source(),
+                )) {
+                    Ok((chain_ids, paths)) => {
+                        channels_from_registry(&chain_ids, &paths, self.preferred_only)
+                    }
+                    Err(e) => Output::error(format!(
+                        "failed to fetch the IBC paths from the chain registry: {e}"
+                    ))
+                    .exit(),
+                }
+            }
+        };
+
+        let mut generated = merge_filters(&config, channels);
+
+        for filters in generated.iter_mut() {
+            if !filters.added.is_empty()
+                && !add_to_packet_filter(&mut document, &filters.chain_id, &filters.added)
+            {
+                warn!(
+                    "chain {} is not defined in '{}', leaving its packet filter untouched",
+                    filters.chain_id,
+                    config_path.display()
+                );
+
+                filters.added.clear();
+                filters.skipped = Some(
+                    "the chain is not defined in the configuration file, it may be included from another file"
+                        .to_string(),
+                );
+            }
+        }
+
+        if generated.iter().any(|filters| !filters.added.is_empty()) {
+            if let Err(e) = fs::write(&path, document.to_string()) {
+                Output::error(format!(
+                    "An error occurred while attempting to write the config file: {e}"
+                ))
+                .exit()
+            }
+
+            info!("packet filters written to '{}'", path.display());
+        } else {
+            info!("no channel to add, the configuration is left untouched");
+        }
+
+        Output::success(generated).exit()
+    }
+}
+
+/// The channels of the configured chains which are open on both ends.
+fn channels_from_topology(topology: &Topology) -> BTreeMap<ChainId, Vec<(PortId, ChannelId)>> {
+    let mut channels: BTreeMap<_, Vec<_>> = BTreeMap::new();
+
+    for channel in &topology.channels {
+        let sides = [&channel.a_side, &channel.b_side];
+
+        // The state of an end is only known if its chain is configured
+        if !sides
+            .iter()
+            .all(|side| side.state.is_some_and(|state| state.is_open()))
+        {
+            continue;
+        }
+
+        for side in sides {
+            if let Some(channel_id) = &side.channel_id {
+                channels
+                    .entry(side.chain_id.clone())
+                    .or_default()
+                    .push((side.port_id.clone(), channel_id.clone()));
+            }
+        }
+    }
+
+    channels
+}
+
+/// The channels of the chain registry paths, keyed by the identifier of the chain they are on.
+fn channels_from_registry(
+    chain_ids: &HashMap<String, ChainId>,
+    paths: &[IBCPath],
+    preferred_only: bool,
+) -> BTreeMap<ChainId, Vec<(PortId, ChannelId)>> {
+    let mut channels: BTreeMap<_, Vec<_>> = BTreeMap::new();
+
+    for path in paths {
+        let (Some(chain_1), Some(chain_2)) = (
+            chain_ids.get(&path.chain_1.chain_name),
+            chain_ids.get(&path.chain_2.chain_name),
+        ) else {
+            continue;
+        };
+
+        for channel in &path.channels {
+            if preferred_only && !channel.tags.preferred {
+                continue;
+            }
+
+            channels.entry(chain_1.clone()).or_default().push((
+                channel.chain_1.port_id.clone(),
+                channel.chain_1.channel_id.clone(),
+            ));
+            channels.entry(chain_2.clone()).or_default().push((
+                channel.chain_2.port_id.clone(),
+                channel.chain_2.channel_id.clone(),
+            ));
+        }
+    }
+
+    channels
+}
+
+/// The channels to add to the packet filters of the configured chains,
+/// leaving out those already allowed.
+fn merge_filters(
+    config: &Config,
+    mut channels: BTreeMap<ChainId, Vec<(PortId, ChannelId)>>,
+) -> Vec<GeneratedFilters> {
+    let mut generated = Vec::new();
+
+    for chain_config in &config.chains {
+        let chain_id = chain_config.id().clone();
+        let Some(chain_channels) = channels.remove(&chain_id) else {
+            continue;
+        };
+
+        // Without packet filter, all channels are allowed: start from an empty allow-list
+        let mut filters = match chain_config.packet_filter().channel_policy.clone() {
+            ChannelPolicy::Allow(filters) => filters,
+            ChannelPolicy::AllowAll => ChannelFilters::new(vec![]),
+            ChannelPolicy::Deny(_) => {
+                warn!("chain {chain_id} has a 'deny' packet filter policy, leaving it untouched");
+
+                generated.push(GeneratedFilters {
+                    chain_id,
+                    added: vec![],
+                    skipped: Some("the packet filter has a 'deny' policy".to_string()),
+                });
+                continue;
+            }
+        };
+
+        let added = filters.extend_exact(chain_channels);
+
+        generated.push(GeneratedFilters {
+            chain_id,
+            added,
+            skipped: None,
+        });
+    }
+
+    for chain_id in channels.keys() {
+        debug!("skipping channels of chain {chain_id}, which is not configured");
+    }
+
+    generated
+}
+
+/// Add the given channels to the allow-list of the packet filter of a chain in the configuration
+/// file, turning a missing or `allowall` packet filter into an `allow` policy, and leaving the
+/// rest of the file untouched.
+///
+/// Returns `false` if the chain is not defined in the configuration file.
+fn add_to_packet_filter(
+    document: &mut DocumentMut,
+    chain_id: &ChainId,
+    channels: &[(PortId, ChannelId)],
+) -> bool {
+    fn has_id(chain: &dyn TableLike, chain_id: &ChainId) -> bool {
+        chain.get("id").and_then(Item::as_str) == Some(chain_id.as_str())
+    }
+
+    // Chains are usually defined as an array of tables, but may be an array of inline tables
+    let chain = match document.get_mut("chains") {
+        Some(Item::ArrayOfTables(chains)) => chains
+            .iter_mut()
+            .find(|chain| has_id(*chain, chain_id))
+            .map(|chain| -> (&mut dyn TableLike, _) { (chain, toml_edit::table()) }),
+        Some(Item::Value(Value::Array(chains))) => chains
+            .iter_mut()
+            .filter_map(Value::as_inline_table_mut)
+            .find(|chain| has_id(*chain, chain_id))
+            .map(|chain| -> (&mut dyn TableLike, _) {
+                (chain, toml_edit::value(InlineTable::new()))
+            }),
+        _ => None,
+    };
+
+    let Some((chain, empty_packet_filter)) = chain else {
+        return false;
+    };
+
+    if chain.get("packet_filter").is_none() {
+        chain.insert("packet_filter", empty_packet_filter);
+    }
+
+    let Some(packet_filter) = chain
+        .get_mut("packet_filter")
+        .and_then(Item::as_table_like_mut)
+    else {
+        return false;
+    };
+
+    if packet_filter.get("policy").and_then(Item::as_str) != Some("allow") {
+        packet_filter.insert("policy", toml_edit::value("allow"));
+        packet_filter.insert("list", toml_edit::value(Array::new()));
+    }
+
+    let Some(list) = packet_filter.get_mut("list").and_then(Item::as_array_mut) else {
+        return false;
+    };
+
+    // Keep a comment following the last channel of the list next to it
+    let mut prefix = list
+        .trailing()
+        .as_str()
+        .unwrap_or_default()
+        .trim_end()
+        .to_string();
+
+    for (port_id, channel_id) in channels {
+        let mut channel: Value = Array::from_iter([port_id.as_str(), channel_id.as_str()]).into();
+        prefix.push_str("\n  ");
+        channel.decor_mut().set_prefix(prefix);
+        list.push_formatted(channel);
+        prefix = String::new();
+    }
+
+    list.set_trailing_comma(true);
+    list.set_trailing("\n");
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        add_to_packet_filter, channels_from_registry, merge_filters, FilterSource,
+        GenerateFiltersCmd,
+    };
+    use crate::commands::config::registry::RegistrySourceArgs;
+
+    use core::str::FromStr;
+    use std::collections::{BTreeMap, HashMap};
+
+    use abscissa_core::clap::Parser;
+    use ibc_chain_registry::paths::{ChainDesc, Channel, ChannelPort, IBCPath, Tags};
+    use ibc_relayer::config::Config;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use toml_edit::DocumentMut;
+
+    fn example_config() -> String {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../relayer/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        std::fs::read_to_string(path).unwrap()
+    }
+
+    fn transfer(channel: u64) -> (PortId, ChannelId) {
+        (PortId::transfer(), ChannelId::new(channel))
+    }
+
+    fn path(chain_1: &str, chain_2: &str, channels: &[(u64, u64, bool)]) -> IBCPath {
+        let chain = |chain_name: &str| ChainDesc {
+            chain_name: chain_name.to_string(),
+            ..Default::default()
+        };
+        let port = |channel: u64| ChannelPort {
+            channel_id: ChannelId::new(channel),
+            port_id: PortId::transfer(),
+        };
+
+        IBCPath {
+            chain_1: chain(chain_1),
+            chain_2: chain(chain_2),
+            channels: channels
+                .iter()
+                .map(|&(channel_1, channel_2, preferred)| Channel {
+                    chain_1: port(channel_1),
+                    chain_2: port(channel_2),
+                    tags: Tags {
+                        preferred,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_filters_defaults() {
+        assert_eq!(
+            GenerateFiltersCmd {
+                source: FilterSource::Channels,
+                registry_chains: vec![],
+                preferred_only: false,
+                commit: None,
+                output: None,
+                registry: RegistrySourceArgs::default(),
+            },
+            GenerateFiltersCmd::parse_from(["test"])
+        )
+    }
+
+    #[test]
+    fn test_generate_filters_registry() {
+        assert_eq!(
+            GenerateFiltersCmd {
+                source: FilterSource::Registry,
+                registry_chains: vec!["cosmoshub".to_string(), "osmosis".to_string()],
+                preferred_only: true,
+                commit: None,
+                output: Some("./config.toml".into()),
+                registry: RegistrySourceArgs::default(),
+            },
+            GenerateFiltersCmd::parse_from([
+                "test",
+                "--source",
+                "registry",
+                "--registry-chains",
+                "cosmoshub",
+                "osmosis",
+                "--preferred-only",
+                "--output",
+                "./config.toml"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_generate_filters_unknown_source() {
+        assert!(GenerateFiltersCmd::try_parse_from(["test", "--source", "rpc"]).is_err())
+    }
+
+    #[test]
+    fn test_channels_from_registry() {
+        let chain_ids = HashMap::from([
+            ("cosmoshub".to_string(), ChainId::from_string("cosmoshub-4")),
+            ("osmosis".to_string(), ChainId::from_string("osmosis-1")),
+        ]);
+        let paths = [
+            path("cosmoshub", "osmosis", &[(141, 0, true), (142, 1, false)]),
+            // Skipped, one of the chains is not configured
+            path("cosmoshub", "juno", &[(207, 1, true)]),
+        ];
+
+        assert_eq!(
+            channels_from_registry(&chain_ids, &paths, false),
+            BTreeMap::from([
+                (
+                    ChainId::from_string("cosmoshub-4"),
+                    vec![transfer(141), transfer(142)]
+                ),
+                (
+                    ChainId::from_string("osmosis-1"),
+                    vec![transfer(0), transfer(1)]
+                ),
+            ])
+        );
+
+        assert_eq!(
+            channels_from_registry(&chain_ids, &paths, true),
+            BTreeMap::from([
+                (ChainId::from_string("cosmoshub-4"), vec![transfer(141)]),
+                (ChainId::from_string("osmosis-1"), vec![transfer(0)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge_filters() {
+        let config: Config = toml::from_str(&example_config()).unwrap();
+
+        let channels = BTreeMap::from([
+            (
+                ChainId::from_string("chain_A"),
+                vec![
+                    // Already allowed, exactly or by a wildcard
+                    transfer(0),
+                    (PortId::from_str("icahost").unwrap(), ChannelId::new(5)),
+                    transfer(1),
+                ],
+            ),
+            (ChainId::from_string("chain_B"), vec![transfer(2)]),
+            (ChainId::from_string("chain_C"), vec![transfer(3)]),
+        ]);
+
+        let generated = merge_filters(&config, channels);
+
+        assert_eq!(generated.len(), 2);
+        assert_eq!(generated[0].chain_id, ChainId::from_string("chain_A"));
+        assert_eq!(generated[0].added, vec![transfer(1)]);
+        assert_eq!(generated[1].chain_id, ChainId::from_string("chain_B"));
+        assert_eq!(generated[1].added, vec![transfer(2)]);
+    }
+
+    #[test]
+    fn test_merge_filters_deny() {
+        let config = example_config().replace("policy = 'allow'", "policy = 'deny'");
+        let config: Config = toml::from_str(&config).unwrap();
+
+        let channels = BTreeMap::from([(ChainId::from_string("chain_A"), vec![transfer(1)])]);
+
+        let generated = merge_filters(&config, channels);
+
+        assert_eq!(generated.len(), 1);
+        assert!(generated[0].added.is_empty());
+        assert!(generated[0].skipped.is_some());
+    }
+
+    #[test]
+    fn test_add_to_packet_filter() {
+        let mut document: DocumentMut = r#"# Relayer configuration
+[global]
+log_level = 'info'
+
+[[chains]]
+id = 'ibc-0'
+# The key is read from the environment
+key_name = '${KEY_NAME}'
+
+[chains.packet_filter]
+policy = 'allow'
+list = [
+  ['transfer', 'channel-0'], # the main channel
+]
+
+[[chains]]
+id = 'ibc-1'
+
+[[chains]]
+id = 'ibc-2'
+packet_filter = { policy = 'allowall' }
+"#
+        .parse()
+        .unwrap();
+
+        assert!(add_to_packet_filter(
+            &mut document,
+            &ChainId::from_string("ibc-0"),
+            &[transfer(1)]
+        ));
+        assert!(add_to_packet_filter(
+            &mut document,
+            &ChainId::from_string("ibc-1"),
+            &[transfer(2), transfer(3)]
+        ));
+        assert!(add_to_packet_filter(
+            &mut document,
+            &ChainId::from_string("ibc-2"),
+            &[transfer(4)]
+        ));
+        assert!(!add_to_packet_filter(
+            &mut document,
+            &ChainId::from_string("ibc-3"),
+            &[transfer(5)]
+        ));
+
+        assert_eq!(
+            document.to_string(),
+            r#"# Relayer configuration
+[global]
+log_level = 'info'
+
+[[chains]]
+id = 'ibc-0'
+# The key is read from the environment
+key_name = '${KEY_NAME}'
+
+[chains.packet_filter]
+policy = 'allow'
+list = [
+  ['transfer', 'channel-0'], # the main channel
+  ["transfer", "channel-1"],
+]
+
+[[chains]]
+id = 'ibc-1'
+
+[chains.packet_filter]
+policy = "allow"
+list = [
+  ["transfer", "channel-2"],
+  ["transfer", "channel-3"],
+]
+
+[[chains]]
+id = 'ibc-2'
+packet_filter = { policy = "allow", list = [
+  ["transfer", "channel-4"],
+] }
+"#
+        );
+
+        // The edited configuration is still valid
+        let edited: toml::Table = toml::from_str(&document.to_string()).unwrap();
+        assert_eq!(
+            edited["chains"][2]["packet_filter"]["list"][0][1].as_str(),
+            Some("channel-4")
+        );
+    }
+}
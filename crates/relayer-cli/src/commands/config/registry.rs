@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+
+use ibc_chain_registry::source::{RegistryLocation, RegistrySource};
+
+/// The arguments selecting the source of the chain registry data,
+/// shared by the commands fetching data from the chain registry.
+///
+/// Without any of them, the data is fetched from the GitHub repository of the chain registry.
+#[derive(Clone, Debug, Default, Parser, PartialEq, Eq)]
+pub struct RegistrySourceArgs {
+    #[clap(
+        long = "registry-dir",
+        value_name = "PATH",
+        conflicts_with_all = &["registry-mirror", "registry-tarball"],
+        help = "Read the chain registry from a local clone of the repository. The commit is ignored."
+    )]
+    registry_dir: Option<PathBuf>,
+
+    #[clap(
+        long = "registry-mirror",
+        value_name = "URL",
+        conflicts_with = "registry-tarball",
+        help = "Fetch the chain registry from a mirror serving the file at <PATH> of the repository at <COMMIT> under <URL>/<COMMIT>/<PATH>"
+    )]
+    registry_mirror: Option<String>,
+
+    #[clap(
+        long = "registry-tarball",
+        value_name = "PATH",
+        help = "Read the chain registry from a .tar or .tar.gz archive of the repository. The commit is ignored."
+    )]
+    registry_tarball: Option<PathBuf>,
+
+    #[clap(
+        long = "registry-cache",
+        value_name = "PATH",
+        help = "Directory in which the files fetched at a given commit, and the unpacked tarballs, are cached"
+    )]
+    registry_cache: Option<PathBuf>,
+}
+
+impl RegistrySourceArgs {
+    pub fn source(&self) -> RegistrySource {
+        let location = match (
+            &self.registry_dir,
+            &self.registry_mirror,
+            &self.registry_tarball,
+        ) {
+            (Some(dir), _, _) => RegistryLocation::Directory(dir.clone()),
+            (_, Some(url), _) => RegistryLocation::Mirror(url.clone()),
+            (_, _, Some(tarball)) => RegistryLocation::Tarball(tarball.clone()),
+            (None, None, None) => RegistryLocation::GitHub,
+        };
+
+        let source = RegistrySource::new(location);
+
+        match &self.registry_cache {
+            Some(cache_dir) => source.with_cache_dir(cache_dir.clone()),
+            None => source,
+        }
+    }
+}
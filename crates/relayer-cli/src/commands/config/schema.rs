@@ -0,0 +1,40 @@
+use abscissa_core::clap::Parser;
+
+use ibc_relayer::config::schema::config_schema;
+
+use crate::conclude::{json, Output};
+use crate::prelude::*;
+
+/// Print the JSON Schema of the configuration file, generated from the configuration types,
+/// with the default values and the descriptions of the fields. It can be used by editors and
+/// CI to validate and autocomplete configuration files.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct SchemaCmd {}
+
+impl Runnable for SchemaCmd {
+    fn run(&self) {
+        let schema = config_schema();
+
+        // Print the schema as is, so that it can be redirected to a file
+        if json() {
+            Output::success(schema).exit()
+        }
+
+        match serde_json::to_string_pretty(&schema) {
+            Ok(schema) => println!("{schema}"),
+            Err(e) => Output::error(format!("failed to serialize the JSON Schema: {e}")).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaCmd;
+
+    use abscissa_core::clap::Parser;
+
+    #[test]
+    fn test_schema() {
+        assert_eq!(SchemaCmd {}, SchemaCmd::parse_from(["test"]))
+    }
+}
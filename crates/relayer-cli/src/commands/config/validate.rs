@@ -1,16 +1,32 @@
 use std::fs;
+use std::sync::Arc;
 
 use abscissa_core::clap::Parser;
+use itertools::Itertools;
 
-use crate::conclude::Output;
+use ibc_relayer::config::store_writer;
+
+use crate::conclude::{json, Output};
 use crate::config;
 use crate::prelude::*;
 
+mod online;
+
 /// In order to validate the configuration file the command will check that the file exists,
 /// that it is readable and not empty. It will then check the validity of the fields inside
-/// the file.
-#[derive(Command, Debug, Parser)]
-pub struct ValidateCmd {}
+/// the file, and print the configuration once its environment variables and includes
+/// have been resolved.
+///
+/// With `--online`, it then connects to every configured chain and checks the configuration
+/// of the chain against it, printing a report of the checks which passed or failed per chain.
+#[derive(Command, Debug, Parser, PartialEq, Eq)]
+pub struct ValidateCmd {
+    #[clap(
+        long = "online",
+        help = "Connect to every configured chain and check its configuration against the chain"
+    )]
+    online: bool,
+}
 
 impl Runnable for ValidateCmd {
     /// Validate the loaded configuration.
@@ -39,9 +55,79 @@ impl Runnable for ValidateCmd {
 
         // No need to output the underlying error, this is done already when the application boots.
         // See `application::CliApp::after_config`.
-        match config.validate_config() {
-            Ok(_) => Output::success_msg("configuration is valid").exit(),
-            Err(_) => Output::error("configuration is invalid").exit(),
+        if config.validate_config().is_err() {
+            Output::error("configuration is invalid").exit()
+        }
+
+        if !self.online {
+            if json() {
+                Output::success((*config).clone()).exit()
+            }
+
+            // Print the configuration after resolving its environment variables and includes.
+            let mut merged = vec![];
+            if let Err(e) = store_writer(&config, &mut merged) {
+                Output::error(format!("failed to serialize the configuration: {e}")).exit()
+            }
+
+            Output::success_msg(format!(
+                "configuration is valid\n\n{}",
+                String::from_utf8_lossy(&merged).trim_end()
+            ))
+            .exit()
         }
+
+        let rt = Arc::new(tokio::runtime::Runtime::new().unwrap());
+
+        let reports = config
+            .chains
+            .iter()
+            .map(|chain_config| online::check_chain(chain_config, rt.clone()))
+            .collect::<Vec<_>>();
+
+        let passed = reports.iter().all(|report| report.passed());
+
+        let output = if passed {
+            Output::with_success()
+        } else {
+            Output::with_error()
+        };
+
+        if json() {
+            output.with_result(reports).exit()
+        } else {
+            let verdict = if passed {
+                "configuration is valid, all online checks passed"
+            } else {
+                "configuration is valid, but some online checks failed"
+            };
+
+            output
+                .with_msg(format!("{}\n{verdict}", reports.iter().join("\n")))
+                .exit()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValidateCmd;
+
+    use abscissa_core::clap::Parser;
+
+    #[test]
+    fn test_validate_offline() {
+        assert_eq!(
+            ValidateCmd { online: false },
+            ValidateCmd::parse_from(["test"])
+        )
+    }
+
+    #[test]
+    fn test_validate_online() {
+        assert_eq!(
+            ValidateCmd { online: true },
+            ValidateCmd::parse_from(["test", "--online"])
+        )
     }
 }
@@ -0,0 +1,507 @@
+use core::cmp::Ordering;
+use core::fmt;
+use core::time::Duration;
+use std::sync::Arc;
+
+use serde::Serialize;
+use tendermint_rpc::endpoint::status;
+use tendermint_rpc::{Client, HttpClient};
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, info};
+
+use ibc_relayer::account::Balance;
+use ibc_relayer::chain::cosmos::config::CosmosSdkConfig;
+use ibc_relayer::chain::cosmos::types::config::TxConfig;
+use ibc_relayer::chain::cosmos::CosmosSdkChain;
+use ibc_relayer::chain::endpoint::ChainEndpoint;
+use ibc_relayer::chain::requests::{PageRequest, QueryClientStatesRequest};
+use ibc_relayer::config::{ChainConfig, EventSourceMode, GasPrice};
+use ibc_relayer::error::Error;
+use ibc_relayer::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+/// The outcome of a single check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// The check could not be performed
+    Skip,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Fail => write!(f, "FAIL"),
+            Self::Skip => write!(f, "SKIP"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckResult {
+    pub check: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn pass(check: &'static str, detail: impl ToString) -> Self {
+        Self {
+            check,
+            status: CheckStatus::Pass,
+            detail: detail.to_string(),
+        }
+    }
+
+    fn fail(check: &'static str, detail: impl ToString) -> Self {
+        Self {
+            check,
+            status: CheckStatus::Fail,
+            detail: detail.to_string(),
+        }
+    }
+
+    fn error(check: &'static str, e: Error) -> Self {
+        Self::fail(check, e.detail())
+    }
+
+    fn skip(check: &'static str, detail: impl ToString) -> Self {
+        Self {
+            check,
+            status: CheckStatus::Skip,
+            detail: detail.to_string(),
+        }
+    }
+}
+
+/// The results of the online checks of a chain.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChainReport {
+    pub chain_id: ChainId,
+    pub checks: Vec<CheckResult>,
+}
+
+impl ChainReport {
+    /// Whether none of the checks failed.
+    pub fn passed(&self) -> bool {
+        self.checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Fail)
+    }
+}
+
+impl fmt::Display for ChainReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.passed() {
+            CheckStatus::Pass
+        } else {
+            CheckStatus::Fail
+        };
+
+        writeln!(f, "{}: {status}", self.chain_id)?;
+
+        for check in &self.checks {
+            writeln!(f, "  {} {}: {}", check.status, check.check, check.detail)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Connect to the given chain and check its configuration against the chain:
+///
+/// - the RPC endpoint answers, the node reports the configured chain identifier and is in sync,
+/// - the transaction settings and the keystore are valid,
+/// - the gRPC endpoint answers,
+/// - the event source can subscribe to events,
+/// - the key exists in the keystore and is funded in the denomination of the gas price,
+/// - the gas price is at or above the minimum gas price of the node,
+/// - the trusting period is below the unbonding period.
+pub fn check_chain(config: &ChainConfig, rt: Arc<TokioRuntime>) -> ChainReport {
+    let chain_id = config.id().clone();
+
+    info!("checking chain {chain_id}");
+
+    let checks = match config {
+        ChainConfig::CosmosSdk(cosmos_config) => check_cosmos_chain(cosmos_config, config, rt),
+        _ => vec![CheckResult::skip(
+            "chain type",
+            "online checks are only supported for Cosmos SDK chains",
+        )],
+    };
+
+    ChainReport { chain_id, checks }
+}
+
+fn check_cosmos_chain(
+    cosmos_config: &CosmosSdkConfig,
+    config: &ChainConfig,
+    rt: Arc<TokioRuntime>,
+) -> Vec<CheckResult> {
+    let mut checks = vec![];
+
+    // Check the settings which the chain needs to start, one by one,
+    // so that a failure to start the chain is reported against its cause
+    match rt.block_on(query_node_status(cosmos_config)) {
+        Ok(status) => {
+            checks.push(CheckResult::pass(
+                "rpc",
+                format!("connected to {}", cosmos_config.rpc_addr),
+            ));
+            checks.push(check_chain_id(
+                &cosmos_config.id,
+                status.node_info.network.as_str(),
+            ));
+            checks.push(check_sync(
+                status.sync_info.catching_up,
+                status.sync_info.latest_block_height,
+            ));
+        }
+        Err(e) => {
+            checks.push(CheckResult::fail("rpc", e));
+            checks.push(CheckResult::skip(
+                "chain id",
+                "the RPC endpoint is unavailable",
+            ));
+            checks.push(CheckResult::skip("sync", "the RPC endpoint is unavailable"));
+        }
+    }
+
+    checks.push(match TxConfig::try_from(cosmos_config) {
+        Ok(_) => CheckResult::pass("tx config", "the transaction settings are valid"),
+        Err(e) => CheckResult::error("tx config", e),
+    });
+
+    checks.push(
+        match KeyRing::<Secp256k1KeyPair>::new_secp256k1(
+            cosmos_config.key_store_type,
+            &cosmos_config.account_prefix,
+            &cosmos_config.id,
+            &cosmos_config.key_store_folder,
+        ) {
+            Ok(_) => CheckResult::pass("keystore", "the keystore can be opened"),
+            Err(e) => CheckResult::fail("keystore", e),
+        },
+    );
+
+    let can_start = checks
+        .iter()
+        .filter(|check| ["rpc", "tx config", "keystore"].contains(&check.check))
+        .all(|check| check.status == CheckStatus::Pass);
+
+    if !can_start {
+        checks.push(CheckResult::skip(
+            "chain",
+            "the chain cannot be started, skipping the remaining checks",
+        ));
+        return checks;
+    }
+
+    let mut chain = match CosmosSdkChain::bootstrap(config.clone(), rt) {
+        Ok(chain) => chain,
+        Err(e) => {
+            checks.push(CheckResult::error("chain", e));
+            return checks;
+        }
+    };
+
+    let chain_config = chain.config().clone();
+
+    let request = QueryClientStatesRequest {
+        pagination: Some(PageRequest {
+            limit: 1,
+            ..PageRequest::default()
+        }),
+    };
+
+    checks.push(match chain.query_clients(request) {
+        Ok(_) => CheckResult::pass("grpc", format!("{} answers", chain_config.grpc_addr)),
+        Err(e) => CheckResult::error("grpc", e),
+    });
+
+    checks.push(match chain.subscribe() {
+        Ok(_) => CheckResult::pass(
+            "event source",
+            describe_event_source(&chain_config.event_source),
+        ),
+        Err(e) => CheckResult::error("event source", e),
+    });
+
+    match chain.get_key() {
+        Ok(key) => {
+            checks.push(CheckResult::pass(
+                "key",
+                format!(
+                    "key '{}' found, with address {}",
+                    chain_config.key_name,
+                    key.account()
+                ),
+            ));
+
+            checks.push(
+                match chain.query_balance(None, Some(&chain_config.gas_price.denom)) {
+                    Ok(balance) => check_balance(&balance),
+                    Err(e) => CheckResult::error("balance", e),
+                },
+            );
+        }
+        Err(e) => {
+            checks.push(CheckResult::error("key", e));
+            checks.push(CheckResult::skip(
+                "balance",
+                "no key to query the balance of",
+            ));
+        }
+    }
+
+    checks.push(match chain.min_gas_price() {
+        Ok(min_gas_prices) => check_gas_price(&chain_config.gas_price, min_gas_prices),
+        Err(e) => CheckResult::error("gas price", e),
+    });
+
+    checks.push(match chain.unbonding_period() {
+        Ok(unbonding_period) => {
+            check_trusting_period(chain_config.trusting_period, unbonding_period)
+        }
+        Err(e) => CheckResult::error("trusting period", e),
+    });
+
+    if let Err(e) = chain.shutdown() {
+        debug!(
+            "failed to shut down the event source of {}: {e}",
+            chain_config.id
+        );
+    }
+
+    checks
+}
+
+/// Query the status of the node at the RPC endpoint of the chain, which is
+/// available whether or not the node is in sync, unlike the chain status.
+async fn query_node_status(config: &CosmosSdkConfig) -> Result<status::Response, String> {
+    let rpc_client = HttpClient::builder(
+        config
+            .rpc_addr
+            .clone()
+            .try_into()
+            .map_err(|e| format!("invalid RPC address {}: {e}", config.rpc_addr))?,
+    )
+    .build()
+    .map_err(|e| e.to_string())?;
+
+    rpc_client.status().await.map_err(|e| e.to_string())
+}
+
+fn check_chain_id(chain_id: &ChainId, network: &str) -> CheckResult {
+    if network == chain_id.as_str() {
+        CheckResult::pass("chain id", format!("the node reports chain id '{network}'"))
+    } else {
+        CheckResult::fail(
+            "chain id",
+            format!("the node reports chain id '{network}' instead of '{chain_id}'"),
+        )
+    }
+}
+
+fn check_sync(catching_up: bool, height: impl fmt::Display) -> CheckResult {
+    if catching_up {
+        CheckResult::fail(
+            "sync",
+            format!("the node is catching up, at height {height}"),
+        )
+    } else {
+        CheckResult::pass("sync", format!("the node is in sync, at height {height}"))
+    }
+}
+
+fn describe_event_source(event_source: &EventSourceMode) -> String {
+    match event_source {
+        EventSourceMode::Push { url, .. } => format!("subscribed over WebSocket to {url}"),
+        EventSourceMode::Pull { interval, .. } => {
+            format!("polling the RPC endpoint every {interval:?}")
+        }
+        EventSourceMode::Replay { path, .. } => {
+            format!("replaying the events of {}", path.display())
+        }
+    }
+}
+
+fn check_balance(balance: &Balance) -> CheckResult {
+    if balance.amount.trim_start_matches('0').is_empty() {
+        CheckResult::fail("balance", format!("the key holds no {}", balance.denom))
+    } else {
+        CheckResult::pass(
+            "balance",
+            format!("the key holds {}{}", balance.amount, balance.denom),
+        )
+    }
+}
+
+fn check_gas_price(gas_price: &GasPrice, min_gas_prices: Option<Vec<GasPrice>>) -> CheckResult {
+    let Some(min_gas_prices) = min_gas_prices else {
+        return CheckResult::skip(
+            "gas price",
+            "the node does not expose its minimum gas prices",
+        );
+    };
+
+    let min_gas_price = min_gas_prices
+        .iter()
+        .find(|min_gas_price| min_gas_price.denom == gas_price.denom);
+
+    match min_gas_price {
+        Some(min_gas_price) if gas_price.partial_cmp(min_gas_price) == Some(Ordering::Less) => {
+            CheckResult::fail(
+                "gas price",
+                format!("{gas_price} is below the minimum gas price of the node, {min_gas_price}"),
+            )
+        }
+        Some(min_gas_price) => CheckResult::pass(
+            "gas price",
+            format!(
+                "{gas_price} is at or above the minimum gas price of the node, {min_gas_price}"
+            ),
+        ),
+        None => CheckResult::skip(
+            "gas price",
+            format!("the node has no minimum gas price in {}", gas_price.denom),
+        ),
+    }
+}
+
+fn check_trusting_period(
+    trusting_period: Option<Duration>,
+    unbonding_period: Duration,
+) -> CheckResult {
+    let Some(trusting_period) = trusting_period else {
+        return CheckResult::pass(
+            "trusting period",
+            format!(
+                "defaults to 2/3 of the unbonding period of {}",
+                humantime::format_duration(unbonding_period)
+            ),
+        );
+    };
+
+    if trusting_period.is_zero() {
+        CheckResult::fail("trusting period", "the trusting period is zero")
+    } else if trusting_period >= unbonding_period {
+        CheckResult::fail(
+            "trusting period",
+            format!(
+                "{} is not below the unbonding period of {}",
+                humantime::format_duration(trusting_period),
+                humantime::format_duration(unbonding_period)
+            ),
+        )
+    } else {
+        CheckResult::pass(
+            "trusting period",
+            format!(
+                "{} is below the unbonding period of {}",
+                humantime::format_duration(trusting_period),
+                humantime::format_duration(unbonding_period)
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gas_price(price: f64, denom: &str) -> GasPrice {
+        GasPrice::new(price, denom.to_string())
+    }
+
+    #[test]
+    fn gas_price_against_node_minimum() {
+        let configured = gas_price(0.025, "uatom");
+
+        let check = check_gas_price(&configured, Some(vec![gas_price(0.01, "uatom")]));
+        assert_eq!(check.status, CheckStatus::Pass);
+
+        let check = check_gas_price(&configured, Some(vec![gas_price(0.025, "uatom")]));
+        assert_eq!(check.status, CheckStatus::Pass);
+
+        let check = check_gas_price(
+            &configured,
+            Some(vec![gas_price(0.1, "uosmo"), gas_price(0.05, "uatom")]),
+        );
+        assert_eq!(check.status, CheckStatus::Fail);
+
+        let check = check_gas_price(&configured, Some(vec![gas_price(0.1, "uosmo")]));
+        assert_eq!(check.status, CheckStatus::Skip);
+
+        let check = check_gas_price(&configured, None);
+        assert_eq!(check.status, CheckStatus::Skip);
+    }
+
+    #[test]
+    fn trusting_period_against_unbonding_period() {
+        let unbonding_period = Duration::from_secs(21 * 24 * 3600);
+
+        let check = check_trusting_period(None, unbonding_period);
+        assert_eq!(check.status, CheckStatus::Pass);
+
+        let check =
+            check_trusting_period(Some(Duration::from_secs(14 * 24 * 3600)), unbonding_period);
+        assert_eq!(check.status, CheckStatus::Pass);
+
+        let check = check_trusting_period(Some(unbonding_period), unbonding_period);
+        assert_eq!(check.status, CheckStatus::Fail);
+
+        let check = check_trusting_period(Some(Duration::ZERO), unbonding_period);
+        assert_eq!(check.status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn balance_must_be_funded() {
+        let balance = |amount: &str| Balance {
+            amount: amount.to_string(),
+            denom: "uatom".to_string(),
+        };
+
+        assert_eq!(check_balance(&balance("1000")).status, CheckStatus::Pass);
+        assert_eq!(check_balance(&balance("0")).status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn report_fails_on_any_failed_check() {
+        let mut report = ChainReport {
+            chain_id: ChainId::from_string("ibc-0"),
+            checks: vec![
+                CheckResult::pass("rpc", "connected"),
+                CheckResult::skip("gas price", "unknown"),
+            ],
+        };
+        assert!(report.passed());
+
+        report.checks.push(CheckResult::fail("key", "not found"));
+        assert!(!report.passed());
+        assert_eq!(
+            report.to_string(),
+            "ibc-0: FAIL\n  PASS rpc: connected\n  SKIP gas price: unknown\n  FAIL key: not found\n"
+        );
+    }
+
+    #[test]
+    fn chain_id_reported_by_the_node() {
+        let chain_id = ChainId::from_string("ibc-0");
+
+        assert_eq!(check_chain_id(&chain_id, "ibc-0").status, CheckStatus::Pass);
+        assert_eq!(check_chain_id(&chain_id, "ibc-1").status, CheckStatus::Fail);
+    }
+
+    #[test]
+    fn node_must_be_in_sync() {
+        assert_eq!(check_sync(false, 100).status, CheckStatus::Pass);
+
+        let check = check_sync(true, 100);
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(check.detail, "the node is catching up, at height 100");
+    }
+}
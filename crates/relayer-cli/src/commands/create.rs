@@ -4,10 +4,12 @@ use abscissa_core::{Command, Runnable};
 
 use crate::commands::create::channel::CreateChannelCommand;
 use crate::commands::create::connection::CreateConnectionCommand;
+use crate::commands::create::path::CreatePathCommand;
 use crate::commands::tx::client::TxCreateClientCmd;
 
 mod channel;
 mod connection;
+mod path;
 
 /// `create` subcommands
 #[derive(Command, Debug, Parser, Runnable)]
@@ -24,4 +26,10 @@ pub enum CreateCmds {
     /// alternatively, create a new client and a new connection underlying
     /// the new channel if a pre-existing connection is not provided.
     Channel(CreateChannelCommand),
+
+    /// Create a new path between two chains, ie. new clients, a new connection and a new channel.
+    ///
+    /// Records the objects created so far in a plan file, and resumes from the last
+    /// completed step when re-run with the same plan file.
+    Path(CreatePathCommand),
 }
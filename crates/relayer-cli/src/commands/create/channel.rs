@@ -238,9 +238,13 @@ impl CreateChannelCommand {
             .map(|(cs, _)| cs.chain_id())
             .unwrap_or_else(exit_with_unrecoverable_error);
 
-        // Spawn the runtime for side b.
-        let chain_b =
-            spawn_chain_runtime(&config, &chain_b).unwrap_or_else(exit_with_unrecoverable_error);
+        // Spawn the runtime for side b, unless the connection is the localhost connection,
+        // in which case both sides are on chain a.
+        let chain_b = if chain_b == chain_a.id() {
+            chain_a.clone()
+        } else {
+            spawn_chain_runtime(&config, &chain_b).unwrap_or_else(exit_with_unrecoverable_error)
+        };
 
         // Create the foreign client handles.
         let client_a = ForeignClient::find(chain_b.clone(), chain_a.clone(), conn_end.client_id())
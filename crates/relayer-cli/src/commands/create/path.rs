@@ -0,0 +1,567 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use abscissa_core::clap::Parser;
+use eyre::eyre;
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::QueryHeight;
+use ibc_relayer::channel::Channel;
+use ibc_relayer::config::default::connection_delay;
+use ibc_relayer::config::Config;
+use ibc_relayer::connection::Connection;
+use ibc_relayer::foreign_client::ForeignClient;
+use ibc_relayer::object::{Channel as ChannelObject, Connection as ConnectionObject};
+use ibc_relayer_types::core::ics04_channel::channel::Ordering;
+use ibc_relayer_types::core::ics04_channel::version::Version;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+
+use crate::cli_utils::ChainHandlePair;
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// Maximum number of handshake rounds before giving up on a connection or channel handshake.
+/// The plan file allows to resume the handshake from where it stopped.
+const MAX_HANDSHAKE_ROUNDS: usize = 20;
+
+/// Delay between two handshake rounds, to let the chains commit the previous step.
+const HANDSHAKE_ROUND_DELAY: Duration = Duration::from_secs(1);
+
+/// Create a new path between two chains, ie. new clients, a new connection and a new channel.
+///
+/// The identifiers of the objects created so far are recorded in the plan file after every
+/// step, so that re-running the command with the same plan file resumes the creation from the
+/// last completed step instead of starting over. Re-running it once the path is fully open
+/// does not create anything.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct CreatePathCommand {
+    #[clap(
+        long = "a-chain",
+        required = true,
+        value_name = "A_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the side `a` chain for the new path"
+    )]
+    chain_a: ChainId,
+
+    #[clap(
+        long = "b-chain",
+        required = true,
+        value_name = "B_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the side `b` chain for the new path"
+    )]
+    chain_b: ChainId,
+
+    #[clap(
+        long = "a-port",
+        required = true,
+        value_name = "A_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the side `a` port for the new channel"
+    )]
+    port_a: PortId,
+
+    #[clap(
+        long = "b-port",
+        required = true,
+        value_name = "B_PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the side `b` port for the new channel"
+    )]
+    port_b: PortId,
+
+    #[clap(
+        long = "plan-file",
+        required = true,
+        value_name = "PATH",
+        help_heading = "REQUIRED",
+        help = "Path to the file recording the objects created so far. \
+                If the file exists, the path creation resumes from the last completed step"
+    )]
+    plan_file: PathBuf,
+
+    #[clap(
+        long = "order",
+        value_name = "ORDER",
+        help = "The channel ordering, valid options 'unordered' (default) and 'ordered'",
+        default_value_t
+    )]
+    order: Ordering,
+
+    #[clap(
+        long = "channel-version",
+        visible_alias = "chan-version",
+        value_name = "VERSION",
+        help = "The version for the new channel"
+    )]
+    version: Option<Version>,
+}
+
+impl Runnable for CreatePathCommand {
+    fn run(&self) {
+        let config = app_config();
+
+        match self.create_path(&config) {
+            Ok(plan) => Output::success(plan).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+/// The identifiers of the objects of a path, as recorded in the plan file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathPlan {
+    pub a_chain: ChainId,
+    pub b_chain: ChainId,
+    pub a_port: PortId,
+    pub b_port: PortId,
+    /// The client on chain `a` tracking chain `b`
+    pub a_client: Option<ClientId>,
+    /// The client on chain `b` tracking chain `a`
+    pub b_client: Option<ClientId>,
+    pub a_connection: Option<ConnectionId>,
+    pub b_connection: Option<ConnectionId>,
+    pub a_channel: Option<ChannelId>,
+    pub b_channel: Option<ChannelId>,
+}
+
+impl PathPlan {
+    fn new(a_chain: ChainId, b_chain: ChainId, a_port: PortId, b_port: PortId) -> Self {
+        Self {
+            a_chain,
+            b_chain,
+            a_port,
+            b_port,
+            a_client: None,
+            b_client: None,
+            a_connection: None,
+            b_connection: None,
+            a_channel: None,
+            b_channel: None,
+        }
+    }
+
+    /// Load the plan from the given file, or start a new plan if the file does not exist.
+    fn load_or_new(
+        path: &Path,
+        a_chain: &ChainId,
+        b_chain: &ChainId,
+        a_port: &PortId,
+        b_port: &PortId,
+    ) -> eyre::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::new(
+                a_chain.clone(),
+                b_chain.clone(),
+                a_port.clone(),
+                b_port.clone(),
+            ));
+        }
+
+        let contents = fs::read_to_string(path)
+            .map_err(|e| eyre!("failed to read plan file {}: {}", path.display(), e))?;
+        let plan: Self = serde_json::from_str(&contents)
+            .map_err(|e| eyre!("failed to parse plan file {}: {}", path.display(), e))?;
+
+        if (&plan.a_chain, &plan.b_chain, &plan.a_port, &plan.b_port)
+            != (a_chain, b_chain, a_port, b_port)
+        {
+            return Err(eyre!(
+                "plan file {} is for the path {}/{} <-> {}/{}, not {}/{} <-> {}/{}",
+                path.display(),
+                plan.a_chain,
+                plan.a_port,
+                plan.b_chain,
+                plan.b_port,
+                a_chain,
+                a_port,
+                b_chain,
+                b_port
+            ));
+        }
+
+        Ok(plan)
+    }
+
+    /// Save the plan to the given file, by writing it to a temporary file next to it
+    /// and renaming it, so that an interrupted write never corrupts an existing plan.
+    fn save(&self, path: &Path) -> eyre::Result<()> {
+        let contents = serde_json::to_string_pretty(self)?;
+
+        let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".tmp");
+        let tmp_path = path.with_file_name(tmp_name);
+
+        fs::write(&tmp_path, contents)
+            .map_err(|e| eyre!("failed to write plan file {}: {}", tmp_path.display(), e))?;
+
+        fs::rename(&tmp_path, path)
+            .map_err(|e| eyre!("failed to write plan file {}: {}", path.display(), e))
+    }
+}
+
+impl CreatePathCommand {
+    fn create_path(&self, config: &Config) -> eyre::Result<PathPlan> {
+        let mut plan = PathPlan::load_or_new(
+            &self.plan_file,
+            &self.chain_a,
+            &self.chain_b,
+            &self.port_a,
+            &self.port_b,
+        )?;
+
+        let chains = ChainHandlePair::spawn(config, &self.chain_a, &self.chain_b)?;
+
+        let client_a = match &plan.a_client {
+            Some(client_id) => {
+                ForeignClient::find(chains.dst.clone(), chains.src.clone(), client_id)?
+            }
+            None => ForeignClient::new(chains.src.clone(), chains.dst.clone())?,
+        };
+        plan.a_client = Some(client_a.id().clone());
+        plan.save(&self.plan_file)?;
+
+        let client_b = match &plan.b_client {
+            Some(client_id) => {
+                ForeignClient::find(chains.src.clone(), chains.dst.clone(), client_id)?
+            }
+            None => ForeignClient::new(chains.dst.clone(), chains.src.clone())?,
+        };
+        plan.b_client = Some(client_b.id().clone());
+        plan.save(&self.plan_file)?;
+
+        let connection_a = match &plan.a_connection {
+            Some(connection_id) => connection_id.clone(),
+            None => {
+                info!(
+                    "creating a new connection between {} and {}",
+                    self.chain_a, self.chain_b
+                );
+
+                let connection = Connection::init(client_a, client_b, connection_delay())?;
+                connection
+                    .a_connection_id()
+                    .cloned()
+                    .ok_or_else(|| eyre!("missing connection identifier after ConnOpenInit"))?
+            }
+        };
+        plan.a_connection = Some(connection_a.clone());
+        plan.save(&self.plan_file)?;
+
+        let connection = self.complete_connection(&chains, connection_a, &mut plan)?;
+
+        let channel_a = match &plan.a_channel {
+            Some(channel_id) => channel_id.clone(),
+            None => {
+                info!(
+                    "creating a new channel with order {} between {} and {}",
+                    self.order, self.chain_a, self.chain_b
+                );
+
+                let channel = Channel::init(
+                    connection,
+                    self.order,
+                    self.port_a.clone(),
+                    self.port_b.clone(),
+                    self.version.clone(),
+                )?;
+                channel
+                    .a_channel_id()
+                    .cloned()
+                    .ok_or_else(|| eyre!("missing channel identifier after ChanOpenInit"))?
+            }
+        };
+        plan.a_channel = Some(channel_a.clone());
+        plan.save(&self.plan_file)?;
+
+        self.complete_channel(&chains, channel_a, &mut plan)?;
+
+        Ok(plan)
+    }
+
+    /// Drive the handshake of the connection with the given identifier on chain `a`
+    /// until both ends are open, restoring the connection from the chain state at every round.
+    fn complete_connection(
+        &self,
+        chains: &ChainHandlePair,
+        connection_a: ConnectionId,
+        plan: &mut PathPlan,
+    ) -> eyre::Result<Connection<BaseChainHandle, BaseChainHandle>> {
+        for _ in 0..MAX_HANDSHAKE_ROUNDS {
+            let (mut connection, a_state) = Connection::restore_from_state(
+                chains.src.clone(),
+                chains.dst.clone(),
+                ConnectionObject {
+                    dst_chain_id: self.chain_b.clone(),
+                    src_chain_id: self.chain_a.clone(),
+                    src_connection_id: connection_a.clone(),
+                },
+                chains.src.query_latest_height()?,
+            )?;
+
+            if let Some(connection_b) = connection.b_connection_id() {
+                plan.b_connection = Some(connection_b.clone());
+                plan.save(&self.plan_file)?;
+            }
+
+            let b_state = connection.counterparty_state()?;
+            if a_state.is_open() && b_state.is_open() {
+                info!("connection {} is open on both chains", connection_a);
+                return Ok(connection);
+            }
+
+            let (event, _) = connection.handshake_step(a_state)?;
+
+            // Some steps can only be taken from the side of chain `b`, eg. the `ConnOpenAck`
+            // to chain `a` once chain `b` is in `TryOpen`.
+            if event.is_none() {
+                if let Some(connection_b) = plan.b_connection.clone() {
+                    let (mut connection, b_state) = Connection::restore_from_state(
+                        chains.dst.clone(),
+                        chains.src.clone(),
+                        ConnectionObject {
+                            dst_chain_id: self.chain_a.clone(),
+                            src_chain_id: self.chain_b.clone(),
+                            src_connection_id: connection_b,
+                        },
+                        chains.dst.query_latest_height()?,
+                    )?;
+
+                    connection.handshake_step(b_state)?;
+                }
+            }
+
+            thread::sleep(HANDSHAKE_ROUND_DELAY);
+        }
+
+        Err(eyre!(
+            "connection {} is not open after {} handshake rounds, re-run the command to resume",
+            connection_a,
+            MAX_HANDSHAKE_ROUNDS
+        ))
+    }
+
+    /// Drive the handshake of the channel with the given identifier on chain `a`
+    /// until both ends are open, restoring the channel from the chain state at every round.
+    fn complete_channel(
+        &self,
+        chains: &ChainHandlePair,
+        channel_a: ChannelId,
+        plan: &mut PathPlan,
+    ) -> eyre::Result<()> {
+        for _ in 0..MAX_HANDSHAKE_ROUNDS {
+            let (mut channel, a_state) = Channel::restore_from_state(
+                chains.src.clone(),
+                chains.dst.clone(),
+                ChannelObject {
+                    dst_chain_id: self.chain_b.clone(),
+                    src_chain_id: self.chain_a.clone(),
+                    src_channel_id: channel_a.clone(),
+                    src_port_id: self.port_a.clone(),
+                },
+                QueryHeight::Latest,
+            )?;
+
+            if let Some(channel_b) = channel.b_channel_id() {
+                plan.b_channel = Some(channel_b.clone());
+                plan.save(&self.plan_file)?;
+            }
+
+            let b_state = channel.counterparty_state()?;
+            if a_state.is_open() && b_state.is_open() {
+                info!("channel {} is open on both chains", channel_a);
+                return Ok(());
+            }
+
+            let (event, _) = channel.handshake_step(a_state)?;
+
+            // Some steps can only be taken from the side of chain `b`, eg. the `ChanOpenAck`
+            // to chain `a` once chain `b` is in `TryOpen`.
+            if event.is_none() {
+                if let Some(channel_b) = plan.b_channel.clone() {
+                    let (mut channel, b_state) = Channel::restore_from_state(
+                        chains.dst.clone(),
+                        chains.src.clone(),
+                        ChannelObject {
+                            dst_chain_id: self.chain_a.clone(),
+                            src_chain_id: self.chain_b.clone(),
+                            src_channel_id: channel_b,
+                            src_port_id: self.port_b.clone(),
+                        },
+                        QueryHeight::Latest,
+                    )?;
+
+                    channel.handshake_step(b_state)?;
+                }
+            }
+
+            thread::sleep(HANDSHAKE_ROUND_DELAY);
+        }
+
+        Err(eyre!(
+            "channel {} is not open after {} handshake rounds, re-run the command to resume",
+            channel_a,
+            MAX_HANDSHAKE_ROUNDS
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use super::{CreatePathCommand, PathPlan};
+    use abscissa_core::clap::Parser;
+
+    use ibc_relayer_types::core::ics04_channel::channel::Ordering;
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId, PortId};
+
+    fn path_ids() -> (ChainId, ChainId, PortId, PortId) {
+        (
+            ChainId::from_string("chain_a"),
+            ChainId::from_string("chain_b"),
+            PortId::transfer(),
+            PortId::transfer(),
+        )
+    }
+
+    #[test]
+    fn test_load_or_new_without_plan_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        let (a_chain, b_chain, a_port, b_port) = path_ids();
+
+        let plan = PathPlan::load_or_new(&path, &a_chain, &b_chain, &a_port, &b_port).unwrap();
+
+        assert_eq!(plan, PathPlan::new(a_chain, b_chain, a_port, b_port));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_save_and_load_plan() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        let (a_chain, b_chain, a_port, b_port) = path_ids();
+
+        let mut plan = PathPlan::new(
+            a_chain.clone(),
+            b_chain.clone(),
+            a_port.clone(),
+            b_port.clone(),
+        );
+        plan.a_client = Some(ClientId::from_str("07-tendermint-0").unwrap());
+        plan.save(&path).unwrap();
+
+        // Saving again replaces the plan, without leaving the temporary file behind
+        plan.b_client = Some(ClientId::from_str("07-tendermint-1").unwrap());
+        plan.save(&path).unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+
+        let loaded = PathPlan::load_or_new(&path, &a_chain, &b_chain, &a_port, &b_port).unwrap();
+        assert_eq!(loaded, plan);
+    }
+
+    #[test]
+    fn test_load_plan_of_another_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        let (a_chain, b_chain, a_port, b_port) = path_ids();
+
+        PathPlan::new(
+            a_chain.clone(),
+            b_chain.clone(),
+            a_port.clone(),
+            b_port.clone(),
+        )
+        .save(&path)
+        .unwrap();
+
+        let other_chain = ChainId::from_string("chain_c");
+        let loaded = PathPlan::load_or_new(&path, &a_chain, &other_chain, &a_port, &b_port);
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn test_create_path() {
+        assert_eq!(
+            CreatePathCommand {
+                chain_a: ChainId::from_string("chain_a"),
+                chain_b: ChainId::from_string("chain_b"),
+                port_a: PortId::from_str("port_id_a").unwrap(),
+                port_b: PortId::from_str("port_id_b").unwrap(),
+                plan_file: PathBuf::from("plan.json"),
+                order: Ordering::Unordered,
+                version: None,
+            },
+            CreatePathCommand::parse_from([
+                "test",
+                "--a-chain",
+                "chain_a",
+                "--b-chain",
+                "chain_b",
+                "--a-port",
+                "port_id_a",
+                "--b-port",
+                "port_id_b",
+                "--plan-file",
+                "plan.json"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_create_path_order_and_version() {
+        assert_eq!(
+            CreatePathCommand {
+                chain_a: ChainId::from_string("chain_a"),
+                chain_b: ChainId::from_string("chain_b"),
+                port_a: PortId::from_str("port_id_a").unwrap(),
+                port_b: PortId::from_str("port_id_b").unwrap(),
+                plan_file: PathBuf::from("plan.json"),
+                order: Ordering::Ordered,
+                version: Some(Version::new("v1".to_owned())),
+            },
+            CreatePathCommand::parse_from([
+                "test",
+                "--a-chain",
+                "chain_a",
+                "--b-chain",
+                "chain_b",
+                "--a-port",
+                "port_id_a",
+                "--b-port",
+                "port_id_b",
+                "--plan-file",
+                "plan.json",
+                "--order",
+                "ordered",
+                "--channel-version",
+                "v1"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_create_path_no_plan_file() {
+        assert!(CreatePathCommand::try_parse_from([
+            "test",
+            "--a-chain",
+            "chain_a",
+            "--b-chain",
+            "chain_b",
+            "--a-port",
+            "port_id_a",
+            "--b-port",
+            "port_id_b"
+        ])
+        .is_err())
+    }
+}
@@ -5,6 +5,8 @@ use std::thread::sleep;
 use std::time::Duration;
 
 use abscissa_core::clap::Parser;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::Protobuf;
 use ibc_relayer::config::{ChainConfig, Config};
 use tokio::runtime::Runtime as TokioRuntime;
 
@@ -19,13 +21,19 @@ use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
 use ibc_relayer::chain::requests::{IncludeProof, PageRequest, QueryHeight};
 use ibc_relayer::chain::tracking::TrackedMsgs;
 use ibc_relayer::foreign_client::{fetch_ccv_consumer_id, ForeignClient};
+use ibc_relayer::misbehaviour::archive::{EvidenceArchive, EvidenceRecord, SubmissionOutcome};
+use ibc_relayer::misbehaviour::MisbehaviourEvidence;
 use ibc_relayer::spawn::spawn_chain_runtime_with_modified_config;
 use ibc_relayer_types::applications::ics28_ccv::msgs::ccv_double_voting::MsgSubmitIcsConsumerDoubleVoting;
 use ibc_relayer_types::applications::ics28_ccv::msgs::ccv_misbehaviour::MsgSubmitIcsConsumerMisbehaviour;
 use ibc_relayer_types::clients::ics07_tendermint::header::Header as TendermintHeader;
 use ibc_relayer_types::clients::ics07_tendermint::misbehaviour::Misbehaviour as TendermintMisbehaviour;
+use ibc_relayer_types::core::ics02_client::header::AnyHeader;
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics02_client::msgs::misbehaviour::MsgSubmitMisbehaviour;
+use ibc_relayer_types::core::ics02_client::msgs::update_client::{
+    MsgUpdateClient, TYPE_URL as UPDATE_CLIENT_TYPE_URL,
+};
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::tx_msg::Msg;
@@ -482,31 +490,125 @@ fn handle_light_client_attack(
             chain_handle.clone(),
         );
 
+        let common_height = Height::from_tm(evidence.common_height, chain.id());
+
+        let counterparty_has_common_consensus_state = has_consensus_state(
+            &counterparty_chain_handle,
+            &counterparty_client_id,
+            common_height,
+        );
+
+        // The client updates bringing the counterparty client to the common height
+        // are the headers supporting the misbehaviour.
+        let update_msgs = if counterparty_has_common_consensus_state {
+            info!(
+                "skip building update client message for client `{}` on counterparty chain `{}`",
+                counterparty_client_id,
+                counterparty_chain_handle.id()
+            );
+            info!(
+                "reason: counterparty chain already has consensus state at common height {common_height}"
+            );
+
+            Vec::new()
+        } else {
+            build_common_height_update(&counterparty_client, common_height)
+        };
+
+        let supporting_headers = supporting_headers(&update_msgs);
+
         let result = submit_light_client_attack_evidence(
-            &evidence,
-            chain,
             counterparty_client,
-            counterparty_client_id,
             &counterparty_chain_handle,
-            misbehaviour,
+            misbehaviour.clone(),
+            common_height,
+            counterparty_has_common_consensus_state,
+            update_msgs,
         );
 
-        if let Err(error) = result {
-            error!("{error}");
-        }
+        let submission = match result {
+            Ok(tx_hashes) => SubmissionOutcome::Submitted { details: tx_hashes },
+            Err(error) => {
+                error!("{error}");
+
+                SubmissionOutcome::Failed {
+                    error: error.to_string(),
+                }
+            }
+        };
+
+        archive_evidence(
+            config,
+            EvidenceRecord::new(
+                chain.id().clone(),
+                counterparty_chain_id,
+                counterparty_client_id,
+                MisbehaviourEvidence {
+                    misbehaviour: misbehaviour.into(),
+                    supporting_headers,
+                },
+                submission,
+            ),
+        );
     }
 
     Ok(())
 }
 
+fn archive_evidence(config: &Config, record: EvidenceRecord) {
+    match EvidenceArchive::from_config(config).and_then(|archive| archive.store(&record)) {
+        Ok(path) => info!(
+            "light client attack evidence archived in {}",
+            path.display()
+        ),
+        Err(e) => warn!("failed to archive light client attack evidence: {e}"),
+    }
+}
+
+/// Build the messages updating the given counterparty client to the common height
+/// of the light client attack.
+fn build_common_height_update(
+    counterparty_client: &ForeignClient<BaseChainHandle, BaseChainHandle>,
+    common_height: Height,
+) -> Vec<Any> {
+    match counterparty_client.wait_and_build_update_client(common_height) {
+        Ok(msgs) => msgs,
+
+        Err(e) => {
+            warn!(
+                "skipping UpdateClient message for client `{}` on counterparty chain `{}`",
+                counterparty_client.id(),
+                counterparty_client.dst_chain().id()
+            );
+            warn!("reason: failed to build UpdateClient message: {e}");
+
+            Vec::new()
+        }
+    }
+}
+
+/// The headers carried by the given client update messages.
+fn supporting_headers(update_msgs: &[Any]) -> Vec<AnyHeader> {
+    update_msgs
+        .iter()
+        .filter(|msg| msg.type_url == UPDATE_CLIENT_TYPE_URL)
+        .filter_map(|msg| MsgUpdateClient::decode_vec(&msg.value).ok())
+        .filter_map(|msg| AnyHeader::try_from(msg.header).ok())
+        .collect()
+}
+
+/// Submit the light client attack evidence to the given counterparty client,
+/// after the given client updates, returning the hashes of the submitted transactions.
 fn submit_light_client_attack_evidence(
-    evidence: &LightClientAttackEvidence,
-    chain: &CosmosSdkChain,
     counterparty_client: ForeignClient<BaseChainHandle, BaseChainHandle>,
-    counterparty_client_id: ClientId,
     counterparty: &BaseChainHandle,
     misbehaviour: TendermintMisbehaviour,
-) -> Result<(), eyre::Error> {
+    common_height: Height,
+    counterparty_has_common_consensus_state: bool,
+    update_msgs: Vec<Any>,
+) -> Result<Vec<String>, eyre::Error> {
+    let counterparty_client_id = counterparty_client.id().clone();
+
     info!(
         "building light client attack evidence for client `{}` on counterparty chain `{}`",
         counterparty_client_id,
@@ -516,38 +618,8 @@ fn submit_light_client_attack_evidence(
     let counterparty_client_is_frozen = counterparty_client.is_frozen();
 
     let signer = counterparty.get_signer()?;
-    let common_height = Height::from_tm(evidence.common_height, chain.id());
-
-    let counterparty_has_common_consensus_state =
-        has_consensus_state(counterparty, &counterparty_client_id, common_height);
 
-    let mut msgs = if counterparty_has_common_consensus_state {
-        info!(
-            "skip building update client message for client `{}` on counterparty chain `{}`",
-            counterparty_client_id,
-            counterparty.id()
-        );
-        info!(
-                "reason: counterparty chain already has consensus state at common height {common_height}"
-            );
-
-        Vec::new()
-    } else {
-        match counterparty_client.wait_and_build_update_client(common_height) {
-            Ok(msgs) => msgs,
-
-            Err(e) => {
-                warn!(
-                    "skipping UpdateClient message for client `{}` on counterparty chain `{}`",
-                    counterparty_client_id,
-                    counterparty.id()
-                );
-                warn!("reason: failed to build UpdateClient message: {e}");
-
-                Vec::new()
-            }
-        }
-    };
+    let mut msgs = update_msgs;
 
     if let Ok(consumer_id) = fetch_ccv_consumer_id(counterparty, &counterparty_client_id) {
         if counterparty_client_is_frozen && !counterparty_has_common_consensus_state {
@@ -558,7 +630,7 @@ fn submit_light_client_attack_evidence(
             );
             warn!("reason: client is frozen and does not have a consensus state at height {common_height}");
 
-            return Ok(());
+            return Ok(vec![]);
         }
         info!(
             "will submit consumer light client attack evidence to client `{}` on provider chain `{}`",
@@ -602,7 +674,7 @@ fn submit_light_client_attack_evidence(
 
         warn!("reason: no messages to submit");
 
-        return Ok(());
+        return Ok(vec![]);
     }
 
     let tracked_msgs = TrackedMsgs::new_static(msgs, "light_client_attack_evidence");
@@ -617,7 +689,7 @@ fn submit_light_client_attack_evidence(
                 response.hash
             );
 
-            Ok(())
+            Ok(vec![response.hash.to_string()])
         }
         Some(response) => Err(eyre::eyre!(
             "failed to submit light client attack evidence to counterparty chain `{}`: {response:?}",
@@ -681,7 +753,7 @@ fn fetch_all_counterparty_clients(
             connection.connection_id
         );
 
-        if client_id.as_str() == "09-localhost" {
+        if client_id.is_localhost() {
             debug!("skipping localhost client `{client_id}`...");
             continue;
         }
@@ -175,6 +175,9 @@ fn subscribe(
                         rt,
                     )
                 }
+                EventSourceMode::Replay { path, speed } => {
+                    EventSource::replay(chain_config.id().clone(), path.clone(), *speed)
+                }
             }?;
 
             thread::spawn(move || event_source.run());
@@ -201,6 +204,9 @@ fn subscribe(
                     *max_retries,
                     rt,
                 ),
+                EventSourceMode::Replay { path, speed } => {
+                    EventSource::replay(chain_config.id().clone(), path.clone(), *speed)
+                }
             }?;
 
             thread::spawn(move || event_source.run());
@@ -208,6 +214,7 @@ fn subscribe(
             let subscription = monitor_tx.subscribe()?;
             Ok(subscription)
         }
+        ChainConfig::SoloMachine(_) => Err(solo_machine_unsupported()),
     }
 }
 
@@ -218,6 +225,7 @@ fn detect_compatibility_mode(
     let rpc_addr = match config {
         ChainConfig::CosmosSdk(config) | ChainConfig::Namada(config) => config.rpc_addr.clone(),
         ChainConfig::Penumbra(config) => config.rpc_addr.clone(),
+        ChainConfig::SoloMachine(_) => return Err(solo_machine_unsupported()),
     };
 
     let client = HttpClient::builder(rpc_addr.try_into()?)
@@ -232,11 +240,17 @@ fn detect_compatibility_mode(
             let status = rt.block_on(client.status())?;
             penumbra::util::compat_mode_from_version(&config.compat_mode, status.node_info.version)?
         }
+        ChainConfig::SoloMachine(_) => return Err(solo_machine_unsupported()),
     };
 
     Ok(compat_mode)
 }
 
+/// The events of a solo machine are only emitted to the Hermes instance which runs it.
+fn solo_machine_unsupported() -> eyre::Report {
+    eyre!("listening to the events of a solo machine is not supported")
+}
+
 #[cfg(test)]
 mod tests {
     use super::{EventFilter, ListenCmd};
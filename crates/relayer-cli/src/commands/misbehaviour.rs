@@ -4,6 +4,7 @@ use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
 use ibc_relayer::config::Config;
 use ibc_relayer::foreign_client::{ForeignClient, MisbehaviourResults};
+use ibc_relayer::misbehaviour::archive::EvidenceArchive;
 use ibc_relayer::util::pretty::PrettySlice;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
@@ -16,8 +17,14 @@ use crate::prelude::*;
 use eyre::eyre;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 
+mod submit;
+
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+#[clap(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct MisbehaviourCmd {
+    #[clap(subcommand)]
+    subcommand: Option<MisbehaviourSubCmd>,
+
     #[clap(
         long = "chain",
         required = true,
@@ -25,7 +32,7 @@ pub struct MisbehaviourCmd {
         help_heading = "REQUIRED",
         help = "Identifier of the chain where client updates are monitored for misbehaviour"
     )]
-    chain_id: ChainId,
+    chain_id: Option<ChainId>,
 
     #[clap(
         long = "client",
@@ -34,14 +41,26 @@ pub struct MisbehaviourCmd {
         help_heading = "REQUIRED",
         help = "Identifier of the client to be monitored for misbehaviour"
     )]
-    client_id: ClientId,
+    client_id: Option<ClientId>,
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq, Runnable)]
+pub enum MisbehaviourSubCmd {
+    /// Submit an archived misbehaviour evidence to a client of the misbehaving chain
+    Submit(submit::SubmitCmd),
 }
 
 impl Runnable for MisbehaviourCmd {
     fn run(&self) {
+        let (chain_id, client_id) = match (&self.subcommand, &self.chain_id, &self.client_id) {
+            (Some(subcommand), _, _) => return subcommand.run(),
+            (None, Some(chain_id), Some(client_id)) => (chain_id, client_id),
+            _ => Output::error("both `--chain` and `--client` are required").exit(),
+        };
+
         let config = app_config();
 
-        let res = monitor_misbehaviour(&self.chain_id, &self.client_id, &config);
+        let res = monitor_misbehaviour(chain_id, client_id, &config);
         match res {
             Ok(some_event) => Output::success(some_event).exit(),
             Err(e) => Output::error(e).exit(),
@@ -130,7 +149,10 @@ fn misbehaviour_handling<Chain: ChainHandle>(
         })?;
 
     let client = ForeignClient::restore(client_id, chain, counterparty_chain);
-    let result = client.detect_misbehaviour_and_submit_evidence(update);
+    let archive = EvidenceArchive::from_config(config)
+        .map_err(|e| eyre!("could not open the evidence archive: {}", e))?;
+
+    let result = client.detect_misbehaviour_and_submit_evidence(update, Some(&archive));
 
     match result {
         MisbehaviourResults::ValidClient => {
@@ -166,8 +188,9 @@ mod tests {
     fn test_misbehaviour() {
         assert_eq!(
             MisbehaviourCmd {
-                chain_id: ChainId::from_string("chain_id"),
-                client_id: ClientId::from_str("client_id").unwrap()
+                subcommand: None,
+                chain_id: Some(ChainId::from_string("chain_id")),
+                client_id: Some(ClientId::from_str("client_id").unwrap())
             },
             MisbehaviourCmd::parse_from(["test", "--chain", "chain_id", "--client", "client_id"])
         )
@@ -182,4 +205,33 @@ mod tests {
     fn test_misbehaviour_no_chain() {
         assert!(MisbehaviourCmd::try_parse_from(["test", "--client", "client_id"]).is_err())
     }
+
+    #[test]
+    fn test_misbehaviour_submit_conflicts_with_monitoring() {
+        assert!(MisbehaviourCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "submit",
+            "--from-file",
+            "evidence.json",
+            "--chain",
+            "chain_id",
+            "--client",
+            "client_id"
+        ])
+        .is_err());
+
+        assert!(MisbehaviourCmd::try_parse_from([
+            "test",
+            "submit",
+            "--from-file",
+            "evidence.json",
+            "--chain",
+            "chain_id",
+            "--client",
+            "client_id"
+        ])
+        .is_ok());
+    }
 }
@@ -0,0 +1,152 @@
+use std::path::{Path, PathBuf};
+
+use abscissa_core::clap::Parser;
+use abscissa_core::Command;
+use eyre::eyre;
+
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use ibc_relayer::config::Config;
+use ibc_relayer::foreign_client::ForeignClient;
+use ibc_relayer::misbehaviour::archive::load_record;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::cli_utils::spawn_chain_runtime_generic;
+use crate::conclude::Output;
+use crate::prelude::*;
+
+/// Replay an archived misbehaviour evidence against a client of the misbehaving chain
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct SubmitCmd {
+    #[clap(
+        long = "from-file",
+        required = true,
+        value_name = "PATH",
+        help_heading = "REQUIRED",
+        help = "Path to the evidence file, either a `.json` or a `.pb` file of the evidence archive"
+    )]
+    from_file: PathBuf,
+
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain hosting the client to submit the evidence to"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "client",
+        required = true,
+        value_name = "CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the client of the misbehaving chain to submit the evidence to"
+    )]
+    client_id: ClientId,
+}
+
+impl Runnable for SubmitCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        match submit_evidence(&config, &self.from_file, &self.chain_id, &self.client_id) {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+fn submit_evidence(
+    config: &Config,
+    from_file: &Path,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+) -> eyre::Result<Vec<IbcEvent>> {
+    let record = load_record(from_file)?;
+
+    let chain = spawn_chain_runtime_generic::<BaseChainHandle>(config, chain_id)
+        .map_err(|e| eyre!("could not spawn the chain runtime for {}: {}", chain_id, e))?;
+
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| eyre!("could not query client state for {}: {}", client_id, e))?;
+
+    if client_state.chain_id() != record.src_chain_id {
+        return Err(eyre!(
+            "client {} on chain {} tracks chain {}, but the evidence is about chain {}",
+            client_id,
+            chain_id,
+            client_state.chain_id(),
+            record.src_chain_id
+        ));
+    }
+
+    let misbehaving_chain =
+        spawn_chain_runtime_generic::<BaseChainHandle>(config, &record.src_chain_id).map_err(
+            |e| {
+                eyre!(
+                    "could not spawn the chain runtime for {}: {}",
+                    record.src_chain_id,
+                    e
+                )
+            },
+        )?;
+
+    let client = ForeignClient::restore(client_id.clone(), chain, misbehaving_chain);
+
+    info!(
+        "submitting evidence of misbehaviour of chain {} detected at {} to client {} on chain {}",
+        record.src_chain_id, record.detected_at, client_id, chain_id
+    );
+
+    let events = client.submit_evidence(record.evidence_for(client_id))?;
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SubmitCmd;
+
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+    #[test]
+    fn test_misbehaviour_submit() {
+        assert_eq!(
+            SubmitCmd {
+                from_file: PathBuf::from("evidence.json"),
+                chain_id: ChainId::from_string("chain_id"),
+                client_id: ClientId::from_str("client_id").unwrap()
+            },
+            SubmitCmd::parse_from([
+                "test",
+                "--from-file",
+                "evidence.json",
+                "--chain",
+                "chain_id",
+                "--client",
+                "client_id"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_misbehaviour_submit_no_file() {
+        assert!(
+            SubmitCmd::try_parse_from(["test", "--chain", "chain_id", "--client", "client_id"])
+                .is_err()
+        )
+    }
+}
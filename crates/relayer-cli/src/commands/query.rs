@@ -17,6 +17,7 @@ mod clients;
 mod connection;
 mod connections;
 mod packet;
+pub(crate) mod topology;
 mod transfer;
 mod tx;
 
@@ -52,6 +53,9 @@ pub enum QueryCmd {
     #[clap(subcommand)]
     Tx(tx::QueryTxCmd),
 
+    /// Query the topology of the IBC paths between the configured chains
+    Topology(topology::QueryTopologyCmd),
+
     /// Query information about token transfers
     #[clap(subcommand)]
     Transfer(transfer::TransferCmd),
@@ -1,9 +1,22 @@
+use core::time::Duration;
+
 use abscissa_core::clap::Parser;
 use ibc_relayer::chain::handle::ChainHandle;
 use serde::Serialize;
 
-use ibc_relayer::chain::requests::{PageRequest, QueryClientStatesRequest};
-use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer::chain::requests::{
+    IncludeProof, PageRequest, QueryClientStatesRequest, QueryConnectionsRequest,
+    QueryConsensusStateRequest, QueryHeight,
+};
+use ibc_relayer::client_state::IdentifiedAnyClientState;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+use ibc_relayer_types::core::ics03_connection::connection::{
+    IdentifiedConnectionEnd, State as ConnectionState,
+};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height;
 
 use crate::cli_utils::spawn_chain_runtime;
 use crate::conclude::{exit_with_unrecoverable_error, Output};
@@ -34,6 +47,12 @@ pub struct QueryAllClientsCmd {
         help = "Omit printing the reference (or target) chain for each client"
     )]
     omit_chain_ids: bool,
+
+    #[clap(
+        long = "status",
+        help = "Report the status of each client: its type, latest height, last update, time to expiry and the open connections using it"
+    )]
+    status: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -42,6 +61,36 @@ struct ClientChain {
     chain_id: ChainId,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub(crate) enum Status {
+    Frozen,
+    Expired,
+    Active,
+    /// The consensus state of the client could not be queried
+    Unknown,
+}
+
+/// The status of a client, as reported by `query clients --status`.
+#[derive(Debug, Serialize)]
+pub(super) struct ClientStatusReport {
+    pub(super) client_id: ClientId,
+    client_type: ClientType,
+    /// The chain tracked by the client
+    chain_id: ChainId,
+    latest_height: Height,
+    /// Time of the consensus state at the latest height of the client,
+    /// unset if the client has no consensus state
+    last_update: Option<Timestamp>,
+    #[serde(with = "humantime_serde")]
+    trusting_period: Duration,
+    /// Unset if the client has no consensus state or is already expired
+    #[serde(with = "humantime_serde")]
+    time_to_expiry: Option<Duration>,
+    frozen: bool,
+    pub(super) status: Status,
+    open_connections: Vec<ConnectionId>,
+}
+
 /// Command for querying all clients.
 /// hermes --config cfg.toml query clients --chain ibc-1
 impl Runnable for QueryAllClientsCmd {
@@ -58,6 +107,18 @@ impl Runnable for QueryAllClientsCmd {
             .map_err(Error::relayer);
 
         match res {
+            Ok(clients) if self.status => {
+                let clients = clients.into_iter().filter(|cs| {
+                    self.src_chain_id.as_ref().map_or(true, |src_chain_id| {
+                        &cs.client_state.chain_id() == src_chain_id
+                    })
+                });
+
+                match client_status_reports(&chain, clients) {
+                    Ok(reports) => Output::success(reports).exit(),
+                    Err(e) => Output::error(e).exit(),
+                }
+            }
             Ok(clients) => {
                 match self.src_chain_id.clone() {
                     None => {
@@ -106,9 +167,113 @@ impl Runnable for QueryAllClientsCmd {
     }
 }
 
+pub(super) fn client_status_reports(
+    chain: &impl ChainHandle,
+    clients: impl Iterator<Item = IdentifiedAnyClientState>,
+) -> Result<Vec<ClientStatusReport>, Error> {
+    // Fetch the application status once, for the network time
+    let now = chain
+        .query_application_status()
+        .map_err(Error::relayer)?
+        .timestamp;
+
+    let connections = chain
+        .query_connections(QueryConnectionsRequest {
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(Error::relayer)?;
+
+    let reports = clients
+        .map(|client| client_status_report(chain, client, now, &connections))
+        .collect();
+
+    Ok(reports)
+}
+
+fn client_status_report(
+    chain: &impl ChainHandle,
+    client: IdentifiedAnyClientState,
+    now: Timestamp,
+    connections: &[IdentifiedConnectionEnd],
+) -> ClientStatusReport {
+    let IdentifiedAnyClientState {
+        client_id,
+        client_state,
+    } = client;
+
+    // The localhost client has no consensus states and never expires
+    let last_update = if client_state.client_type() == ClientType::Localhost {
+        Ok(None)
+    } else {
+        chain
+            .query_consensus_state(
+                QueryConsensusStateRequest {
+                    client_id: client_id.clone(),
+                    consensus_height: client_state.latest_height(),
+                    query_height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            )
+            .map(|(consensus_state, _)| Some(consensus_state.timestamp()))
+    };
+
+    let elapsed = last_update
+        .as_ref()
+        .ok()
+        .and_then(Option::as_ref)
+        .map(|last_update| now.duration_since(last_update).unwrap_or_default());
+
+    let trusting_period = client_state.trusting_period();
+    let frozen = client_state.is_frozen();
+
+    let status = match &last_update {
+        _ if frozen => Status::Frozen,
+        Err(e) => {
+            warn!("failed to query the latest consensus state of client {client_id}: {e}");
+            Status::Unknown
+        }
+        Ok(_) if elapsed.is_some_and(|elapsed| client_state.expired(elapsed)) => Status::Expired,
+        Ok(_) => Status::Active,
+    };
+
+    let last_update = last_update.ok().flatten();
+
+    let time_to_expiry = elapsed
+        .and_then(|elapsed| trusting_period.checked_sub(elapsed))
+        .filter(|_| !frozen);
+
+    let open_connections = connections
+        .iter()
+        .filter(|connection| {
+            connection.connection_end.client_id() == &client_id
+                && connection
+                    .connection_end
+                    .state_matches(&ConnectionState::Open)
+        })
+        .map(|connection| connection.connection_id.clone())
+        .collect();
+
+    ClientStatusReport {
+        client_type: client_state.client_type(),
+        chain_id: client_state.chain_id(),
+        latest_height: client_state.latest_height(),
+        client_id,
+        last_update,
+        trusting_period,
+        time_to_expiry,
+        frozen,
+        status,
+        open_connections,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::QueryAllClientsCmd;
+    use super::{ClientStatusReport, QueryAllClientsCmd, Status};
+
+    use core::time::Duration;
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+    use ibc_relayer_types::Height;
 
     use abscissa_core::clap::Parser;
     use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -119,7 +284,8 @@ mod tests {
             QueryAllClientsCmd {
                 chain_id: ChainId::from_string("chain_host_id"),
                 src_chain_id: None,
-                omit_chain_ids: false
+                omit_chain_ids: false,
+                status: false
             },
             QueryAllClientsCmd::parse_from(["test", "--host-chain", "chain_host_id"])
         )
@@ -131,7 +297,8 @@ mod tests {
             QueryAllClientsCmd {
                 chain_id: ChainId::from_string("chain_host_id"),
                 src_chain_id: None,
-                omit_chain_ids: true
+                omit_chain_ids: true,
+                status: false
             },
             QueryAllClientsCmd::parse_from([
                 "test",
@@ -148,7 +315,8 @@ mod tests {
             QueryAllClientsCmd {
                 chain_id: ChainId::from_string("chain_host_id"),
                 src_chain_id: Some(ChainId::from_string("reference_chain_id")),
-                omit_chain_ids: false
+                omit_chain_ids: false,
+                status: false
             },
             QueryAllClientsCmd::parse_from([
                 "test",
@@ -160,8 +328,43 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_query_clients_status() {
+        assert_eq!(
+            QueryAllClientsCmd {
+                chain_id: ChainId::from_string("chain_host_id"),
+                src_chain_id: None,
+                omit_chain_ids: false,
+                status: true
+            },
+            QueryAllClientsCmd::parse_from(["test", "--host-chain", "chain_host_id", "--status"])
+        )
+    }
+
     #[test]
     fn test_query_clients_no_chain() {
         assert!(QueryAllClientsCmd::try_parse_from(["test"]).is_err())
     }
+
+    #[test]
+    fn test_client_status_report_durations_are_human_readable() {
+        let report = ClientStatusReport {
+            client_id: "07-tendermint-0".parse().unwrap(),
+            client_type: ClientType::Tendermint,
+            chain_id: ChainId::from_string("ibc-1"),
+            latest_height: Height::new(1, 10).unwrap(),
+            last_update: None,
+            trusting_period: Duration::from_secs(14 * 24 * 3600),
+            time_to_expiry: Some(Duration::from_secs(3 * 3600 + 30 * 60)),
+            frozen: false,
+            status: Status::Unknown,
+            open_connections: vec![],
+        };
+
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["trusting_period"], "14days");
+        assert_eq!(json["time_to_expiry"], "3h 30m");
+        assert_eq!(json["status"], "Unknown");
+    }
 }
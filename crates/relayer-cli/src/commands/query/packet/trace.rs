@@ -0,0 +1,728 @@
+use core::fmt;
+
+use abscissa_core::clap::Parser;
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{
+    IncludeProof, Qualified, QueryClientStateRequest, QueryHeight,
+    QueryPacketAcknowledgementRequest, QueryPacketCommitmentRequest, QueryPacketEventDataRequest,
+    QueryUnreceivedPacketsRequest,
+};
+use ibc_relayer::config::Config;
+use ibc_relayer::event::IbcEventWithTxHash;
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId};
+use ibc_relayer_types::events::WithBlockDataType;
+use ibc_relayer_types::Height;
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Trace the lifecycle of a packet across both ends of its channel.
+///
+/// This command does the following:
+///
+/// 1. finds the `SendPacket` event of the packet on the source chain,
+/// 2. checks the packet commitment on the source chain, and the packet receipt
+///    and acknowledgement on the destination chain,
+/// 3. finds the transactions which received, acknowledged or timed out the packet,
+/// 4. reports the stage at which the packet currently is, and why it may be stuck there.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryPacketTraceCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain which sent the packet"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Port identifier on the chain given by <CHAIN_ID>"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Channel identifier on the chain given by <CHAIN_ID>"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "sequence",
+        visible_alias = "seq",
+        required = true,
+        value_name = "SEQUENCE",
+        help_heading = "REQUIRED",
+        help = "Sequence of the packet to trace"
+    )]
+    sequence: Sequence,
+}
+
+/// The stage of the lifecycle at which a packet is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PacketStage {
+    /// No packet with this sequence was found on the source chain
+    NotFound,
+    /// The packet was sent but not yet received on the destination chain
+    AwaitingReceive,
+    /// The packet timed out on the destination chain, but the timeout was not yet relayed
+    AwaitingTimeout,
+    /// The packet was received but the destination application did not write an acknowledgement yet
+    AwaitingAckWrite,
+    /// The packet was received and acknowledged, but the acknowledgement was not yet relayed
+    AwaitingAck,
+    /// The acknowledgement was relayed to the source chain
+    Acknowledged,
+    /// The timeout was relayed to the source chain
+    TimedOut,
+    /// The packet commitment was cleared, but the transaction which cleared it was not found
+    Completed,
+}
+
+impl fmt::Display for PacketStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self {
+            Self::NotFound => "not found",
+            Self::AwaitingReceive => "awaiting receive",
+            Self::AwaitingTimeout => "awaiting timeout",
+            Self::AwaitingAckWrite => "awaiting acknowledgement write",
+            Self::AwaitingAck => "awaiting acknowledgement",
+            Self::Acknowledged => "acknowledged",
+            Self::TimedOut => "timed out",
+            Self::Completed => "completed",
+        };
+
+        write!(f, "{stage}")
+    }
+}
+
+impl PacketStage {
+    /// Whether the packet reached the end of its lifecycle.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Acknowledged | Self::TimedOut | Self::Completed)
+    }
+}
+
+/// A transaction which took part in the lifecycle of a packet.
+#[derive(Clone, Debug, Serialize)]
+pub struct PacketTx {
+    pub height: Height,
+    pub tx_hash: String,
+}
+
+impl From<IbcEventWithTxHash> for PacketTx {
+    fn from(tx: IbcEventWithTxHash) -> Self {
+        Self {
+            height: tx.height,
+            tx_hash: tx.tx_hash.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for PacketTx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at height {}", self.tx_hash, self.height)
+    }
+}
+
+/// The trace of a packet across both ends of its channel.
+#[derive(Clone, Debug, Serialize)]
+pub struct PacketTrace {
+    pub src_chain: ChainId,
+    pub src_port: PortId,
+    pub src_channel: ChannelId,
+    pub dst_chain: ChainId,
+    pub dst_port: PortId,
+    pub dst_channel: ChannelId,
+    pub sequence: Sequence,
+
+    pub packet: Option<Packet>,
+    pub stage: PacketStage,
+    /// Why the packet may be stuck at its current stage
+    pub reasons: Vec<String>,
+
+    /// Whether the packet commitment is still stored on the source chain
+    pub commitment_on_src: bool,
+    /// Whether the packet was received on the destination chain
+    pub received_on_dst: bool,
+    /// Whether an acknowledgement of the packet is stored on the destination chain
+    pub ack_on_dst: bool,
+
+    pub send_tx: Option<PacketTx>,
+    /// The transaction which received the packet on the destination chain
+    pub recv_tx: Option<PacketTx>,
+    pub ack_tx: Option<PacketTx>,
+    pub timeout_tx: Option<PacketTx>,
+}
+
+impl fmt::Display for PacketTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn tx(tx: &Option<PacketTx>) -> String {
+            tx.as_ref()
+                .map_or_else(|| "not found".to_string(), ToString::to_string)
+        }
+
+        writeln!(
+            f,
+            "Packet {} from {}/{} on {} to {}/{} on {}: {}",
+            self.sequence,
+            self.src_port,
+            self.src_channel,
+            self.src_chain,
+            self.dst_port,
+            self.dst_channel,
+            self.dst_chain,
+            self.stage
+        )?;
+
+        writeln!(f, "  Send tx:                      {}", tx(&self.send_tx))?;
+        writeln!(
+            f,
+            "  Commitment on source:         {}",
+            self.commitment_on_src
+        )?;
+        writeln!(
+            f,
+            "  Received on destination:      {}",
+            self.received_on_dst
+        )?;
+        writeln!(f, "  Recv tx:                      {}", tx(&self.recv_tx))?;
+        writeln!(f, "  Acknowledged on destination:  {}", self.ack_on_dst)?;
+        writeln!(f, "  Ack tx:                       {}", tx(&self.ack_tx))?;
+        writeln!(
+            f,
+            "  Timeout tx:                   {}",
+            tx(&self.timeout_tx)
+        )?;
+
+        if !self.reasons.is_empty() {
+            writeln!(f, "  Reasons:")?;
+            for reason in &self.reasons {
+                writeln!(f, "    - {reason}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl QueryPacketTraceCmd {
+    fn execute(&self) -> Result<PacketTrace, Error> {
+        let config = app_config();
+
+        trace_packet(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+            self.sequence,
+        )
+    }
+}
+
+impl Runnable for QueryPacketTraceCmd {
+    fn run(&self) {
+        use crate::conclude::json;
+
+        match self.execute() {
+            Ok(trace) if json() => Output::success(trace).exit(),
+            Ok(trace) => Output::success_msg(trace.to_string()).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+/// Trace the packet with the given sequence, sent on the given channel of the given chain.
+pub fn trace_packet(
+    config: &Config,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Result<PacketTrace, Error> {
+    let (chains, chan_conn_cli) =
+        spawn_chain_counterparty::<BaseChainHandle>(config, chain_id, port_id, channel_id)?;
+
+    let counterparty = chan_conn_cli.channel.channel_end.counterparty();
+    let dst_port = counterparty.port_id().clone();
+    let dst_channel = counterparty
+        .channel_id()
+        .cloned()
+        .ok_or_else(|| Error::missing_counterparty_channel_id(chan_conn_cli.channel.clone()))?;
+
+    let packet_request = |event_id| QueryPacketEventDataRequest {
+        event_id,
+        source_channel_id: channel_id.clone(),
+        source_port_id: port_id.clone(),
+        destination_channel_id: dst_channel.clone(),
+        destination_port_id: dst_port.clone(),
+        sequences: vec![sequence],
+        height: Qualified::SmallerEqual(QueryHeight::Latest),
+    };
+
+    let send_tx = chains
+        .src
+        .query_packet_txs(packet_request(WithBlockDataType::SendPacket))
+        .map_err(Error::relayer)?
+        .into_iter()
+        .next();
+
+    // The packet may have been sent outside of a transaction, eg. by a begin or end blocker
+    let send_event = match &send_tx {
+        Some(tx) => Some((tx.event.clone(), tx.height)),
+        None => chains
+            .src
+            .query_packet_events(packet_request(WithBlockDataType::SendPacket))
+            .map_err(Error::relayer)?
+            .into_iter()
+            .next()
+            .map(|ev| (ev.event, ev.height)),
+    };
+
+    let packet = send_event
+        .as_ref()
+        .and_then(|(event, _)| event.packet().cloned());
+    let send_height = send_event.map(|(_, height)| height);
+
+    let (commitment, _) = chains
+        .src
+        .query_packet_commitment(
+            QueryPacketCommitmentRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                sequence,
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+    let commitment_on_src = !commitment.is_empty();
+
+    let unreceived = chains
+        .dst
+        .query_unreceived_packets(QueryUnreceivedPacketsRequest {
+            port_id: dst_port.clone(),
+            channel_id: dst_channel.clone(),
+            packet_commitment_sequences: vec![sequence],
+        })
+        .map_err(Error::relayer)?;
+    let received_on_dst = unreceived.is_empty();
+
+    // Chains report a missing acknowledgement either as empty bytes or as an error
+    let ack_on_dst = chains
+        .dst
+        .query_packet_acknowledgement(
+            QueryPacketAcknowledgementRequest {
+                port_id: dst_port.clone(),
+                channel_id: dst_channel.clone(),
+                sequence,
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .is_ok_and(|(ack, _)| !ack.is_empty());
+
+    let first_tx = |chain: &BaseChainHandle, event_id| -> Result<Option<PacketTx>, Error> {
+        Ok(chain
+            .query_packet_txs(packet_request(event_id))
+            .map_err(Error::relayer)?
+            .into_iter()
+            .next()
+            .map(PacketTx::from))
+    };
+
+    let recv_tx = first_tx(&chains.dst, WithBlockDataType::RecvPacket)?;
+    let ack_tx = first_tx(&chains.src, WithBlockDataType::AcknowledgePacket)?;
+    let timeout_tx = first_tx(&chains.src, WithBlockDataType::TimeoutPacket)?;
+
+    // Whether the packet timed out only matters while it is not received
+    let dst_status = if commitment_on_src && !received_on_dst {
+        let status = chains
+            .dst
+            .query_application_status()
+            .map_err(Error::relayer)?;
+
+        Some(status)
+    } else {
+        None
+    };
+
+    let timed_out_on_dst = match (&packet, &dst_status) {
+        (Some(packet), Some(status)) => packet.timed_out(&status.timestamp, status.height),
+        _ => false,
+    };
+
+    let stage = packet_stage(&PacketState {
+        sent: packet.is_some(),
+        commitment_on_src,
+        received_on_dst,
+        ack_on_dst,
+        timed_out_on_dst,
+        ack_relayed: ack_tx.is_some(),
+        timeout_relayed: timeout_tx.is_some(),
+    });
+
+    let mut reasons = vec![];
+
+    match stage {
+        PacketStage::Completed => reasons.push(
+            "the packet commitment was cleared, but no acknowledgement or timeout \
+            transaction was found, the node may have pruned it"
+                .to_string(),
+        ),
+        PacketStage::AwaitingAck => {
+            // The acknowledgement is relayed from the destination back to the source chain,
+            // using the client of the destination chain hosted on the source chain.
+            let recv_height = recv_tx.as_ref().map(|tx| tx.height);
+            client_reasons(
+                &chains.src,
+                &chan_conn_cli.client.client_id,
+                recv_height,
+                &mut reasons,
+            )?;
+        }
+        PacketStage::AwaitingAckWrite => reasons.push(format!(
+            "the application on {} has not written an acknowledgement for the packet yet",
+            chains.dst.id()
+        )),
+        PacketStage::AwaitingTimeout => {
+            if let Some(status) = &dst_status {
+                reasons.push(format!(
+                    "the packet timed out on {} at height {}, the timeout must be relayed to {}",
+                    chains.dst.id(),
+                    status.height,
+                    chains.src.id()
+                ));
+            }
+        }
+        PacketStage::AwaitingReceive => {
+            // The packet is relayed from the source to the destination chain,
+            // using the client of the source chain hosted on the destination chain.
+            client_reasons(
+                &chains.dst,
+                chan_conn_cli
+                    .connection
+                    .connection_end
+                    .counterparty()
+                    .client_id(),
+                send_height,
+                &mut reasons,
+            )?;
+        }
+        PacketStage::NotFound | PacketStage::Acknowledged | PacketStage::TimedOut => {}
+    }
+
+    if !stage.is_final() && stage != PacketStage::NotFound {
+        filter_reasons(config, &chains.src.id(), port_id, channel_id, &mut reasons);
+        filter_reasons(
+            config,
+            &chains.dst.id(),
+            &dst_port,
+            &dst_channel,
+            &mut reasons,
+        );
+
+        if !config.mode.packets.enabled {
+            reasons.push("packet relaying is disabled in the configuration".to_string());
+        }
+    }
+
+    Ok(PacketTrace {
+        src_chain: chains.src.id(),
+        src_port: port_id.clone(),
+        src_channel: channel_id.clone(),
+        dst_chain: chains.dst.id(),
+        dst_port,
+        dst_channel,
+        sequence,
+        packet,
+        stage,
+        reasons,
+        commitment_on_src,
+        received_on_dst,
+        ack_on_dst,
+        send_tx: send_tx.map(PacketTx::from),
+        recv_tx,
+        ack_tx,
+        timeout_tx,
+    })
+}
+
+/// What is known of a packet on both chains, from which the stage of its lifecycle is derived.
+#[derive(Copy, Clone, Debug, Default)]
+struct PacketState {
+    /// Whether the event sending the packet was found on the source chain
+    sent: bool,
+    commitment_on_src: bool,
+    received_on_dst: bool,
+    ack_on_dst: bool,
+    /// Whether the packet timed out on the destination chain
+    timed_out_on_dst: bool,
+    /// Whether the transaction relaying the acknowledgement to the source chain was found
+    ack_relayed: bool,
+    /// Whether the transaction relaying the timeout to the source chain was found
+    timeout_relayed: bool,
+}
+
+/// The stage of the lifecycle at which a packet is, given what is known of it.
+fn packet_stage(state: &PacketState) -> PacketStage {
+    if !state.commitment_on_src {
+        if state.ack_relayed {
+            PacketStage::Acknowledged
+        } else if state.timeout_relayed {
+            PacketStage::TimedOut
+        } else if !state.sent {
+            PacketStage::NotFound
+        } else {
+            PacketStage::Completed
+        }
+    } else if state.received_on_dst {
+        if state.ack_on_dst {
+            PacketStage::AwaitingAck
+        } else {
+            PacketStage::AwaitingAckWrite
+        }
+    } else if state.timed_out_on_dst {
+        PacketStage::AwaitingTimeout
+    } else {
+        PacketStage::AwaitingReceive
+    }
+}
+
+/// Check whether the given client can be used to prove a packet event emitted at
+/// `event_height` on the chain it tracks.
+fn client_reasons(
+    host: &impl ChainHandle,
+    client_id: &ClientId,
+    event_height: Option<Height>,
+    reasons: &mut Vec<String>,
+) -> Result<(), Error> {
+    let (client_state, _) = host
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(Error::relayer)?;
+
+    if let Some(frozen_height) = client_state.frozen_height() {
+        reasons.push(format!(
+            "client {} on {} is frozen at height {}",
+            client_id,
+            host.id(),
+            frozen_height
+        ));
+    }
+
+    if let Some(event_height) = event_height {
+        if client_state.latest_height() <= event_height {
+            reasons.push(format!(
+                "client {} on {} has not been updated past height {} of {}, its latest height is {}",
+                client_id,
+                host.id(),
+                event_height,
+                client_state.chain_id(),
+                client_state.latest_height()
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether the packet filter of the given chain allows relaying on the given channel.
+fn filter_reasons(
+    config: &Config,
+    chain_id: &ChainId,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    reasons: &mut Vec<String>,
+) {
+    let Some(chain_config) = config.find_chain(chain_id) else {
+        return;
+    };
+
+    if !chain_config
+        .packet_filter()
+        .channel_policy
+        .is_allowed(port_id, channel_id)
+    {
+        reasons.push(format!(
+            "channel {port_id}/{channel_id} is filtered out by the packet filter of {chain_id}"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{packet_stage, PacketStage, PacketState, QueryPacketTraceCmd};
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_query_packet_trace() {
+        assert_eq!(
+            QueryPacketTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                sequence: Sequence::from(42)
+            },
+            QueryPacketTraceCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07",
+                "--sequence",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_trace_aliases() {
+        assert_eq!(
+            QueryPacketTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                sequence: Sequence::from(42)
+            },
+            QueryPacketTraceCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--chan",
+                "channel-07",
+                "--seq",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_packet_trace_no_sequence() {
+        assert!(QueryPacketTraceCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "port_id",
+            "--channel",
+            "channel-07"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_packet_stage_not_found() {
+        assert_eq!(packet_stage(&PacketState::default()), PacketStage::NotFound);
+    }
+
+    #[test]
+    fn test_packet_stage_awaiting_receive() {
+        let state = PacketState {
+            sent: true,
+            commitment_on_src: true,
+            ..Default::default()
+        };
+
+        assert_eq!(packet_stage(&state), PacketStage::AwaitingReceive);
+    }
+
+    #[test]
+    fn test_packet_stage_awaiting_timeout() {
+        let state = PacketState {
+            sent: true,
+            commitment_on_src: true,
+            timed_out_on_dst: true,
+            ..Default::default()
+        };
+
+        assert_eq!(packet_stage(&state), PacketStage::AwaitingTimeout);
+    }
+
+    #[test]
+    fn test_packet_stage_awaiting_ack_write() {
+        let state = PacketState {
+            sent: true,
+            commitment_on_src: true,
+            received_on_dst: true,
+            ..Default::default()
+        };
+
+        assert_eq!(packet_stage(&state), PacketStage::AwaitingAckWrite);
+    }
+
+    #[test]
+    fn test_packet_stage_awaiting_ack() {
+        let state = PacketState {
+            sent: true,
+            commitment_on_src: true,
+            received_on_dst: true,
+            ack_on_dst: true,
+            ..Default::default()
+        };
+
+        assert_eq!(packet_stage(&state), PacketStage::AwaitingAck);
+    }
+
+    #[test]
+    fn test_packet_stage_acknowledged() {
+        let state = PacketState {
+            sent: true,
+            received_on_dst: true,
+            ack_on_dst: true,
+            ack_relayed: true,
+            ..Default::default()
+        };
+
+        assert_eq!(packet_stage(&state), PacketStage::Acknowledged);
+    }
+
+    #[test]
+    fn test_packet_stage_timed_out() {
+        let state = PacketState {
+            sent: true,
+            timeout_relayed: true,
+            ..Default::default()
+        };
+
+        assert_eq!(packet_stage(&state), PacketStage::TimedOut);
+    }
+
+    #[test]
+    fn test_packet_stage_completed() {
+        let state = PacketState {
+            sent: true,
+            ..Default::default()
+        };
+
+        assert_eq!(packet_stage(&state), PacketStage::Completed);
+    }
+}
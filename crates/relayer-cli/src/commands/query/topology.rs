@@ -0,0 +1,824 @@
+use core::fmt::Write;
+use core::str::FromStr;
+use std::collections::BTreeMap;
+
+use abscissa_core::clap::Parser;
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::{
+    PageRequest, QueryChannelsRequest, QueryClientStatesRequest, QueryConnectionsRequest,
+};
+use ibc_relayer::config::filter::ChannelPolicy;
+use ibc_relayer::config::Config;
+use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
+use ibc_relayer_types::core::ics04_channel::channel::{IdentifiedChannelEnd, Ordering, State};
+use ibc_relayer_types::core::ics04_channel::version::Version;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::commands::query::clients::{client_status_reports, Status};
+use crate::conclude::{json, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// The format in which the topology is exported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TopologyFormat {
+    #[default]
+    Json,
+    Dot,
+    Mermaid,
+}
+
+impl FromStr for TopologyFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "dot" => Ok(Self::Dot),
+            "mermaid" => Ok(Self::Mermaid),
+            _ => Err(format!(
+                "unknown format '{s}', expected one of 'json', 'dot' or 'mermaid'"
+            )),
+        }
+    }
+}
+
+/// The data structure that represents the arguments when invoking the `query topology` CLI command.
+///
+/// The command has the following format:
+///
+/// `query topology [--format <FORMAT>]`
+///
+/// It walks the clients, connections and channels of every configured chain, matches the
+/// channel ends with their counterparties and exports the resulting graph, where nodes are
+/// chains and edges are channels.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryTopologyCmd {
+    #[clap(
+        long = "format",
+        value_name = "FORMAT",
+        default_value = "json",
+        help = "Output format of the topology: 'json', 'dot' (Graphviz) or 'mermaid'"
+    )]
+    format: TopologyFormat,
+}
+
+/// A chain of the topology.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChainNode {
+    pub chain_id: ChainId,
+    /// Whether the chain is present in the configuration, or only known as a counterparty
+    pub configured: bool,
+    /// Set if the chain could not be queried
+    pub error: Option<String>,
+}
+
+/// One end of a channel of the topology.
+///
+/// When the chain hosting the end is not configured, only the chain, port and channel
+/// identifiers, as reported by the counterparty, are known.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChannelSide {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub channel_id: Option<ChannelId>,
+    pub state: Option<State>,
+    pub connection_id: Option<ConnectionId>,
+    pub client_id: Option<ClientId>,
+    /// The status of the client tracking the counterparty chain
+    pub client_status: Option<Status>,
+    /// Whether the packet filter of the chain allows relaying on the channel
+    pub allowed_by_filter: Option<bool>,
+}
+
+impl ChannelSide {
+    fn is_healthy(&self) -> bool {
+        self.client_status
+            .map_or(true, |status| status == Status::Active)
+    }
+}
+
+/// A channel of the topology, between two chains.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChannelEdge {
+    pub ordering: Ordering,
+    pub version: Version,
+    pub a_side: ChannelSide,
+    pub b_side: ChannelSide,
+}
+
+impl ChannelEdge {
+    fn is_open(&self) -> bool {
+        [&self.a_side, &self.b_side]
+            .iter()
+            .all(|side| side.state.is_some_and(|state| state.is_open()))
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.a_side.is_healthy() && self.b_side.is_healthy()
+    }
+
+    fn is_filtered(&self) -> bool {
+        self.a_side.allowed_by_filter == Some(false) || self.b_side.allowed_by_filter == Some(false)
+    }
+
+    fn label(&self, line_break: &str) -> String {
+        fn side(side: &ChannelSide) -> String {
+            let channel_id = side
+                .channel_id
+                .as_ref()
+                .map_or_else(|| "?".to_string(), ToString::to_string);
+            format!("{}/{}", side.port_id, channel_id)
+        }
+
+        fn state(side: &ChannelSide) -> String {
+            side.state
+                .map_or_else(|| "UNKNOWN".to_string(), |state| state.to_string())
+        }
+
+        let state = if self.is_open() {
+            state(&self.a_side)
+        } else {
+            format!("{} / {}", state(&self.a_side), state(&self.b_side))
+        };
+
+        let mut label = format!(
+            "{} <-> {}{line_break}{}, {}, {}",
+            side(&self.a_side),
+            side(&self.b_side),
+            state,
+            self.ordering,
+            self.version
+        );
+
+        for side in [&self.a_side, &self.b_side] {
+            if let (Some(client_id), Some(status)) = (&side.client_id, side.client_status) {
+                if status != Status::Active {
+                    label.push_str(&format!(
+                        "{line_break}client {client_id} on {} is {status:?}",
+                        side.chain_id
+                    ));
+                }
+            }
+        }
+
+        if self.is_filtered() {
+            label.push_str(&format!("{line_break}filtered out"));
+        }
+
+        label
+    }
+}
+
+/// The graph of IBC paths between the configured chains.
+#[derive(Clone, Debug, Serialize)]
+pub struct Topology {
+    pub chains: Vec<ChainNode>,
+    pub channels: Vec<ChannelEdge>,
+}
+
+impl Topology {
+    /// Render the topology as a Graphviz DOT graph.
+    pub fn to_dot(&self) -> String {
+        fn escape(s: &str) -> String {
+            s.replace('\\', "\\\\").replace('"', "\\\"")
+        }
+
+        let mut dot = String::from("graph ibc {\n");
+
+        for chain in &self.chains {
+            let style = match (&chain.error, chain.configured) {
+                (Some(_), _) => " [color=red]",
+                (None, false) => " [style=dashed]",
+                (None, true) => "",
+            };
+            let _ = writeln!(dot, "  \"{}\"{style};", escape(chain.chain_id.as_str()));
+        }
+
+        for channel in &self.channels {
+            let label = escape(&channel.label("\n")).replace('\n', "\\n");
+            let mut attributes = vec![format!("label=\"{label}\"")];
+            if !channel.is_open() {
+                attributes.push("style=dashed".to_string());
+            }
+            if !channel.is_healthy() {
+                attributes.push("color=red".to_string());
+            } else if channel.is_filtered() {
+                attributes.push("color=gray".to_string());
+            }
+
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -- \"{}\" [{}];",
+                escape(channel.a_side.chain_id.as_str()),
+                escape(channel.b_side.chain_id.as_str()),
+                attributes.join(", ")
+            );
+        }
+
+        dot.push('}');
+        dot
+    }
+
+    /// Render the topology as a Mermaid flowchart.
+    pub fn to_mermaid(&self) -> String {
+        fn escape(s: &str) -> String {
+            s.replace('"', "#quot;")
+        }
+
+        // Mermaid node identifiers cannot contain all the characters allowed in chain identifiers
+        let node_ids: BTreeMap<&ChainId, String> = self
+            .chains
+            .iter()
+            .enumerate()
+            .map(|(index, chain)| (&chain.chain_id, format!("chain{index}")))
+            .collect();
+
+        let mut mermaid = String::from("graph LR\n");
+
+        for chain in &self.chains {
+            let _ = writeln!(
+                mermaid,
+                "  {}[\"{}\"]",
+                node_ids[&chain.chain_id],
+                escape(chain.chain_id.as_str())
+            );
+        }
+
+        for channel in &self.channels {
+            let link = if channel.is_open() { "---" } else { "-.-" };
+            let _ = writeln!(
+                mermaid,
+                "  {} {link}|\"{}\"| {}",
+                node_ids[&channel.a_side.chain_id],
+                escape(&channel.label("<br/>")),
+                node_ids[&channel.b_side.chain_id]
+            );
+        }
+
+        for (index, channel) in self.channels.iter().enumerate() {
+            if !channel.is_healthy() {
+                let _ = writeln!(mermaid, "  linkStyle {index} stroke:red");
+            }
+        }
+
+        mermaid.truncate(mermaid.trim_end().len());
+        mermaid
+    }
+}
+
+impl Runnable for QueryTopologyCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let topology = discover_topology(&config);
+
+        let rendered = match self.format {
+            TopologyFormat::Json => Output::success(topology).exit(),
+            TopologyFormat::Dot => topology.to_dot(),
+            TopologyFormat::Mermaid => topology.to_mermaid(),
+        };
+
+        // Print the graph as is, so that it can be piped to other tools
+        if json() {
+            Output::success(rendered).exit()
+        } else {
+            println!("{rendered}");
+        }
+    }
+}
+
+/// A channel end discovered on a configured chain, along with its counterparty.
+struct DiscoveredEnd {
+    side: ChannelSide,
+    ordering: Ordering,
+    version: Version,
+    counterparty_chain_id: ChainId,
+    counterparty_port_id: PortId,
+    counterparty_channel_id: Option<ChannelId>,
+    /// The connection on the counterparty chain, used to match an end whose
+    /// counterparty channel is not known yet, eg. in the INIT state
+    counterparty_connection_id: Option<ConnectionId>,
+}
+
+/// Walk all the configured chains and build the topology of the channels between them.
+pub fn discover_topology(config: &Config) -> Topology {
+    let mut chains = Vec::new();
+    let mut ends = Vec::new();
+
+    for chain_config in &config.chains {
+        let chain_id = chain_config.id().clone();
+
+        let error = match discover_chain(config, &chain_id) {
+            Ok(chain_ends) => {
+                ends.extend(chain_ends);
+                None
+            }
+            Err(e) => {
+                warn!("failed to query the channels of chain {chain_id}: {e}");
+                Some(e.to_string())
+            }
+        };
+
+        chains.push(ChainNode {
+            chain_id,
+            configured: true,
+            error,
+        });
+    }
+
+    let channels = match_counterparties(ends);
+
+    // Add the counterparty chains which are not configured
+    for channel in &channels {
+        let chain_id = &channel.b_side.chain_id;
+        if !chains.iter().any(|chain| &chain.chain_id == chain_id) {
+            chains.push(ChainNode {
+                chain_id: chain_id.clone(),
+                configured: false,
+                error: None,
+            });
+        }
+    }
+
+    Topology { chains, channels }
+}
+
+/// Query all the channel ends of a chain, along with the connection and client they use.
+fn discover_chain(config: &Config, chain_id: &ChainId) -> Result<Vec<DiscoveredEnd>, Error> {
+    let chain = spawn_chain_runtime(config, chain_id)?;
+
+    let channels = chain
+        .query_channels(QueryChannelsRequest {
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(Error::relayer)?;
+
+    let connections: BTreeMap<_, _> = chain
+        .query_connections(QueryConnectionsRequest {
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(Error::relayer)?
+        .into_iter()
+        .map(|connection| (connection.connection_id, connection.connection_end))
+        .collect();
+
+    let clients = chain
+        .query_clients(QueryClientStatesRequest {
+            pagination: Some(PageRequest::all()),
+        })
+        .map_err(Error::relayer)?;
+
+    let client_chains: BTreeMap<_, _> = clients
+        .iter()
+        .map(|client| (client.client_id.clone(), client.client_state.chain_id()))
+        .collect();
+
+    let client_statuses: BTreeMap<_, _> = client_status_reports(&chain, clients.into_iter())?
+        .into_iter()
+        .map(|report| (report.client_id, report.status))
+        .collect();
+
+    let channel_policy = config
+        .find_chain(chain_id)
+        .map(|chain_config| chain_config.packet_filter().channel_policy.clone());
+
+    Ok(channel_ends(
+        chain_id,
+        channels,
+        &connections,
+        &client_chains,
+        &client_statuses,
+        channel_policy.as_ref(),
+    ))
+}
+
+/// Build the channel ends of a chain from its channels, connections and clients,
+/// skipping the channels whose counterparty chain cannot be determined.
+fn channel_ends(
+    chain_id: &ChainId,
+    channels: Vec<IdentifiedChannelEnd>,
+    connections: &BTreeMap<ConnectionId, ConnectionEnd>,
+    client_chains: &BTreeMap<ClientId, ChainId>,
+    client_statuses: &BTreeMap<ClientId, Status>,
+    channel_policy: Option<&ChannelPolicy>,
+) -> Vec<DiscoveredEnd> {
+    let mut ends = Vec::new();
+
+    for channel in channels {
+        let channel_end = channel.channel_end;
+        if channel_end.state_matches(&State::Uninitialized) {
+            continue;
+        }
+
+        let connection_id = channel_end.connection_hops().first().cloned();
+        let connection_end = connection_id
+            .as_ref()
+            .and_then(|connection_id| connections.get(connection_id));
+        let client_id = connection_end.map(|connection_end| connection_end.client_id().clone());
+
+        let Some(counterparty_chain_id) = client_id
+            .as_ref()
+            .and_then(|client_id| client_chains.get(client_id))
+            .cloned()
+        else {
+            warn!(
+                "skipping channel {}/{} on chain {chain_id}: its client could not be found",
+                channel.port_id, channel.channel_id
+            );
+            continue;
+        };
+
+        let counterparty = channel_end.counterparty();
+
+        ends.push(DiscoveredEnd {
+            side: ChannelSide {
+                chain_id: chain_id.clone(),
+                allowed_by_filter: channel_policy
+                    .map(|policy| policy.is_allowed(&channel.port_id, &channel.channel_id)),
+                port_id: channel.port_id,
+                channel_id: Some(channel.channel_id),
+                state: Some(*channel_end.state()),
+                connection_id,
+                client_status: client_id
+                    .as_ref()
+                    .and_then(|client_id| client_statuses.get(client_id))
+                    .copied(),
+                client_id,
+            },
+            ordering: *channel_end.ordering(),
+            version: channel_end.version().clone(),
+            counterparty_chain_id,
+            counterparty_port_id: counterparty.port_id.clone(),
+            counterparty_channel_id: counterparty.channel_id.clone(),
+            counterparty_connection_id: connection_end
+                .and_then(|connection_end| connection_end.counterparty().connection_id())
+                .cloned(),
+        });
+    }
+
+    ends
+}
+
+/// Pair each channel end with its counterparty end, if it was discovered.
+fn match_counterparties(ends: Vec<DiscoveredEnd>) -> Vec<ChannelEdge> {
+    fn points_to(end: &DiscoveredEnd, other: &DiscoveredEnd) -> bool {
+        let same_channel = match &end.counterparty_channel_id {
+            Some(channel_id) => other.side.channel_id.as_ref() == Some(channel_id),
+            // The counterparty channel is not known before the TRYOPEN step,
+            // fall back to the connection the counterparty channel must be using
+            None => {
+                end.counterparty_connection_id.is_some()
+                    && end.counterparty_connection_id == other.side.connection_id
+            }
+        };
+
+        end.counterparty_chain_id == other.side.chain_id
+            && end.counterparty_port_id == other.side.port_id
+            && same_channel
+    }
+
+    // At least one of the ends must know its counterparty channel,
+    // two ends in the INIT state are never the two ends of the same channel
+    fn are_counterparties(end: &DiscoveredEnd, other: &DiscoveredEnd) -> bool {
+        (end.counterparty_channel_id.is_some() || other.counterparty_channel_id.is_some())
+            && points_to(end, other)
+            && points_to(other, end)
+    }
+
+    let mut ends: Vec<Option<DiscoveredEnd>> = ends.into_iter().map(Some).collect();
+    let mut edges = Vec::new();
+
+    for index in 0..ends.len() {
+        let Some(end) = ends[index].take() else {
+            continue;
+        };
+
+        let counterparty = ends.iter_mut().find(|other| {
+            other
+                .as_ref()
+                .is_some_and(|other| are_counterparties(&end, other))
+        });
+
+        let b_side = match counterparty.and_then(Option::take) {
+            Some(counterparty) => counterparty.side,
+            None => ChannelSide {
+                chain_id: end.counterparty_chain_id,
+                port_id: end.counterparty_port_id,
+                channel_id: end.counterparty_channel_id,
+                state: None,
+                connection_id: None,
+                client_id: None,
+                client_status: None,
+                allowed_by_filter: None,
+            },
+        };
+
+        edges.push(ChannelEdge {
+            ordering: end.ordering,
+            version: end.version,
+            a_side: end.side,
+            b_side,
+        });
+    }
+
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        channel_ends, match_counterparties, ChainNode, ChannelEdge, ChannelSide, DiscoveredEnd,
+        QueryTopologyCmd, Topology, TopologyFormat,
+    };
+
+    use std::collections::BTreeMap;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer::config::filter::{ChannelFilters, ChannelPolicy, FilterPattern};
+    use ibc_relayer_types::core::ics03_connection::connection::{
+        ConnectionEnd, Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use ibc_relayer_types::core::ics04_channel::channel::{
+        ChannelEnd, Counterparty, IdentifiedChannelEnd, Ordering, State, UpgradeState,
+    };
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::core::ics24_host::identifier::{
+        ChainId, ChannelId, ClientId, ConnectionId, PortId,
+    };
+
+    use crate::commands::query::clients::Status;
+
+    fn side(chain_id: &str, channel: u64, status: Status) -> ChannelSide {
+        ChannelSide {
+            chain_id: ChainId::from_string(chain_id),
+            port_id: PortId::transfer(),
+            channel_id: Some(ChannelId::new(channel)),
+            state: Some(State::Open(UpgradeState::NotUpgrading)),
+            connection_id: None,
+            client_id: Some("07-tendermint-0".parse().unwrap()),
+            client_status: Some(status),
+            allowed_by_filter: Some(true),
+        }
+    }
+
+    fn topology() -> Topology {
+        Topology {
+            chains: vec![
+                ChainNode {
+                    chain_id: ChainId::from_string("ibc-0"),
+                    configured: true,
+                    error: None,
+                },
+                ChainNode {
+                    chain_id: ChainId::from_string("ibc-1"),
+                    configured: true,
+                    error: None,
+                },
+            ],
+            channels: vec![ChannelEdge {
+                ordering: Ordering::Unordered,
+                version: Version::ics20(),
+                a_side: side("ibc-0", 0, Status::Active),
+                b_side: side("ibc-1", 3, Status::Expired),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_topology_to_dot() {
+        assert_eq!(
+            topology().to_dot(),
+            concat!(
+                "graph ibc {\n",
+                "  \"ibc-0\";\n",
+                "  \"ibc-1\";\n",
+                "  \"ibc-0\" -- \"ibc-1\" [label=\"transfer/channel-0 <-> transfer/channel-3\\nOPEN, ORDER_UNORDERED, ics20-1\\nclient 07-tendermint-0 on ibc-1 is Expired\", color=red];\n",
+                "}"
+            )
+        )
+    }
+
+    #[test]
+    fn test_topology_to_mermaid() {
+        assert_eq!(
+            topology().to_mermaid(),
+            concat!(
+                "graph LR\n",
+                "  chain0[\"ibc-0\"]\n",
+                "  chain1[\"ibc-1\"]\n",
+                "  chain0 ---|\"transfer/channel-0 <-> transfer/channel-3<br/>OPEN, ORDER_UNORDERED, ics20-1<br/>client 07-tendermint-0 on ibc-1 is Expired\"| chain1\n",
+                "  linkStyle 0 stroke:red"
+            )
+        )
+    }
+
+    #[test]
+    fn test_query_topology_default_format() {
+        assert_eq!(
+            QueryTopologyCmd {
+                format: TopologyFormat::Json
+            },
+            QueryTopologyCmd::parse_from(["test"])
+        )
+    }
+
+    #[test]
+    fn test_query_topology_format() {
+        assert_eq!(
+            QueryTopologyCmd {
+                format: TopologyFormat::Mermaid
+            },
+            QueryTopologyCmd::parse_from(["test", "--format", "mermaid"])
+        )
+    }
+
+    #[test]
+    fn test_query_topology_unknown_format() {
+        assert!(QueryTopologyCmd::try_parse_from(["test", "--format", "svg"]).is_err())
+    }
+
+    const OPEN: State = State::Open(UpgradeState::NotUpgrading);
+
+    fn channel(
+        channel: u64,
+        state: State,
+        counterparty_channel: Option<u64>,
+        connection: u64,
+    ) -> IdentifiedChannelEnd {
+        IdentifiedChannelEnd::new(
+            PortId::transfer(),
+            ChannelId::new(channel),
+            ChannelEnd::new(
+                state,
+                Ordering::Unordered,
+                Counterparty::new(PortId::transfer(), counterparty_channel.map(ChannelId::new)),
+                vec![ConnectionId::new(connection)],
+                Version::ics20(),
+                0.into(),
+            ),
+        )
+    }
+
+    /// A channel end discovered on `chain_id`, using `connection-<connection>` whose
+    /// counterparty is `connection-<counterparty_connection>` on `counterparty_chain_id`.
+    fn end(
+        chain_id: &str,
+        channel: u64,
+        state: State,
+        counterparty_chain_id: &str,
+        counterparty_channel: Option<u64>,
+        connection: u64,
+        counterparty_connection: u64,
+    ) -> DiscoveredEnd {
+        DiscoveredEnd {
+            side: ChannelSide {
+                chain_id: ChainId::from_string(chain_id),
+                port_id: PortId::transfer(),
+                channel_id: Some(ChannelId::new(channel)),
+                state: Some(state),
+                connection_id: Some(ConnectionId::new(connection)),
+                client_id: Some("07-tendermint-0".parse().unwrap()),
+                client_status: Some(Status::Active),
+                allowed_by_filter: None,
+            },
+            ordering: Ordering::Unordered,
+            version: Version::ics20(),
+            counterparty_chain_id: ChainId::from_string(counterparty_chain_id),
+            counterparty_port_id: PortId::transfer(),
+            counterparty_channel_id: counterparty_channel.map(ChannelId::new),
+            counterparty_connection_id: Some(ConnectionId::new(counterparty_connection)),
+        }
+    }
+
+    fn states(edge: &ChannelEdge) -> (Option<State>, Option<State>) {
+        (edge.a_side.state, edge.b_side.state)
+    }
+
+    #[test]
+    fn test_channel_ends() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let client_id: ClientId = "07-tendermint-0".parse().unwrap();
+
+        let connections = BTreeMap::from([(
+            ConnectionId::new(0),
+            ConnectionEnd::new(
+                ConnectionState::Open,
+                client_id.clone(),
+                ConnectionCounterparty::new(
+                    "07-tendermint-1".parse().unwrap(),
+                    Some(ConnectionId::new(1)),
+                    Default::default(),
+                ),
+                vec![],
+                Default::default(),
+            ),
+        )]);
+        let client_chains = BTreeMap::from([(client_id.clone(), ChainId::from_string("ibc-1"))]);
+        let client_statuses = BTreeMap::from([(client_id.clone(), Status::Expired)]);
+        let policy = ChannelPolicy::Deny(ChannelFilters::new(vec![(
+            FilterPattern::Exact(PortId::transfer()),
+            FilterPattern::Exact(ChannelId::new(1)),
+        )]));
+
+        let channels = vec![
+            channel(0, OPEN, Some(3), 0),
+            channel(1, State::Init, None, 0),
+            // Skipped, the channel is not initialized
+            channel(2, State::Uninitialized, None, 0),
+            // Skipped, the connection is not known
+            channel(3, OPEN, Some(4), 7),
+        ];
+
+        let ends = channel_ends(
+            &chain_id,
+            channels,
+            &connections,
+            &client_chains,
+            &client_statuses,
+            Some(&policy),
+        );
+
+        assert_eq!(ends.len(), 2);
+
+        let open = &ends[0];
+        assert_eq!(open.side.chain_id, chain_id);
+        assert_eq!(open.side.channel_id, Some(ChannelId::new(0)));
+        assert_eq!(open.side.state, Some(OPEN));
+        assert_eq!(open.side.connection_id, Some(ConnectionId::new(0)));
+        assert_eq!(open.side.client_id, Some(client_id));
+        assert_eq!(open.side.client_status, Some(Status::Expired));
+        assert_eq!(open.side.allowed_by_filter, Some(true));
+        assert_eq!(open.counterparty_chain_id, ChainId::from_string("ibc-1"));
+        assert_eq!(open.counterparty_channel_id, Some(ChannelId::new(3)));
+        assert_eq!(open.counterparty_connection_id, Some(ConnectionId::new(1)));
+
+        let init = &ends[1];
+        assert_eq!(init.side.channel_id, Some(ChannelId::new(1)));
+        assert_eq!(init.side.allowed_by_filter, Some(false));
+        assert_eq!(init.counterparty_channel_id, None);
+        assert_eq!(init.counterparty_connection_id, Some(ConnectionId::new(1)));
+    }
+
+    #[test]
+    fn test_match_counterparties_open() {
+        let edges = match_counterparties(vec![
+            end("ibc-0", 0, OPEN, "ibc-1", Some(3), 0, 1),
+            end("ibc-1", 3, OPEN, "ibc-0", Some(0), 1, 0),
+        ]);
+
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].a_side.channel_id, Some(ChannelId::new(0)));
+        assert_eq!(edges[0].b_side.channel_id, Some(ChannelId::new(3)));
+        assert_eq!(states(&edges[0]), (Some(OPEN), Some(OPEN)));
+    }
+
+    #[test]
+    fn test_match_counterparties_init_try_open() {
+        let edges = match_counterparties(vec![
+            end("ibc-0", 0, State::Init, "ibc-1", None, 0, 1),
+            // Another channel on the same connection, opened by the counterparty
+            end("ibc-1", 2, OPEN, "ibc-0", Some(5), 1, 0),
+            end("ibc-1", 3, State::TryOpen, "ibc-0", Some(0), 1, 0),
+        ]);
+
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[0].a_side.channel_id, Some(ChannelId::new(0)));
+        assert_eq!(edges[0].b_side.channel_id, Some(ChannelId::new(3)));
+        assert_eq!(states(&edges[0]), (Some(State::Init), Some(State::TryOpen)));
+        assert_eq!(edges[1].a_side.channel_id, Some(ChannelId::new(2)));
+        assert_eq!(edges[1].b_side.channel_id, Some(ChannelId::new(5)));
+        assert_eq!(states(&edges[1]), (Some(OPEN), None));
+    }
+
+    #[test]
+    fn test_match_counterparties_init_init() {
+        let edges = match_counterparties(vec![
+            end("ibc-0", 0, State::Init, "ibc-1", None, 0, 1),
+            end("ibc-1", 3, State::Init, "ibc-0", None, 1, 0),
+        ]);
+
+        assert_eq!(edges.len(), 2);
+        assert!(edges
+            .iter()
+            .all(|edge| states(edge) == (Some(State::Init), None)));
+    }
+
+    #[test]
+    fn test_match_counterparties_unconfigured_counterparty() {
+        let edges = match_counterparties(vec![end("ibc-0", 0, OPEN, "osmosis-1", Some(7), 0, 1)]);
+
+        assert_eq!(edges.len(), 1);
+
+        let b_side = &edges[0].b_side;
+        assert_eq!(b_side.chain_id, ChainId::from_string("osmosis-1"));
+        assert_eq!(b_side.port_id, PortId::transfer());
+        assert_eq!(b_side.channel_id, Some(ChannelId::new(7)));
+        assert_eq!(b_side.state, None);
+    }
+}
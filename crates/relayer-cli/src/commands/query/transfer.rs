@@ -4,10 +4,14 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 
 mod denom_trace;
+mod trace;
 
 /// `query transfer` subcommand
 #[derive(Command, Debug, Parser, Runnable)]
 pub enum TransferCmd {
     /// Query the denomination trace info from a trace hash
     DenomTrace(denom_trace::DenomTraceCmd),
+
+    /// Follow a transfer across the chains it is forwarded through by the packet-forward-middleware
+    Trace(trace::TransferTraceCmd),
 }
@@ -0,0 +1,359 @@
+use core::fmt;
+use core::str::FromStr;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+use serde::{Deserialize, Serialize};
+use tendermint::Hash;
+
+use ibc_relayer::chain::handle::{BaseChainHandle, ChainHandle};
+use ibc_relayer::chain::requests::{QueryTxHash, QueryTxRequest};
+use ibc_relayer::config::Config;
+use ibc_relayer_types::applications::transfer::denom::{is_receiver_chain_source, TracePrefix};
+use ibc_relayer_types::applications::transfer::packet::PacketData;
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::cli_utils::spawn_chain_runtime_generic;
+use crate::commands::query::packet::trace::{trace_packet, PacketStage, PacketTx};
+use crate::conclude::{json, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+/// Maximum number of hops followed, to guard against forwarding loops.
+const MAX_HOPS: usize = 10;
+
+/// The data structure that represents the arguments when invoking the `query transfer trace` CLI command.
+///
+/// The command has the following format:
+///
+/// `query transfer trace --chain <CHAIN_ID> --hash <HASH>`
+///
+/// Starting from the `SendPacket` event of the given transaction, it follows an ICS-20 transfer
+/// across every chain it is forwarded through by the packet-forward-middleware, as requested by
+/// the `forward` field of the transfer memo, and reports the status and denomination trace at
+/// every hop.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TransferTraceCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain on which the transfer was submitted"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "hash",
+        required = true,
+        value_name = "HASH",
+        help_heading = "REQUIRED",
+        help = "Hash of the transaction which submitted the transfer"
+    )]
+    hash: String,
+}
+
+/// The `forward` field of an ICS-20 transfer memo, as handled by the packet-forward-middleware.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Forward {
+    pub receiver: String,
+    pub port: PortId,
+    pub channel: ChannelId,
+}
+
+#[derive(Deserialize)]
+struct ForwardMemo {
+    forward: Option<Forward>,
+}
+
+impl Forward {
+    /// The forwarding requested by the given transfer memo, if any.
+    fn from_memo(memo: Option<&str>) -> Option<Self> {
+        let memo: ForwardMemo = serde_json::from_str(memo?).ok()?;
+        memo.forward
+    }
+}
+
+/// A single hop of a transfer.
+#[derive(Clone, Debug, Serialize)]
+pub struct TransferHop {
+    pub src_chain: ChainId,
+    pub src_port: PortId,
+    pub src_channel: ChannelId,
+    pub dst_chain: ChainId,
+    pub dst_port: PortId,
+    pub dst_channel: ChannelId,
+    pub sequence: Sequence,
+
+    pub amount: String,
+    /// The denomination trace of the tokens on the source chain of the hop
+    pub src_denom: String,
+    /// The denomination trace of the tokens on the destination chain of the hop
+    pub dst_denom: String,
+    pub receiver: String,
+    /// Where the destination chain of the hop is asked to forward the tokens to
+    pub forward: Option<Forward>,
+
+    pub stage: PacketStage,
+    pub reasons: Vec<String>,
+    pub send_tx: Option<PacketTx>,
+    pub recv_tx: Option<PacketTx>,
+}
+
+impl fmt::Display for TransferHop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn tx(tx: &Option<PacketTx>) -> String {
+            tx.as_ref()
+                .map_or_else(|| "not found".to_string(), ToString::to_string)
+        }
+
+        writeln!(
+            f,
+            "{} ({}/{}) -> {} ({}/{}), sequence {}: {}",
+            self.src_chain,
+            self.src_port,
+            self.src_channel,
+            self.dst_chain,
+            self.dst_port,
+            self.dst_channel,
+            self.sequence,
+            self.stage
+        )?;
+        writeln!(
+            f,
+            "  Amount:    {} {} -> {}",
+            self.amount, self.src_denom, self.dst_denom
+        )?;
+        writeln!(f, "  Receiver:  {}", self.receiver)?;
+        if let Some(forward) = &self.forward {
+            writeln!(
+                f,
+                "  Forward:   to {} over {}/{}",
+                forward.receiver, forward.port, forward.channel
+            )?;
+        }
+        writeln!(f, "  Send tx:   {}", tx(&self.send_tx))?;
+        writeln!(f, "  Recv tx:   {}", tx(&self.recv_tx))?;
+
+        for reason in &self.reasons {
+            writeln!(f, "  - {reason}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The trace of a transfer across all of its hops.
+#[derive(Clone, Debug, Serialize)]
+pub struct TransferTrace {
+    pub hops: Vec<TransferHop>,
+}
+
+impl fmt::Display for TransferTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, hop) in self.hops.iter().enumerate() {
+            write!(f, "Hop {}: {}", index + 1, hop)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Runnable for TransferTraceCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        match trace_transfer(&config, &self.chain_id, &self.hash) {
+            Ok(trace) if json() => Output::success(trace).exit(),
+            Ok(trace) => Output::success_msg(trace.to_string()).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+/// Follow the transfer sent by the given transaction across all of its hops.
+fn trace_transfer(config: &Config, chain_id: &ChainId, hash: &str) -> Result<TransferTrace, Error> {
+    let mut chain_id = chain_id.clone();
+    let mut packet = find_sent_packet(config, &chain_id, hash, None)?
+        .ok_or_else(|| Error::transfer_trace(format!("no SendPacket event found in tx {hash}")))?;
+
+    let mut hops = vec![];
+
+    while hops.len() < MAX_HOPS {
+        let trace = trace_packet(
+            config,
+            &chain_id,
+            &packet.source_port,
+            &packet.source_channel,
+            packet.sequence,
+        )?;
+
+        let data: PacketData = serde_json::from_slice(&packet.data).map_err(|e| {
+            Error::transfer_trace(format!(
+                "packet {} sent on {}/{} of {} is not an ICS-20 transfer: {}",
+                packet.sequence, packet.source_port, packet.source_channel, chain_id, e
+            ))
+        })?;
+
+        let src_denom = data.token.denom.clone();
+        let mut dst_denom = src_denom.clone();
+        if is_receiver_chain_source(
+            packet.source_port.clone(),
+            packet.source_channel.clone(),
+            &src_denom,
+        ) {
+            dst_denom.remove_trace_prefix(&TracePrefix::new(
+                packet.source_port.clone(),
+                packet.source_channel.clone(),
+            ));
+        } else {
+            dst_denom.add_trace_prefix(TracePrefix::new(
+                packet.destination_port.clone(),
+                packet.destination_channel.clone(),
+            ));
+        }
+
+        let forward = Forward::from_memo(data.memo.as_deref());
+
+        let mut reasons = trace.reasons;
+        if forward.is_some() && trace.stage == PacketStage::AwaitingAckWrite {
+            reasons.push(
+                "the packet-forward-middleware writes the acknowledgement once the forwarded \
+                packet is acknowledged or timed out"
+                    .to_string(),
+            );
+        }
+
+        let mut hop = TransferHop {
+            src_chain: trace.src_chain,
+            src_port: trace.src_port,
+            src_channel: trace.src_channel,
+            dst_chain: trace.dst_chain,
+            dst_port: trace.dst_port,
+            dst_channel: trace.dst_channel,
+            sequence: trace.sequence,
+            amount: data.token.amount.to_string(),
+            src_denom: src_denom.to_string(),
+            dst_denom: dst_denom.to_string(),
+            receiver: data.receiver.to_string(),
+            forward: forward.clone(),
+            stage: trace.stage,
+            reasons,
+            send_tx: trace.send_tx,
+            recv_tx: trace.recv_tx,
+        };
+
+        // The packet-forward-middleware sends the forwarded packet
+        // in the same transaction as the one receiving the packet.
+        let next = match (&forward, &hop.recv_tx) {
+            (Some(forward), Some(recv_tx)) => {
+                let next =
+                    find_sent_packet(config, &hop.dst_chain, &recv_tx.tx_hash, Some(forward))?;
+                if next.is_none() {
+                    hop.reasons.push(format!(
+                        "the packet was received on {} but not forwarded over {}/{}, \
+                        the forward may have failed",
+                        hop.dst_chain, forward.port, forward.channel
+                    ));
+                }
+                next
+            }
+            (Some(_), None) => {
+                hop.reasons.push(format!(
+                    "the packet has not been received on {} yet, so it was not forwarded",
+                    hop.dst_chain
+                ));
+                None
+            }
+            (None, _) => None,
+        };
+
+        chain_id = hop.dst_chain.clone();
+        hops.push(hop);
+
+        match next {
+            Some(next) => packet = next,
+            None => break,
+        }
+    }
+
+    Ok(TransferTrace { hops })
+}
+
+/// Find the packet sent by the given transaction, optionally restricted
+/// to the port and channel the packet was forwarded over.
+fn find_sent_packet(
+    config: &Config,
+    chain_id: &ChainId,
+    hash: &str,
+    forward: Option<&Forward>,
+) -> Result<Option<Packet>, Error> {
+    let chain = spawn_chain_runtime_generic::<BaseChainHandle>(config, chain_id)?;
+
+    let hash = Hash::from_str(hash).map_err(|e| Error::invalid_hash(hash.to_string(), e))?;
+
+    let events = chain
+        .query_txs(QueryTxRequest::Transaction(QueryTxHash(hash)))
+        .map_err(Error::relayer)?;
+
+    Ok(events.into_iter().find_map(|ev| match ev.event {
+        IbcEvent::SendPacket(send)
+            if forward.map_or(true, |forward| {
+                send.packet.source_port == forward.port
+                    && send.packet.source_channel == forward.channel
+            }) =>
+        {
+            Some(send.packet)
+        }
+        _ => None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Forward, TransferTraceCmd};
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_query_transfer_trace() {
+        assert_eq!(
+            TransferTraceCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                hash: "abcdefg".to_owned()
+            },
+            TransferTraceCmd::parse_from(["test", "--chain", "chain_id", "--hash", "abcdefg"])
+        )
+    }
+
+    #[test]
+    fn test_query_transfer_trace_no_hash() {
+        assert!(TransferTraceCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
+    }
+
+    #[test]
+    fn test_forward_from_memo() {
+        let memo = r#"{
+            "forward": {
+                "receiver": "cosmos1receiver",
+                "port": "transfer",
+                "channel": "channel-1",
+                "timeout": "10m",
+                "retries": 2,
+                "next": { "forward": { "receiver": "osmo1receiver", "port": "transfer", "channel": "channel-7" } }
+            }
+        }"#;
+
+        let forward = Forward::from_memo(Some(memo)).unwrap();
+        assert_eq!(forward.receiver, "cosmos1receiver");
+        assert_eq!(forward.port.as_str(), "transfer");
+        assert_eq!(forward.channel.as_str(), "channel-1");
+
+        assert!(Forward::from_memo(Some("just a memo")).is_none());
+        assert!(Forward::from_memo(Some(r#"{"wasm": {}}"#)).is_none());
+        assert!(Forward::from_memo(None).is_none());
+    }
+}
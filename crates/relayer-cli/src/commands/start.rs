@@ -2,6 +2,7 @@ use ibc_relayer::supervisor::SupervisorOptions;
 use ibc_relayer::util::debug_section::DebugSection;
 use std::error::Error;
 use std::io;
+use std::path::PathBuf;
 
 use abscissa_core::clap::Parser;
 use crossbeam_channel::Sender;
@@ -23,6 +24,29 @@ pub struct StartCmd {
         help = "Force a full scan of the chains for clients, connections and channels"
     )]
     full_scan: bool,
+
+    #[clap(
+        long = "record-events",
+        value_name = "DIR",
+        help = "Record all the events received from each chain into a compressed NDJSON file in the given directory, \
+                for later replay with the `replay` event source mode"
+    )]
+    record_events: Option<PathBuf>,
+
+    #[clap(
+        long = "dry-run",
+        help = "Simulate and log the transactions which would be submitted to the chains, along with their estimated fees, \
+                without ever broadcasting them"
+    )]
+    dry_run: bool,
+
+    #[clap(
+        long = "dry-run-output",
+        value_name = "FILE",
+        requires = "dry-run",
+        help = "Append the transactions simulated in dry-run mode to the given file, as newline-delimited JSON"
+    )]
+    dry_run_output: Option<PathBuf>,
 }
 
 impl Runnable for StartCmd {
@@ -52,11 +76,26 @@ impl Runnable for StartCmd {
             open_or_create_profile_file(Path::new(&path_str));
         }
 
-        let config = (*app_config()).clone();
+        let mut config = (*app_config()).clone();
+
+        if self.dry_run {
+            for chain_config in config.chains.iter_mut() {
+                if !chain_config.set_dry_run(self.dry_run_output.clone()) {
+                    warn!(
+                        "dry-run mode is only supported for Cosmos SDK chains, \
+                         any transaction to chain '{}' will be rejected",
+                        chain_config.id()
+                    );
+                }
+            }
+
+            warn!("Hermes is running in dry-run mode, transactions will be simulated but never broadcast");
+        }
 
         let options = SupervisorOptions {
             force_full_scan: self.full_scan,
             health_check: true,
+            record_events: self.record_events.clone(),
         };
 
         let supervisor_handle = make_supervisor::<CachingChainHandle>(config, options)
@@ -224,7 +263,12 @@ mod tests {
     #[test]
     fn test_start_required_only() {
         assert_eq!(
-            StartCmd { full_scan: false },
+            StartCmd {
+                full_scan: false,
+                record_events: None,
+                dry_run: false,
+                dry_run_output: None,
+            },
             StartCmd::parse_from(["test"])
         )
     }
@@ -232,8 +276,44 @@ mod tests {
     #[test]
     fn test_start_full_scan() {
         assert_eq!(
-            StartCmd { full_scan: true },
+            StartCmd {
+                full_scan: true,
+                record_events: None,
+                dry_run: false,
+                dry_run_output: None,
+            },
             StartCmd::parse_from(["test", "--full-scan"])
         )
     }
+
+    #[test]
+    fn test_start_record_events() {
+        assert_eq!(
+            StartCmd {
+                full_scan: false,
+                record_events: Some("recordings".into()),
+                dry_run: false,
+                dry_run_output: None,
+            },
+            StartCmd::parse_from(["test", "--record-events", "recordings"])
+        )
+    }
+
+    #[test]
+    fn test_start_dry_run() {
+        assert_eq!(
+            StartCmd {
+                full_scan: false,
+                record_events: None,
+                dry_run: true,
+                dry_run_output: Some("txs.ndjson".into()),
+            },
+            StartCmd::parse_from(["test", "--dry-run", "--dry-run-output", "txs.ndjson"])
+        )
+    }
+
+    #[test]
+    fn test_start_dry_run_output_requires_dry_run() {
+        assert!(StartCmd::try_parse_from(["test", "--dry-run-output", "txs.ndjson"]).is_err())
+    }
 }
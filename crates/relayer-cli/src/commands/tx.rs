@@ -3,10 +3,12 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{config::Override, Command, Runnable};
 use ibc_relayer::config::Config;
 
+mod broadcast;
 mod channel;
 pub(crate) mod client;
 mod connection;
 mod packet;
+mod recover;
 mod transfer;
 mod upgrade;
 
@@ -73,6 +75,12 @@ pub enum TxCmd {
 
     /// Send an IBC upgrade plan
     UpgradeChain(upgrade::TxIbcUpgradeChainCmd),
+
+    /// Recover an expired or frozen client by substituting it through a governance proposal (MsgRecoverClient)
+    RecoverClient(recover::TxRecoverClientCmd),
+
+    /// Broadcast a transaction exported with `--generate-only` and signed offline
+    Broadcast(broadcast::TxBroadcastCmd),
 }
 
 impl Override<Config> for TxCmd {
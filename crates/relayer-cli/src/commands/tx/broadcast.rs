@@ -0,0 +1,137 @@
+use alloc::sync::Arc;
+use std::fs;
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+
+use ibc_relayer::chain::cosmos::generate::{decode_signed_tx, TxFormat};
+use ibc_relayer::chain::cosmos::CosmosSdkChain;
+use ibc_relayer::chain::endpoint::ChainEndpoint;
+use ibc_relayer::config::ChainConfig;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::prelude::*;
+
+/// Broadcast a transaction which was exported with `--generate-only`
+/// and signed outside of Hermes, eg. by a multisig account.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxBroadcastCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to broadcast the transaction to"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "file",
+        required = true,
+        value_name = "FILE",
+        help_heading = "REQUIRED",
+        help = "Path to the signed transaction"
+    )]
+    file: PathBuf,
+
+    #[clap(
+        long = "format",
+        value_name = "FORMAT",
+        default_value = "json",
+        help = "Encoding of the signed transaction, either `json` or `proto`"
+    )]
+    format: TxFormat,
+}
+
+impl Runnable for TxBroadcastCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain_config = match config.find_chain(&self.chain_id) {
+            Some(chain_config @ ChainConfig::CosmosSdk(_)) => chain_config.clone(),
+            Some(_) => Output::error(format!(
+                "chain '{}' is not a Cosmos SDK chain",
+                self.chain_id
+            ))
+            .exit(),
+            None => Output::error(format!(
+                "chain '{}' not found in configuration",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        let bytes = fs::read(&self.file).unwrap_or_else(|e| {
+            Output::error(format!(
+                "failed to read signed transaction from '{}': {e}",
+                self.file.display()
+            ))
+            .exit()
+        });
+
+        let tx_raw =
+            decode_signed_tx(&bytes, self.format).unwrap_or_else(exit_with_unrecoverable_error);
+
+        let rt = Arc::new(
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap(),
+        );
+
+        let chain = CosmosSdkChain::bootstrap(chain_config, rt)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        match chain.send_signed_tx(tx_raw) {
+            Ok(events) => Output::success(events).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TxBroadcastCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer::chain::cosmos::generate::TxFormat;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_broadcast_required_only() {
+        assert_eq!(
+            TxBroadcastCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                file: "tx.signed.json".into(),
+                format: TxFormat::Json,
+            },
+            TxBroadcastCmd::parse_from(["test", "--chain", "chain_id", "--file", "tx.signed.json"])
+        )
+    }
+
+    #[test]
+    fn test_broadcast_proto() {
+        assert_eq!(
+            TxBroadcastCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                file: "tx.signed.pb".into(),
+                format: TxFormat::Proto,
+            },
+            TxBroadcastCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--file",
+                "tx.signed.pb",
+                "--format",
+                "proto"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_broadcast_no_file() {
+        assert!(TxBroadcastCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
+    }
+}
@@ -75,6 +75,12 @@ pub struct TxCreateClientCmd {
     /// and trusted validator set is sufficient for a commit to be accepted going forward.
     #[clap(long = "trust-threshold", value_name = "TRUST_THRESHOLD", parse(try_from_str = parse_trust_threshold))]
     trust_threshold: Option<TrustThreshold>,
+
+    /// Create the client as a `08-wasm` client, wrapping the client which would otherwise be created.
+    ///
+    /// The checksum, in hex, identifies the Wasm light client contract stored on the host chain.
+    #[clap(long = "wasm-checksum", value_name = "WASM_CHECKSUM", parse(try_from_str = parse_wasm_checksum))]
+    wasm_checksum: Option<WasmChecksum>,
 }
 
 /// Sample to run this tx:
@@ -98,6 +104,7 @@ impl Runnable for TxCreateClientCmd {
             max_clock_drift: self.clock_drift.map(Into::into),
             trusting_period: self.trusting_period.map(Into::into),
             trust_threshold: self.trust_threshold.map(Into::into),
+            wasm_checksum: self.wasm_checksum.clone().map(|checksum| checksum.0),
         };
 
         // Trigger client creation via the "build" interface, so that we obtain the resulting event
@@ -212,6 +219,13 @@ impl Runnable for TxUpdateClientCmd {
                     ChainConfig::Penumbra(chain_config) => {
                         chain_config.genesis_restart = Some(restart_params)
                     }
+                    ChainConfig::SoloMachine(_) => {
+                        Output::error(format!(
+                            "Chain '{}' is a solo machine, which has no genesis to restart from",
+                            reference_chain_id
+                        ))
+                        .exit();
+                    }
                 },
                 None => {
                     Output::error(format!(
@@ -536,6 +550,24 @@ fn parse_trust_threshold(input: &str) -> Result<TrustThreshold, Error> {
         .map_err(|e| Error::cli_arg(format!("invalid trust threshold fraction: {e}")))
 }
 
+/// Checksum of a Wasm light client contract, ie. the SHA-256 hash of its byte code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct WasmChecksum(Vec<u8>);
+
+fn parse_wasm_checksum(input: &str) -> Result<WasmChecksum, Error> {
+    let checksum = subtle_encoding::hex::decode(input.trim().to_lowercase())
+        .map_err(|e| Error::cli_arg(format!("invalid hex-encoded Wasm checksum: {e}")))?;
+
+    if checksum.len() != 32 {
+        return Err(Error::cli_arg(format!(
+            "invalid Wasm checksum: expected 32 bytes, got {}",
+            checksum.len()
+        )));
+    }
+
+    Ok(WasmChecksum(checksum))
+}
+
 type UpgradeClientResult = Result<Vec<IbcEvent>, Error>;
 type UpgradeClientsForChainResult = Result<Vec<UpgradeClientResult>, Error>;
 
@@ -613,7 +645,7 @@ impl OutputBuffer {
 mod tests {
     use super::{
         parse_trust_threshold, TxCreateClientCmd, TxUpdateClientCmd, TxUpgradeClientCmd,
-        TxUpgradeClientsCmd,
+        TxUpgradeClientsCmd, WasmChecksum,
     };
 
     use std::str::FromStr;
@@ -646,7 +678,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: None,
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_checksum: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -666,7 +699,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: Some("5s".parse::<Duration>().unwrap()),
                 trusting_period: None,
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_checksum: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -684,7 +718,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: Some("3s".parse::<Duration>().unwrap()),
                 trusting_period: None,
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_checksum: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -706,7 +741,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: Some("5s".parse::<Duration>().unwrap()),
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_checksum: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -724,7 +760,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: Some("3s".parse::<Duration>().unwrap()),
-                trust_threshold: None
+                trust_threshold: None,
+                wasm_checksum: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -746,7 +783,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: None,
                 trusting_period: None,
-                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap())
+                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap()),
+                wasm_checksum: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -768,7 +806,8 @@ mod tests {
                 src_chain_id: ChainId::from_string("reference_chain"),
                 clock_drift: Some("5s".parse::<Duration>().unwrap()),
                 trusting_period: Some("3s".parse::<Duration>().unwrap()),
-                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap())
+                trust_threshold: Some(TrustThreshold::new(1, 2).unwrap()),
+                wasm_checksum: None
             },
             TxCreateClientCmd::parse_from([
                 "test",
@@ -786,6 +825,39 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_create_client_wasm_checksum() {
+        assert_eq!(
+            TxCreateClientCmd {
+                dst_chain_id: ChainId::from_string("host_chain"),
+                src_chain_id: ChainId::from_string("reference_chain"),
+                clock_drift: None,
+                trusting_period: None,
+                trust_threshold: None,
+                wasm_checksum: Some(WasmChecksum(vec![0xab; 32]))
+            },
+            TxCreateClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "host_chain",
+                "--reference-chain",
+                "reference_chain",
+                "--wasm-checksum",
+                &"ab".repeat(32)
+            ])
+        );
+        assert!(TxCreateClientCmd::try_parse_from([
+            "test",
+            "--host-chain",
+            "host_chain",
+            "--reference-chain",
+            "reference_chain",
+            "--wasm-checksum",
+            "abcd"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_create_client_no_host_chain() {
         assert!(TxCreateClientCmd::try_parse_from([
@@ -0,0 +1,271 @@
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use serde_json::json;
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use ibc_relayer::foreign_client::ForeignClient;
+use ibc_relayer::recover_client::{
+    check_existing_substitute, create_substitute_client, query_subject_client,
+    send_recover_client_proposal, write_recover_client_proposal, RecoverClientOptions,
+};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::prelude::*;
+
+/// Recover an expired or frozen client by substituting it with a healthy client
+/// through a governance proposal
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxRecoverClientCmd {
+    #[clap(
+        long = "host-chain",
+        required = true,
+        value_name = "HOST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain that hosts the client"
+    )]
+    host_chain_id: ChainId,
+
+    #[clap(
+        long = "client",
+        required = true,
+        value_name = "CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the expired or frozen client to recover"
+    )]
+    client_id: ClientId,
+
+    #[clap(
+        long = "gov-account",
+        required = true,
+        value_name = "GOV_ACCOUNT",
+        help_heading = "REQUIRED",
+        help = "Authority account which signs the MsgRecoverClient message, usually the governance module account"
+    )]
+    gov_account: String,
+
+    #[clap(
+        long = "amount",
+        required = true,
+        value_name = "AMOUNT",
+        help_heading = "REQUIRED",
+        help = "Amount of the proposal deposit"
+    )]
+    amount: u64,
+
+    #[clap(
+        long = "denom",
+        value_name = "DENOM",
+        help = "Denomination of the proposal deposit (default: 'stake')"
+    )]
+    denom: Option<String>,
+
+    #[clap(
+        long = "substitute-client",
+        value_name = "SUBSTITUTE_CLIENT_ID",
+        help = "Identifier of an existing client to use as substitute, instead of creating a new one"
+    )]
+    substitute_client_id: Option<ClientId>,
+
+    #[clap(
+        long = "title",
+        value_name = "TITLE",
+        help = "Title of the proposal (default: 'Recover client <CLIENT_ID>')"
+    )]
+    title: Option<String>,
+
+    #[clap(
+        long = "summary",
+        value_name = "SUMMARY",
+        help = "Summary of the proposal"
+    )]
+    summary: Option<String>,
+
+    #[clap(
+        long = "proposal-file",
+        value_name = "FILE",
+        help = "Write the proposal as JSON to this file, to be submitted with the chain's CLI, instead of submitting it"
+    )]
+    proposal_file: Option<PathBuf>,
+}
+
+impl TxRecoverClientCmd {
+    fn options(&self) -> RecoverClientOptions {
+        RecoverClientOptions {
+            subject_client_id: self.client_id.clone(),
+            gov_account: self.gov_account.clone(),
+            amount: self.amount,
+            denom: self.denom.clone().unwrap_or_else(|| "stake".to_string()),
+            title: self
+                .title
+                .clone()
+                .unwrap_or_else(|| format!("Recover client {}", self.client_id)),
+            summary: self.summary.clone().unwrap_or_else(|| {
+                format!(
+                    "Replace the expired or frozen client {} on chain {} with a healthy substitute client",
+                    self.client_id, self.host_chain_id
+                )
+            }),
+        }
+    }
+}
+
+impl Runnable for TxRecoverClientCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let host_chain = spawn_chain_runtime(&config, &self.host_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let reference_chain_id = match host_chain.query_client_state(
+            QueryClientStateRequest {
+                client_id: self.client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        ) {
+            Ok((cs, _)) => cs.chain_id(),
+            Err(e) => Output::error(format!(
+                "Query of client '{}' on chain '{}' failed with error: {}",
+                self.client_id, self.host_chain_id, e
+            ))
+            .exit(),
+        };
+
+        let reference_chain = spawn_chain_runtime(&config, &reference_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let client = ForeignClient::restore(self.client_id.clone(), host_chain, reference_chain);
+
+        let subject = query_subject_client(&client).unwrap_or_else(exit_with_unrecoverable_error);
+
+        let substitute_client_id = match &self.substitute_client_id {
+            Some(substitute_client_id) => {
+                check_existing_substitute(
+                    &client.dst_chain,
+                    &self.client_id,
+                    &subject,
+                    substitute_client_id,
+                )
+                .unwrap_or_else(exit_with_unrecoverable_error);
+
+                substitute_client_id.clone()
+            }
+            None => create_substitute_client(&client, &subject)
+                .unwrap_or_else(exit_with_unrecoverable_error),
+        };
+
+        let opts = self.options();
+
+        match &self.proposal_file {
+            Some(path) => match write_recover_client_proposal(path, &substitute_client_id, &opts) {
+                Ok(proposal) => Output::success(json!({
+                    "substitute_client_id": substitute_client_id.to_string(),
+                    "proposal_file": path,
+                    "proposal": proposal,
+                }))
+                .exit(),
+                Err(e) => Output::error(e).exit(),
+            },
+            None => {
+                match send_recover_client_proposal(&client.dst_chain, &substitute_client_id, &opts)
+                {
+                    Ok(tx_hash) => Output::success(json!({
+                        "substitute_client_id": substitute_client_id.to_string(),
+                        "tx_hash": tx_hash.to_string(),
+                    }))
+                    .exit(),
+                    Err(e) => Output::error(e).exit(),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TxRecoverClientCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_recover_client_required_only() {
+        assert_eq!(
+            TxRecoverClientCmd {
+                host_chain_id: ChainId::from_string("chain_host"),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                gov_account: "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn".to_string(),
+                amount: 42,
+                denom: None,
+                substitute_client_id: None,
+                title: None,
+                summary: None,
+                proposal_file: None,
+            },
+            TxRecoverClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host",
+                "--client",
+                "07-tendermint-0",
+                "--gov-account",
+                "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn",
+                "--amount",
+                "42",
+            ])
+        )
+    }
+
+    #[test]
+    fn test_recover_client_substitute_and_proposal_file() {
+        assert_eq!(
+            TxRecoverClientCmd {
+                host_chain_id: ChainId::from_string("chain_host"),
+                client_id: ClientId::from_str("07-tendermint-0").unwrap(),
+                gov_account: "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn".to_string(),
+                amount: 42,
+                denom: Some("uatom".to_string()),
+                substitute_client_id: Some(ClientId::from_str("07-tendermint-1").unwrap()),
+                title: None,
+                summary: None,
+                proposal_file: Some("proposal.json".into()),
+            },
+            TxRecoverClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host",
+                "--client",
+                "07-tendermint-0",
+                "--gov-account",
+                "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn",
+                "--amount",
+                "42",
+                "--denom",
+                "uatom",
+                "--substitute-client",
+                "07-tendermint-1",
+                "--proposal-file",
+                "proposal.json",
+            ])
+        )
+    }
+
+    #[test]
+    fn test_recover_client_no_gov_account() {
+        assert!(TxRecoverClientCmd::try_parse_from([
+            "test",
+            "--host-chain",
+            "chain_host",
+            "--client",
+            "07-tendermint-0",
+            "--amount",
+            "42",
+        ])
+        .is_err())
+    }
+}
@@ -2,12 +2,15 @@
 
 use std::path::PathBuf;
 use std::process;
+use std::str::FromStr;
 
 use abscissa_core::clap::Parser;
-use abscissa_core::{Command, Configurable, FrameworkError, Runnable};
+use abscissa_core::{Command, Configurable, FrameworkError, FrameworkErrorKind, Runnable};
 use clap::{IntoApp, ValueEnum};
+use ibc_relayer::chain::cosmos::generate::{GenerateOnly, TxFormat};
 use ibc_relayer::config::Config;
 use ibc_relayer::util::debug_section::DebugSection;
+use ibc_relayer_types::signer::Signer;
 
 use crate::commands::CliCmd;
 
@@ -49,6 +52,36 @@ pub struct EntryPoint {
     )]
     pub debug: Vec<CliDebugSection>,
 
+    /// Export the transactions unsigned to the given directory, instead of broadcasting them.
+    #[clap(
+        long = "generate-only",
+        value_name = "DIR",
+        help = "Write the transactions built by the `tx`, `create`, `update` and `upgrade` commands \
+                unsigned to the given directory, instead of signing and broadcasting them"
+    )]
+    pub generate_only: Option<PathBuf>,
+
+    /// Encoding of the transactions exported with `--generate-only`.
+    #[clap(
+        long = "generate-format",
+        value_name = "FORMAT",
+        default_value = "json",
+        requires = "generate-only",
+        help = "Encoding of the unsigned transactions, either `json` or `proto`"
+    )]
+    pub generate_format: TxFormat,
+
+    /// Address of the account signing the transactions exported with `--generate-only`.
+    #[clap(
+        long = "signer",
+        value_name = "ADDRESS",
+        requires = "generate-only",
+        help = "Address of the account which signs the transactions exported with `--generate-only`, \
+                eg. a multisig, used as the signer of their messages. Its account number and \
+                sequence are queried from the chain. Defaults to the account of the configured key"
+    )]
+    pub signer: Option<String>,
+
     /// Subcommand to execute.
     ///
     /// The `command` option will delegate option parsing to the command type,
@@ -93,9 +126,45 @@ impl Configurable<Config> for EntryPoint {
     /// Process the configuration after it has been loaded, potentially
     /// modifying it or returning an error if options are incompatible
     fn process_config(&self, config: Config) -> Result<Config, FrameworkError> {
-        match &self.command {
-            Some(cmd) => cmd.process_config(config),
-            None => Ok(config),
+        let mut config = match &self.command {
+            Some(cmd) => cmd.process_config(config)?,
+            None => config,
+        };
+
+        if let Some(output_dir) = &self.generate_only {
+            if !matches!(
+                &self.command,
+                Some(CliCmd::Tx(_) | CliCmd::Create(_) | CliCmd::Update(_) | CliCmd::Upgrade(_))
+            ) {
+                return Err(FrameworkErrorKind::ConfigError
+                    .context(
+                        "`--generate-only` can only be used with the `tx`, `create`, `update` and `upgrade` commands",
+                    )
+                    .into());
+            }
+
+            let signer = self
+                .signer
+                .as_deref()
+                .map(Signer::from_str)
+                .transpose()
+                .map_err(|e| {
+                    FrameworkErrorKind::ConfigError.context(format!("invalid `--signer`: {e}"))
+                })?;
+
+            let generate_only = GenerateOnly {
+                output_dir: output_dir.clone(),
+                format: self.generate_format,
+                signer,
+            };
+
+            // The chains which do not support generate-only mode refuse to submit
+            // any transaction, so that only the commands which submit to them fail.
+            for chain_config in config.chains.iter_mut() {
+                chain_config.set_generate_only(generate_only.clone());
+            }
         }
+
+        Ok(config)
     }
 }
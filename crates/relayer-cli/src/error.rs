@@ -92,6 +92,13 @@ define_error! {
             [ TransferError ]
             |_| { "transfer error" },
 
+        TransferTrace
+            { reason: String }
+            | e | {
+                format_args!("failed to trace transfer: {0}",
+                    e.reason)
+            },
+
         Channel
             [ ChannelError ]
             |_| { "channel error" },
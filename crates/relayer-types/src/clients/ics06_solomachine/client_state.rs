@@ -0,0 +1,168 @@
+use std::time::Duration;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v3::ClientState as RawSmClientState;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics06_solomachine::consensus_state::ConsensusState;
+use crate::clients::ics06_solomachine::error::Error;
+use crate::core::ics02_client::client_state::ClientState as Ics2ClientState;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics24_host::identifier::ChainId;
+use crate::Height;
+
+pub const SOLOMACHINE_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.ClientState";
+
+/// The client state of a `06-solomachine` client.
+///
+/// The sequence is the number of the next signature the client expects from the
+/// solo machine, and doubles as the revision height of the client.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    pub sequence: u64,
+    pub is_frozen: bool,
+    pub consensus_state: ConsensusState,
+}
+
+impl ClientState {
+    pub fn new(sequence: u64, consensus_state: ConsensusState) -> Result<Self, Error> {
+        if sequence == 0 {
+            return Err(Error::invalid_sequence());
+        }
+
+        Ok(Self {
+            sequence,
+            is_frozen: false,
+            consensus_state,
+        })
+    }
+
+    pub fn latest_height(&self) -> Height {
+        Height::new(0, self.sequence).expect("solo machine client state has a sequence of 0")
+    }
+}
+
+impl Ics2ClientState for ClientState {
+    /// Solo machines are not chains, the diversifier stands in for their identifier.
+    fn chain_id(&self) -> ChainId {
+        ChainId::from_string(&self.consensus_state.diversifier)
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn latest_height(&self) -> Height {
+        self.latest_height()
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        self.is_frozen.then(|| self.latest_height())
+    }
+
+    /// A solo machine client has no trusting period and never expires.
+    fn expired(&self, _elapsed: Duration) -> bool {
+        false
+    }
+}
+
+impl Protobuf<RawSmClientState> for ClientState {}
+
+impl TryFrom<RawSmClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawSmClientState) -> Result<Self, Self::Error> {
+        let consensus_state = raw
+            .consensus_state
+            .ok_or_else(Error::missing_consensus_state)?
+            .try_into()?;
+
+        let mut client_state = Self::new(raw.sequence, consensus_state)?;
+        client_state.is_frozen = raw.is_frozen;
+
+        Ok(client_state)
+    }
+}
+
+impl From<ClientState> for RawSmClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            sequence: value.sequence,
+            is_frozen: value.is_frozen,
+            consensus_state: Some(value.consensus_state.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => RawSmClientState::decode(raw.value.as_slice())
+                .map_err(Error::decode)?
+                .try_into()
+                .map_err(Into::into),
+            _ => Err(Ics02Error::unexpected_client_state_type(
+                SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
+                raw.type_url,
+            )),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmClientState>::encode_vec(client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::Protobuf;
+    use test_log::test;
+
+    use super::ClientState;
+    use crate::clients::ics06_solomachine::consensus_state::ConsensusState;
+    use crate::clients::ics06_solomachine::public_key::PublicKey;
+    use crate::core::ics02_client::client_state::ClientState as _;
+    use crate::timestamp::Timestamp;
+    use crate::Height;
+
+    fn consensus_state() -> ConsensusState {
+        let mut key = [0x11; 33];
+        key[0] = 0x02;
+
+        ConsensusState::new(
+            PublicKey::from_secp256k1_bytes(&key).unwrap(),
+            "solo-0".to_string(),
+            Timestamp::from_nanoseconds(1_700_000_000_000_000_000).unwrap(),
+        )
+    }
+
+    #[test]
+    fn solomachine_client_state_roundtrip() {
+        let client_state = ClientState::new(7, consensus_state()).unwrap();
+
+        let encoded = Protobuf::<Any>::encode_vec(client_state.clone());
+        let decoded: ClientState = Protobuf::<Any>::decode_vec(&encoded).unwrap();
+
+        assert_eq!(decoded, client_state);
+        assert_eq!(decoded.latest_height(), Height::new(0, 7).unwrap());
+        assert_eq!(decoded.chain_id().as_str(), "solo-0");
+    }
+
+    #[test]
+    fn solomachine_client_state_rejects_zero_sequence() {
+        assert!(ClientState::new(0, consensus_state()).is_err());
+    }
+}
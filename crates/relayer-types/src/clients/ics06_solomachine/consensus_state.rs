@@ -0,0 +1,118 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v3::ConsensusState as RawSmConsensusState;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::public_key::PublicKey;
+use crate::core::ics02_client::client_type::ClientType;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::core::ics23_commitment::commitment::CommitmentRoot;
+use crate::timestamp::Timestamp;
+
+pub const SOLOMACHINE_CONSENSUS_STATE_TYPE_URL: &str =
+    "/ibc.lightclients.solomachine.v3.ConsensusState";
+
+/// The consensus state of a solo machine, that is the key it currently signs with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusState {
+    pub public_key: PublicKey,
+    /// Arbitrary string included in the signed bytes, which lets the same key
+    /// be used by several solo machines without their signatures being interchangeable.
+    pub diversifier: String,
+    pub timestamp: Timestamp,
+    /// Solo machines have no commitment root, this one is always empty.
+    #[serde(skip, default = "empty_root")]
+    root: CommitmentRoot,
+}
+
+fn empty_root() -> CommitmentRoot {
+    CommitmentRoot::from_bytes(&[])
+}
+
+impl ConsensusState {
+    pub fn new(public_key: PublicKey, diversifier: String, timestamp: Timestamp) -> Self {
+        Self {
+            public_key,
+            diversifier,
+            timestamp,
+            root: empty_root(),
+        }
+    }
+}
+
+impl crate::core::ics02_client::consensus_state::ConsensusState for ConsensusState {
+    fn client_type(&self) -> ClientType {
+        ClientType::SoloMachine
+    }
+
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+}
+
+impl Protobuf<RawSmConsensusState> for ConsensusState {}
+
+impl TryFrom<RawSmConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawSmConsensusState) -> Result<Self, Self::Error> {
+        let public_key = raw
+            .public_key
+            .ok_or_else(Error::missing_public_key)?
+            .try_into()?;
+
+        if raw.diversifier.is_empty() {
+            return Err(Error::empty_diversifier());
+        }
+
+        let timestamp = Timestamp::from_nanoseconds(raw.timestamp)
+            .ok()
+            .filter(|timestamp| *timestamp != Timestamp::none())
+            .ok_or_else(|| Error::invalid_timestamp(raw.timestamp))?;
+
+        Ok(Self::new(public_key, raw.diversifier, timestamp))
+    }
+}
+
+impl From<ConsensusState> for RawSmConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        Self {
+            public_key: Some(value.public_key.into()),
+            diversifier: value.diversifier,
+            timestamp: value.timestamp.nanoseconds(),
+        }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => {
+                RawSmConsensusState::decode(raw.value.as_slice())
+                    .map_err(Error::decode)?
+                    .try_into()
+                    .map_err(Into::into)
+            }
+            _ => Err(Ics02Error::unknown_consensus_state_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: SOLOMACHINE_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmConsensusState>::encode_vec(consensus_state),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use flex_error::{define_error, TraceError};
+
+use crate::core::ics02_client::error::Error as Ics02Error;
+
+define_error! {
+    #[derive(Debug, PartialEq, Eq)]
+    Error {
+        MissingConsensusState
+            |_| { "missing consensus state" },
+
+        MissingPublicKey
+            |_| { "missing public key" },
+
+        UnsupportedPublicKeyType
+            { type_url: String }
+            |e| { format_args!("unsupported public key type: {}", e.type_url) },
+
+        InvalidPublicKey
+            { reason: String }
+            |e| { format_args!("invalid public key: {}", e.reason) },
+
+        InvalidSequence
+            |_| { "invalid sequence, the sequence of a solo machine client starts at 1" },
+
+        InvalidTimestamp
+            { timestamp: u64 }
+            |e| { format_args!("invalid timestamp: {}", e.timestamp) },
+
+        EmptyDiversifier
+            |_| { "empty diversifier" },
+
+        EmptySignature
+            |_| { "empty signature" },
+
+        Decode
+            [ TraceError<prost::DecodeError> ]
+            |_| { "decode error" },
+    }
+}
+
+impl From<Error> for Ics02Error {
+    fn from(e: Error) -> Self {
+        Self::client_specific(e.to_string())
+    }
+}
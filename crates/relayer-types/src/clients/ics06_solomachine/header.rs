@@ -0,0 +1,92 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::solomachine::v3::Header as RawSmHeader;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics06_solomachine::error::Error;
+use crate::clients::ics06_solomachine::public_key::PublicKey;
+use crate::core::ics02_client::error::Error as Ics02Error;
+use crate::timestamp::Timestamp;
+
+pub const SOLOMACHINE_HEADER_TYPE_URL: &str = "/ibc.lightclients.solomachine.v3.Header";
+
+/// A solo machine header, which rotates the key and diversifier of the solo machine.
+///
+/// The header is signed with the current key over the current sequence of the client,
+/// see [`header_sign_bytes`](crate::clients::ics06_solomachine::proof::header_sign_bytes).
+/// Since that sequence is not part of the header, the header does not carry a height.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub timestamp: Timestamp,
+    pub signature: Vec<u8>,
+    pub new_public_key: PublicKey,
+    pub new_diversifier: String,
+}
+
+impl Protobuf<RawSmHeader> for Header {}
+
+impl TryFrom<RawSmHeader> for Header {
+    type Error = Error;
+
+    fn try_from(raw: RawSmHeader) -> Result<Self, Self::Error> {
+        let timestamp = Timestamp::from_nanoseconds(raw.timestamp)
+            .ok()
+            .filter(|timestamp| *timestamp != Timestamp::none())
+            .ok_or_else(|| Error::invalid_timestamp(raw.timestamp))?;
+
+        if raw.signature.is_empty() {
+            return Err(Error::empty_signature());
+        }
+
+        if raw.new_diversifier.is_empty() {
+            return Err(Error::empty_diversifier());
+        }
+
+        Ok(Self {
+            timestamp,
+            signature: raw.signature,
+            new_public_key: raw
+                .new_public_key
+                .ok_or_else(Error::missing_public_key)?
+                .try_into()?,
+            new_diversifier: raw.new_diversifier,
+        })
+    }
+}
+
+impl From<Header> for RawSmHeader {
+    fn from(value: Header) -> Self {
+        Self {
+            timestamp: value.timestamp.nanoseconds(),
+            signature: value.signature,
+            new_public_key: Some(value.new_public_key.into()),
+            new_diversifier: value.new_diversifier,
+        }
+    }
+}
+
+impl Protobuf<Any> for Header {}
+
+impl TryFrom<Any> for Header {
+    type Error = Ics02Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SOLOMACHINE_HEADER_TYPE_URL => RawSmHeader::decode(raw.value.as_slice())
+                .map_err(Error::decode)?
+                .try_into()
+                .map_err(Into::into),
+            _ => Err(Ics02Error::unknown_header_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<Header> for Any {
+    fn from(header: Header) -> Self {
+        Any {
+            type_url: SOLOMACHINE_HEADER_TYPE_URL.to_string(),
+            value: Protobuf::<RawSmHeader>::encode_vec(header),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! ICS 06: Solo Machine Client verifies the state of a standalone machine,
+//! such as a phone, a browser or an off-chain process, which holds a single key.
+//!
+//! Instead of a commitment root, the consensus state of a solo machine client
+//! holds the public key of the machine. Every header and every proof is a signature
+//! by that key over the next sequence of the client, which therefore advances by
+//! one with every signature accepted by the client.
+
+pub mod client_state;
+pub mod consensus_state;
+pub mod error;
+pub mod header;
+pub mod proof;
+pub mod public_key;
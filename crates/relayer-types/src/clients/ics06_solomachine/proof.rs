@@ -0,0 +1,144 @@
+//! Signature-based proofs of a solo machine.
+//!
+//! The solo machine proves both headers and the membership (or absence) of a value
+//! at some path by signing a [`SignBytes`] message over the current sequence of its client.
+
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::data::{Single, Sum};
+use ibc_proto::cosmos::tx::signing::v1beta1::signature_descriptor::Data as SignatureData;
+use ibc_proto::cosmos::tx::signing::v1beta1::SignMode;
+use ibc_proto::ibc::core::commitment::v1::MerklePath;
+use ibc_proto::ibc::lightclients::solomachine::v3::{
+    HeaderData, SignBytes, TimestampedSignatureData,
+};
+use prost::Message;
+
+use crate::clients::ics06_solomachine::public_key::PublicKey;
+use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
+use crate::proofs::ProofError;
+use crate::timestamp::Timestamp;
+
+/// The path signed over by the solo machine when producing a header.
+pub const SENTINEL_HEADER_PATH: &str = "solomachine:header";
+
+/// The bytes signed by the solo machine to rotate its key and diversifier.
+pub fn header_sign_bytes(
+    sequence: u64,
+    timestamp: Timestamp,
+    diversifier: &str,
+    new_public_key: PublicKey,
+    new_diversifier: String,
+) -> Vec<u8> {
+    let data = HeaderData {
+        new_pub_key: Some(new_public_key.into()),
+        new_diversifier,
+    };
+
+    SignBytes {
+        sequence,
+        timestamp: timestamp.nanoseconds(),
+        diversifier: diversifier.to_string(),
+        path: SENTINEL_HEADER_PATH.as_bytes().to_vec(),
+        data: data.encode_to_vec(),
+    }
+    .encode_to_vec()
+}
+
+/// The bytes signed by the solo machine to prove that `value` is stored at `path`,
+/// or that nothing is stored at `path` if `value` is empty.
+pub fn membership_sign_bytes(
+    sequence: u64,
+    timestamp: Timestamp,
+    diversifier: &str,
+    path: MerklePath,
+    value: Vec<u8>,
+) -> Vec<u8> {
+    SignBytes {
+        sequence,
+        timestamp: timestamp.nanoseconds(),
+        diversifier: diversifier.to_string(),
+        path: path.encode_to_vec(),
+        data: value,
+    }
+    .encode_to_vec()
+}
+
+/// Encode a raw signature by a single key in the form expected by the solo machine client.
+pub fn signature_data(signature: Vec<u8>) -> Vec<u8> {
+    SignatureData {
+        sum: Some(Sum::Single(Single {
+            mode: SignMode::Direct as i32,
+            signature,
+        })),
+    }
+    .encode_to_vec()
+}
+
+/// The proof of a solo machine, that is a signature over sign bytes
+/// along with the timestamp included in those bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimestampedSignature {
+    pub signature: Vec<u8>,
+    pub timestamp: Timestamp,
+}
+
+impl TimestampedSignature {
+    pub fn new(signature: Vec<u8>, timestamp: Timestamp) -> Self {
+        Self {
+            signature,
+            timestamp,
+        }
+    }
+
+    pub fn into_proof(self) -> Result<CommitmentProofBytes, ProofError> {
+        TimestampedSignatureData {
+            signature_data: signature_data(self.signature),
+            timestamp: self.timestamp.nanoseconds(),
+        }
+        .encode_to_vec()
+        .try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::ibc::lightclients::solomachine::v3::{SignBytes, TimestampedSignatureData};
+    use prost::Message;
+    use test_log::test;
+
+    use super::{header_sign_bytes, TimestampedSignature, SENTINEL_HEADER_PATH};
+    use crate::clients::ics06_solomachine::public_key::PublicKey;
+    use crate::timestamp::Timestamp;
+
+    #[test]
+    fn header_sign_bytes_cover_sequence_and_sentinel_path() {
+        let mut key = [0x22; 33];
+        key[0] = 0x03;
+        let timestamp = Timestamp::from_nanoseconds(42).unwrap();
+
+        let bytes = header_sign_bytes(
+            3,
+            timestamp,
+            "solo-0",
+            PublicKey::from_secp256k1_bytes(&key).unwrap(),
+            "solo-1".to_string(),
+        );
+        let sign_bytes = SignBytes::decode(bytes.as_slice()).unwrap();
+
+        assert_eq!(sign_bytes.sequence, 3);
+        assert_eq!(sign_bytes.timestamp, 42);
+        assert_eq!(sign_bytes.diversifier, "solo-0");
+        assert_eq!(sign_bytes.path, SENTINEL_HEADER_PATH.as_bytes());
+    }
+
+    #[test]
+    fn timestamped_signature_proof() {
+        let timestamp = Timestamp::from_nanoseconds(42).unwrap();
+        let proof = TimestampedSignature::new(vec![0xAB; 64], timestamp)
+            .into_proof()
+            .unwrap();
+
+        let raw = TimestampedSignatureData::decode(proof.as_bytes()).unwrap();
+        assert_eq!(raw.timestamp, 42);
+        assert!(!raw.signature_data.is_empty());
+    }
+}
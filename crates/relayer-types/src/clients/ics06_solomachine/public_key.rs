@@ -0,0 +1,56 @@
+use ibc_proto::cosmos::crypto::secp256k1::PubKey as RawSecp256k1PubKey;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tendermint_proto::google::protobuf::Any as TmAny;
+
+use crate::clients::ics06_solomachine::error::Error;
+
+pub const SECP256K1_PUB_KEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
+
+/// Length of a compressed secp256k1 public key.
+const COMPRESSED_SECP256K1_LEN: usize = 33;
+
+/// The public key of a solo machine, in compressed secp256k1 form.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublicKey(Vec<u8>);
+
+impl PublicKey {
+    pub fn from_secp256k1_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != COMPRESSED_SECP256K1_LEN {
+            return Err(Error::invalid_public_key(format!(
+                "expected {COMPRESSED_SECP256K1_LEN} bytes for a compressed secp256k1 key, got {}",
+                bytes.len()
+            )));
+        }
+
+        Ok(Self(bytes.to_vec()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<TmAny> for PublicKey {
+    type Error = Error;
+
+    fn try_from(raw: TmAny) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            SECP256K1_PUB_KEY_TYPE_URL => {
+                let raw_key =
+                    RawSecp256k1PubKey::decode(raw.value.as_slice()).map_err(Error::decode)?;
+                Self::from_secp256k1_bytes(&raw_key.key)
+            }
+            _ => Err(Error::unsupported_public_key_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<PublicKey> for TmAny {
+    fn from(public_key: PublicKey) -> Self {
+        TmAny {
+            type_url: SECP256K1_PUB_KEY_TYPE_URL.to_string(),
+            value: RawSecp256k1PubKey { key: public_key.0 }.encode_to_vec(),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::wasm::v1::ClientMessage as RawWasmClientMessage;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics08_wasm::{decode_inner, encode_inner};
+use crate::core::ics02_client::error::Error;
+
+pub const WASM_CLIENT_MESSAGE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientMessage";
+
+/// A header or misbehaviour submitted to a `08-wasm` client, which wraps
+/// the header or misbehaviour of the light client implemented by the contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientMessage {
+    pub data: Vec<u8>,
+}
+
+impl ClientMessage {
+    pub fn new(inner: Any) -> Self {
+        Self {
+            data: encode_inner(inner),
+        }
+    }
+
+    /// The header or misbehaviour of the wrapped light client.
+    pub fn inner(&self) -> Result<Any, Error> {
+        decode_inner(&self.data)
+    }
+}
+
+impl Protobuf<RawWasmClientMessage> for ClientMessage {}
+
+impl TryFrom<RawWasmClientMessage> for ClientMessage {
+    type Error = Error;
+
+    fn try_from(raw: RawWasmClientMessage) -> Result<Self, Self::Error> {
+        Ok(Self { data: raw.data })
+    }
+}
+
+impl From<ClientMessage> for RawWasmClientMessage {
+    fn from(value: ClientMessage) -> Self {
+        Self { data: value.data }
+    }
+}
+
+impl Protobuf<Any> for ClientMessage {}
+
+impl TryFrom<Any> for ClientMessage {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            WASM_CLIENT_MESSAGE_TYPE_URL => RawWasmClientMessage::decode(raw.value.as_slice())
+                .map_err(Error::decode)?
+                .try_into(),
+            _ => Err(Error::unknown_header_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ClientMessage> for Any {
+    fn from(message: ClientMessage) -> Self {
+        Any {
+            type_url: WASM_CLIENT_MESSAGE_TYPE_URL.to_string(),
+            value: Protobuf::<RawWasmClientMessage>::encode_vec(message),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::wasm::v1::ClientState as RawWasmClientState;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics08_wasm::{decode_inner, encode_inner};
+use crate::core::ics02_client::error::Error;
+use crate::Height;
+
+pub const WASM_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientState";
+
+/// The client state of a `08-wasm` client, which wraps the client state
+/// of the light client implemented by the contract with the given checksum.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    pub data: Vec<u8>,
+    pub checksum: Vec<u8>,
+    pub latest_height: Height,
+}
+
+impl ClientState {
+    pub fn new(inner: Any, checksum: Vec<u8>, latest_height: Height) -> Self {
+        Self {
+            data: encode_inner(inner),
+            checksum,
+            latest_height,
+        }
+    }
+
+    /// The client state of the wrapped light client.
+    pub fn inner(&self) -> Result<Any, Error> {
+        decode_inner(&self.data)
+    }
+}
+
+impl Protobuf<RawWasmClientState> for ClientState {}
+
+impl TryFrom<RawWasmClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawWasmClientState) -> Result<Self, Self::Error> {
+        let latest_height = raw
+            .latest_height
+            .ok_or_else(Error::missing_height)?
+            .try_into()?;
+
+        Ok(Self {
+            data: raw.data,
+            checksum: raw.checksum,
+            latest_height,
+        })
+    }
+}
+
+impl From<ClientState> for RawWasmClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            data: value.data,
+            checksum: value.checksum,
+            latest_height: Some(value.latest_height.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            WASM_CLIENT_STATE_TYPE_URL => RawWasmClientState::decode(raw.value.as_slice())
+                .map_err(Error::decode)?
+                .try_into(),
+            _ => Err(Error::unexpected_client_state_type(
+                WASM_CLIENT_STATE_TYPE_URL.to_string(),
+                raw.type_url,
+            )),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: WASM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawWasmClientState>::encode_vec(client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::Protobuf;
+    use test_log::test;
+
+    use super::ClientState;
+    use crate::Height;
+
+    #[test]
+    fn wasm_client_state_roundtrip() {
+        let inner = Any {
+            type_url: "/ibc.lightclients.tendermint.v1.ClientState".to_string(),
+            value: vec![1, 2, 3],
+        };
+
+        let client_state =
+            ClientState::new(inner.clone(), vec![0xab; 32], Height::new(1, 42).unwrap());
+
+        let encoded = Protobuf::<Any>::encode_vec(client_state.clone());
+        let decoded: ClientState = Protobuf::<Any>::decode_vec(&encoded).unwrap();
+
+        assert_eq!(decoded, client_state);
+        assert_eq!(decoded.inner().unwrap(), inner);
+    }
+}
@@ -0,0 +1,70 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::wasm::v1::ConsensusState as RawWasmConsensusState;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::clients::ics08_wasm::{decode_inner, encode_inner};
+use crate::core::ics02_client::error::Error;
+
+pub const WASM_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ConsensusState";
+
+/// The consensus state of a `08-wasm` client, which wraps the consensus state
+/// of the light client implemented by the contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsensusState {
+    pub data: Vec<u8>,
+}
+
+impl ConsensusState {
+    pub fn new(inner: Any) -> Self {
+        Self {
+            data: encode_inner(inner),
+        }
+    }
+
+    /// The consensus state of the wrapped light client.
+    pub fn inner(&self) -> Result<Any, Error> {
+        decode_inner(&self.data)
+    }
+}
+
+impl Protobuf<RawWasmConsensusState> for ConsensusState {}
+
+impl TryFrom<RawWasmConsensusState> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: RawWasmConsensusState) -> Result<Self, Self::Error> {
+        Ok(Self { data: raw.data })
+    }
+}
+
+impl From<ConsensusState> for RawWasmConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        Self { data: value.data }
+    }
+}
+
+impl Protobuf<Any> for ConsensusState {}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            WASM_CONSENSUS_STATE_TYPE_URL => RawWasmConsensusState::decode(raw.value.as_slice())
+                .map_err(Error::decode)?
+                .try_into(),
+            _ => Err(Error::unknown_consensus_state_type(raw.type_url)),
+        }
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(consensus_state: ConsensusState) -> Self {
+        Any {
+            type_url: WASM_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawWasmConsensusState>::encode_vec(consensus_state),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! ICS 08: Wasm Client wraps the states and messages of another light client,
+//! whose verification algorithm is implemented by a Wasm contract stored on the host chain.
+//!
+//! The wrapped data is the protobuf encoding of the inner light client's `Any` message.
+
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+
+use crate::core::ics02_client::error::Error;
+
+pub mod client_message;
+pub mod client_state;
+pub mod consensus_state;
+
+pub(crate) fn encode_inner(inner: Any) -> Vec<u8> {
+    inner.encode_to_vec()
+}
+
+pub(crate) fn decode_inner(data: &[u8]) -> Result<Any, Error> {
+    Any::decode(data).map_err(Error::decode)
+}
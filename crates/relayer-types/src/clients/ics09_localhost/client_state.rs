@@ -0,0 +1,95 @@
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::lightclients::localhost::v2::ClientState as RawLocalhostClientState;
+use ibc_proto::Protobuf;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::core::ics02_client::error::Error;
+use crate::Height;
+
+pub const LOCALHOST_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.localhost.v2.ClientState";
+
+/// The client state of the `09-localhost` client, which only tracks
+/// the latest height of the chain hosting it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientState {
+    pub latest_height: Height,
+}
+
+impl ClientState {
+    pub fn new(latest_height: Height) -> Self {
+        Self { latest_height }
+    }
+}
+
+impl Protobuf<RawLocalhostClientState> for ClientState {}
+
+impl TryFrom<RawLocalhostClientState> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: RawLocalhostClientState) -> Result<Self, Self::Error> {
+        let latest_height = raw
+            .latest_height
+            .ok_or_else(Error::missing_height)?
+            .try_into()?;
+
+        Ok(Self { latest_height })
+    }
+}
+
+impl From<ClientState> for RawLocalhostClientState {
+    fn from(value: ClientState) -> Self {
+        Self {
+            latest_height: Some(value.latest_height.into()),
+        }
+    }
+}
+
+impl Protobuf<Any> for ClientState {}
+
+impl TryFrom<Any> for ClientState {
+    type Error = Error;
+
+    fn try_from(raw: Any) -> Result<Self, Self::Error> {
+        match raw.type_url.as_str() {
+            LOCALHOST_CLIENT_STATE_TYPE_URL => {
+                RawLocalhostClientState::decode(raw.value.as_slice())
+                    .map_err(Error::decode)?
+                    .try_into()
+            }
+            _ => Err(Error::unexpected_client_state_type(
+                LOCALHOST_CLIENT_STATE_TYPE_URL.to_string(),
+                raw.type_url,
+            )),
+        }
+    }
+}
+
+impl From<ClientState> for Any {
+    fn from(client_state: ClientState) -> Self {
+        Any {
+            type_url: LOCALHOST_CLIENT_STATE_TYPE_URL.to_string(),
+            value: Protobuf::<RawLocalhostClientState>::encode_vec(client_state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::Protobuf;
+    use test_log::test;
+
+    use super::ClientState;
+    use crate::Height;
+
+    #[test]
+    fn localhost_client_state_roundtrip() {
+        let client_state = ClientState::new(Height::new(1, 42).unwrap());
+
+        let encoded = Protobuf::<Any>::encode_vec(client_state);
+        let decoded: ClientState = Protobuf::<Any>::decode_vec(&encoded).unwrap();
+
+        assert_eq!(decoded, client_state);
+    }
+}
@@ -0,0 +1,16 @@
+//! ICS 09: Localhost Client verifies the state of the chain hosting it, enabling channels
+//! between two modules of the same chain over the `connection-localhost` connection.
+//!
+//! The localhost client is updated by the host chain itself at every block,
+//! and accepts a sentinel value instead of actual proofs.
+
+pub mod client_state;
+
+/// Identifier of the localhost client, of which there is a single instance per chain.
+pub const LOCALHOST_CLIENT_ID: &str = "09-localhost";
+
+/// Identifier of the sentinel connection backed by the localhost client.
+pub const LOCALHOST_CONNECTION_ID: &str = "connection-localhost";
+
+/// The proof expected by the localhost client in place of a commitment proof.
+pub const SENTINEL_PROOF: &[u8] = &[0x01];
@@ -7,15 +7,24 @@ use super::error::Error;
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum ClientType {
     Tendermint = 1,
+    SoloMachine = 6,
+    Wasm = 8,
+    Localhost = 9,
 }
 
 impl ClientType {
     const TENDERMINT_STR: &'static str = "07-tendermint";
+    const SOLOMACHINE_STR: &'static str = "06-solomachine";
+    const WASM_STR: &'static str = "08-wasm";
+    const LOCALHOST_STR: &'static str = "09-localhost";
 
     /// Yields the identifier of this client type as a string
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Tendermint => Self::TENDERMINT_STR,
+            Self::SoloMachine => Self::SOLOMACHINE_STR,
+            Self::Wasm => Self::WASM_STR,
+            Self::Localhost => Self::LOCALHOST_STR,
         }
     }
 }
@@ -32,6 +41,9 @@ impl core::str::FromStr for ClientType {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             Self::TENDERMINT_STR => Ok(Self::Tendermint),
+            Self::SOLOMACHINE_STR => Ok(Self::SoloMachine),
+            Self::WASM_STR => Ok(Self::Wasm),
+            Self::LOCALHOST_STR => Ok(Self::Localhost),
 
             _ => Err(Error::unknown_client_type(s.to_string())),
         }
@@ -56,6 +68,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_solomachine_client_type() {
+        let client_type = ClientType::from_str("06-solomachine");
+
+        match client_type {
+            Ok(ClientType::SoloMachine) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
+    #[test]
+    fn parse_wasm_client_type() {
+        let client_type = ClientType::from_str("08-wasm");
+
+        match client_type {
+            Ok(ClientType::Wasm) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
+    #[test]
+    fn parse_localhost_client_type() {
+        let client_type = ClientType::from_str("09-localhost");
+
+        match client_type {
+            Ok(ClientType::Localhost) => (),
+            _ => panic!("parse failed"),
+        }
+    }
+
     #[test]
     fn parse_unknown_client_type() {
         let client_type_str = "some-random-client-type";
@@ -26,7 +26,7 @@ pub const HEADER_ATTRIBUTE_KEY: &str = "header";
 
 /// NewBlock event signals the committing & execution of a new block.
 // TODO - find a better place for NewBlock
-#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
 pub struct NewBlock {
     pub height: Height,
 }
@@ -97,7 +97,7 @@ impl From<Attributes> for Vec<abci::EventAttribute> {
 }
 
 /// CreateClient event signals the creation of a new on-chain client (IBC client).
-#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct CreateClient(pub Attributes);
 
 impl CreateClient {
@@ -134,7 +134,7 @@ impl From<CreateClient> for abci::Event {
 }
 
 /// UpdateClient event signals a recent update of an on-chain client (IBC Client).
-#[derive(Clone, Debug, PartialEq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct UpdateClient {
     pub common: Attributes,
     pub header: Option<AnyHeader>,
@@ -199,7 +199,7 @@ impl From<UpdateClient> for abci::Event {
 
 /// ClientMisbehaviour event signals the update of an on-chain client (IBC Client) with evidence of
 /// misbehaviour.
-#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
 pub struct ClientMisbehaviour(pub Attributes);
 
 impl ClientMisbehaviour {
@@ -236,7 +236,7 @@ impl From<ClientMisbehaviour> for abci::Event {
 }
 
 /// Signals a recent upgrade of an on-chain client (IBC Client).
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct UpgradeClient(pub Attributes);
 
 impl UpgradeClient {
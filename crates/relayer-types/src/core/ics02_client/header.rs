@@ -8,6 +8,9 @@ use ibc_proto::Protobuf;
 use crate::clients::ics07_tendermint::header::{
     decode_header as tm_decode_header, Header as TendermintHeader, TENDERMINT_HEADER_TYPE_URL,
 };
+use crate::clients::ics08_wasm::client_message::{
+    ClientMessage as WasmClientMessage, WASM_CLIENT_MESSAGE_TYPE_URL,
+};
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics02_client::error::Error;
 use crate::timestamp::Timestamp;
@@ -28,37 +31,51 @@ pub trait Header: Debug + Send + Sync // Any: From<Self>,
 
 /// Decodes an encoded header into a known `Header` type,
 pub fn decode_header(header_bytes: &[u8]) -> Result<AnyHeader, Error> {
-    // For now, we only have tendermint; however when there is more than one, we
-    // can try decoding into all the known types, and return an error only if
-    // none work
-    let header: TendermintHeader =
-        Protobuf::<Any>::decode(header_bytes).map_err(Error::invalid_raw_header)?;
-
-    Ok(AnyHeader::Tendermint(header))
+    Protobuf::<Any>::decode(header_bytes).map_err(Error::invalid_raw_header)
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum AnyHeader {
     Tendermint(TendermintHeader),
+    /// The header of a light client wrapped in a `08-wasm` client message
+    Wasm(Box<AnyHeader>),
+}
+
+impl AnyHeader {
+    /// The header of the underlying light client, unwrapping any `08-wasm` client message.
+    pub fn inner(&self) -> &AnyHeader {
+        match self {
+            Self::Wasm(header) => header.inner(),
+            _ => self,
+        }
+    }
+
+    /// Wrap this header in a `08-wasm` client message.
+    pub fn wrap_wasm(self) -> Self {
+        Self::Wasm(Box::new(self))
+    }
 }
 
 impl Header for AnyHeader {
     fn client_type(&self) -> ClientType {
         match self {
             Self::Tendermint(header) => header.client_type(),
+            Self::Wasm(_) => ClientType::Wasm,
         }
     }
 
     fn height(&self) -> Height {
         match self {
             Self::Tendermint(header) => header.height(),
+            Self::Wasm(header) => header.height(),
         }
     }
 
     fn timestamp(&self) -> Timestamp {
         match self {
             Self::Tendermint(header) => header.timestamp(),
+            Self::Wasm(header) => header.timestamp(),
         }
     }
 }
@@ -75,6 +92,12 @@ impl TryFrom<Any> for AnyHeader {
                 Ok(AnyHeader::Tendermint(val))
             }
 
+            WASM_CLIENT_MESSAGE_TYPE_URL => {
+                let message = WasmClientMessage::try_from(raw)?;
+                let inner = AnyHeader::try_from(message.inner()?)?;
+                Ok(AnyHeader::Wasm(Box::new(inner)))
+            }
+
             _ => Err(Error::unknown_header_type(raw.type_url)),
         }
     }
@@ -89,6 +112,7 @@ impl From<AnyHeader> for Any {
                 type_url: TENDERMINT_HEADER_TYPE_URL.to_string(),
                 value: Protobuf::<RawHeader>::encode_vec(header),
             },
+            AnyHeader::Wasm(header) => WasmClientMessage::new((*header).into()).into(),
         }
     }
 }
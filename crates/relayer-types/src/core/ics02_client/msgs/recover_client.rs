@@ -0,0 +1,76 @@
+//! Definition of domain type message `MsgRecoverClient`.
+
+use std::str::FromStr;
+
+use ibc_proto::ibc::core::client::v1::MsgRecoverClient as RawMsgRecoverClient;
+use ibc_proto::Protobuf;
+
+use crate::core::ics02_client::error::Error;
+use crate::core::ics24_host::identifier::ClientId;
+use crate::signer::Signer;
+use crate::tx_msg::Msg;
+
+pub const TYPE_URL: &str = "/ibc.core.client.v1.MsgRecoverClient";
+
+/// A type of message that replaces an expired or frozen (IBC) client, the subject,
+/// with a healthy client tracking the same chain, the substitute.
+/// Must be signed by the authority of the IBC module, ie. usually the governance module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MsgRecoverClient {
+    pub subject_client_id: ClientId,
+    pub substitute_client_id: ClientId,
+    pub signer: Signer,
+}
+
+impl MsgRecoverClient {
+    pub fn new(
+        subject_client_id: ClientId,
+        substitute_client_id: ClientId,
+        signer: Signer,
+    ) -> Self {
+        MsgRecoverClient {
+            subject_client_id,
+            substitute_client_id,
+            signer,
+        }
+    }
+}
+
+impl Msg for MsgRecoverClient {
+    type ValidationError = crate::core::ics24_host::error::ValidationError;
+    type Raw = RawMsgRecoverClient;
+
+    fn route(&self) -> String {
+        crate::keys::ROUTER_KEY.to_string()
+    }
+
+    fn type_url(&self) -> String {
+        TYPE_URL.to_string()
+    }
+}
+
+impl Protobuf<RawMsgRecoverClient> for MsgRecoverClient {}
+
+impl TryFrom<RawMsgRecoverClient> for MsgRecoverClient {
+    type Error = Error;
+
+    fn try_from(raw: RawMsgRecoverClient) -> Result<Self, Self::Error> {
+        Ok(MsgRecoverClient {
+            subject_client_id: ClientId::from_str(&raw.subject_client_id)
+                .map_err(Error::invalid_client_identifier)?,
+            substitute_client_id: ClientId::from_str(&raw.substitute_client_id)
+                .map_err(Error::invalid_client_identifier)?,
+            signer: raw.signer.parse().map_err(Error::signer)?,
+        })
+    }
+}
+
+impl From<MsgRecoverClient> for RawMsgRecoverClient {
+    fn from(ics_msg: MsgRecoverClient) -> Self {
+        RawMsgRecoverClient {
+            subject_client_id: ics_msg.subject_client_id.to_string(),
+            substitute_client_id: ics_msg.substitute_client_id.to_string(),
+            signer: ics_msg.signer.to_string(),
+        }
+    }
+}
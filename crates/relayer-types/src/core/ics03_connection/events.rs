@@ -57,7 +57,7 @@ impl From<Attributes> for Vec<abci::EventAttribute> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenInit(pub Attributes);
 
 impl OpenInit {
@@ -102,7 +102,7 @@ impl From<OpenInit> for abci::Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenTry(pub Attributes);
 
 impl OpenTry {
@@ -147,7 +147,7 @@ impl From<OpenTry> for abci::Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenAck(pub Attributes);
 
 impl OpenAck {
@@ -192,7 +192,7 @@ impl From<OpenAck> for abci::Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenConfirm(pub Attributes);
 
 impl OpenConfirm {
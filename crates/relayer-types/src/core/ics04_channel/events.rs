@@ -159,7 +159,7 @@ impl From<UpgradeAttributes> for Vec<abci::EventAttribute> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenInit {
     pub port_id: PortId,
     pub channel_id: Option<ChannelId>,
@@ -212,7 +212,7 @@ impl EventType for OpenInit {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenTry {
     pub port_id: PortId,
     pub channel_id: Option<ChannelId>,
@@ -264,7 +264,7 @@ impl EventType for OpenTry {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenAck {
     pub port_id: PortId,
     pub channel_id: Option<ChannelId>,
@@ -321,7 +321,7 @@ impl EventType for OpenAck {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct OpenConfirm {
     pub port_id: PortId,
     pub channel_id: Option<ChannelId>,
@@ -374,7 +374,7 @@ impl EventType for OpenConfirm {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct CloseInit {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -451,7 +451,7 @@ impl EventType for CloseInit {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct CloseConfirm {
     pub channel_id: Option<ChannelId>,
     pub port_id: PortId,
@@ -501,7 +501,7 @@ impl EventType for CloseConfirm {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeInit {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -580,7 +580,7 @@ impl EventType for UpgradeInit {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeTry {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -659,7 +659,7 @@ impl EventType for UpgradeTry {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeAck {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -738,7 +738,7 @@ impl EventType for UpgradeAck {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeConfirm {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -817,7 +817,7 @@ impl EventType for UpgradeConfirm {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeOpen {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -896,7 +896,7 @@ impl EventType for UpgradeOpen {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeCancel {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -975,7 +975,7 @@ impl EventType for UpgradeCancel {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeTimeout {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -1071,7 +1071,7 @@ impl EventType for UpgradeTimeout {
 }
 //
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct UpgradeError {
     pub port_id: PortId,
     pub channel_id: ChannelId,
@@ -1201,7 +1201,7 @@ impl_from_ibc_to_abci_event!(
     CloseConfirm
 );
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct SendPacket {
     pub packet: Packet,
 }
@@ -1233,7 +1233,7 @@ impl From<SendPacket> for IbcEvent {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct ReceivePacket {
     pub packet: Packet,
 }
@@ -1265,10 +1265,13 @@ impl From<ReceivePacket> for IbcEvent {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct WriteAcknowledgement {
     pub packet: Packet,
-    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    #[serde(
+        serialize_with = "crate::serializers::ser_hex_upper",
+        deserialize_with = "crate::serializers::deser_hex_upper"
+    )]
     pub ack: Vec<u8>,
 }
 
@@ -1304,7 +1307,7 @@ impl From<WriteAcknowledgement> for IbcEvent {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct AcknowledgePacket {
     pub packet: Packet,
 }
@@ -1330,7 +1333,7 @@ impl From<AcknowledgePacket> for IbcEvent {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct TimeoutPacket {
     pub packet: Packet,
 }
@@ -1362,7 +1365,7 @@ impl From<TimeoutPacket> for IbcEvent {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct TimeoutOnClosePacket {
     pub packet: Packet,
 }
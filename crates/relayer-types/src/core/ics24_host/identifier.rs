@@ -5,6 +5,7 @@ use std::str::FromStr;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::clients::ics09_localhost::{LOCALHOST_CLIENT_ID, LOCALHOST_CONNECTION_ID};
 use crate::core::ics02_client::client_type::ClientType;
 use crate::core::ics24_host::error::ValidationError;
 
@@ -190,6 +191,9 @@ impl ClientId {
     pub fn prefix(client_type: ClientType) -> &'static str {
         match client_type {
             ClientType::Tendermint => ClientType::Tendermint.as_str(),
+            ClientType::SoloMachine => ClientType::SoloMachine.as_str(),
+            ClientType::Wasm => ClientType::Wasm.as_str(),
+            ClientType::Localhost => ClientType::Localhost.as_str(),
         }
     }
 
@@ -197,6 +201,16 @@ impl ClientId {
     pub fn as_bytes(&self) -> &[u8] {
         self.0.as_bytes()
     }
+
+    /// The identifier of the `09-localhost` client.
+    pub fn localhost() -> Self {
+        Self(LOCALHOST_CLIENT_ID.to_string())
+    }
+
+    /// Whether this is the identifier of the `09-localhost` client.
+    pub fn is_localhost(&self) -> bool {
+        self.as_str() == LOCALHOST_CLIENT_ID
+    }
 }
 
 /// This implementation provides a `to_string` method.
@@ -258,6 +272,16 @@ impl ConnectionId {
         "connection"
     }
 
+    /// The identifier of the sentinel connection backed by the `09-localhost` client.
+    pub fn localhost() -> Self {
+        Self(LOCALHOST_CONNECTION_ID.to_string())
+    }
+
+    /// Whether this is the identifier of the `09-localhost` connection.
+    pub fn is_localhost(&self) -> bool {
+        self.as_str() == LOCALHOST_CONNECTION_ID
+    }
+
     /// Get this identifier as a borrowed `&str`
     pub fn as_str(&self) -> &str {
         &self.0
@@ -532,4 +556,17 @@ mod tests {
             assert_eq!(chain_id.name(), expected);
         }
     }
+
+    #[test]
+    fn localhost_identifiers() {
+        assert_eq!(ClientId::localhost().as_str(), "09-localhost");
+        assert!(ClientId::from_str("09-localhost").unwrap().is_localhost());
+        assert!(!ClientId::default().is_localhost());
+
+        assert_eq!(ConnectionId::localhost().as_str(), "connection-localhost");
+        assert!(ConnectionId::from_str("connection-localhost")
+            .unwrap()
+            .is_localhost());
+        assert!(!ConnectionId::new(0).is_localhost());
+    }
 }
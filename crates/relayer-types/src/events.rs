@@ -98,6 +98,9 @@ pub enum WithBlockDataType {
     UpdateClient,
     SendPacket,
     WriteAck,
+    RecvPacket,
+    AcknowledgePacket,
+    TimeoutPacket,
 }
 
 impl WithBlockDataType {
@@ -107,6 +110,9 @@ impl WithBlockDataType {
             WithBlockDataType::UpdateClient => "update_client",
             WithBlockDataType::SendPacket => "send_packet",
             WithBlockDataType::WriteAck => "write_acknowledgement",
+            WithBlockDataType::RecvPacket => "recv_packet",
+            WithBlockDataType::AcknowledgePacket => "acknowledge_packet",
+            WithBlockDataType::TimeoutPacket => "timeout_packet",
         }
     }
 }
@@ -282,7 +288,7 @@ impl FromStr for IbcEventType {
 }
 
 /// Events created by the IBC component of a chain, destined for a relayer.
-#[derive(Debug, Clone, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum IbcEvent {
     NewBlock(NewBlock),
 
@@ -515,7 +521,7 @@ impl FromStr for ModuleId {
     }
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct ModuleEvent {
     pub kind: String,
     pub module_name: ModuleId,
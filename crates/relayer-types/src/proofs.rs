@@ -2,6 +2,7 @@ use flex_error::{define_error, TraceError};
 use prost::EncodeError;
 use serde::Serialize;
 
+use crate::clients::ics09_localhost::SENTINEL_PROOF;
 use crate::core::ics23_commitment::commitment::CommitmentProofBytes;
 use crate::Height;
 
@@ -55,6 +56,25 @@ impl Proofs {
         })
     }
 
+    /// Build the proofs expected by the `09-localhost` client, which verifies the state
+    /// of the host chain directly and only requires the sentinel proof in place of each
+    /// object proof. The `other_proof` is only needed for timeouts on close.
+    pub fn localhost(with_other_proof: bool, height: Height) -> Self {
+        let sentinel = || {
+            CommitmentProofBytes::try_from(SENTINEL_PROOF.to_vec())
+                .expect("sentinel proof is not empty")
+        };
+
+        Self {
+            object_proof: sentinel(),
+            client_proof: None,
+            consensus_proof: None,
+            host_consensus_state_proof: None,
+            other_proof: with_other_proof.then(sentinel),
+            height,
+        }
+    }
+
     /// Getter for the consensus_proof field of this proof. Intuitively, this is a proof that a
     /// client on the source chain stores a consensus state for the destination chain.
     pub fn consensus_proof(&self) -> Option<&ConsensusProof> {
@@ -116,3 +136,24 @@ impl ConsensusProof {
         &self.proof
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localhost_proofs() {
+        let height = Height::new(0, 10).unwrap();
+        let sentinel = CommitmentProofBytes::try_from(SENTINEL_PROOF.to_vec()).unwrap();
+
+        let proofs = Proofs::localhost(false, height);
+        assert_eq!(proofs.object_proof(), &sentinel);
+        assert_eq!(proofs.other_proof(), None);
+        assert_eq!(proofs.client_proof(), None);
+        assert!(proofs.consensus_proof().is_none());
+        assert_eq!(proofs.height(), height);
+
+        let proofs = Proofs::localhost(true, height);
+        assert_eq!(proofs.other_proof(), Some(&sentinel));
+    }
+}
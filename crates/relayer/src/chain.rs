@@ -8,5 +8,7 @@ pub mod namada;
 pub mod penumbra;
 pub mod requests;
 pub mod runtime;
+pub mod solomachine;
 pub mod tracking;
+pub mod update_batch;
 pub mod version;
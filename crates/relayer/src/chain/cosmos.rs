@@ -13,10 +13,11 @@ use std::thread;
 use tokio::runtime::Runtime as TokioRuntime;
 use tonic::codegen::http::Uri;
 use tonic::metadata::AsciiMetadataValue;
-use tracing::{debug, error, instrument, trace, warn};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use ibc_proto::cosmos::base::node::v1beta1::ConfigResponse;
 use ibc_proto::cosmos::staking::v1beta1::{Params as StakingParams, QueryParamsResponse};
+use ibc_proto::cosmos::tx::v1beta1::TxRaw;
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
@@ -66,7 +67,7 @@ use tendermint_light_client::verifier::types::LightBlock as TmLightBlock;
 use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use tendermint_rpc::endpoint::status;
-use tendermint_rpc::{Client, HttpClient, Order};
+use tendermint_rpc::{Client, HttpClient, Order, Scheme, Url, WebSocketClientUrl};
 
 use crate::account::Balance;
 use crate::chain::client::ClientSettings;
@@ -74,9 +75,11 @@ use crate::chain::cosmos::batch::{
     send_batched_messages_and_wait_check_tx, send_batched_messages_and_wait_commit,
     sequential_send_batched_messages_and_wait_commit,
 };
+use crate::chain::cosmos::dry_run::{dry_run_events, simulate_batched_messages};
 use crate::chain::cosmos::encode::key_pair_to_signer;
 use crate::chain::cosmos::fee::maybe_register_counterparty_payee;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
+use crate::chain::cosmos::generate::{generate_unsigned_txs, generated_tx_events};
 use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::balance::{query_all_balances, query_balance};
 use crate::chain::cosmos::query::connection::query_connection_params;
@@ -86,9 +89,15 @@ use crate::chain::cosmos::query::denom_trace::query_denom_trace;
 use crate::chain::cosmos::query::fee::query_incentivized_packet;
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::{
-    filter_matching_event, query_packets_from_block, query_packets_from_txs, query_txs,
+    filter_matching_event, query_packet_txs, query_packets_from_block, query_packets_from_txs,
+    query_txs,
 };
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
+use crate::chain::cosmos::sequence::{
+    pipelined_send_batched_messages_and_wait_check_tx,
+    pipelined_send_batched_messages_and_wait_commit, SequenceManager,
+};
+use crate::chain::cosmos::tx::send_signed_tx;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{
@@ -99,18 +108,19 @@ use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::version::Specs;
-use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+use crate::client_state::{AnyClientState, IdentifiedAnyClientState, LocalhostClientState};
 use crate::config::Error as ConfigError;
-use crate::config::{parse_gas_prices, ChainConfig, GasPrice};
+use crate::config::{parse_gas_prices, ChainConfig, EventSourceMode, GasPrice};
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
 use crate::event::source::{EventSource, TxEventSourceCmd};
-use crate::event::IbcEventWithHeight;
+use crate::event::{IbcEventWithHeight, IbcEventWithTxHash};
 use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::upgrade_chain::UpgradePlan;
 use crate::util::collate::CollatedIterExt;
 use crate::util::create_grpc_client;
 use crate::util::pretty::{
@@ -125,13 +135,16 @@ pub mod batch;
 pub mod client;
 pub mod compatibility;
 pub mod config;
+pub mod dry_run;
 pub mod eip_base_fee;
 pub mod encode;
 pub mod estimate;
 pub mod fee;
 pub mod gas;
+pub mod generate;
 pub mod query;
 pub mod retry;
+pub mod sequence;
 pub mod simulate;
 pub mod tx;
 pub mod types;
@@ -167,6 +180,9 @@ pub struct CosmosSdkChain {
     /// A cached copy of the account information
     account: Option<Account>,
 
+    /// The transactions in flight when pipelining transactions
+    sequences: SequenceManager,
+
     tx_monitor_cmd: Option<TxEventSourceCmd>,
 }
 
@@ -176,6 +192,13 @@ impl CosmosSdkChain {
         &self.config
     }
 
+    /// Broadcast a transaction which was signed outside of Hermes,
+    /// and wait for it to be committed.
+    pub fn send_signed_tx(&self, tx_raw: TxRaw) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.rt
+            .block_on(send_signed_tx(&self.rpc_client, &self.tx_config, tx_raw))
+    }
+
     /// The maximum size of any transaction sent by the relayer to this chain
     fn max_tx_size(&self) -> usize {
         self.config.max_tx_size.into()
@@ -360,6 +383,9 @@ impl CosmosSdkChain {
                 *max_retries,
                 self.rt.clone(),
             ),
+            Mode::Replay { path, speed } => {
+                EventSource::replay(self.config.id.clone(), path.clone(), *speed)
+            }
         }
         .map_err(Error::event_source)?;
 
@@ -638,7 +664,7 @@ impl CosmosSdkChain {
     /// Query the chain status of the RPC and gRPC nodes.
     ///
     /// Returns an error if any of the node is still syncing and has not caught up.
-    fn chain_status(&self) -> Result<status::Response, Error> {
+    pub fn chain_status(&self) -> Result<status::Response, Error> {
         crate::time!(
             "chain_status",
             {
@@ -712,7 +738,50 @@ impl CosmosSdkChain {
             self.config.memo_prefix.clone()
         };
 
-        if self.config.sequential_batch_tx {
+        if let Some(generate_only) = &self.config.generate_only {
+            let paths = generate_unsigned_txs(
+                &self.tx_config,
+                &key_pair,
+                account,
+                &memo_prefix,
+                proto_msgs,
+                generate_only,
+            )
+            .await?;
+
+            if paths.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            let status = query_status(self.id(), &self.rpc_client, &self.config.rpc_addr).await?;
+
+            Ok(generated_tx_events(&paths, status.height))
+        } else if self.config.dry_run {
+            simulate_batched_messages(
+                &self.tx_config,
+                &key_pair,
+                account,
+                &memo_prefix,
+                proto_msgs.clone(),
+                self.config.dry_run_output.as_deref(),
+            )
+            .await?;
+
+            let status = query_status(self.id(), &self.rpc_client, &self.config.rpc_addr).await?;
+
+            Ok(dry_run_events(&proto_msgs, status.height))
+        } else if self.config.pipeline_txs {
+            pipelined_send_batched_messages_and_wait_commit(
+                &self.rpc_client,
+                &self.tx_config,
+                &key_pair,
+                account,
+                &mut self.sequences,
+                &memo_prefix,
+                proto_msgs,
+            )
+            .await
+        } else if self.config.sequential_batch_tx {
             sequential_send_batched_messages_and_wait_commit(
                 &self.rpc_client,
                 &self.tx_config,
@@ -755,11 +824,21 @@ impl CosmosSdkChain {
             }
         );
 
+        let fee_bump_attempt = tracked_msgs.fee_bump_attempt;
         let proto_msgs = tracked_msgs.msgs;
 
         let key_pair = self.key()?;
         let key_account = key_pair.account();
 
+        let fee_bump = self.config.fee_bump.multiplier(fee_bump_attempt);
+
+        // When replacing a transaction which is stuck in the mempool, drop the cached
+        // account so that its sequence is re-fetched from the chain, which has not yet
+        // committed the stuck transaction. The replacement thus reuses its sequence.
+        if fee_bump.is_some() && self.config.fee_bump.reuse_sequence {
+            self.account = None;
+        }
+
         let account =
             get_or_fetch_account(&self.grpc_addr, &key_account, &mut self.account).await?;
 
@@ -769,9 +848,79 @@ impl CosmosSdkChain {
             self.config.memo_prefix.clone()
         };
 
+        if let Some(generate_only) = &self.config.generate_only {
+            let paths = generate_unsigned_txs(
+                &self.tx_config,
+                &key_pair,
+                account,
+                &memo_prefix,
+                proto_msgs,
+                generate_only,
+            )
+            .await?;
+
+            // Nothing was broadcast, so there is no `CheckTx` response to report
+            info!(
+                "generate-only mode: wrote {} unsigned transaction(s) to `{}`",
+                paths.len(),
+                generate_only.output_dir.display()
+            );
+
+            return Ok(Vec::new());
+        }
+
+        // Nothing is broadcast, so there is no pending transaction for the caller to track
+        if self.config.dry_run {
+            simulate_batched_messages(
+                &self.tx_config,
+                &key_pair,
+                account,
+                &memo_prefix,
+                proto_msgs,
+                self.config.dry_run_output.as_deref(),
+            )
+            .await?;
+
+            return Ok(Vec::new());
+        }
+
+        let bumped_tx_config = fee_bump.map(|multiplier| {
+            let gas_config = self.tx_config.gas_config.bumped(multiplier);
+
+            info!(
+                attempt = fee_bump_attempt,
+                multiplier,
+                sequence = %account.sequence,
+                "resubmitting transactions with bumped gas price {}",
+                gas_config.gas_price
+            );
+
+            crate::telemetry!(tx_fee_bumps, &self.config.id);
+
+            TxConfig {
+                gas_config,
+                ..self.tx_config.clone()
+            }
+        });
+
+        let tx_config = bumped_tx_config.as_ref().unwrap_or(&self.tx_config);
+
+        if self.config.pipeline_txs {
+            return pipelined_send_batched_messages_and_wait_check_tx(
+                &self.rpc_client,
+                tx_config,
+                &key_pair,
+                account,
+                &mut self.sequences,
+                &memo_prefix,
+                proto_msgs,
+            )
+            .await;
+        }
+
         send_batched_messages_and_wait_check_tx(
             &self.rpc_client,
-            &self.tx_config,
+            tx_config,
             &key_pair,
             account,
             &memo_prefix,
@@ -911,20 +1060,14 @@ impl ChainEndpoint for CosmosSdkChain {
 
     fn bootstrap(config: ChainConfig, rt: Arc<TokioRuntime>) -> Result<Self, Error> {
         #[allow(irrefutable_let_patterns)]
-        let ChainConfig::CosmosSdk(config) = config
+        let ChainConfig::CosmosSdk(mut config) = config
         else {
             return Err(Error::config(ConfigError::wrong_type()));
         };
 
-        let mut rpc_client = HttpClient::builder(config.rpc_addr.clone().try_into().unwrap())
-            .user_agent(format!("hermes/{}", HERMES_VERSION))
-            .build()
-            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
-
-        let compat_mode = rt.block_on(fetch_compat_mode(&rpc_client, &config))?;
-        rpc_client.set_compat_mode(compat_mode);
+        let (rpc_client, compat_mode, node_info) = rt.block_on(connect_rpc(&mut config))?;
+        let grpc_addr = rt.block_on(connect_grpc(&mut config))?;
 
-        let node_info = rt.block_on(fetch_node_info(&rpc_client, &config))?;
         let light_client = TmLightClient::from_cosmos_sdk_config(&config, node_info.id)?;
 
         // Initialize key store and load key
@@ -936,11 +1079,10 @@ impl ChainEndpoint for CosmosSdkChain {
         )
         .map_err(Error::key_base)?;
 
-        let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
-            .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
-
         let tx_config = TxConfig::try_from(&config)?;
 
+        let sequences = SequenceManager::new(config.max_in_flight_txs);
+
         // Retrieve the version specification of this chain
 
         let chain = Self {
@@ -953,6 +1095,7 @@ impl ChainEndpoint for CosmosSdkChain {
             keybase,
             tx_config,
             account: None,
+            sequences,
             tx_monitor_cmd: None,
         };
 
@@ -1100,6 +1243,16 @@ impl ChainEndpoint for CosmosSdkChain {
 
     /// Get the account for the signer
     fn get_signer(&self) -> Result<Signer, Error> {
+        // The messages exported in generate-only mode are signed offline by the given signer
+        if let Some(signer) = self
+            .config
+            .generate_only
+            .as_ref()
+            .and_then(|generate_only| generate_only.signer.clone())
+        {
+            return Ok(signer);
+        }
+
         // Get the key from key seed file
         let key_pair = self.key()?;
 
@@ -1277,6 +1430,22 @@ impl ChainEndpoint for CosmosSdkChain {
         );
         crate::telemetry!(query, self.id(), "query_client_state");
 
+        // The localhost client tracks this chain, and is not necessarily stored in the IBC store.
+        // No proof of its state is ever required, since the localhost connection has no handshake.
+        if request.client_id.is_localhost() && matches!(include_proof, IncludeProof::No) {
+            let latest_height = match request.height {
+                QueryHeight::Latest => self.query_chain_latest_height()?,
+                QueryHeight::Specific(height) => height,
+            };
+
+            let client_state = AnyClientState::Localhost(LocalhostClientState {
+                chain_id: self.id().clone(),
+                latest_height,
+            });
+
+            return Ok((client_state, None));
+        }
+
         let res = self.query(
             ClientStatePath(request.client_id.clone()),
             request.height,
@@ -1387,7 +1556,7 @@ impl ChainEndpoint for CosmosSdkChain {
 
         let consensus_state = AnyConsensusState::decode_vec(&res.value).map_err(Error::decode)?;
 
-        if !matches!(consensus_state, AnyConsensusState::Tendermint(_)) {
+        if !matches!(consensus_state.inner(), AnyConsensusState::Tendermint(_)) {
             return Err(Error::consensus_state_type_mismatch(
                 ClientType::Tendermint,
                 consensus_state.client_type(),
@@ -2376,6 +2545,26 @@ impl ChainEndpoint for CosmosSdkChain {
         }
     }
 
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        crate::time!(
+            "query_packet_txs",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_packet_txs");
+
+        self.block_on(query_packet_txs(
+            self.id(),
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            &request,
+        ))
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -2494,6 +2683,14 @@ impl ChainEndpoint for CosmosSdkChain {
         port_id: &PortId,
         counterparty_payee: &Signer,
     ) -> Result<(), Error> {
+        if self.config.dry_run {
+            info!(
+                "[dry-run] skipping registration of counterparty payee {counterparty_payee} on {port_id}/{channel_id}"
+            );
+
+            return Ok(());
+        }
+
         let address = self.get_signer()?;
         let key_pair = self.key()?;
 
@@ -2580,6 +2777,33 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(result)
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        use ibc_proto::cosmos::upgrade::v1beta1::query_client::QueryClient;
+        use ibc_proto::cosmos::upgrade::v1beta1::QueryCurrentPlanRequest;
+
+        crate::time!(
+            "query_upgrade_plan",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+        crate::telemetry!(query, self.id(), "query_upgrade_plan");
+
+        let mut client = self.block_on(create_grpc_client(&self.grpc_addr, QueryClient::new))?;
+
+        let request = tonic::Request::new(QueryCurrentPlanRequest {});
+
+        let response = self
+            .block_on(client.current_plan(request))
+            .map_err(|e| Error::grpc_status(e, "query_upgrade_plan".to_owned()))?
+            .into_inner();
+
+        response
+            .plan
+            .map(|plan| UpgradePlan::from_raw(self.id(), plan))
+            .transpose()
+    }
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
@@ -2699,6 +2923,119 @@ async fn fetch_node_info(
         .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))
 }
 
+/// Connect to the RPC endpoint of the chain, or to the first available of its backup RPC
+/// endpoints if it is unavailable, which then replaces `rpc_addr` in the given config.
+///
+/// When switching to a backup endpoint, a push event source is switched to
+/// the WebSocket endpoint of the backup node as well.
+async fn connect_rpc(
+    config: &mut CosmosSdkConfig,
+) -> Result<(HttpClient, CompatMode, node::Info), Error> {
+    async fn try_connect(
+        config: &CosmosSdkConfig,
+    ) -> Result<(HttpClient, CompatMode, node::Info), Error> {
+        let mut rpc_client = HttpClient::builder(config.rpc_addr.clone().try_into().unwrap())
+            .user_agent(format!("hermes/{}", HERMES_VERSION))
+            .build()
+            .map_err(|e| Error::rpc(config.rpc_addr.clone(), e))?;
+
+        let compat_mode = fetch_compat_mode(&rpc_client, config).await?;
+        rpc_client.set_compat_mode(compat_mode);
+
+        let node_info = fetch_node_info(&rpc_client, config).await?;
+
+        Ok((rpc_client, compat_mode, node_info))
+    }
+
+    let mut result = try_connect(config).await;
+
+    for rpc_addr in config.backup_rpc_addrs.clone() {
+        let Err(e) = &result else {
+            break;
+        };
+
+        warn!(
+            "RPC endpoint {} of chain {} is unavailable, switching to backup endpoint {rpc_addr}: {e}",
+            config.rpc_addr, config.id
+        );
+
+        config.rpc_addr = rpc_addr;
+        result = try_connect(config).await;
+
+        if result.is_ok() {
+            if let EventSourceMode::Push { url, .. } = &mut config.event_source {
+                let backup_url = websocket_url(&config.rpc_addr)?;
+
+                warn!(
+                    "switching the event source of chain {} from {url} to {backup_url}",
+                    config.id
+                );
+
+                *url = backup_url;
+            }
+        }
+    }
+
+    result
+}
+
+/// The WebSocket endpoint of the node at the given RPC address.
+fn websocket_url(rpc_addr: &Url) -> Result<WebSocketClientUrl, Error> {
+    let scheme = match rpc_addr.scheme() {
+        Scheme::Https | Scheme::SecureWebSocket => "wss",
+        Scheme::Http | Scheme::WebSocket => "ws",
+    };
+
+    let url = format!(
+        "{scheme}://{}:{}/websocket",
+        rpc_addr.host(),
+        rpc_addr.port()
+    );
+
+    WebSocketClientUrl::from_str(&url).map_err(|e| Error::rpc(rpc_addr.clone(), e))
+}
+
+/// Connect to the gRPC endpoint of the chain, or to the first available of its backup gRPC
+/// endpoints if it is unavailable, which then replaces `grpc_addr` in the given config.
+///
+/// Without backup endpoints, the gRPC endpoint is used as is, without checking it first.
+async fn connect_grpc(config: &mut CosmosSdkConfig) -> Result<Uri, Error> {
+    use ibc_proto::cosmos::base::tendermint::v1beta1::service_client::ServiceClient;
+
+    fn grpc_uri(config: &CosmosSdkConfig) -> Result<Uri, Error> {
+        Uri::from_str(&config.grpc_addr.to_string())
+            .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))
+    }
+
+    async fn try_connect(config: &CosmosSdkConfig) -> Result<Uri, Error> {
+        let grpc_addr = grpc_uri(config)?;
+        create_grpc_client(&grpc_addr, ServiceClient::new).await?;
+        Ok(grpc_addr)
+    }
+
+    if config.backup_grpc_addrs.is_empty() {
+        return grpc_uri(config);
+    }
+
+    let mut result = try_connect(config).await;
+
+    for grpc_addr in config.backup_grpc_addrs.clone() {
+        let Err(e) = &result else {
+            break;
+        };
+
+        warn!(
+            "gRPC endpoint {} of chain {} is unavailable, switching to backup endpoint {grpc_addr}: {e}",
+            config.grpc_addr, config.id
+        );
+
+        config.grpc_addr = grpc_addr;
+        result = try_connect(config).await;
+    }
+
+    result
+}
+
 /// Returns the suffix counter for a CosmosSDK client id.
 /// Returns `None` if the client identifier is malformed
 /// and the suffix could not be parsed.
@@ -2859,8 +3196,78 @@ pub async fn fetch_compat_mode(
 
 #[cfg(test)]
 mod tests {
-    use super::calculate_fee;
-    use crate::config::GasPrice;
+    use super::{calculate_fee, connect_grpc, connect_rpc, websocket_url, CosmosSdkConfig};
+    use crate::config::{self, ChainConfig, GasPrice};
+
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    /// The config of a chain whose endpoints, and their backups, all refuse connections
+    fn unavailable_chain_config() -> CosmosSdkConfig {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = config::load(path).expect("could not parse config");
+
+        #[allow(irrefutable_let_patterns)]
+        let Some(ChainConfig::CosmosSdk(chain_config)) =
+            config.find_chain(&ChainId::from_string("chain_A")).cloned()
+        else {
+            panic!("should be a cosmos sdk chain config");
+        };
+
+        CosmosSdkConfig {
+            rpc_addr: "http://127.0.0.1:1".parse().unwrap(),
+            grpc_addr: "http://127.0.0.1:1".parse().unwrap(),
+            backup_rpc_addrs: vec![
+                "http://127.0.0.1:2".parse().unwrap(),
+                "http://127.0.0.1:3".parse().unwrap(),
+            ],
+            backup_grpc_addrs: vec!["http://127.0.0.1:2".parse().unwrap()],
+            ..chain_config
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_to_backup_endpoints() {
+        let mut config = unavailable_chain_config();
+
+        assert!(connect_rpc(&mut config).await.is_err());
+        assert_eq!(config.rpc_addr.to_string(), "http://127.0.0.1:3/");
+
+        assert!(connect_grpc(&mut config).await.is_err());
+        assert_eq!(config.grpc_addr.to_string(), "http://127.0.0.1:2/");
+    }
+
+    #[test]
+    fn websocket_url_of_backup_node() {
+        let url = |rpc_addr: &str| {
+            websocket_url(&rpc_addr.parse().unwrap())
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(
+            url("http://127.0.0.1:26657"),
+            "ws://127.0.0.1:26657/websocket"
+        );
+        assert_eq!(
+            url("https://rpc.example.com/"),
+            "wss://rpc.example.com/websocket"
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_grpc_without_backup_endpoints() {
+        let mut config = CosmosSdkConfig {
+            backup_grpc_addrs: vec![],
+            ..unavailable_chain_config()
+        };
+
+        // The endpoint is used without being checked
+        let grpc_addr = connect_grpc(&mut config).await.unwrap();
+        assert_eq!(grpc_addr.to_string(), "http://127.0.0.1:1/");
+    }
 
     #[test]
     fn mul_ceil() {
@@ -238,7 +238,7 @@ pub fn response_to_tx_sync_result(
     }
 }
 
-async fn batch_messages(
+pub async fn batch_messages(
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &Account,
@@ -2,6 +2,7 @@ use core::time::Duration;
 use std::path::PathBuf;
 
 use byte_unit::Byte;
+use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 
@@ -9,12 +10,15 @@ use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::chain::cosmos::config::error::Error as ConfigError;
+use crate::chain::cosmos::generate::GenerateOnly;
 use crate::config::compat_mode::CompatMode;
 use crate::config::dynamic_gas::DynamicGasPrice;
+use crate::config::fee_bump::FeeBump;
 use crate::config::gas_multiplier::GasMultiplier;
 use crate::config::types::{MaxMsgNum, MaxTxSize, Memo, TrustThreshold};
 use crate::config::{
-    self, AddressType, EventSourceMode, ExtensionOption, GasPrice, GenesisRestart, PacketFilter,
+    self, schema, AddressType, EventSourceMode, ExtensionOption, GasPrice, GenesisRestart,
+    PacketFilter,
 };
 use crate::config::{default, RefreshRate};
 use crate::keyring::Store;
@@ -22,36 +26,60 @@ use crate::util::excluded_sequences::ExcludedSequences;
 
 pub mod error;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct CosmosSdkConfig {
     /// The chain's network identifier
+    #[schemars(schema_with = "schema::chain_id")]
     pub id: ChainId,
 
     /// The RPC URL to connect to
+    #[schemars(schema_with = "schema::url")]
     pub rpc_addr: Url,
 
     /// The gRPC URL to connect to
+    #[schemars(schema_with = "schema::url")]
     pub grpc_addr: Url,
 
+    /// Alternative RPC URLs of the chain, ranked from best to worst, the first
+    /// available of which is used if `rpc_addr` is unavailable when the chain starts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(schema_with = "schema::urls")]
+    pub backup_rpc_addrs: Vec<Url>,
+
+    /// Alternative gRPC URLs of the chain, ranked from best to worst, the first
+    /// available of which is used if `grpc_addr` is unavailable when the chain starts.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[schemars(schema_with = "schema::urls")]
+    pub backup_grpc_addrs: Vec<Url>,
+
     /// The type of event source and associated settings
     pub event_source: EventSourceMode,
 
     /// Timeout used when issuing RPC queries
     #[serde(default = "default::rpc_timeout", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
     pub rpc_timeout: Duration,
 
     /// Whether or not the full node Hermes connects to is trusted
     #[serde(default = "default::trusted_node")]
     pub trusted_node: bool,
 
+    /// The prefix of the account addresses of the chain, eg. `cosmos`
     pub account_prefix: String,
+    /// The name of the key used to sign the transactions
     pub key_name: String,
+    /// The type of keystore holding the key
     #[serde(default)]
     pub key_store_type: Store,
+    /// The folder of the keystore, defaults to `$HOME/.hermes/keys`
     pub key_store_folder: Option<PathBuf>,
+    /// The store prefix of the IBC module of the chain, usually `ibc`
     pub store_prefix: String,
+    /// The gas to use when the gas of a transaction cannot be estimated,
+    /// defaults to `max_gas`
     pub default_gas: Option<u64>,
+    /// The maximum gas of a transaction
     pub max_gas: Option<u64>,
 
     // This field is only meant to be set via the `update client` command,
@@ -59,19 +87,25 @@ pub struct CosmosSdkConfig {
     // therefore need and archive node to fetch blocks from.
     pub genesis_restart: Option<GenesisRestart>,
 
-    // This field is deprecated, use `gas_multiplier` instead
+    /// Deprecated, use `gas_multiplier` instead
     pub gas_adjustment: Option<f64>,
+    /// The multiplier applied to the estimated gas of a transaction
     pub gas_multiplier: Option<GasMultiplier>,
 
+    /// The address of the granter paying the fees of the transactions
     pub fee_granter: Option<String>,
 
+    /// The maximum number of messages in a transaction
     #[serde(default)]
     pub max_msg_num: MaxMsgNum,
 
+    /// The maximum size, in bytes, of a transaction
     #[serde(default)]
     pub max_tx_size: MaxTxSize,
 
+    /// The maximum size of the gRPC responses of the node
     #[serde(default = "default::max_grpc_decoding_size")]
+    #[schemars(schema_with = "schema::byte_size")]
     pub max_grpc_decoding_size: Byte,
 
     /// How many packets to fetch at once from the chain when clearing packets
@@ -85,14 +119,19 @@ pub struct CosmosSdkConfig {
     /// that uses this configuration, unless it is overridden by the client-specific
     /// clock drift option.
     #[serde(default = "default::clock_drift", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
     pub clock_drift: Duration,
 
+    /// The maximum time between two blocks of the chain
     #[serde(default = "default::max_block_time", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
     pub max_block_time: Duration,
 
     /// The trusting period specifies how long a validator set is trusted for
     /// (must be shorter than the chain's unbonding period).
     #[serde(default, with = "humantime_serde")]
+    #[schemars(skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "schema::duration")]
     pub trusting_period: Option<Duration>,
 
     /// The rate at which to refresh the client referencing this chain,
@@ -104,9 +143,11 @@ pub struct CosmosSdkConfig {
     #[serde(default = "default::ccv_consumer_chain")]
     pub ccv_consumer_chain: bool,
 
+    /// The prefix of the memo of the transactions
     #[serde(default)]
     pub memo_prefix: Memo,
 
+    /// The memo of the transactions, overwriting `memo_prefix` and the information appended to it
     #[serde(default)]
     pub memo_overwrite: Option<Memo>,
 
@@ -118,6 +159,31 @@ pub struct CosmosSdkConfig {
     #[serde(default)]
     pub sequential_batch_tx: bool,
 
+    /// Submit transactions back to back with locally tracked account sequences,
+    /// without waiting for the previous transactions to be committed.
+    #[serde(default)]
+    pub pipeline_txs: bool,
+
+    /// Maximum number of uncommitted transactions when `pipeline_txs` is enabled.
+    #[serde(default = "default::max_in_flight_txs")]
+    pub max_in_flight_txs: usize,
+
+    /// Simulate the transactions which would be submitted to this chain and
+    /// log them along with their estimated fees, instead of broadcasting them.
+    #[serde(default)]
+    pub dry_run: bool,
+
+    /// File to which the transactions which would have been submitted in
+    /// dry-run mode are appended, as newline-delimited JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dry_run_output: Option<PathBuf>,
+
+    /// Export the transactions which would be submitted to this chain unsigned,
+    /// instead of signing and broadcasting them.
+    /// Only set at runtime by `hermes --generate-only`.
+    #[serde(skip)]
+    pub generate_only: Option<GenerateOnly>,
+
     // Note: These last few need to be last otherwise we run into `ValueAfterTable` error when serializing to TOML.
     //       That's because these are all tables and have to come last when serializing.
     #[serde(
@@ -125,31 +191,49 @@ pub struct CosmosSdkConfig {
         skip_serializing_if = "Option::is_none",
         with = "config::proof_specs"
     )]
+    #[schemars(schema_with = "schema::proof_specs")]
     pub proof_specs: Option<ProofSpecs>,
 
     // These last few need to be last otherwise we run into `ValueAfterTable` error when serializing to TOML
     /// The trust threshold defines what fraction of the total voting power of a known
     /// and trusted validator set is sufficient for a commit to be accepted going forward.
     #[serde(default)]
+    #[schemars(schema_with = "schema::trust_threshold")]
     pub trust_threshold: TrustThreshold,
 
+    /// The gas price of the transactions
     pub gas_price: GasPrice,
 
+    /// Which packets to relay
     #[serde(default)]
     pub packet_filter: PacketFilter,
 
+    /// Query the gas price of the chain instead of using `gas_price`
     #[serde(default)]
     pub dynamic_gas_price: DynamicGasPrice,
 
+    /// Resubmit transactions which stay unconfirmed for too long
+    /// with an increasing fee.
+    #[serde(default)]
+    pub fee_bump: FeeBump,
+
+    /// How the account addresses of the chain are derived
     #[serde(default)]
     pub address_type: AddressType,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    /// The extension options of the transactions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub extension_options: Vec<ExtensionOption>,
+    #[schemars(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "schema::compat_mode")]
     pub compat_mode: Option<CompatMode>,
+    /// Interval, in number of blocks, at which pending packets are cleared,
+    /// overriding `mode.packets.clear_interval`
     pub clear_interval: Option<u64>,
+    /// Sequences of the packets not to clear, per channel
     #[serde(default)]
     pub excluded_sequences: ExcludedSequences,
 
+    /// Whether to relay ICS31 cross-chain query packets
     #[serde(default = "default::allow_ccq")]
     pub allow_ccq: bool,
 }
@@ -158,6 +242,7 @@ impl CosmosSdkConfig {
     pub fn validate(&self) -> Result<(), Diagnostic<ConfigError>> {
         validate_trust_threshold(&self.id, self.trust_threshold)?;
         validate_gas_settings(&self.id, self.gas_adjustment)?;
+        validate_pipeline_settings(&self.id, self)?;
         Ok(())
     }
 }
@@ -215,8 +300,111 @@ fn validate_gas_settings(
 
     Ok(())
 }
+
+fn validate_pipeline_settings(
+    id: &ChainId,
+    config: &CosmosSdkConfig,
+) -> Result<(), Diagnostic<ConfigError>> {
+    if !config.pipeline_txs {
+        return Ok(());
+    }
+
+    if config.sequential_batch_tx {
+        return Err(Diagnostic::Error(
+            ConfigError::pipeline_with_sequential_batch_tx(id.clone()),
+        ));
+    }
+
+    if config.max_in_flight_txs == 0 {
+        return Err(Diagnostic::Error(ConfigError::invalid_max_in_flight_txs(
+            id.clone(),
+        )));
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub enum Diagnostic<E> {
     Warning(E),
     Error(E),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain::cosmos::config::error::ErrorDetail as ConfigErrorDetail;
+    use crate::config::ChainConfig;
+
+    fn chain_config() -> CosmosSdkConfig {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = config::load(path).expect("could not parse config");
+
+        let Some(ChainConfig::CosmosSdk(chain_config)) =
+            config.find_chain(&ChainId::from_string("chain_A"))
+        else {
+            panic!("should be a cosmos sdk chain config");
+        };
+
+        chain_config.clone()
+    }
+
+    #[test]
+    fn pipeline_settings_disabled() {
+        let config = CosmosSdkConfig {
+            pipeline_txs: false,
+            sequential_batch_tx: true,
+            max_in_flight_txs: 0,
+            ..chain_config()
+        };
+
+        assert!(validate_pipeline_settings(&config.id, &config).is_ok());
+    }
+
+    #[test]
+    fn pipeline_settings_valid() {
+        let config = CosmosSdkConfig {
+            pipeline_txs: true,
+            max_in_flight_txs: 4,
+            ..chain_config()
+        };
+
+        assert!(validate_pipeline_settings(&config.id, &config).is_ok());
+    }
+
+    #[test]
+    fn pipeline_with_sequential_batch_tx() {
+        let config = CosmosSdkConfig {
+            pipeline_txs: true,
+            sequential_batch_tx: true,
+            ..chain_config()
+        };
+
+        let result = validate_pipeline_settings(&config.id, &config);
+        assert!(matches!(
+            result,
+            Err(Diagnostic::Error(e))
+                if matches!(e.detail(), ConfigErrorDetail::PipelineWithSequentialBatchTx(_))
+        ));
+    }
+
+    #[test]
+    fn pipeline_without_txs_in_flight() {
+        let config = CosmosSdkConfig {
+            pipeline_txs: true,
+            max_in_flight_txs: 0,
+            ..chain_config()
+        };
+
+        let result = validate_pipeline_settings(&config.id, &config);
+        assert!(matches!(
+            result,
+            Err(Diagnostic::Error(e))
+                if matches!(e.detail(), ConfigErrorDetail::InvalidMaxInFlightTxs(_))
+        ));
+    }
+}
@@ -31,6 +31,20 @@ define_error! {
             )
         },
 
+        InvalidMaxInFlightTxs
+        { chain_id: ChainId }
+        |e| {
+            format!("config file specifies `pipeline_txs = true` with `max_in_flight_txs = 0` for the chain '{}', \
+                at least one transaction must be allowed in flight", e.chain_id)
+        },
+
+        PipelineWithSequentialBatchTx
+        { chain_id: ChainId }
+        |e| {
+            format!("config file specifies both `pipeline_txs` and `sequential_batch_tx` for the chain '{}', \
+                only one of them can be enabled", e.chain_id)
+        },
+
         ExpectedExcludedSequencesArray
         |_| { "expected excluded_sequences to be an array of values" },
 
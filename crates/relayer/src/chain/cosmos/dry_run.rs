@@ -0,0 +1,466 @@
+//! Dry-run mode, in which the transactions that Hermes would submit to a chain
+//! are simulated and logged along with their estimated fees, but never broadcast.
+
+use core::str::FromStr;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use ibc_proto::cosmos::tx::v1beta1::Fee;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::channel::v1::{
+    MsgAcknowledgement as RawMsgAcknowledgement, MsgRecvPacket as RawMsgRecvPacket,
+    MsgTimeout as RawMsgTimeout, MsgTimeoutOnClose as RawMsgTimeoutOnClose, Packet as RawPacket,
+};
+use ibc_proto::ibc::core::client::v1::MsgUpdateClient as RawMsgUpdateClient;
+use ibc_relayer_types::core::ics02_client::events::{Attributes, UpdateClient};
+use ibc_relayer_types::core::ics02_client::header::{AnyHeader, Header};
+use ibc_relayer_types::core::ics02_client::msgs::update_client;
+use ibc_relayer_types::core::ics04_channel::events::{
+    AcknowledgePacket, ReceivePacket, TimeoutOnClosePacket, TimeoutPacket,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::{
+    acknowledgement, recv_packet, timeout, timeout_on_close,
+};
+use ibc_relayer_types::core::ics04_channel::packet::Packet;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::events::{IbcEvent, ModuleEvent, ModuleEventAttribute, ModuleId};
+use ibc_relayer_types::Height;
+use prost::Message;
+use serde::Serialize;
+use subtle_encoding::hex;
+use tracing::{info, warn};
+
+use crate::chain::cosmos::batch::batch_messages;
+use crate::chain::cosmos::estimate::{estimate_tx_fees, EstimatedGas};
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::config::types::Memo;
+use crate::error::Error;
+use crate::event::IbcEventWithHeight;
+use crate::keyring::Secp256k1KeyPair;
+use crate::util::pretty::PrettyFee;
+
+/// A transaction which would have been submitted to a chain, had
+/// dry-run mode been disabled.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunTx {
+    pub chain_id: ChainId,
+    pub account_sequence: u64,
+    pub memo: String,
+    pub messages: Vec<DryRunMsg>,
+    /// Whether the gas was estimated by simulating the transaction,
+    /// or if Hermes fell back to the configured default gas
+    pub simulated: bool,
+    pub estimated_gas: u64,
+    pub fee: DryRunFee,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunMsg {
+    pub type_url: String,
+    /// Hex-encoded Protobuf value of the message
+    pub value: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunFee {
+    pub amount: Vec<DryRunCoin>,
+    pub gas_limit: u64,
+    pub payer: String,
+    pub granter: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunCoin {
+    pub denom: String,
+    pub amount: String,
+}
+
+impl DryRunTx {
+    fn new(
+        config: &TxConfig,
+        account: &Account,
+        tx_memo: &Memo,
+        messages: &[Any],
+        fee: &Fee,
+        estimated_gas: &EstimatedGas,
+    ) -> Self {
+        Self {
+            chain_id: config.chain_id.clone(),
+            account_sequence: account.sequence.to_u64(),
+            memo: tx_memo.to_string(),
+            messages: messages.iter().map(DryRunMsg::from).collect(),
+            simulated: matches!(estimated_gas, EstimatedGas::Simulated(_)),
+            estimated_gas: estimated_gas.get_amount(),
+            fee: DryRunFee::from(fee),
+        }
+    }
+}
+
+impl From<&Any> for DryRunMsg {
+    fn from(msg: &Any) -> Self {
+        Self {
+            type_url: msg.type_url.clone(),
+            value: String::from_utf8(hex::encode_upper(&msg.value)).unwrap(),
+        }
+    }
+}
+
+impl From<&Fee> for DryRunFee {
+    fn from(fee: &Fee) -> Self {
+        Self {
+            amount: fee
+                .amount
+                .iter()
+                .map(|coin| DryRunCoin {
+                    denom: coin.denom.clone(),
+                    amount: coin.amount.clone(),
+                })
+                .collect(),
+            gas_limit: fee.gas_limit,
+            payer: fee.payer.clone(),
+            granter: fee.granter.clone(),
+        }
+    }
+}
+
+/**
+   Batch the given messages the same way they would be when broadcast,
+   and simulate each of the resulting transactions instead of broadcasting it.
+
+   The would-be transactions are logged and, if an output file is given,
+   appended to it as newline-delimited JSON.
+   Since none of them is committed, each transaction is simulated at the account
+   sequence found on chain, which the ante handler checks during simulation.
+*/
+pub async fn simulate_batched_messages(
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+    output: Option<&Path>,
+) -> Result<Vec<DryRunTx>, Error> {
+    if messages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let batches = batch_messages(config, key_pair, account, tx_memo, messages).await?;
+
+    let mut txs = Vec::with_capacity(batches.len());
+
+    for batch in batches {
+        let (fee, estimated_gas) =
+            estimate_tx_fees(config, key_pair, account, tx_memo, &batch).await?;
+
+        let tx = DryRunTx::new(config, account, tx_memo, &batch, &fee, &estimated_gas);
+
+        info!(
+            sequence = %account.sequence,
+            messages = ?tx.messages.iter().map(|msg| &msg.type_url).collect::<Vec<_>>(),
+            simulated = tx.simulated,
+            "[dry-run] would have submitted a transaction with {} message(s) and fee {}",
+            tx.messages.len(),
+            PrettyFee(&fee),
+        );
+
+        if let Some(path) = output {
+            if let Err(e) = append_to_output(path, &tx) {
+                warn!(
+                    "[dry-run] failed to write transaction to `{}`: {e}",
+                    path.display()
+                );
+            }
+        }
+
+        txs.push(tx);
+    }
+
+    Ok(txs)
+}
+
+/// The events the given messages would have emitted at the given height, had they been
+/// committed, so that the relaying pipeline carries on as if they had been.
+///
+/// Client updates and packet messages are mapped to their IBC event, the other
+/// messages to a `dry_run_msg` module event carrying their type URL.
+pub fn dry_run_events(messages: &[Any], height: Height) -> Vec<IbcEventWithHeight> {
+    messages
+        .iter()
+        .map(|msg| {
+            let event = dry_run_event(msg).unwrap_or_else(|| {
+                IbcEvent::AppModule(ModuleEvent {
+                    kind: "dry_run_msg".to_string(),
+                    module_name: ModuleId::from_str("hermes").expect("valid module id"),
+                    attributes: vec![ModuleEventAttribute {
+                        key: "type_url".to_string(),
+                        value: msg.type_url.clone(),
+                    }],
+                })
+            });
+
+            IbcEventWithHeight::new(event, height)
+        })
+        .collect()
+}
+
+fn dry_run_event(msg: &Any) -> Option<IbcEvent> {
+    let value = msg.value.as_slice();
+
+    let event = match msg.type_url.as_str() {
+        update_client::TYPE_URL => {
+            let msg = RawMsgUpdateClient::decode(value).ok()?;
+            let header = AnyHeader::try_from(msg.client_message?).ok()?;
+
+            IbcEvent::UpdateClient(UpdateClient {
+                common: Attributes {
+                    client_id: ClientId::from_str(&msg.client_id).ok()?,
+                    client_type: header.client_type(),
+                    consensus_height: header.height(),
+                },
+                header: Some(header),
+            })
+        }
+        recv_packet::TYPE_URL => {
+            let packet = packet(RawMsgRecvPacket::decode(value).ok()?.packet)?;
+            IbcEvent::ReceivePacket(ReceivePacket { packet })
+        }
+        acknowledgement::TYPE_URL => {
+            let packet = packet(RawMsgAcknowledgement::decode(value).ok()?.packet)?;
+            IbcEvent::AcknowledgePacket(AcknowledgePacket { packet })
+        }
+        timeout::TYPE_URL => {
+            let packet = packet(RawMsgTimeout::decode(value).ok()?.packet)?;
+            IbcEvent::TimeoutPacket(TimeoutPacket { packet })
+        }
+        timeout_on_close::TYPE_URL => {
+            let packet = packet(RawMsgTimeoutOnClose::decode(value).ok()?.packet)?;
+            IbcEvent::TimeoutOnClosePacket(TimeoutOnClosePacket { packet })
+        }
+        _ => return None,
+    };
+
+    Some(event)
+}
+
+fn packet(raw: Option<RawPacket>) -> Option<Packet> {
+    Packet::try_from(raw?).ok()
+}
+
+fn append_to_output(path: &Path, tx: &DryRunTx) -> std::io::Result<()> {
+    let line = serde_json::to_string(tx)?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+
+    use crate::chain::cosmos::types::account::{AccountAddress, AccountNumber, AccountSequence};
+    use crate::config::{self, ChainConfig};
+    use crate::keyring::SigningKeyPair;
+
+    const COSMOS_HD_PATH: &str = "m/44'/118'/0'/0/0";
+
+    fn tx_config() -> TxConfig {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+        let config = config::load(path).expect("could not parse config");
+
+        let Some(ChainConfig::CosmosSdk(chain_config)) =
+            config.find_chain(&ChainId::from_string("chain_A"))
+        else {
+            panic!("should be a cosmos sdk chain config");
+        };
+
+        TxConfig::try_from(chain_config).expect("could not obtain tx config")
+    }
+
+    fn key_pair() -> Secp256k1KeyPair {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer-seed.json"
+        );
+        let seed_file_content = fs::read_to_string(path).unwrap();
+        let hd_path = COSMOS_HD_PATH.parse().unwrap();
+
+        Secp256k1KeyPair::from_seed_file(&seed_file_content, &hd_path).unwrap()
+    }
+
+    fn account(sequence: u64) -> Account {
+        Account {
+            address: AccountAddress::new("cosmos1relayer".to_owned()),
+            number: AccountNumber::new(7),
+            sequence: AccountSequence::new(sequence),
+        }
+    }
+
+    fn fee() -> Fee {
+        Fee {
+            amount: vec![Coin {
+                denom: "stake".to_string(),
+                amount: "2500".to_string(),
+            }],
+            gas_limit: 100_000,
+            payer: String::new(),
+            granter: "cosmos1granter".to_string(),
+        }
+    }
+
+    fn message() -> Any {
+        Any {
+            type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+            value: vec![0x0a, 0xbc, 0x01],
+        }
+    }
+
+    #[test]
+    fn dry_run_tx_describes_the_would_be_tx() {
+        let config = tx_config();
+        let memo = Memo::new("relayed by hermes").unwrap();
+
+        let tx = DryRunTx::new(
+            &config,
+            &account(42),
+            &memo,
+            &[message()],
+            &fee(),
+            &EstimatedGas::Simulated(80_000),
+        );
+
+        assert_eq!(tx.chain_id, config.chain_id);
+        assert_eq!(tx.account_sequence, 42);
+        assert_eq!(tx.memo, "relayed by hermes");
+        assert!(tx.simulated);
+        assert_eq!(tx.estimated_gas, 80_000);
+
+        assert_eq!(tx.messages.len(), 1);
+        assert_eq!(
+            tx.messages[0].type_url,
+            "/ibc.core.client.v1.MsgUpdateClient"
+        );
+        assert_eq!(tx.messages[0].value, "0ABC01");
+
+        assert_eq!(tx.fee.gas_limit, 100_000);
+        assert_eq!(tx.fee.granter, "cosmos1granter");
+        assert_eq!(tx.fee.amount.len(), 1);
+        assert_eq!(tx.fee.amount[0].denom, "stake");
+        assert_eq!(tx.fee.amount[0].amount, "2500");
+
+        let tx = DryRunTx::new(
+            &config,
+            &account(42),
+            &memo,
+            &[message()],
+            &fee(),
+            &EstimatedGas::Default(config.gas_config.default_gas),
+        );
+
+        assert!(!tx.simulated);
+        assert_eq!(tx.estimated_gas, config.gas_config.default_gas);
+    }
+
+    #[test]
+    fn output_is_appended_as_ndjson() {
+        let dir = std::env::temp_dir().join(format!("hermes-dry-run-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("txs.ndjson");
+
+        let config = tx_config();
+        let memo = Memo::default();
+
+        for sequence in [1, 2] {
+            let tx = DryRunTx::new(
+                &config,
+                &account(sequence),
+                &memo,
+                &[message()],
+                &fee(),
+                &EstimatedGas::Simulated(80_000),
+            );
+
+            append_to_output(&path, &tx).unwrap();
+        }
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let sequences = contents
+            .lines()
+            .map(|line| {
+                let tx: serde_json::Value = serde_json::from_str(line).unwrap();
+                tx["account_sequence"].as_u64().unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(sequences, [1, 2]);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dry_run_events_of_messages() {
+        let packet = RawPacket {
+            sequence: 3,
+            source_port: "transfer".to_string(),
+            source_channel: "channel-0".to_string(),
+            destination_port: "transfer".to_string(),
+            destination_channel: "channel-1".to_string(),
+            data: b"data".to_vec(),
+            timeout_height: None,
+            timeout_timestamp: 1,
+        };
+        let recv_packet = Any {
+            type_url: recv_packet::TYPE_URL.to_string(),
+            value: RawMsgRecvPacket {
+                packet: Some(packet),
+                ..Default::default()
+            }
+            .encode_to_vec(),
+        };
+        let height = Height::new(0, 10).unwrap();
+
+        let events = dry_run_events(&[recv_packet, message()], height);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.height == height));
+
+        let IbcEvent::ReceivePacket(event) = &events[0].event else {
+            panic!("expected a receive packet event, got {}", events[0].event);
+        };
+        assert_eq!(u64::from(event.packet.sequence), 3);
+
+        // The header of the update cannot be decoded
+        let IbcEvent::AppModule(event) = &events[1].event else {
+            panic!("expected a module event, got {}", events[1].event);
+        };
+        assert_eq!(event.kind, "dry_run_msg");
+        assert_eq!(
+            event.attributes[0].value,
+            "/ibc.core.client.v1.MsgUpdateClient"
+        );
+    }
+
+    #[tokio::test]
+    async fn no_messages_simulates_nothing() {
+        // The chain of the fixture is not running, any simulation would fail
+        let txs = simulate_batched_messages(
+            &tx_config(),
+            &key_pair(),
+            &account(0),
+            &Memo::default(),
+            vec![],
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert!(txs.is_empty());
+    }
+}
@@ -1,7 +1,9 @@
 use bech32::{ToBase32, Variant};
 use core::str::FromStr;
 use ibc_proto::cosmos::tx::v1beta1::mode_info::{Single, Sum};
-use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw};
+use ibc_proto::cosmos::tx::v1beta1::{
+    AuthInfo, Fee, ModeInfo, SignDoc, SignerInfo, Tx, TxBody, TxRaw,
+};
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
@@ -108,6 +110,31 @@ pub fn sign_tx(
     })
 }
 
+/// Build a transaction without any signature, to be signed offline,
+/// eg. by a multisig account, by the signer described by `signer_info`.
+pub fn unsigned_tx(
+    config: &TxConfig,
+    signer_info: SignerInfo,
+    tx_memo: &Memo,
+    messages: &[Any],
+    fee: &Fee,
+) -> Result<Tx, Error> {
+    let (body, _) = tx_body_and_bytes(messages, tx_memo, config.extension_options.clone())?;
+
+    #[allow(deprecated)]
+    let auth_info = AuthInfo {
+        signer_infos: vec![signer_info],
+        fee: Some(fee.clone()),
+        tip: None,
+    };
+
+    Ok(Tx {
+        body: Some(body),
+        auth_info: Some(auth_info),
+        signatures: vec![],
+    })
+}
+
 fn encode_key_bytes(key_pair: &Secp256k1KeyPair) -> Result<Vec<u8>, Error> {
     let mut pk_buf = Vec::new();
 
@@ -166,7 +193,7 @@ fn encode_signer_info(
     Ok(signer_info)
 }
 
-fn encode_tx_raw(tx_raw: TxRaw) -> Result<Vec<u8>, Error> {
+pub fn encode_tx_raw(tx_raw: TxRaw) -> Result<Vec<u8>, Error> {
     let mut tx_bytes = Vec::new();
     Message::encode(&tx_raw, &mut tx_bytes)
         .map_err(|e| Error::protobuf_encode("Transaction".to_string(), e))?;
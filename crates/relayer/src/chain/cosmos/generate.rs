@@ -0,0 +1,328 @@
+//! Generate-only mode, in which the transactions that Hermes would submit to a chain
+//! are exported unsigned, to be signed offline (eg. by a multisig account) and later
+//! broadcast with `hermes tx broadcast`.
+
+use core::fmt::{Display, Error as FmtError, Formatter};
+use core::str::FromStr;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ibc_proto::cosmos::tx::v1beta1::{SignerInfo, Tx, TxRaw};
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::events::{IbcEvent, ModuleEvent, ModuleEventAttribute, ModuleId};
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::Height;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use crate::chain::cosmos::batch::batch_messages;
+use crate::chain::cosmos::encode::unsigned_tx;
+use crate::chain::cosmos::estimate::estimate_tx_fees;
+use crate::chain::cosmos::query::account::query_account;
+use crate::chain::cosmos::types::account::Account;
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::config::types::Memo;
+use crate::error::Error;
+use crate::event::IbcEventWithHeight;
+use crate::keyring::Secp256k1KeyPair;
+use crate::util::pretty::PrettyFee;
+
+/// Encoding of the transactions exported in generate-only mode.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxFormat {
+    /// Protobuf JSON encoding of the `cosmos.tx.v1beta1.Tx` message, in which
+    /// the messages are `{ "typeUrl", "value" }` objects with a base64 value.
+    #[default]
+    Json,
+
+    /// Protobuf encoding of the `cosmos.tx.v1beta1.Tx` message, which can be
+    /// converted to the JSON encoding read by `<appd> tx sign` with `<appd> tx decode`.
+    Proto,
+}
+
+impl TxFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Proto => "pb",
+        }
+    }
+}
+
+impl Display for TxFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Proto => write!(f, "proto"),
+        }
+    }
+}
+
+impl FromStr for TxFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "proto" => Ok(Self::Proto),
+            _ => Err(format!(
+                "invalid transaction format '{s}', expected one of: json, proto"
+            )),
+        }
+    }
+}
+
+/// Where and how to export the transactions in generate-only mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenerateOnly {
+    pub output_dir: PathBuf,
+    pub format: TxFormat,
+    /// Address of the account which signs the transactions offline, eg. a multisig,
+    /// or `None` for the account of the configured key.
+    pub signer: Option<Signer>,
+}
+
+/**
+   Batch the given messages the same way they would be when broadcast,
+   and write each of the resulting transactions, unsigned, to the output directory.
+
+   The transactions are built for the account of the configured signer, if any,
+   whose account number and sequence are queried from the chain, or for the
+   account of the configured key otherwise. Their messages are expected to have
+   been built with the same signer, see `CosmosSdkChain::get_signer`.
+   The fee of each transaction is estimated by simulating it at the account
+   sequence found on chain, since none of them is committed in the meantime.
+
+   Files are named `<chain_id>.<account_sequence>.unsigned.<json|pb>`.
+*/
+pub async fn generate_unsigned_txs(
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &Account,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+    options: &GenerateOnly,
+) -> Result<Vec<PathBuf>, Error> {
+    if messages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (account, public_key) = match &options.signer {
+        Some(signer) => {
+            let signer_account = query_account(&config.grpc_address, signer.as_ref()).await?;
+            let public_key = signer_account.pub_key.clone();
+
+            (Account::from(signer_account), public_key)
+        }
+        None => (account.clone(), None),
+    };
+
+    fs::create_dir_all(&options.output_dir).map_err(Error::io)?;
+
+    let batches = batch_messages(config, key_pair, &account, tx_memo, messages).await?;
+
+    let mut sequence = account.sequence;
+    let mut paths = Vec::with_capacity(batches.len());
+
+    for batch in batches {
+        let (fee, _) = estimate_tx_fees(config, key_pair, &account, tx_memo, &batch).await?;
+
+        let signer_info = SignerInfo {
+            public_key: public_key.clone(),
+            mode_info: None,
+            sequence: sequence.to_u64(),
+        };
+
+        let tx = unsigned_tx(config, signer_info, tx_memo, &batch, &fee)?;
+
+        let path = options.output_dir.join(format!(
+            "{}.{}.unsigned.{}",
+            config.chain_id,
+            sequence,
+            options.format.extension()
+        ));
+
+        write_tx(&path, &tx, options.format)?;
+
+        info!(
+            account = %account.address,
+            account_number = %account.number,
+            %sequence,
+            "wrote unsigned transaction with {} message(s) and fee {} to `{}`",
+            batch.len(),
+            PrettyFee(&fee),
+            path.display()
+        );
+
+        sequence.increment_mut();
+        paths.push(path);
+    }
+
+    Ok(paths)
+}
+
+/// Describe each exported transaction by a `generated_tx` event, reported
+/// at the given height in place of the events of the transactions, which
+/// are only known once they are signed and broadcast.
+pub fn generated_tx_events(paths: &[PathBuf], height: Height) -> Vec<IbcEventWithHeight> {
+    paths
+        .iter()
+        .map(|path| {
+            let event = ModuleEvent {
+                kind: "generated_tx".to_string(),
+                module_name: ModuleId::from_str("hermes").expect("valid module id"),
+                attributes: vec![ModuleEventAttribute {
+                    key: "path".to_string(),
+                    value: path.display().to_string(),
+                }],
+            };
+
+            IbcEventWithHeight::new(IbcEvent::AppModule(event), height)
+        })
+        .collect()
+}
+
+fn write_tx(path: &Path, tx: &Tx, format: TxFormat) -> Result<(), Error> {
+    let bytes = match format {
+        TxFormat::Json => {
+            serde_json::to_vec_pretty(tx).map_err(|e| Error::tx_json(e.to_string()))?
+        }
+        TxFormat::Proto => tx.encode_to_vec(),
+    };
+
+    fs::write(path, bytes).map_err(Error::io)
+}
+
+/// Decode a signed transaction, encoded in the given format, into a [`TxRaw`]
+/// ready to be broadcast.
+pub fn decode_signed_tx(bytes: &[u8], format: TxFormat) -> Result<TxRaw, Error> {
+    let tx_raw = match format {
+        TxFormat::Json => {
+            let tx: Tx = serde_json::from_slice(bytes)
+                .map_err(|e| Error::invalid_signed_tx(format!("failed to decode JSON: {e}")))?;
+
+            TxRaw {
+                body_bytes: tx.body.unwrap_or_default().encode_to_vec(),
+                auth_info_bytes: tx.auth_info.unwrap_or_default().encode_to_vec(),
+                signatures: tx.signatures,
+            }
+        }
+        // `Tx` and `TxRaw` share the same wire encoding, decoding as `TxRaw`
+        // keeps the exact bytes which were signed.
+        TxFormat::Proto => {
+            TxRaw::decode(bytes).map_err(|e| Error::protobuf_decode("TxRaw".to_string(), e))?
+        }
+    };
+
+    if tx_raw.signatures.is_empty() {
+        return Err(Error::invalid_signed_tx(
+            "the transaction has no signature".to_string(),
+        ));
+    }
+
+    Ok(tx_raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_proto::cosmos::base::v1beta1::Coin;
+    use ibc_proto::cosmos::tx::v1beta1::{AuthInfo, Fee, TxBody};
+    use ibc_proto::ibc::core::client::v1::MsgUpdateClient;
+
+    fn signed_tx() -> Tx {
+        #[allow(deprecated)]
+        Tx {
+            body: Some(TxBody {
+                messages: vec![Any {
+                    type_url: "/ibc.core.client.v1.MsgUpdateClient".to_string(),
+                    value: MsgUpdateClient {
+                        client_id: "07-tendermint-0".to_string(),
+                        client_message: None,
+                        signer: "cosmos1signer".to_string(),
+                    }
+                    .encode_to_vec(),
+                }],
+                memo: "hermes".to_string(),
+                ..Default::default()
+            }),
+            auth_info: Some(AuthInfo {
+                signer_infos: vec![],
+                fee: Some(Fee {
+                    amount: vec![Coin {
+                        denom: "stake".to_string(),
+                        amount: "1000".to_string(),
+                    }],
+                    gas_limit: 200000,
+                    payer: String::new(),
+                    granter: String::new(),
+                }),
+                tip: None,
+            }),
+            signatures: vec![vec![42; 64]],
+        }
+    }
+
+    fn expected_tx_raw(tx: &Tx) -> TxRaw {
+        TxRaw {
+            body_bytes: tx.body.as_ref().unwrap().encode_to_vec(),
+            auth_info_bytes: tx.auth_info.as_ref().unwrap().encode_to_vec(),
+            signatures: tx.signatures.clone(),
+        }
+    }
+
+    #[test]
+    fn decode_signed_tx_json() {
+        let tx = signed_tx();
+        let bytes = serde_json::to_vec(&tx).unwrap();
+
+        let tx_raw = decode_signed_tx(&bytes, TxFormat::Json).unwrap();
+        assert_eq!(tx_raw, expected_tx_raw(&tx));
+    }
+
+    #[test]
+    fn decode_signed_tx_proto() {
+        let tx = signed_tx();
+        let bytes = tx.encode_to_vec();
+
+        let tx_raw = decode_signed_tx(&bytes, TxFormat::Proto).unwrap();
+        assert_eq!(tx_raw, expected_tx_raw(&tx));
+    }
+
+    #[test]
+    fn generated_tx_events_describe_each_tx() {
+        let paths = vec![
+            PathBuf::from("txs/ibc-0.3.unsigned.json"),
+            PathBuf::from("txs/ibc-0.4.unsigned.json"),
+        ];
+        let height = Height::new(0, 10).unwrap();
+
+        let events = generated_tx_events(&paths, height);
+        assert_eq!(events.len(), 2);
+
+        for (event, path) in events.iter().zip(&paths) {
+            assert_eq!(event.height, height);
+
+            let IbcEvent::AppModule(event) = &event.event else {
+                panic!("expected a module event, got {}", event.event);
+            };
+
+            assert_eq!(event.kind, "generated_tx");
+            assert_eq!(event.attributes[0].value, path.display().to_string());
+        }
+    }
+
+    #[test]
+    fn decode_unsigned_tx_fails() {
+        let tx = Tx {
+            signatures: vec![],
+            ..signed_tx()
+        };
+        let bytes = tx.encode_to_vec();
+
+        assert!(decode_signed_tx(&bytes, TxFormat::Proto).is_err());
+    }
+}
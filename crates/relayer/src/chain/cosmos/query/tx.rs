@@ -1,7 +1,7 @@
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
 use ibc_relayer_types::Height as ICSHeight;
 use tendermint::abci::Event;
 use tendermint::Hash as TxHash;
@@ -15,7 +15,10 @@ use crate::chain::requests::{
     QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
 };
 use crate::error::Error;
-use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
+use crate::event::{
+    ibc_event_try_from_abci_event, receive_packet_try_from_abci_event, IbcEventWithHeight,
+    IbcEventWithTxHash,
+};
 
 /// This function queries transactions for events matching certain criteria.
 /// 1. Client Update request - returns a vector with at most one update client event
@@ -126,7 +129,26 @@ pub async fn query_packets_from_txs(
     );
     crate::telemetry!(query, chain_id, "query_packets_from_txs");
 
-    let mut result: Vec<IbcEventWithHeight> = vec![];
+    let packet_txs = query_packet_txs(chain_id, rpc_client, rpc_address, request).await?;
+
+    Ok(packet_txs
+        .into_iter()
+        .map(|tx| IbcEventWithHeight::new(tx.event, tx.height))
+        .collect())
+}
+
+/// This function queries transactions for packet events matching certain criteria,
+/// keeping the hash of the transaction which emitted each event.
+///
+/// It returns at most one packet event for each sequence specified in the request,
+/// see [`query_packets_from_txs`].
+pub async fn query_packet_txs(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    request: &QueryPacketEventDataRequest,
+) -> Result<Vec<IbcEventWithTxHash>, Error> {
+    let mut result: Vec<IbcEventWithTxHash> = vec![];
 
     for seq in &request.sequences {
         // Query the latest 10 txs which include the event specified in the query request
@@ -146,7 +168,7 @@ pub async fn query_packets_from_txs(
             // Check if the tx contains and event which matches the query
             if let Some(event) = packet_from_tx_search_response(chain_id, request, *seq, &tx)? {
                 // We found the event
-                tx_events.push((event, tx.hash, tx.height));
+                tx_events.push(event);
             }
         }
 
@@ -159,14 +181,16 @@ pub async fn query_packets_from_txs(
         if tx_events.len() > 1 {
             warn!("more than one packet event found for sequence {seq}, this should not happen",);
 
-            for (event, hash, height) in &tx_events {
-                warn!("seq: {seq}, tx hash: {hash}, tx height: {height}, event: {event}",);
+            for tx in &tx_events {
+                warn!(
+                    "seq: {seq}, tx hash: {}, tx height: {}, event: {}",
+                    tx.tx_hash, tx.height, tx.event
+                );
             }
         }
 
         // In either case, use the first (latest) event found for this sequence
-        let (first_event, _, _) = tx_events.remove(0);
-        result.push(first_event);
+        result.push(tx_events.remove(0));
     }
 
     Ok(result)
@@ -298,7 +322,7 @@ fn packet_from_tx_search_response(
     request: &QueryPacketEventDataRequest,
     seq: Sequence,
     response: &TxResponse,
-) -> Result<Option<IbcEventWithHeight>, Error> {
+) -> Result<Option<IbcEventWithTxHash>, Error> {
     let height = ICSHeight::new(chain_id.version(), u64::from(response.height))
         .map_err(|_| Error::invalid_height_no_source())?;
 
@@ -313,7 +337,11 @@ fn packet_from_tx_search_response(
         .events
         .iter()
         .find_map(|ev| filter_matching_event(ev, request, &[seq]))
-        .map(|ibc_event| IbcEventWithHeight::new(ibc_event, height)))
+        .map(|event| IbcEventWithTxHash {
+            event,
+            height,
+            tx_hash: response.hash,
+        }))
 }
 
 /// Returns the given event wrapped in `Some` if the event data
@@ -340,7 +368,14 @@ pub fn filter_matching_event(
         return None;
     }
 
-    let ibc_event = ibc_event_try_from_abci_event(event).ok()?;
+    // The `recv_packet` events are not decoded by `ibc_event_try_from_abci_event`,
+    // since the relayer does not otherwise react to them.
+    let ibc_event = match request.event_id {
+        WithBlockDataType::RecvPacket => {
+            IbcEvent::ReceivePacket(receive_packet_try_from_abci_event(event).ok()?)
+        }
+        _ => ibc_event_try_from_abci_event(event).ok()?,
+    };
 
     match ibc_event {
         IbcEvent::SendPacket(ref send_ev)
@@ -353,6 +388,21 @@ pub fn filter_matching_event(
         {
             Some(ibc_event)
         }
+        IbcEvent::ReceivePacket(ref recv_ev)
+            if matches_packet(request, seqs.to_vec(), &recv_ev.packet) =>
+        {
+            Some(ibc_event)
+        }
+        IbcEvent::AcknowledgePacket(ref ack_ev)
+            if matches_packet(request, seqs.to_vec(), &ack_ev.packet) =>
+        {
+            Some(ibc_event)
+        }
+        IbcEvent::TimeoutPacket(ref timeout_ev)
+            if matches_packet(request, seqs.to_vec(), &timeout_ev.packet) =>
+        {
+            Some(ibc_event)
+        }
         _ => None,
     }
 }
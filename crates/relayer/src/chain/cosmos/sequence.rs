@@ -0,0 +1,421 @@
+//! Local tracking of the account sequences used by transactions which were
+//! broadcast but not yet committed, allowing to submit several transactions
+//! back to back without waiting for each of them to be included in a block.
+
+use alloc::collections::BTreeMap;
+use core::time::Duration;
+use std::time::Instant;
+
+use ibc_proto::google::protobuf::Any;
+use tendermint::abci::Code;
+use tendermint::Hash as TxHash;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use tendermint_rpc::HttpClient;
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+use crate::chain::cosmos::batch::{batch_messages, response_to_tx_sync_result};
+use crate::chain::cosmos::query::account::refresh_account;
+use crate::chain::cosmos::query::tx::query_tx_response;
+use crate::chain::cosmos::retry::send_tx_with_account_sequence_retry;
+use crate::chain::cosmos::types::account::{Account, AccountSequence};
+use crate::chain::cosmos::types::config::TxConfig;
+use crate::chain::cosmos::wait::wait_for_block_commits;
+use crate::config::types::Memo;
+use crate::error::Error;
+use crate::event::IbcEventWithHeight;
+use crate::keyring::{Secp256k1KeyPair, SigningKeyPair};
+
+const CONFIRMATION_BACKOFF: Duration = Duration::from_millis(300);
+
+/// A transaction which was accepted in the mempool but not yet committed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InFlightTx {
+    pub hash: TxHash,
+    pub message_count: usize,
+    pub submitted_at: Instant,
+}
+
+/// Keeps track of the transactions in flight, indexed by the account sequence they were signed with.
+///
+/// The next sequence to use is the one of the cached [`Account`], which the manager
+/// rewinds whenever transactions in flight become invalid, eg. because one of
+/// the transactions before them was evicted from the mempool.
+#[derive(Debug)]
+pub struct SequenceManager {
+    in_flight: BTreeMap<AccountSequence, InFlightTx>,
+    max_in_flight: usize,
+}
+
+impl SequenceManager {
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            in_flight: BTreeMap::new(),
+            max_in_flight,
+        }
+    }
+
+    pub fn in_flight_count(&self) -> usize {
+        self.in_flight.len()
+    }
+
+    /// Whether the maximum number of transactions in flight has been reached.
+    pub fn is_full(&self) -> bool {
+        self.in_flight.len() >= self.max_in_flight
+    }
+
+    /// The oldest transaction in flight, ie. the one with the lowest sequence.
+    pub fn oldest(&self) -> Option<(AccountSequence, &InFlightTx)> {
+        self.in_flight.iter().next().map(|(seq, tx)| (*seq, tx))
+    }
+
+    /// Record a transaction which was accepted in the mempool with the given sequence.
+    pub fn track(&mut self, sequence: AccountSequence, hash: TxHash, message_count: usize) {
+        self.in_flight.insert(
+            sequence,
+            InFlightTx {
+                hash,
+                message_count,
+                submitted_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Stop tracking the transaction with the given hash, which was committed.
+    pub fn confirm(&mut self, hash: &TxHash) -> Option<AccountSequence> {
+        let sequence = self
+            .in_flight
+            .iter()
+            .find(|(_, tx)| &tx.hash == hash)
+            .map(|(seq, _)| *seq)?;
+
+        self.in_flight.remove(&sequence);
+        Some(sequence)
+    }
+
+    /// Reconcile the transactions in flight with the given sequence, which is
+    /// the next sequence the chain expects for the account.
+    ///
+    /// Transactions with a lower sequence were committed, and are no longer tracked.
+    /// Transactions with the given sequence or a higher one are invalid, since the
+    /// chain still expects a transaction with that sequence: they are dropped and returned,
+    /// and the account sequence is rewound so that it gets reused.
+    pub fn resync(&mut self, account: &mut Account, next: AccountSequence) -> Vec<InFlightTx> {
+        let invalid = self.in_flight.split_off(&next);
+        self.in_flight.clear();
+
+        account.sequence = next;
+
+        invalid.into_values().collect()
+    }
+}
+
+/**
+   Broadcast the messages as multiple batched transactions back to back,
+   without waiting for the transactions in flight to be committed.
+
+   The sequences of the transactions in flight are tracked locally, and their confirmation
+   is checked before each submission. When the maximum number of transactions in flight
+   is reached, wait for the oldest one to be committed. If it does not get committed
+   in time, the account is refreshed from the chain and the transactions which were
+   queued after it are dropped, since their sequence can no longer be valid.
+*/
+pub async fn pipelined_send_batched_messages_and_wait_check_tx(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    sequences: &mut SequenceManager,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<Vec<Response>, Error> {
+    if messages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let responses = pipelined_send_messages_as_batches(
+        rpc_client, config, key_pair, account, sequences, tx_memo, messages,
+    )
+    .await?;
+
+    Ok(responses
+        .into_iter()
+        .map(|(response, _)| response)
+        .collect())
+}
+
+/**
+   Broadcast the messages as multiple batched transactions back to back, with at most
+   `max_in_flight_txs` transactions in flight at any time, and then wait for all of
+   them to be committed.
+
+   See [`pipelined_send_batched_messages_and_wait_check_tx`] for how the transactions
+   in flight are tracked. Once committed, the transactions are no longer tracked.
+   If one of them is not committed in time, the remaining ones stay tracked, and
+   are reconciled with the account sequence on the next submission.
+*/
+pub async fn pipelined_send_batched_messages_and_wait_commit(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    sequences: &mut SequenceManager,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<Vec<IbcEventWithHeight>, Error> {
+    if messages.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let responses = pipelined_send_messages_as_batches(
+        rpc_client, config, key_pair, account, sequences, tx_memo, messages,
+    )
+    .await?;
+
+    let mut tx_sync_results = responses
+        .into_iter()
+        .map(|(response, message_count)| {
+            response_to_tx_sync_result(&config.chain_id, message_count, response)
+        })
+        .collect::<Vec<_>>();
+
+    wait_for_block_commits(
+        &config.chain_id,
+        rpc_client,
+        &config.rpc_address,
+        &config.rpc_timeout,
+        &mut tx_sync_results,
+    )
+    .await?;
+
+    for tx_sync_result in &tx_sync_results {
+        sequences.confirm(&tx_sync_result.response.hash);
+    }
+
+    let events = tx_sync_results
+        .into_iter()
+        .flat_map(|el| el.events)
+        .collect();
+
+    Ok(events)
+}
+
+/// Send the messages as batches, waiting for capacity before each of them,
+/// and return the response to each transaction along with its number of messages.
+async fn pipelined_send_messages_as_batches(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    sequences: &mut SequenceManager,
+    tx_memo: &Memo,
+    messages: Vec<Any>,
+) -> Result<Vec<(Response, usize)>, Error> {
+    // The account may have been re-fetched since the last submission,
+    // in which case the transactions using the re-fetched sequence or a later one
+    // are about to be replaced.
+    if let Some((oldest, _)) = sequences.oldest() {
+        if account.sequence <= oldest {
+            sequences.resync(account, account.sequence);
+        }
+    }
+
+    let batches = batch_messages(config, key_pair, account, tx_memo, messages).await?;
+
+    debug!(
+        "sending {} batches to chain {} with {} transaction(s) in flight",
+        batches.len(),
+        config.chain_id,
+        sequences.in_flight_count()
+    );
+
+    let mut responses = Vec::with_capacity(batches.len());
+
+    for batch in batches {
+        wait_for_capacity(rpc_client, config, key_pair, account, sequences).await?;
+
+        let sequence = account.sequence;
+
+        let response = send_tx_with_account_sequence_retry(
+            rpc_client, config, key_pair, account, tx_memo, &batch,
+        )
+        .await?;
+
+        if response.code == Code::Ok {
+            // The account sequence is incremented on success. If it was refreshed
+            // because of a sequence mismatch, the transaction was signed with another
+            // sequence than the one we expected, and the local view has to be reconciled.
+            let used = AccountSequence::new(account.sequence.to_u64() - 1);
+
+            if used != sequence {
+                warn!(
+                    expected = %sequence,
+                    actual = %used,
+                    "account sequence was refreshed after a mismatch, resyncing transactions in flight"
+                );
+
+                let invalid = sequences.resync(account, used);
+                log_invalidated(&invalid);
+                account.sequence = used.increment();
+            }
+
+            sequences.track(used, response.hash, batch.len());
+        }
+
+        responses.push((response, batch.len()));
+    }
+
+    Ok(responses)
+}
+
+/// Stop tracking the transactions in flight which were committed, and wait for the oldest
+/// one to be committed if the maximum number of transactions in flight is reached.
+async fn wait_for_capacity(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    key_pair: &Secp256k1KeyPair,
+    account: &mut Account,
+    sequences: &mut SequenceManager,
+) -> Result<(), Error> {
+    loop {
+        update_in_flight(rpc_client, config, sequences).await;
+
+        let Some((_, oldest)) = sequences.oldest() else {
+            return Ok(());
+        };
+
+        if !sequences.is_full() {
+            return Ok(());
+        }
+
+        if oldest.submitted_at.elapsed() > config.rpc_timeout {
+            warn!(
+                tx_hash = %oldest.hash,
+                "transaction in flight was not committed in time, refreshing account sequence"
+            );
+
+            refresh_account(&config.grpc_address, &key_pair.account(), account).await?;
+
+            let next = account.sequence;
+            let invalid = sequences.resync(account, next);
+            log_invalidated(&invalid);
+
+            return Ok(());
+        }
+
+        sleep(CONFIRMATION_BACKOFF).await;
+    }
+}
+
+async fn update_in_flight(
+    rpc_client: &HttpClient,
+    config: &TxConfig,
+    sequences: &mut SequenceManager,
+) {
+    let hashes = sequences
+        .in_flight
+        .values()
+        .map(|tx| tx.hash)
+        .collect::<Vec<_>>();
+
+    for hash in hashes {
+        match query_tx_response(rpc_client, &config.rpc_address, &hash).await {
+            Ok(Some(_)) => {
+                if let Some(sequence) = sequences.confirm(&hash) {
+                    debug!(%sequence, tx_hash = %hash, "transaction in flight was committed");
+                }
+            }
+            Ok(None) => {
+                // Transactions are committed in sequence order,
+                // so the next ones cannot be committed either.
+                break;
+            }
+            Err(e) => {
+                debug!(tx_hash = %hash, "failed to query transaction in flight: {e}");
+                break;
+            }
+        }
+    }
+}
+
+fn log_invalidated(invalid: &[InFlightTx]) {
+    for tx in invalid {
+        warn!(
+            tx_hash = %tx.hash,
+            message_count = tx.message_count,
+            "dropping transaction in flight whose account sequence is no longer valid, \
+            its messages will be resubmitted once its confirmation times out"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::chain::cosmos::types::account::{AccountAddress, AccountNumber};
+
+    fn account(sequence: u64) -> Account {
+        Account {
+            address: AccountAddress::new("cosmos1".to_owned()),
+            number: AccountNumber::new(0),
+            sequence: AccountSequence::new(sequence),
+        }
+    }
+
+    fn hash(byte: u8) -> TxHash {
+        TxHash::Sha256([byte; 32])
+    }
+
+    fn manager_with_in_flight(sequences: &[u64]) -> SequenceManager {
+        let mut manager = SequenceManager::new(3);
+
+        for seq in sequences {
+            manager.track(AccountSequence::new(*seq), hash(*seq as u8), 1);
+        }
+
+        manager
+    }
+
+    #[test]
+    fn tracks_and_confirms_in_flight_txs() {
+        let mut manager = manager_with_in_flight(&[5, 6, 7]);
+
+        assert!(manager.is_full());
+        assert_eq!(manager.oldest().unwrap().0, AccountSequence::new(5));
+
+        assert_eq!(manager.confirm(&hash(5)), Some(AccountSequence::new(5)));
+        assert_eq!(manager.confirm(&hash(5)), None);
+
+        assert!(!manager.is_full());
+        assert_eq!(manager.oldest().unwrap().0, AccountSequence::new(6));
+    }
+
+    #[test]
+    fn resync_drops_invalid_txs_and_rewinds_sequence() {
+        let mut manager = manager_with_in_flight(&[5, 6, 7]);
+        let mut account = account(8);
+
+        // The tx with sequence 5 was committed, but not the one with sequence 6
+        let invalid = manager.resync(&mut account, AccountSequence::new(6));
+
+        assert_eq!(
+            invalid.iter().map(|tx| tx.hash).collect::<Vec<_>>(),
+            vec![hash(6), hash(7)]
+        );
+        assert_eq!(manager.in_flight_count(), 0);
+        assert_eq!(account.sequence, AccountSequence::new(6));
+    }
+
+    #[test]
+    fn resync_after_all_committed() {
+        let mut manager = manager_with_in_flight(&[5, 6]);
+        let mut account = account(7);
+
+        let invalid = manager.resync(&mut account, AccountSequence::new(7));
+
+        assert!(invalid.is_empty());
+        assert_eq!(manager.in_flight_count(), 0);
+        assert_eq!(account.sequence, AccountSequence::new(7));
+    }
+}
@@ -35,6 +35,29 @@ impl<'a> From<&'a CosmosSdkConfig> for GasConfig {
     }
 }
 
+impl GasConfig {
+    /// Returns a copy of this gas configuration with the gas price multiplied
+    /// by the given fee bump multiplier, capped by `dynamic_gas_price.max`.
+    ///
+    /// When the dynamic gas price is enabled, its multiplier is bumped instead,
+    /// and the cap is applied to the resulting price when it is queried.
+    /// A configured gas price above the cap is never bumped, but is not lowered either.
+    pub fn bumped(&self, multiplier: f64) -> Self {
+        let mut gas_config = self.clone();
+
+        if gas_config.dynamic_gas_price.enabled {
+            gas_config.dynamic_gas_price.multiplier *= multiplier;
+        } else {
+            let price = gas_config.gas_price.price;
+            let max_price = gas_config.dynamic_gas_price.max.max(price);
+
+            gas_config.gas_price.price = (price * multiplier).min(max_price);
+        }
+
+        gas_config
+    }
+}
+
 /// The default amount of gas the relayer is willing to pay for a transaction,
 /// when it cannot simulate the tx and therefore estimate the gas amount needed.
 pub fn default_gas_from_config(config: &CosmosSdkConfig) -> u64 {
@@ -82,3 +105,50 @@ fn max_fee_from_config(config: &CosmosSdkConfig) -> Fee {
         granter: fee_granter,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gas_config(price: f64, dynamic_gas_price: DynamicGasPrice) -> GasConfig {
+        GasConfig {
+            default_gas: DEFAULT_MAX_GAS,
+            max_gas: DEFAULT_MAX_GAS,
+            gas_multiplier: 1.1,
+            gas_price: GasPrice::new(price, "stake".to_string()),
+            max_fee: Fee::default(),
+            fee_granter: DEFAULT_FEE_GRANTER.to_string(),
+            dynamic_gas_price,
+        }
+    }
+
+    #[test]
+    fn bump_static_gas_price() {
+        let config = gas_config(0.1, DynamicGasPrice::disabled());
+
+        assert_eq!(config.bumped(1.5).gas_price.price, 0.1 * 1.5);
+    }
+
+    #[test]
+    fn bump_static_gas_price_capped_by_max() {
+        let config = gas_config(0.5, DynamicGasPrice::disabled());
+
+        assert_eq!(config.bumped(2.0).gas_price.price, 0.6);
+    }
+
+    #[test]
+    fn bump_static_gas_price_above_max() {
+        let config = gas_config(1.0, DynamicGasPrice::disabled());
+
+        assert_eq!(config.bumped(2.0).gas_price.price, 1.0);
+    }
+
+    #[test]
+    fn bump_dynamic_gas_price() {
+        let config = gas_config(0.1, DynamicGasPrice::unsafe_new(true, 1.1, 0.6));
+        let bumped = config.bumped(2.0);
+
+        assert_eq!(bumped.gas_price.price, 0.1);
+        assert_eq!(bumped.dynamic_gas_price.multiplier, 1.1 * 2.0);
+    }
+}
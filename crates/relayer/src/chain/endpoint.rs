@@ -46,9 +46,10 @@ use crate::connection::ConnectionMsgType;
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::event::IbcEventWithHeight;
+use crate::event::{IbcEventWithHeight, IbcEventWithTxHash};
 use crate::keyring::{AnySigningKeyPair, KeyRing, SigningKeyPairSized};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::upgrade_chain::UpgradePlan;
 
 /// The result of a health check.
 #[derive(Debug)]
@@ -353,6 +354,13 @@ pub trait ChainEndpoint: Sized {
         request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query the transactions which emitted the packet events matching the request,
+    /// returning the events along with the hash of their transaction.
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error>;
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -691,6 +699,9 @@ pub trait ChainEndpoint: Sized {
 
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error>;
 
+    /// Query the software upgrade plan currently scheduled on the chain, if any.
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error>;
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
@@ -40,10 +40,11 @@ use crate::{
     error::Error,
     event::{
         source::{EventBatch, Result as MonitorResult},
-        IbcEventWithHeight,
+        IbcEventWithHeight, IbcEventWithTxHash,
     },
     keyring::AnySigningKeyPair,
     misbehaviour::MisbehaviourEvidence,
+    upgrade_chain::UpgradePlan,
 };
 
 use super::{
@@ -349,6 +350,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     },
 
+    QueryPacketTxs {
+        request: QueryPacketEventDataRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithTxHash>>,
+    },
+
     QueryHostConsensusState {
         request: QueryHostConsensusStateRequest,
         reply_to: ReplyTo<AnyConsensusState>,
@@ -375,6 +381,10 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<ConsumerChain>>,
     },
 
+    QueryUpgradePlan {
+        reply_to: ReplyTo<Option<UpgradePlan>>,
+    },
+
     QueryUpgrade {
         request: QueryUpgradeRequest,
         height: Height,
@@ -683,6 +693,13 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query the transactions which emitted the packet events matching the request,
+    /// returning the events along with the hash of their transaction.
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error>;
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -707,6 +724,9 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
 
     fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error>;
 
+    /// Query the software upgrade plan currently scheduled on the chain, if any.
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error>;
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
@@ -43,9 +43,10 @@ use crate::{
     consensus_state::AnyConsensusState,
     denom::DenomTrace,
     error::Error,
-    event::IbcEventWithHeight,
+    event::{IbcEventWithHeight, IbcEventWithTxHash},
     keyring::AnySigningKeyPair,
     misbehaviour::MisbehaviourEvidence,
+    upgrade_chain::UpgradePlan,
 };
 
 use super::{reply_channel, ChainHandle, ChainRequest, HealthCheck, ReplyTo, Subscription};
@@ -490,6 +491,13 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryPacketEventData { request, reply_to })
     }
 
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        self.send(|reply_to| ChainRequest::QueryPacketTxs { request, reply_to })
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -529,6 +537,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryConsumerChains { reply_to })
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.send(|reply_to| ChainRequest::QueryUpgradePlan { reply_to })
+    }
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
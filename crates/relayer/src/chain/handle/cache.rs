@@ -41,10 +41,11 @@ use crate::connection::ConnectionMsgType;
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::event::IbcEventWithHeight;
+use crate::event::{IbcEventWithHeight, IbcEventWithTxHash};
 use crate::keyring::AnySigningKeyPair;
 use crate::misbehaviour::MisbehaviourEvidence;
 use crate::telemetry;
+use crate::upgrade_chain::UpgradePlan;
 
 /// A chain handle with support for caching.
 /// To be used for the passive relaying mode (i.e., `start` CLI).
@@ -486,6 +487,13 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_packet_events(request)
     }
 
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        self.inner().query_packet_txs(request)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -521,6 +529,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner.query_consumer_chains()
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.inner.query_upgrade_plan()
+    }
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
@@ -42,9 +42,10 @@ use crate::connection::ConnectionMsgType;
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
-use crate::event::IbcEventWithHeight;
+use crate::event::{IbcEventWithHeight, IbcEventWithTxHash};
 use crate::keyring::AnySigningKeyPair;
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::upgrade_chain::UpgradePlan;
 use crate::util::lock::LockExt;
 
 #[derive(Debug, Clone)]
@@ -473,6 +474,14 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner().query_packet_events(request)
     }
 
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        self.inc_metric("query_packet_txs");
+        self.inner().query_packet_txs(request)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -513,6 +522,11 @@ impl<Handle: ChainHandle> ChainHandle for CountingChainHandle<Handle> {
         self.inner.query_consumer_chains()
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.inc_metric("query_upgrade_plan");
+        self.inner.query_upgrade_plan()
+    }
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
@@ -78,11 +78,12 @@ use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
 use crate::error::Error;
 use crate::event::source::{EventSource, TxEventSourceCmd};
-use crate::event::IbcEventWithHeight;
+use crate::event::{IbcEventWithHeight, IbcEventWithTxHash};
 use crate::keyring::{KeyRing, NamadaKeyPair, SigningKeyPair};
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::{LightClient, Verified};
 use crate::misbehaviour::MisbehaviourEvidence;
+use crate::upgrade_chain::UpgradePlan;
 
 use self::error::Error as NamadaError;
 
@@ -140,6 +141,9 @@ impl NamadaChain {
                 *max_retries,
                 self.rt.clone(),
             ),
+            Mode::Replay { path, speed } => {
+                EventSource::replay(self.config.id.clone(), path.clone(), *speed)
+            }
         }
         .map_err(Error::event_source)?;
 
@@ -364,6 +368,14 @@ impl ChainEndpoint for NamadaChain {
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
         crate::time!("send_messages_and_wait_commit");
 
+        if self.config.dry_run {
+            return Err(Error::dry_run_unsupported(self.config.id.clone()));
+        }
+
+        if self.config.generate_only.is_some() {
+            return Err(Error::generate_only_unsupported(self.config.id.clone()));
+        }
+
         let proto_msgs = tracked_msgs.messages();
         if proto_msgs.is_empty() {
             return Ok(vec![]);
@@ -406,6 +418,14 @@ impl ChainEndpoint for NamadaChain {
     ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
         crate::time!("send_messages_and_wait_check_tx");
 
+        if self.config.dry_run {
+            return Err(Error::dry_run_unsupported(self.config.id.clone()));
+        }
+
+        if self.config.generate_only.is_some() {
+            return Err(Error::generate_only_unsupported(self.config.id.clone()));
+        }
+
         let proto_msgs = tracked_msgs.messages();
         if proto_msgs.is_empty() {
             return Ok(vec![]);
@@ -1189,6 +1209,17 @@ impl ChainEndpoint for NamadaChain {
         self.query_packet_events_from_block(&request)
     }
 
+    fn query_packet_txs(
+        &self,
+        _request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        // Namada packet events are only queried from blocks
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_packet_txs".to_string(),
+        ))
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -1322,6 +1353,11 @@ impl ChainEndpoint for NamadaChain {
         unimplemented!()
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        // Namada has no Cosmos SDK upgrade module
+        Ok(None)
+    }
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
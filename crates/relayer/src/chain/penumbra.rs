@@ -42,7 +42,7 @@ use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
 use crate::consensus_state::AnyConsensusState;
 use crate::event::source::{EventSource, TxEventSourceCmd};
-use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
+use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight, IbcEventWithTxHash};
 use crate::keyring::KeyRing;
 use crate::light_client::tendermint::LightClient as TmLightClient;
 use crate::light_client::LightClient;
@@ -106,6 +106,7 @@ use crate::{
 };
 
 use crate::chain::penumbra::config::PenumbraConfig;
+use crate::upgrade_chain::UpgradePlan;
 
 pub struct PenumbraChain {
     config: PenumbraConfig,
@@ -148,6 +149,9 @@ impl PenumbraChain {
                 *max_retries,
                 self.rt.clone(),
             ),
+            Mode::Replay { path, speed } => {
+                EventSource::replay(self.config.id.clone(), path.clone(), *speed)
+            }
             _ => unimplemented!(),
         }
         .map_err(Error::event_source)?;
@@ -681,6 +685,14 @@ impl ChainEndpoint for PenumbraChain {
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        if self.config.dry_run {
+            return Err(Error::dry_run_unsupported(self.config.id.clone()));
+        }
+
+        if self.config.generate_only {
+            return Err(Error::generate_only_unsupported(self.config.id.clone()));
+        }
+
         let runtime = self.rt.clone();
         let txid = runtime.block_on(self.send_messages_in_penumbratx(tracked_msgs, true))?;
         let events = runtime.block_on(self.ibc_events_for_penumbratx(txid))?;
@@ -692,6 +704,14 @@ impl ChainEndpoint for PenumbraChain {
         &mut self,
         tracked_msgs: TrackedMsgs,
     ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
+        if self.config.dry_run {
+            return Err(Error::dry_run_unsupported(self.config.id.clone()));
+        }
+
+        if self.config.generate_only {
+            return Err(Error::generate_only_unsupported(self.config.id.clone()));
+        }
+
         let runtime = self.rt.clone();
         let penumbra_tx = runtime
             .block_on(self.build_penumbra_tx(tracked_msgs.clone()))
@@ -951,7 +971,7 @@ impl ChainEndpoint for PenumbraChain {
             .try_into()
             .map_err(|e: ics02_client::error::Error| Error::other(e.to_string()))?;
 
-        if !matches!(consensus_state, AnyConsensusState::Tendermint(_)) {
+        if !matches!(consensus_state.inner(), AnyConsensusState::Tendermint(_)) {
             return Err(Error::consensus_state_type_mismatch(
                 ClientType::Tendermint,
                 consensus_state.client_type(),
@@ -1640,6 +1660,20 @@ impl ChainEndpoint for PenumbraChain {
         }
     }
 
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        use crate::chain::cosmos::query::tx::query_packet_txs;
+
+        self.rt.block_on(query_packet_txs(
+            self.id(),
+            &self.tendermint_rpc_client,
+            &self.config.rpc_addr,
+            &request,
+        ))
+    }
+
     fn query_host_consensus_state(
         &self,
         _request: QueryHostConsensusStateRequest,
@@ -1737,6 +1771,11 @@ impl ChainEndpoint for PenumbraChain {
         unimplemented!("not currently implemented in penumbra")
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        // Penumbra has no Cosmos SDK upgrade module
+        Ok(None)
+    }
+
     fn query_upgrade(
         &self,
         _request: ibc_proto::ibc::core::channel::v1::QueryUpgradeRequest,
@@ -2,15 +2,16 @@ use core::time::Duration;
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use penumbra_sdk_custody::soft_kms;
+use schemars::JsonSchema;
 use serde_derive::{Deserialize, Serialize};
 use tendermint_rpc::Url;
 
 use crate::config::{
-    compat_mode::CompatMode, default, types::TrustThreshold, EventSourceMode, GenesisRestart,
-    PacketFilter, RefreshRate,
+    compat_mode::CompatMode, default, schema, types::TrustThreshold, EventSourceMode,
+    GenesisRestart, PacketFilter, RefreshRate,
 };
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct PenumbraConfig {
     /// A fake key name, not used except to satisfy external interfaces.
@@ -22,14 +23,18 @@ pub struct PenumbraConfig {
     /// 2. create a separate PR to change the method signature
     /// 3. rebase our change stack once it's merged and remove the stub field.
     #[serde(default)]
+    #[schemars(description = "A fake key name, unused")]
     pub stub_key_name: String,
 
+    #[schemars(schema_with = "schema::chain_id")]
     pub id: ChainId,
     /// The URL of the app's gRPC endpoint.
+    #[schemars(schema_with = "schema::url")]
     pub grpc_addr: Url,
     /// The URL of the cometbft RPC endpoint.
     ///
     /// TODO: in the long term, it would be desirable to eliminate this entirely.
+    #[schemars(schema_with = "schema::url")]
     pub rpc_addr: Url,
 
     /// The type of event source and associated settings
@@ -37,6 +42,7 @@ pub struct PenumbraConfig {
 
     /// Timeout used when issuing RPC queries
     #[serde(default = "default::rpc_timeout", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
     pub rpc_timeout: Duration,
 
     /// Controls which packets will be relayed.
@@ -47,6 +53,7 @@ pub struct PenumbraConfig {
     #[serde(default = "default::query_packets_chunk_size")]
     pub query_packets_chunk_size: usize,
     #[serde(default = "default::max_block_time", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
     pub max_block_time: Duration,
 
     // This field is only meant to be set via the `update client` command,
@@ -61,6 +68,7 @@ pub struct PenumbraConfig {
     /// that uses this configuration, unless it is overridden by the client-specific
     /// clock drift option.
     #[serde(default = "default::clock_drift", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
     pub clock_drift: Duration,
 
     /// The rate at which to refresh the client referencing this chain,
@@ -71,18 +79,32 @@ pub struct PenumbraConfig {
     /// The trust threshold defines what fraction of the total voting power of a known
     /// and trusted validator set is sufficient for a commit to be accepted going forward.
     #[serde(default)]
+    #[schemars(schema_with = "schema::trust_threshold")]
     pub trust_threshold: TrustThreshold,
+    #[schemars(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(schema_with = "schema::compat_mode")]
     pub compat_mode: Option<CompatMode>,
 
     /// The storage location for the penumbra view service, which tracks private state of the
     /// relayer's penumbra account.
     pub view_service_storage_dir: Option<String>,
 
+    /// Set by `hermes start --dry-run`. Penumbra does not support dry-run mode,
+    /// so no transaction is submitted to this chain while it is set.
+    #[serde(skip)]
+    pub dry_run: bool,
+
+    /// Set by `hermes --generate-only`. Penumbra does not support generate-only mode,
+    /// so no transaction is submitted to this chain while it is set.
+    #[serde(skip)]
+    pub generate_only: bool,
+
     // These last few need to be last otherwise we run into `ValueAfterTable` error when serializing to TOML
     /// Key configuration
     ///
     /// This is used instead of the Hermes keyring, which doesn't yet handle
     /// Penumbra-specific key concerns. In the future, we may want to merge
     /// this with the Hermes keyring, but it's a low-priority item.
+    #[schemars(schema_with = "schema::kms_config")]
     pub kms_config: soft_kms::Config,
 }
@@ -3,7 +3,7 @@ use std::thread;
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
-use tracing::{error, Span};
+use tracing::{error, info, Span};
 
 use ibc_proto::ibc::{
     apps::fee::v1::{QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse},
@@ -41,9 +41,10 @@ use crate::{
     consensus_state::AnyConsensusState,
     denom::DenomTrace,
     error::Error,
-    event::IbcEventWithHeight,
+    event::{IbcEventWithHeight, IbcEventWithTxHash},
     keyring::AnySigningKeyPair,
     misbehaviour::MisbehaviourEvidence,
+    upgrade_chain::UpgradePlan,
 };
 
 use super::{
@@ -51,7 +52,11 @@ use super::{
     endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
     requests::*,
-    tracking::TrackedMsgs,
+    tracking::{TrackedMsgs, TrackingId},
+    update_batch::{
+        events_for_request, is_client_update_only, is_committed, merge_client_updates,
+        rebuild_update_events,
+    },
     version::Specs,
 };
 
@@ -72,6 +77,9 @@ pub struct ChainRuntime<Endpoint: ChainEndpoint> {
     /// in through this channel.
     request_receiver: channel::Receiver<(Span, ChainRequest)>,
 
+    /// A request taken from the channel ahead of time, to be processed next
+    pending: Option<(Span, ChainRequest)>,
+
     #[allow(dead_code)]
     rt: Arc<TokioRuntime>, // Making this future-proof, so we keep the runtime around.
 }
@@ -124,6 +132,7 @@ where
             chain,
             request_sender,
             request_receiver,
+            pending: None,
         }
     }
 
@@ -136,242 +145,320 @@ where
 
     fn run(mut self) -> Result<(), Error> {
         loop {
-            channel::select! {
-                recv(self.request_receiver) -> event => {
-                    let (span, event) = match event {
-                        Ok((span, event)) => (span, event),
-                        Err(e) => {
-                            error!("received error via chain request channel: {}", e);
-                            continue;
-                        }
-                    };
-
-                    let _span = span.entered();
-
-                    match event {
-                        ChainRequest::Shutdown { reply_to } => {
-                            let res = self.chain.shutdown();
-
-                            reply_to.send(res)
-                                .map_err(Error::send)?;
-
-                            break;
-                        },
-
-                        ChainRequest::HealthCheck { reply_to } => {
-                            self.health_check(reply_to)?
-                        },
-
-                        ChainRequest::Subscribe { reply_to } => {
-                            self.subscribe(reply_to)?
-                        },
-
-                        ChainRequest::SendMessagesAndWaitCommit { tracked_msgs, reply_to } => {
-                            self.send_messages_and_wait_commit(tracked_msgs, reply_to)?
-                        },
-
-                        ChainRequest::SendMessagesAndWaitCheckTx { tracked_msgs, reply_to } => {
-                            self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
-                        },
-
-                        ChainRequest::Signer { reply_to } => {
-                            self.get_signer(reply_to)?
-                        },
-
-                        ChainRequest::Config { reply_to } => {
-                            self.get_config(reply_to)?
-                        },
-
-                        ChainRequest::GetKey { reply_to } => {
-                            self.get_key(reply_to)?
-                        },
-
-                        ChainRequest::AddKey { key_name, key, reply_to } => {
-                            self.add_key(key_name, key, reply_to)?
-                        },
-
-                        ChainRequest::VersionSpecs { reply_to } => {
-                            self.version_specs(reply_to)?
-                        },
-
-                        ChainRequest::BuildHeader { trusted_height, target_height, client_state, reply_to } => {
-                            self.build_header(trusted_height, target_height, client_state, reply_to)?
-                        },
-
-                        ChainRequest::BuildClientState { height, settings, reply_to } => {
-                            self.build_client_state(height, settings, reply_to)?
-                        },
+            let (span, request) = match self.pending.take() {
+                Some(pending) => pending,
+                None => match self.request_receiver.recv() {
+                    Ok(request) => request,
+                    Err(e) => {
+                        error!("received error via chain request channel: {}", e);
+                        continue;
+                    }
+                },
+            };
 
-                        ChainRequest::BuildConsensusState { trusted, target, client_state, reply_to } => {
-                            self.build_consensus_state(trusted, target, client_state, reply_to)?
-                        },
+            let _span = span.entered();
 
-                        ChainRequest::BuildMisbehaviour { client_state, update_event, reply_to } => {
-                            self.check_misbehaviour(update_event, client_state, reply_to)?
-                        },
+            if let ChainRequest::Shutdown { reply_to } = request {
+                let res = self.chain.shutdown();
 
-                        ChainRequest::BuildConnectionProofsAndClientState { message_type, connection_id, client_id, height, reply_to } => {
-                            self.build_connection_proofs_and_client_state(message_type, connection_id, client_id, height, reply_to)?
-                        },
+                reply_to.send(res).map_err(Error::send)?;
 
-                        ChainRequest::BuildChannelProofs { port_id, channel_id, height, reply_to } => {
-                            self.build_channel_proofs(port_id, channel_id, height, reply_to)?
-                        },
+                break;
+            }
 
-                        ChainRequest::QueryBalance { key_name, denom, reply_to } => {
-                            self.query_balance(key_name, denom, reply_to)?
-                        },
+            self.handle_request(request)?;
+        }
 
-                        ChainRequest::QueryAllBalances { key_name, reply_to } => {
-                            self.query_all_balances(key_name, reply_to)?
-                        },
+        Ok(())
+    }
 
-                        ChainRequest::QueryDenomTrace { hash, reply_to } => {
-                            self.query_denom_trace(hash, reply_to)?
-                        },
+    fn handle_request(&mut self, request: ChainRequest) -> Result<(), Error> {
+        match request {
+            ChainRequest::Shutdown { .. } => unreachable!("shutdown is handled by the run loop"),
 
-                        ChainRequest::QueryApplicationStatus { reply_to } => {
-                            self.query_application_status(reply_to)?
-                        },
+            ChainRequest::HealthCheck { reply_to } => self.health_check(reply_to)?,
 
-                        ChainRequest::QueryClients { request, reply_to } => {
-                            self.query_clients(request, reply_to)?
-                        },
+            ChainRequest::Subscribe { reply_to } => self.subscribe(reply_to)?,
 
-                        ChainRequest::QueryClientConnections { request, reply_to } => {
-                            self.query_client_connections(request, reply_to)?
-                        },
+            ChainRequest::SendMessagesAndWaitCommit {
+                tracked_msgs,
+                reply_to,
+            } => {
+                if is_client_update_only(&tracked_msgs) {
+                    self.send_client_updates(tracked_msgs, reply_to)?
+                } else {
+                    self.send_messages_and_wait_commit(tracked_msgs, reply_to)?
+                }
+            }
 
-                        ChainRequest::QueryClientState { request, include_proof, reply_to } => {
-                            self.query_client_state(request, include_proof, reply_to)?
-                        },
+            ChainRequest::SendMessagesAndWaitCheckTx {
+                tracked_msgs,
+                reply_to,
+            } => self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?,
+
+            ChainRequest::Signer { reply_to } => self.get_signer(reply_to)?,
+
+            ChainRequest::Config { reply_to } => self.get_config(reply_to)?,
+
+            ChainRequest::GetKey { reply_to } => self.get_key(reply_to)?,
+
+            ChainRequest::AddKey {
+                key_name,
+                key,
+                reply_to,
+            } => self.add_key(key_name, key, reply_to)?,
+
+            ChainRequest::VersionSpecs { reply_to } => self.version_specs(reply_to)?,
+
+            ChainRequest::BuildHeader {
+                trusted_height,
+                target_height,
+                client_state,
+                reply_to,
+            } => self.build_header(trusted_height, target_height, client_state, reply_to)?,
+
+            ChainRequest::BuildClientState {
+                height,
+                settings,
+                reply_to,
+            } => self.build_client_state(height, settings, reply_to)?,
+
+            ChainRequest::BuildConsensusState {
+                trusted,
+                target,
+                client_state,
+                reply_to,
+            } => self.build_consensus_state(trusted, target, client_state, reply_to)?,
+
+            ChainRequest::BuildMisbehaviour {
+                client_state,
+                update_event,
+                reply_to,
+            } => self.check_misbehaviour(update_event, client_state, reply_to)?,
+
+            ChainRequest::BuildConnectionProofsAndClientState {
+                message_type,
+                connection_id,
+                client_id,
+                height,
+                reply_to,
+            } => self.build_connection_proofs_and_client_state(
+                message_type,
+                connection_id,
+                client_id,
+                height,
+                reply_to,
+            )?,
+
+            ChainRequest::BuildChannelProofs {
+                port_id,
+                channel_id,
+                height,
+                reply_to,
+            } => self.build_channel_proofs(port_id, channel_id, height, reply_to)?,
+
+            ChainRequest::QueryBalance {
+                key_name,
+                denom,
+                reply_to,
+            } => self.query_balance(key_name, denom, reply_to)?,
+
+            ChainRequest::QueryAllBalances { key_name, reply_to } => {
+                self.query_all_balances(key_name, reply_to)?
+            }
 
-                        ChainRequest::QueryConsensusStateHeights { request, reply_to } => {
-                            self.query_consensus_state_heights(request, reply_to)?
-                        },
+            ChainRequest::QueryDenomTrace { hash, reply_to } => {
+                self.query_denom_trace(hash, reply_to)?
+            }
 
-                        ChainRequest::QueryConsensusState { request, include_proof, reply_to } => {
-                            self.query_consensus_state(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryApplicationStatus { reply_to } => {
+                self.query_application_status(reply_to)?
+            }
 
-                        ChainRequest::QueryUpgradedClientState { request, reply_to } => {
-                            self.query_upgraded_client_state(request, reply_to)?
-                        },
+            ChainRequest::QueryClients { request, reply_to } => {
+                self.query_clients(request, reply_to)?
+            }
 
-                        ChainRequest::QueryUpgradedConsensusState { request, reply_to } => {
-                            self.query_upgraded_consensus_state(request, reply_to)?
-                        },
+            ChainRequest::QueryClientConnections { request, reply_to } => {
+                self.query_client_connections(request, reply_to)?
+            }
 
-                        ChainRequest::QueryCommitmentPrefix { reply_to } => {
-                            self.query_commitment_prefix(reply_to)?
-                        },
+            ChainRequest::QueryClientState {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_client_state(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryCompatibleVersions { reply_to } => {
-                            self.query_compatible_versions(reply_to)?
-                        },
+            ChainRequest::QueryConsensusStateHeights { request, reply_to } => {
+                self.query_consensus_state_heights(request, reply_to)?
+            }
 
-                        ChainRequest::QueryConnection { request, include_proof, reply_to } => {
-                            self.query_connection(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryConsensusState {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_consensus_state(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryConnections { request, reply_to } => {
-                            self.query_connections(request, reply_to)?
-                        },
+            ChainRequest::QueryUpgradedClientState { request, reply_to } => {
+                self.query_upgraded_client_state(request, reply_to)?
+            }
 
-                        ChainRequest::QueryConnectionChannels { request, reply_to } => {
-                            self.query_connection_channels(request, reply_to)?
-                        },
+            ChainRequest::QueryUpgradedConsensusState { request, reply_to } => {
+                self.query_upgraded_consensus_state(request, reply_to)?
+            }
 
-                        ChainRequest::QueryChannels { request, reply_to } => {
-                            self.query_channels(request, reply_to)?
-                        },
+            ChainRequest::QueryCommitmentPrefix { reply_to } => {
+                self.query_commitment_prefix(reply_to)?
+            }
 
-                        ChainRequest::QueryChannel { request, include_proof, reply_to } => {
-                            self.query_channel(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryCompatibleVersions { reply_to } => {
+                self.query_compatible_versions(reply_to)?
+            }
 
-                        ChainRequest::QueryChannelClientState { request, reply_to } => {
-                            self.query_channel_client_state(request, reply_to)?
-                        },
+            ChainRequest::QueryConnection {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_connection(request, include_proof, reply_to)?,
 
-                        ChainRequest::BuildPacketProofs { packet_type, port_id, channel_id, sequence, height, reply_to } => {
-                            self.build_packet_proofs(packet_type, port_id, channel_id, sequence, height, reply_to)?
-                        },
+            ChainRequest::QueryConnections { request, reply_to } => {
+                self.query_connections(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketCommitment { request, include_proof, reply_to } => {
-                            self.query_packet_commitment(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryConnectionChannels { request, reply_to } => {
+                self.query_connection_channels(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketCommitments { request, reply_to } => {
-                            self.query_packet_commitments(request, reply_to)?
-                        },
+            ChainRequest::QueryChannels { request, reply_to } => {
+                self.query_channels(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketReceipt { request, include_proof, reply_to } => {
-                            self.query_packet_receipt(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryChannel {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_channel(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryUnreceivedPackets { request, reply_to } => {
-                            self.query_unreceived_packets(request, reply_to)?
-                        },
+            ChainRequest::QueryChannelClientState { request, reply_to } => {
+                self.query_channel_client_state(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketAcknowledgement { request, include_proof, reply_to } => {
-                            self.query_packet_acknowledgement(request, include_proof, reply_to)?
-                        },
+            ChainRequest::BuildPacketProofs {
+                packet_type,
+                port_id,
+                channel_id,
+                sequence,
+                height,
+                reply_to,
+            } => self.build_packet_proofs(
+                packet_type,
+                port_id,
+                channel_id,
+                sequence,
+                height,
+                reply_to,
+            )?,
+
+            ChainRequest::QueryPacketCommitment {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_packet_commitment(request, include_proof, reply_to)?,
+
+            ChainRequest::QueryPacketCommitments { request, reply_to } => {
+                self.query_packet_commitments(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketAcknowledgements { request, reply_to } => {
-                            self.query_packet_acknowledgements(request, reply_to)?
-                        },
+            ChainRequest::QueryPacketReceipt {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_packet_receipt(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryUnreceivedAcknowledgement { request, reply_to } => {
-                            self.query_unreceived_acknowledgement(request, reply_to)?
-                        },
+            ChainRequest::QueryUnreceivedPackets { request, reply_to } => {
+                self.query_unreceived_packets(request, reply_to)?
+            }
 
-                        ChainRequest::QueryNextSequenceReceive { request, include_proof, reply_to } => {
-                            self.query_next_sequence_receive(request, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryPacketAcknowledgement {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_packet_acknowledgement(request, include_proof, reply_to)?,
 
-                        ChainRequest::QueryPacketEventDataFromTxs { request, reply_to } => {
-                            self.query_txs(request, reply_to)?
-                        },
+            ChainRequest::QueryPacketAcknowledgements { request, reply_to } => {
+                self.query_packet_acknowledgements(request, reply_to)?
+            }
 
-                        ChainRequest::QueryPacketEventData { request, reply_to } => {
-                            self.query_packet_events(request, reply_to)?
-                        },
+            ChainRequest::QueryUnreceivedAcknowledgement { request, reply_to } => {
+                self.query_unreceived_acknowledgement(request, reply_to)?
+            }
 
-                        ChainRequest::QueryHostConsensusState { request, reply_to } => {
-                            self.query_host_consensus_state(request, reply_to)?
-                        },
+            ChainRequest::QueryNextSequenceReceive {
+                request,
+                include_proof,
+                reply_to,
+            } => self.query_next_sequence_receive(request, include_proof, reply_to)?,
 
-                        ChainRequest::MaybeRegisterCounterpartyPayee { channel_id, port_id, counterparty_payee, reply_to } => {
-                            self.maybe_register_counterparty_payee(&channel_id, &port_id, &counterparty_payee, reply_to)?
-                        },
+            ChainRequest::QueryPacketEventDataFromTxs { request, reply_to } => {
+                self.query_txs(request, reply_to)?
+            }
 
-                        ChainRequest::CrossChainQuery { request, reply_to } => {
-                            self.cross_chain_query(request, reply_to)?
-                        },
+            ChainRequest::QueryPacketEventData { request, reply_to } => {
+                self.query_packet_events(request, reply_to)?
+            }
 
-                        ChainRequest::QueryIncentivizedPacket { request, reply_to } => {
-                            self.query_incentivized_packet(request, reply_to)?
-                        },
+            ChainRequest::QueryPacketTxs { request, reply_to } => {
+                self.query_packet_txs(request, reply_to)?
+            }
 
-                        ChainRequest::QueryConsumerChains { reply_to } => {
-                            self.query_consumer_chains(reply_to)?
-                        },
+            ChainRequest::QueryHostConsensusState { request, reply_to } => {
+                self.query_host_consensus_state(request, reply_to)?
+            }
 
-                        ChainRequest::QueryUpgrade { request, height, include_proof, reply_to } => {
-                            self.query_upgrade(request, height, include_proof, reply_to)?
-                        },
+            ChainRequest::MaybeRegisterCounterpartyPayee {
+                channel_id,
+                port_id,
+                counterparty_payee,
+                reply_to,
+            } => self.maybe_register_counterparty_payee(
+                &channel_id,
+                &port_id,
+                &counterparty_payee,
+                reply_to,
+            )?,
+
+            ChainRequest::CrossChainQuery { request, reply_to } => {
+                self.cross_chain_query(request, reply_to)?
+            }
 
-                        ChainRequest::QueryUpgradeError { request, height, include_proof, reply_to } => {
-                            self.query_upgrade_error(request, height, include_proof, reply_to)?
-                        },
+            ChainRequest::QueryIncentivizedPacket { request, reply_to } => {
+                self.query_incentivized_packet(request, reply_to)?
+            }
 
-                        ChainRequest::QueryConsumerId { client_id, reply_to } => {
-                            self.query_ccv_consumer_id(client_id, reply_to)?
-                        },
-                    }
-                },
+            ChainRequest::QueryConsumerChains { reply_to } => {
+                self.query_consumer_chains(reply_to)?
             }
+
+            ChainRequest::QueryUpgradePlan { reply_to } => self.query_upgrade_plan(reply_to)?,
+
+            ChainRequest::QueryUpgrade {
+                request,
+                height,
+                include_proof,
+                reply_to,
+            } => self.query_upgrade(request, height, include_proof, reply_to)?,
+
+            ChainRequest::QueryUpgradeError {
+                request,
+                height,
+                include_proof,
+                reply_to,
+            } => self.query_upgrade_error(request, height, include_proof, reply_to)?,
+
+            ChainRequest::QueryConsumerId {
+                client_id,
+                reply_to,
+            } => self.query_ccv_consumer_id(client_id, reply_to)?,
         }
 
         Ok(())
@@ -396,6 +483,114 @@ where
         reply_to.send(result).map_err(Error::send)
     }
 
+    /// Submit the given client updates along with the other client updates
+    /// waiting in the queue of this runtime, merging them into as few messages
+    /// and transactions as possible.
+    fn send_client_updates(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    ) -> Result<(), Error> {
+        let mut batch = vec![(tracked_msgs, reply_to)];
+
+        // Every handle waits for the reply to a request before sending the next one,
+        // so the requests waiting in the queue are independent from one another and
+        // the ones which are not client updates can be processed right away, in order.
+        // Only the requests queued so far are drained, so that a steady stream of other
+        // requests cannot hold back the submission of the updates.
+        let queued = self.request_receiver.len();
+
+        for _ in 0..queued {
+            let Ok((span, request)) = self.request_receiver.try_recv() else {
+                break;
+            };
+
+            match request {
+                ChainRequest::SendMessagesAndWaitCommit {
+                    tracked_msgs,
+                    reply_to,
+                } if is_client_update_only(&tracked_msgs) => batch.push((tracked_msgs, reply_to)),
+
+                // Submit the updates before shutting down
+                request @ ChainRequest::Shutdown { .. } => {
+                    self.pending = Some((span, request));
+                    break;
+                }
+
+                request => {
+                    let _span = span.entered();
+                    self.handle_request(request)?;
+                }
+            }
+        }
+
+        if batch.len() == 1 {
+            let (tracked_msgs, reply_to) = batch.remove(0);
+            return self.send_messages_and_wait_commit(tracked_msgs, reply_to);
+        }
+
+        let msgs = merge_client_updates(batch.iter().map(|(tracked_msgs, _)| tracked_msgs));
+        let requested: usize = batch
+            .iter()
+            .map(|(tracked_msgs, _)| tracked_msgs.msgs.len())
+            .sum();
+
+        info!(
+            "merged {} client updates from {} workers into {} messages",
+            requested,
+            batch.len(),
+            msgs.len()
+        );
+
+        let merged = TrackedMsgs::new(msgs, TrackingId::new_static("merged client updates"));
+
+        let result = self.chain.send_messages_and_wait_commit(merged);
+
+        for (tracked_msgs, reply_to) in batch {
+            let events = match &result {
+                Ok(events) if is_committed(&tracked_msgs, events) => {
+                    Some(events_for_request(&tracked_msgs, events))
+                }
+
+                // Some of the transactions may have been committed before the error
+                Err(_) => self.applied_update_events(&tracked_msgs),
+
+                Ok(_) => None,
+            };
+
+            match events {
+                Some(events) => reply_to.send(Ok(events)).map_err(Error::send)?,
+
+                // A single failing update fails the whole transaction it is part of, so submit
+                // the updates of the workers whose transaction failed on their own, for errors
+                // to only reach the workers concerned.
+                None => self.send_messages_and_wait_commit(tracked_msgs, reply_to)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The `UpdateClient` events of the given client updates, rebuilt from the consensus
+    /// states found on the chain, or `None` if not all the updates were committed.
+    fn applied_update_events(&self, tracked_msgs: &TrackedMsgs) -> Option<Vec<IbcEventWithHeight>> {
+        let height = self.chain.query_application_status().ok()?.height;
+
+        rebuild_update_events(tracked_msgs, height, |client_id, consensus_height| {
+            self.chain
+                .query_consensus_state(
+                    QueryConsensusStateRequest {
+                        client_id: client_id.clone(),
+                        consensus_height,
+                        query_height: QueryHeight::Specific(height),
+                    },
+                    IncludeProof::No,
+                )
+                .ok()
+                .map(|(consensus_state, _)| consensus_state.client_type())
+        })
+    }
+
     fn send_messages_and_wait_check_tx(
         &mut self,
         tracked_msgs: TrackedMsgs,
@@ -818,6 +1013,18 @@ where
         Ok(())
     }
 
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+        reply_to: ReplyTo<Vec<IbcEventWithTxHash>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_packet_txs(request);
+
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -878,6 +1085,13 @@ where
         Ok(())
     }
 
+    fn query_upgrade_plan(&self, reply_to: ReplyTo<Option<UpgradePlan>>) -> Result<(), Error> {
+        let result = self.chain.query_upgrade_plan();
+        reply_to.send(result).map_err(Error::send)?;
+
+        Ok(())
+    }
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
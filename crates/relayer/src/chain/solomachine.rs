@@ -0,0 +1,1002 @@
+//! A solo machine, which lets Hermes act as an IBC counterparty without running
+//! a second chain, eg. for integration tests or light bridges.
+//!
+//! The solo machine is tracked on its counterparty by a `06-solomachine` client,
+//! to which it proves its IBC state by signing it with a key of the keyring.
+//! It hosts light clients of its counterparties in a local IBC store, to which the
+//! messages submitted by the relayer are applied without verification: the solo
+//! machine trusts the relayer it runs in.
+//!
+//! Every proof consumes a sequence of the `06-solomachine` client, so the proofs must
+//! be submitted to the counterparty in the order in which they are built. A proof that
+//! is built but never delivered leaves the solo machine ahead of its client.
+
+pub mod config;
+pub mod error;
+pub mod signer;
+pub mod store;
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use std::fs;
+use std::sync::{Mutex, MutexGuard};
+
+use sha2::{Digest, Sha256};
+use tendermint::abci::Code;
+use tendermint::Hash;
+use tendermint_rpc::endpoint::broadcast::tx_sync::Response as TxResponse;
+use tokio::runtime::Runtime as TokioRuntime;
+
+use ibc_proto::ibc::apps::fee::v1::{
+    QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
+};
+use ibc_proto::ibc::core::channel::v1::{QueryUpgradeErrorRequest, QueryUpgradeRequest};
+use ibc_proto::Protobuf;
+use ibc_relayer_types::applications::ics28_ccv::msgs::{ConsumerChain, ConsumerId};
+use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::clients::ics06_solomachine::client_state::ClientState as SmClientState;
+use ibc_relayer_types::clients::ics06_solomachine::consensus_state::ConsensusState as SmConsensusState;
+use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
+use ibc_relayer_types::core::ics02_client::events::{Attributes as ClientAttributes, UpdateClient};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, IdentifiedConnectionEnd, State as ConnectionState,
+};
+use ibc_relayer_types::core::ics04_channel::channel::{ChannelEnd, IdentifiedChannelEnd};
+use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics04_channel::upgrade::{ErrorReceipt, Upgrade};
+use ibc_relayer_types::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes,
+};
+use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ClientStatePath, CommitmentsPath, ConnectionsPath, Path as IbcPath,
+    ReceiptsPath, SeqRecvsPath,
+};
+use ibc_relayer_types::events::{IbcEvent, WithBlockDataType};
+use ibc_relayer_types::proofs::{ConsensusProof, Proofs};
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height as ICSHeight;
+
+use crate::account::Balance;
+use crate::chain::client::ClientSettings;
+use crate::chain::cosmos::encode::key_pair_to_signer;
+use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::handle::Subscription;
+use crate::chain::requests::*;
+use crate::chain::tracking::TrackedMsgs;
+use crate::chain::version::Specs;
+use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+use crate::config::{ChainConfig, Error as ConfigError};
+use crate::connection::ConnectionMsgType;
+use crate::consensus_state::AnyConsensusState;
+use crate::denom::DenomTrace;
+use crate::error::Error;
+use crate::event::bus::EventBus;
+use crate::event::source::{EventBatch, Result as MonitorResult};
+use crate::event::{IbcEventWithHeight, IbcEventWithTxHash};
+use crate::keyring::{KeyRing, Secp256k1KeyPair};
+use crate::misbehaviour::MisbehaviourEvidence;
+use crate::upgrade_chain::UpgradePlan;
+
+use self::config::SoloMachineConfig;
+use self::error::Error as SoloMachineError;
+use self::signer::Signer as SoloMachineSigner;
+use self::store::{consensus_state_path, IbcStore};
+
+/// The file holding the sequence of the solo machine, in its state directory.
+pub const SIGNER_FILE: &str = "signer.json";
+
+/// The file holding the IBC store of the solo machine, in its state directory.
+pub const STORE_FILE: &str = "store.json";
+
+/// How many of the latest messages applied to the solo machine can be queried by hash.
+const MAX_RECENT_TXS: usize = 100;
+
+pub struct SoloMachineChain {
+    config: SoloMachineConfig,
+    keybase: KeyRing<Secp256k1KeyPair>,
+    /// Proofs are built from `&self`, but signing one consumes a sequence.
+    signer: Mutex<SoloMachineSigner>,
+    store: IbcStore,
+    event_bus: EventBus<Arc<MonitorResult<EventBatch>>>,
+    recent_txs: VecDeque<(Hash, Vec<IbcEventWithHeight>)>,
+}
+
+impl SoloMachineChain {
+    /// Apply the given messages to the store, all at once or not at all, like a transaction.
+    fn deliver(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<(Hash, Vec<IbcEventWithHeight>), Error> {
+        if self.config.dry_run {
+            return Err(Error::dry_run_unsupported(self.config.id.clone()));
+        }
+
+        if self.config.generate_only {
+            return Err(Error::generate_only_unsupported(self.config.id.clone()));
+        }
+
+        let height = self.store.height();
+        let mut store = self.store.clone();
+        let mut events = Vec::new();
+        let mut hasher = Sha256::new();
+
+        for msg in tracked_msgs.msgs.iter() {
+            hasher.update(prost::Message::encode_to_vec(msg));
+
+            let msg_events = store.deliver(msg).map_err(Error::solo_machine)?;
+            events.extend(
+                msg_events
+                    .into_iter()
+                    .map(|event| IbcEventWithHeight::new(event, height)),
+            );
+        }
+
+        store
+            .persist(&self.config.state_dir.join(STORE_FILE))
+            .map_err(Error::solo_machine)?;
+        self.store = store;
+
+        hasher.update(Timestamp::now().nanoseconds().to_be_bytes());
+        let hash = Hash::Sha256(hasher.finalize().into());
+
+        if self.recent_txs.len() == MAX_RECENT_TXS {
+            self.recent_txs.pop_front();
+        }
+        self.recent_txs.push_back((hash, events.clone()));
+
+        if !events.is_empty() {
+            self.event_bus.broadcast(Arc::new(Ok(EventBatch {
+                chain_id: self.config.id.clone(),
+                tracking_id: tracked_msgs.tracking_id,
+                height,
+                events: events.clone(),
+            })));
+        }
+
+        Ok((hash, events))
+    }
+
+    /// Sign a proof of the value stored at `path`, consuming a sequence of the solo machine.
+    fn prove(
+        &self,
+        path: impl Into<IbcPath>,
+        value: Vec<u8>,
+    ) -> Result<CommitmentProofBytes, Error> {
+        let prefix = self.query_commitment_prefix()?;
+
+        self.signer()
+            .prove(&prefix, path.into(), value)
+            .map_err(Error::solo_machine)
+    }
+
+    fn signer(&self) -> MutexGuard<'_, SoloMachineSigner> {
+        self.signer.lock().expect("poisoned lock")
+    }
+}
+
+impl ChainEndpoint for SoloMachineChain {
+    type LightBlock = SmConsensusState;
+    // Note: this is a placeholder, we won't actually use it.
+    // Clients of a solo machine are never updated, see `build_header`.
+    type Header = TmHeader;
+    type ConsensusState = SmConsensusState;
+    type ClientState = SmClientState;
+    type Time = Timestamp;
+    type SigningKeyPair = Secp256k1KeyPair;
+
+    fn id(&self) -> &ChainId {
+        &self.config.id
+    }
+
+    fn config(&self) -> ChainConfig {
+        ChainConfig::SoloMachine(self.config.clone())
+    }
+
+    fn bootstrap(config: ChainConfig, _rt: Arc<TokioRuntime>) -> Result<Self, Error> {
+        let ChainConfig::SoloMachine(config) = config else {
+            return Err(Error::config(ConfigError::wrong_type()));
+        };
+
+        fs::create_dir_all(&config.state_dir)
+            .map_err(|e| Error::solo_machine(SoloMachineError::io(config.state_dir.clone(), e)))?;
+
+        let keybase = KeyRing::new_secp256k1(
+            config.key_store_type,
+            &config.account_prefix,
+            &config.id,
+            &config.key_store_folder,
+        )
+        .map_err(Error::key_base)?;
+
+        let signer = SoloMachineSigner::load_or_init(
+            &keybase,
+            &config.key_name,
+            config.id.as_str(),
+            &config.state_dir.join(SIGNER_FILE),
+        )
+        .map_err(Error::solo_machine)?;
+
+        let store = IbcStore::load_or_init(&config.state_dir.join(STORE_FILE))
+            .map_err(Error::solo_machine)?;
+
+        Ok(Self {
+            config,
+            keybase,
+            signer: Mutex::new(signer),
+            store,
+            event_bus: EventBus::new(),
+            recent_txs: VecDeque::new(),
+        })
+    }
+
+    fn shutdown(self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn health_check(&mut self) -> Result<HealthCheck, Error> {
+        Ok(HealthCheck::Healthy)
+    }
+
+    fn subscribe(&mut self) -> Result<Subscription, Error> {
+        Ok(self.event_bus.subscribe())
+    }
+
+    fn keybase(&self) -> &KeyRing<Self::SigningKeyPair> {
+        &self.keybase
+    }
+
+    fn keybase_mut(&mut self) -> &mut KeyRing<Self::SigningKeyPair> {
+        &mut self.keybase
+    }
+
+    fn get_signer(&self) -> Result<Signer, Error> {
+        key_pair_to_signer(&self.get_key()?)
+    }
+
+    fn get_key(&self) -> Result<Self::SigningKeyPair, Error> {
+        self.keybase
+            .get_key(&self.config.key_name)
+            .map_err(|e| Error::key_not_found(self.config.key_name.clone(), e))
+    }
+
+    fn version_specs(&self) -> Result<Specs, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "version_specs".to_string(),
+        ))
+    }
+
+    fn send_messages_and_wait_commit(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        let (_, events) = self.deliver(tracked_msgs)?;
+        Ok(events)
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &mut self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<TxResponse>, Error> {
+        let (hash, _) = self.deliver(tracked_msgs)?;
+
+        Ok(vec![TxResponse {
+            codespace: String::new(),
+            code: Code::Ok,
+            data: Default::default(),
+            log: String::new(),
+            hash,
+        }])
+    }
+
+    /// The solo machine has no headers, its consensus state is the one it signs with.
+    fn verify_header(
+        &mut self,
+        _trusted: ICSHeight,
+        _target: ICSHeight,
+        _client_state: &AnyClientState,
+    ) -> Result<Self::LightBlock, Error> {
+        Ok(self.signer().consensus_state())
+    }
+
+    fn check_misbehaviour(
+        &mut self,
+        _update: &UpdateClient,
+        _client_state: &AnyClientState,
+    ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        Ok(None)
+    }
+
+    fn query_balance(
+        &self,
+        _key_name: Option<&str>,
+        _denom: Option<&str>,
+    ) -> Result<Balance, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_balance".to_string(),
+        ))
+    }
+
+    fn query_all_balances(&self, _key_name: Option<&str>) -> Result<Vec<Balance>, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_all_balances".to_string(),
+        ))
+    }
+
+    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_denom_trace".to_string(),
+        ))
+    }
+
+    fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        Ok(b"ibc".to_vec().try_into().expect("the prefix is not empty"))
+    }
+
+    fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        Ok(ChainStatus {
+            height: self.store.height(),
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    fn query_clients(
+        &self,
+        _request: QueryClientStatesRequest,
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        let clients = self.store.clients().map_err(Error::solo_machine)?;
+
+        Ok(clients
+            .into_iter()
+            .map(|(client_id, client_state)| IdentifiedAnyClientState::new(client_id, client_state))
+            .collect())
+    }
+
+    fn query_client_state(
+        &self,
+        request: QueryClientStateRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        let client_state = self
+            .store
+            .client_state(&request.client_id)
+            .map_err(Error::solo_machine)?
+            .ok_or_else(Error::empty_response_value)?;
+
+        Ok((client_state, None))
+    }
+
+    fn query_consensus_state(
+        &self,
+        request: QueryConsensusStateRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        let consensus_state = self
+            .store
+            .consensus_state(&request.client_id, request.consensus_height)
+            .map_err(Error::solo_machine)?
+            .ok_or_else(Error::empty_response_value)?;
+
+        Ok((consensus_state, None))
+    }
+
+    fn query_consensus_state_heights(
+        &self,
+        request: QueryConsensusStateHeightsRequest,
+    ) -> Result<Vec<ICSHeight>, Error> {
+        let mut heights = self.store.consensus_state_heights(&request.client_id);
+        heights.sort_unstable();
+
+        Ok(heights)
+    }
+
+    fn query_upgraded_client_state(
+        &self,
+        _request: QueryUpgradedClientStateRequest,
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_upgraded_client_state".to_string(),
+        ))
+    }
+
+    fn query_upgraded_consensus_state(
+        &self,
+        _request: QueryUpgradedConsensusStateRequest,
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_upgraded_consensus_state".to_string(),
+        ))
+    }
+
+    fn query_connections(
+        &self,
+        _request: QueryConnectionsRequest,
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.store.connections().map_err(Error::solo_machine)
+    }
+
+    fn query_client_connections(
+        &self,
+        request: QueryClientConnectionsRequest,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        self.store
+            .client_connections(&request.client_id)
+            .map_err(Error::solo_machine)
+    }
+
+    fn query_connection(
+        &self,
+        request: QueryConnectionRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        let connection_end = self
+            .store
+            .connection(&request.connection_id)
+            .map_err(Error::solo_machine)?
+            .ok_or_else(|| Error::connection_not_found(request.connection_id.clone()))?;
+
+        Ok((connection_end, None))
+    }
+
+    fn query_connection_channels(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        let channels = self.store.channels().map_err(Error::solo_machine)?;
+
+        Ok(channels
+            .into_iter()
+            .filter(|channel| {
+                channel
+                    .channel_end
+                    .connection_hops_matches(&vec![request.connection_id.clone()])
+            })
+            .collect())
+    }
+
+    fn query_channels(
+        &self,
+        _request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.store.channels().map_err(Error::solo_machine)
+    }
+
+    fn query_channel(
+        &self,
+        request: QueryChannelRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        let channel_end = self
+            .store
+            .channel(&request.port_id, &request.channel_id)
+            .map_err(Error::solo_machine)?;
+
+        Ok((channel_end, None))
+    }
+
+    fn query_channel_client_state(
+        &self,
+        request: QueryChannelClientStateRequest,
+    ) -> Result<Option<IdentifiedAnyClientState>, Error> {
+        let channel_end = self
+            .store
+            .channel(&request.port_id, &request.channel_id)
+            .map_err(Error::solo_machine)?;
+
+        let Some(connection_id) = channel_end.connection_hops().first() else {
+            return Ok(None);
+        };
+
+        let Some(connection_end) = self
+            .store
+            .connection(connection_id)
+            .map_err(Error::solo_machine)?
+        else {
+            return Ok(None);
+        };
+
+        let client_id = connection_end.client_id();
+
+        Ok(self
+            .store
+            .client_state(client_id)
+            .map_err(Error::solo_machine)?
+            .map(|client_state| IdentifiedAnyClientState::new(client_id.clone(), client_state)))
+    }
+
+    fn query_packet_commitment(
+        &self,
+        request: QueryPacketCommitmentRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let commitment = self.store.get(CommitmentsPath {
+            port_id: request.port_id,
+            channel_id: request.channel_id,
+            sequence: request.sequence,
+        });
+
+        Ok((commitment, None))
+    }
+
+    /// The solo machine does not send packets, so it never has packet commitments.
+    fn query_packet_commitments(
+        &self,
+        _request: QueryPacketCommitmentsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        Ok((Vec::new(), self.store.height()))
+    }
+
+    fn query_packet_receipt(
+        &self,
+        request: QueryPacketReceiptRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let receipt = self.store.get(ReceiptsPath {
+            port_id: request.port_id,
+            channel_id: request.channel_id,
+            sequence: request.sequence,
+        });
+
+        Ok((receipt, None))
+    }
+
+    fn query_unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        let mut unreceived = Vec::new();
+
+        for sequence in request.packet_commitment_sequences {
+            let received = self
+                .store
+                .packet_received(&request.port_id, &request.channel_id, sequence)
+                .map_err(Error::solo_machine)?;
+
+            if !received {
+                unreceived.push(sequence);
+            }
+        }
+
+        Ok(unreceived)
+    }
+
+    fn query_packet_acknowledgement(
+        &self,
+        request: QueryPacketAcknowledgementRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        let ack_commitment = self.store.get(AcksPath {
+            port_id: request.port_id,
+            channel_id: request.channel_id,
+            sequence: request.sequence,
+        });
+
+        Ok((ack_commitment, None))
+    }
+
+    fn query_packet_acknowledgements(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Result<(Vec<Sequence>, ICSHeight), Error> {
+        let sequences = self
+            .store
+            .acknowledgement_sequences(&request.port_id, &request.channel_id)
+            .into_iter()
+            .filter(|sequence| request.packet_commitment_sequences.contains(sequence))
+            .collect();
+
+        Ok((sequences, self.store.height()))
+    }
+
+    /// The solo machine does not send packets, so it never awaits acknowledgements.
+    fn query_unreceived_acknowledgements(
+        &self,
+        _request: QueryUnreceivedAcksRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        Ok(Vec::new())
+    }
+
+    fn query_next_sequence_receive(
+        &self,
+        request: QueryNextSequenceReceiveRequest,
+        _include_proof: IncludeProof,
+    ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        let sequence = self
+            .store
+            .next_sequence_recv(&request.port_id, &request.channel_id);
+
+        Ok((sequence, None))
+    }
+
+    fn query_txs(&self, request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
+        match request {
+            QueryTxRequest::Transaction(QueryTxHash(hash)) => Ok(self
+                .recent_txs
+                .iter()
+                .find(|(tx_hash, _)| *tx_hash == hash)
+                .map(|(_, events)| events.clone())
+                .unwrap_or_default()),
+
+            QueryTxRequest::Client(request) => {
+                if !matches!(request.event_id, WithBlockDataType::UpdateClient) {
+                    return Ok(Vec::new());
+                }
+
+                let Some(height) = self
+                    .store
+                    .update_height(&request.client_id, request.consensus_height)
+                else {
+                    return Ok(Vec::new());
+                };
+
+                let client_type = self
+                    .store
+                    .client_state(&request.client_id)
+                    .map_err(Error::solo_machine)?
+                    .ok_or_else(Error::empty_response_value)?
+                    .client_type();
+
+                let event = IbcEvent::UpdateClient(UpdateClient {
+                    common: ClientAttributes {
+                        client_id: request.client_id,
+                        client_type,
+                        consensus_height: request.consensus_height,
+                    },
+                    header: None,
+                });
+
+                Ok(vec![IbcEventWithHeight::new(event, height)])
+            }
+        }
+    }
+
+    /// Only the acknowledgements written by the solo machine can be queried,
+    /// since it does not send packets.
+    fn query_packet_events(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        if !matches!(request.event_id, WithBlockDataType::WriteAck) {
+            return Ok(Vec::new());
+        }
+
+        let height = self.store.height();
+
+        Ok(request
+            .sequences
+            .iter()
+            .filter_map(|sequence| {
+                self.store.acknowledgement(
+                    &request.destination_port_id,
+                    &request.destination_channel_id,
+                    *sequence,
+                )
+            })
+            .filter(|write_ack| {
+                write_ack.packet.source_port == request.source_port_id
+                    && write_ack.packet.source_channel == request.source_channel_id
+            })
+            .map(|write_ack| {
+                IbcEventWithHeight::new(IbcEvent::WriteAcknowledgement(write_ack.clone()), height)
+            })
+            .collect())
+    }
+
+    fn query_packet_txs(
+        &self,
+        _request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_packet_txs".to_string(),
+        ))
+    }
+
+    fn query_host_consensus_state(
+        &self,
+        _request: QueryHostConsensusStateRequest,
+    ) -> Result<Self::ConsensusState, Error> {
+        Ok(self.signer().consensus_state())
+    }
+
+    fn build_client_state(
+        &self,
+        _height: ICSHeight,
+        _settings: ClientSettings,
+    ) -> Result<Self::ClientState, Error> {
+        self.signer().client_state().map_err(Error::solo_machine)
+    }
+
+    fn build_consensus_state(
+        &self,
+        light_block: Self::LightBlock,
+    ) -> Result<Self::ConsensusState, Error> {
+        Ok(light_block)
+    }
+
+    /// The client state of a solo machine client only changes when it verifies a proof
+    /// of the solo machine, so its clients are never updated.
+    fn build_header(
+        &mut self,
+        _trusted_height: ICSHeight,
+        _target_height: ICSHeight,
+        _client_state: &AnyClientState,
+    ) -> Result<(Self::Header, Vec<Self::Header>), Error> {
+        Err(Error::solo_machine(SoloMachineError::update_unsupported()))
+    }
+
+    /// Signs the proofs in the order in which the counterparty verifies them,
+    /// that is the connection, then its client state and consensus state.
+    fn build_connection_proofs_and_client_state(
+        &self,
+        message_type: ConnectionMsgType,
+        connection_id: &ConnectionId,
+        client_id: &ClientId,
+        height: ICSHeight,
+    ) -> Result<(Option<AnyClientState>, Proofs), Error> {
+        let (connection_end, _) = self.query_connection(
+            QueryConnectionRequest {
+                connection_id: connection_id.clone(),
+                height: QueryHeight::Specific(height),
+            },
+            IncludeProof::No,
+        )?;
+
+        let expected_states: &[ConnectionState] = match message_type {
+            ConnectionMsgType::OpenTry => &[ConnectionState::Init, ConnectionState::TryOpen],
+            ConnectionMsgType::OpenAck => &[ConnectionState::TryOpen, ConnectionState::Open],
+            ConnectionMsgType::OpenConfirm => &[ConnectionState::Open],
+        };
+
+        if !expected_states
+            .iter()
+            .any(|state| connection_end.state_matches(state))
+        {
+            return Err(Error::bad_connection_state());
+        }
+
+        let connection_proof = self.prove(
+            ConnectionsPath(connection_id.clone()),
+            connection_end.encode_vec(),
+        )?;
+
+        let mut client_state = None;
+        let mut client_proof = None;
+        let mut consensus_proof = None;
+
+        if matches!(
+            message_type,
+            ConnectionMsgType::OpenTry | ConnectionMsgType::OpenAck
+        ) {
+            let (client_state_value, _) = self.query_client_state(
+                QueryClientStateRequest {
+                    client_id: client_id.clone(),
+                    height: QueryHeight::Specific(height),
+                },
+                IncludeProof::No,
+            )?;
+
+            if self.config.connection_client_proofs {
+                let consensus_height = client_state_value.latest_height();
+
+                let (consensus_state, _) = self.query_consensus_state(
+                    QueryConsensusStateRequest {
+                        client_id: client_id.clone(),
+                        consensus_height,
+                        query_height: QueryHeight::Specific(height),
+                    },
+                    IncludeProof::No,
+                )?;
+
+                client_proof = Some(self.prove(
+                    ClientStatePath(client_id.clone()),
+                    client_state_value.clone().encode_vec(),
+                )?);
+
+                consensus_proof = Some(
+                    ConsensusProof::new(
+                        self.prove(
+                            consensus_state_path(client_id, consensus_height),
+                            consensus_state.encode_vec(),
+                        )?,
+                        consensus_height,
+                    )
+                    .map_err(Error::consensus_proof)?,
+                );
+            }
+
+            client_state = Some(client_state_value);
+        }
+
+        Ok((
+            client_state,
+            Proofs::new(
+                connection_proof,
+                client_proof,
+                consensus_proof,
+                None,
+                None,
+                height.increment(),
+            )
+            .map_err(Error::malformed_proof)?,
+        ))
+    }
+
+    fn build_channel_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: ICSHeight,
+    ) -> Result<Proofs, Error> {
+        let (channel_end, _) = self.query_channel(
+            QueryChannelRequest {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                height: QueryHeight::Specific(height),
+            },
+            IncludeProof::No,
+        )?;
+
+        let channel_proof = self.prove(
+            ChannelEndsPath(port_id.clone(), channel_id.clone()),
+            channel_end.encode_vec(),
+        )?;
+
+        Proofs::new(channel_proof, None, None, None, None, height.increment())
+            .map_err(Error::malformed_proof)
+    }
+
+    /// Signs the proofs in the order in which the counterparty verifies them,
+    /// that is the channel before the packet for timeouts on close.
+    fn build_packet_proofs(
+        &self,
+        packet_type: PacketMsgType,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        height: ICSHeight,
+    ) -> Result<Proofs, Error> {
+        let channel_proof = match packet_type {
+            PacketMsgType::TimeoutOnCloseUnordered | PacketMsgType::TimeoutOnCloseOrdered => {
+                let channel_end = self
+                    .store
+                    .channel(&port_id, &channel_id)
+                    .map_err(Error::solo_machine)?;
+
+                Some(self.prove(
+                    ChannelEndsPath(port_id.clone(), channel_id.clone()),
+                    channel_end.encode_vec(),
+                )?)
+            }
+            _ => None,
+        };
+
+        let packet_path: IbcPath = match packet_type {
+            PacketMsgType::Recv => CommitmentsPath {
+                port_id,
+                channel_id,
+                sequence,
+            }
+            .into(),
+            PacketMsgType::Ack => AcksPath {
+                port_id,
+                channel_id,
+                sequence,
+            }
+            .into(),
+            PacketMsgType::TimeoutUnordered | PacketMsgType::TimeoutOnCloseUnordered => {
+                ReceiptsPath {
+                    port_id,
+                    channel_id,
+                    sequence,
+                }
+                .into()
+            }
+            PacketMsgType::TimeoutOrdered | PacketMsgType::TimeoutOnCloseOrdered => {
+                SeqRecvsPath(port_id, channel_id).into()
+            }
+        };
+
+        // Timeouts of unordered packets prove the absence of their receipt
+        let value = self.store.get(packet_path.clone());
+
+        if value.is_empty() && matches!(packet_type, PacketMsgType::Recv | PacketMsgType::Ack) {
+            return Err(Error::queried_proof_not_found());
+        }
+
+        let packet_proof = self.prove(packet_path, value)?;
+
+        Proofs::new(
+            packet_proof,
+            None,
+            None,
+            None,
+            channel_proof,
+            height.increment(),
+        )
+        .map_err(Error::malformed_proof)
+    }
+
+    fn maybe_register_counterparty_payee(
+        &mut self,
+        _channel_id: &ChannelId,
+        _port_id: &PortId,
+        _counterparty_payee: &Signer,
+    ) -> Result<(), Error> {
+        // The solo machine has no fee middleware, so there is no payee to register
+        Ok(())
+    }
+
+    fn cross_chain_query(
+        &self,
+        _requests: Vec<CrossChainQueryRequest>,
+    ) -> Result<Vec<CrossChainQueryResponse>, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "cross_chain_query".to_string(),
+        ))
+    }
+
+    fn query_incentivized_packet(
+        &self,
+        _request: QueryIncentivizedPacketRequest,
+    ) -> Result<QueryIncentivizedPacketResponse, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_incentivized_packet".to_string(),
+        ))
+    }
+
+    fn query_consumer_chains(&self) -> Result<Vec<ConsumerChain>, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_consumer_chains".to_string(),
+        ))
+    }
+
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        // The solo machine has no Cosmos SDK upgrade module
+        Ok(None)
+    }
+
+    fn query_upgrade(
+        &self,
+        _request: QueryUpgradeRequest,
+        _height: ICSHeight,
+        _include_proof: IncludeProof,
+    ) -> Result<(Upgrade, Option<MerkleProof>), Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_upgrade".to_string(),
+        ))
+    }
+
+    fn query_upgrade_error(
+        &self,
+        _request: QueryUpgradeErrorRequest,
+        _height: ICSHeight,
+        _include_proof: IncludeProof,
+    ) -> Result<(ErrorReceipt, Option<MerkleProof>), Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_upgrade_error".to_string(),
+        ))
+    }
+
+    fn query_ccv_consumer_id(&self, _client_id: ClientId) -> Result<ConsumerId, Error> {
+        Err(Error::query_unsupported(
+            self.config.id.clone(),
+            "query_ccv_consumer_id".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,77 @@
+use core::time::Duration;
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde_derive::{Deserialize, Serialize};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::config::{default, schema, PacketFilter};
+use crate::keyring::Store;
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SoloMachineConfig {
+    /// The identifier of the solo machine, which doubles as its diversifier
+    #[schemars(schema_with = "schema::chain_id")]
+    pub id: ChainId,
+
+    /// The prefix of the address of the solo machine key, eg. `cosmos`
+    pub account_prefix: String,
+    /// The name of the key the solo machine signs with
+    pub key_name: String,
+    /// The type of keystore holding the key
+    #[serde(default)]
+    pub key_store_type: Store,
+    /// The folder of the keystore, defaults to `$HOME/.hermes/keys`
+    pub key_store_folder: Option<PathBuf>,
+
+    /// The folder in which the solo machine keeps its sequence and its IBC store.
+    ///
+    /// The solo machine can only be run by a single Hermes instance at a time,
+    /// since the client tracking it rejects any signature over a sequence it has
+    /// already seen.
+    pub state_dir: PathBuf,
+
+    /// Whether to sign the client and consensus state proofs of the connection handshake.
+    ///
+    /// Must be disabled when the counterparty runs ibc-go v9 or later, which no longer
+    /// verifies these proofs, as every signature consumes a sequence of the solo machine.
+    #[serde(default = "default::connection_client_proofs")]
+    pub connection_client_proofs: bool,
+
+    /// Which packets to relay
+    #[serde(default)]
+    pub packet_filter: PacketFilter,
+    /// Interval, in number of blocks, at which pending packets are cleared,
+    /// overriding `mode.packets.clear_interval`
+    pub clear_interval: Option<u64>,
+    /// How many packets to fetch at once from the solo machine when clearing packets
+    #[serde(default = "default::query_packets_chunk_size")]
+    pub query_packets_chunk_size: usize,
+
+    /// The maximum time between two heights of the solo machine, whose height
+    /// increases by one every second
+    #[serde(default = "default::max_block_time", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
+    pub max_block_time: Duration,
+
+    /// A correction parameter that helps deal with clocks that are only approximately synchronized
+    /// between the source and destination chains for a client.
+    /// This parameter is used when deciding to accept or reject a new header
+    /// (originating from the source chain) for any client with the solo machine,
+    /// unless it is overridden by the client-specific clock drift option.
+    #[serde(default = "default::clock_drift", with = "humantime_serde")]
+    #[schemars(schema_with = "schema::duration")]
+    pub clock_drift: Duration,
+
+    /// Set by `hermes start --dry-run`. The solo machine does not support dry-run mode,
+    /// so no message is applied to it while it is set.
+    #[serde(skip)]
+    pub dry_run: bool,
+
+    /// Set by `hermes --generate-only`. The solo machine does not support generate-only mode,
+    /// so no message is applied to it while it is set.
+    #[serde(skip)]
+    pub generate_only: bool,
+}
@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use flex_error::{define_error, TraceError};
+use tendermint_proto::Error as TendermintProtoError;
+
+use ibc_relayer_types::clients::ics06_solomachine::error::Error as SmError;
+use ibc_relayer_types::clients::ics07_tendermint::error::Error as TendermintClientError;
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics03_connection::error::Error as ConnectionError;
+use ibc_relayer_types::proofs::ProofError;
+
+use crate::keyring::errors::Error as KeyringError;
+
+define_error! {
+    Error {
+        Io
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            |e| { format!("failed to access the solo machine state file {}", e.path.display()) },
+
+        Encode
+            { path: PathBuf }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("failed to encode or decode the solo machine state file {}", e.path.display()) },
+
+        Key
+            { key_name: String }
+            [ KeyringError ]
+            |e| { format!("failed to load solo machine key {}", e.key_name) },
+
+        Sign
+            [ KeyringError ]
+            |_| { "failed to sign with the solo machine key" },
+
+        PublicKeyMismatch
+            { path: PathBuf }
+            |e| { format!("the key of the solo machine does not match the public key recorded in state file {}", e.path.display()) },
+
+        SoloMachine
+            [ SmError ]
+            |_| { "invalid solo machine state" },
+
+        Proof
+            [ ProofError ]
+            |_| { "failed to build solo machine proof" },
+
+        CorruptStore
+            { path: String }
+            [ TendermintProtoError ]
+            |e| { format!("failed to decode the value stored at path {} of the solo machine store", e.path) },
+
+        DecodeMessage
+            { type_url: String }
+            [ TendermintProtoError ]
+            |e| { format!("failed to decode message of type {}", e.type_url) },
+
+        UnsupportedMessage
+            { type_url: String }
+            |e| { format!("the solo machine does not support messages of type {}", e.type_url) },
+
+        InvalidMessage
+            { type_url: String, reason: String }
+            |e| { format!("invalid message of type {}: {}", e.type_url, e.reason) },
+
+        Client
+            [ ClientError ]
+            |_| { "invalid client message" },
+
+        TendermintClient
+            [ TendermintClientError ]
+            |_| { "failed to update Tendermint client" },
+
+        Connection
+            [ ConnectionError ]
+            |_| { "invalid connection message" },
+
+        UpdateUnsupported
+            |_| { "clients of a solo machine are never updated" },
+    }
+}
@@ -0,0 +1,205 @@
+//! The signer of a solo machine, backed by a secp256k1 key of the keyring.
+//!
+//! The solo machine signs proofs over the sequence of the client tracking it on the
+//! counterparty chain. Since that client only accepts each sequence once, the sequence
+//! and timestamp of the solo machine are persisted in a local state file after every
+//! signature, so that a restarted solo machine carries on from where it left off
+//! instead of producing signatures the client would reject.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer_types::clients::ics06_solomachine::client_state::ClientState as SmClientState;
+use ibc_relayer_types::clients::ics06_solomachine::consensus_state::ConsensusState as SmConsensusState;
+use ibc_relayer_types::clients::ics06_solomachine::proof::{
+    membership_sign_bytes, TimestampedSignature,
+};
+use ibc_relayer_types::clients::ics06_solomachine::public_key::PublicKey;
+use ibc_relayer_types::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes,
+};
+use ibc_relayer_types::core::ics23_commitment::merkle::apply_prefix;
+use ibc_relayer_types::core::ics24_host::path::Path as IbcPath;
+use ibc_relayer_types::timestamp::Timestamp;
+
+use crate::chain::solomachine::error::Error;
+use crate::chain::solomachine::store::{read_state_file, write_state_file};
+use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair};
+
+/// The state of a solo machine, as persisted in its state file.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignerState {
+    /// The sequence the next signature of the solo machine will be made over.
+    pub sequence: u64,
+    pub timestamp: Timestamp,
+    pub diversifier: String,
+    pub public_key: PublicKey,
+}
+
+/// The signer of a solo machine, which signs with a key of the keyring.
+pub struct Signer {
+    key: Secp256k1KeyPair,
+    state: SignerState,
+    state_file: PathBuf,
+}
+
+impl Signer {
+    /// Load the signer whose state is stored in `state_file`, signing with
+    /// the key `key_name` of the given keyring.
+    ///
+    /// If the state file does not exist yet, a fresh signer is initialized
+    /// with the given diversifier at sequence 1, and its state is written to the file.
+    pub fn load_or_init(
+        keyring: &KeyRing<Secp256k1KeyPair>,
+        key_name: &str,
+        diversifier: &str,
+        state_file: &Path,
+    ) -> Result<Self, Error> {
+        let key = keyring
+            .get_key(key_name)
+            .map_err(|e| Error::key(key_name.to_string(), e))?;
+
+        let public_key = public_key_of(&key)?;
+
+        let state = match read_state_file::<SignerState>(state_file)? {
+            Some(state) if state.public_key != public_key => {
+                return Err(Error::public_key_mismatch(state_file.to_path_buf()));
+            }
+            Some(state) => state,
+            None => SignerState {
+                sequence: 1,
+                timestamp: Timestamp::now(),
+                diversifier: diversifier.to_string(),
+                public_key,
+            },
+        };
+
+        let signer = Self {
+            key,
+            state,
+            state_file: state_file.to_path_buf(),
+        };
+
+        signer.persist()?;
+
+        Ok(signer)
+    }
+
+    pub fn state(&self) -> &SignerState {
+        &self.state
+    }
+
+    /// The consensus state to create or check a client of this solo machine with.
+    pub fn consensus_state(&self) -> SmConsensusState {
+        SmConsensusState::new(
+            self.state.public_key.clone(),
+            self.state.diversifier.clone(),
+            self.state.timestamp,
+        )
+    }
+
+    /// The client state to create a client of this solo machine with.
+    pub fn client_state(&self) -> Result<SmClientState, Error> {
+        SmClientState::new(self.state.sequence, self.consensus_state()).map_err(Error::solo_machine)
+    }
+
+    /// Sign a proof that `value` is stored at `path` under the given prefix,
+    /// or that nothing is stored at `path` if `value` is empty.
+    ///
+    /// The proof consumes the current sequence of the solo machine.
+    pub fn prove(
+        &mut self,
+        prefix: &CommitmentPrefix,
+        path: IbcPath,
+        value: Vec<u8>,
+    ) -> Result<CommitmentProofBytes, Error> {
+        let timestamp = self.next_timestamp();
+        let merkle_path = apply_prefix(prefix, vec![path.to_string()]);
+
+        let sign_bytes = membership_sign_bytes(
+            self.state.sequence,
+            timestamp,
+            &self.state.diversifier,
+            merkle_path,
+            value,
+        );
+        let signature = self.key.sign(&sign_bytes).map_err(Error::sign)?;
+
+        self.state.sequence += 1;
+        self.state.timestamp = timestamp;
+        self.persist()?;
+
+        TimestampedSignature::new(signature, timestamp)
+            .into_proof()
+            .map_err(Error::proof)
+    }
+
+    /// The client rejects signatures with a timestamp lower than that of its consensus state,
+    /// so never go back in time even if the local clock does.
+    fn next_timestamp(&self) -> Timestamp {
+        let now = Timestamp::now();
+
+        if now.after(&self.state.timestamp) {
+            now
+        } else {
+            self.state.timestamp
+        }
+    }
+
+    fn persist(&self) -> Result<(), Error> {
+        write_state_file(&self.state_file, &self.state)
+    }
+}
+
+fn public_key_of(key: &Secp256k1KeyPair) -> Result<PublicKey, Error> {
+    PublicKey::from_secp256k1_bytes(&key.public_key.serialize()).map_err(Error::solo_machine)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use hdpath::StandardHDPath;
+    use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+    use ibc_relayer_types::core::ics24_host::path::{ClientStatePath, Path as IbcPath};
+    use test_log::test;
+
+    use super::Signer;
+    use crate::config::AddressType;
+    use crate::keyring::{KeyRing, Secp256k1KeyPair, SigningKeyPair, Store};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn signer_persists_sequence() {
+        let hd_path = StandardHDPath::from_str("m/44'/118'/0'/0/0").unwrap();
+        let key =
+            Secp256k1KeyPair::from_mnemonic(MNEMONIC, &hd_path, &AddressType::Cosmos, "cosmos")
+                .unwrap();
+
+        let mut keyring = KeyRing::new_secp256k1(
+            Store::Memory,
+            "cosmos",
+            &ChainId::from_string("solo"),
+            &None,
+        )
+        .unwrap();
+        keyring.add_key("solo", key).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let state_file = dir.path().join("signer.json");
+
+        let mut signer = Signer::load_or_init(&keyring, "solo", "solo", &state_file).unwrap();
+        assert_eq!(signer.client_state().unwrap().sequence, 1);
+
+        let prefix = CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap();
+        let path = IbcPath::ClientState(ClientStatePath(ClientId::default()));
+        signer.prove(&prefix, path, vec![1, 2, 3]).unwrap();
+        assert_eq!(signer.state().sequence, 2);
+
+        let reloaded = Signer::load_or_init(&keyring, "solo", "ignored", &state_file).unwrap();
+        assert_eq!(reloaded.state(), signer.state());
+    }
+}
@@ -0,0 +1,1216 @@
+//! The IBC store of a solo machine.
+//!
+//! The solo machine has no application of its own: it applies the IBC messages
+//! submitted to it without verifying their proofs, since it trusts the relayer it
+//! runs in, and keeps the resulting IBC state in a local file. Values are stored
+//! at their ICS-24 path in the encoding used by ibc-go, so that the proofs signed
+//! by the solo machine are over the same bytes as a counterparty would expect.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use prost::Message;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use subtle_encoding::{Encoding, Hex};
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::connection::v1::ClientPaths;
+use ibc_proto::Protobuf;
+use ibc_relayer_types::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+use ibc_relayer_types::core::ics02_client::events::{
+    Attributes as ClientAttributes, CreateClient, UpdateClient,
+};
+use ibc_relayer_types::core::ics02_client::header::{AnyHeader, Header};
+use ibc_relayer_types::core::ics02_client::msgs::{create_client, update_client};
+use ibc_relayer_types::core::ics02_client::msgs::{
+    create_client::MsgCreateClient, update_client::MsgUpdateClient,
+};
+use ibc_relayer_types::core::ics03_connection::connection::{
+    ConnectionEnd, Counterparty as ConnectionCounterparty, IdentifiedConnectionEnd,
+    State as ConnectionState,
+};
+use ibc_relayer_types::core::ics03_connection::events::{
+    self as connection_events, Attributes as ConnectionAttributes,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_ack::{self, MsgConnectionOpenAck};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_confirm::{
+    self, MsgConnectionOpenConfirm,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_init::{
+    self, MsgConnectionOpenInit,
+};
+use ibc_relayer_types::core::ics03_connection::msgs::conn_open_try::{self, MsgConnectionOpenTry};
+use ibc_relayer_types::core::ics03_connection::version::{get_compatible_versions, pick_version};
+use ibc_relayer_types::core::ics04_channel::channel::{
+    ChannelEnd, IdentifiedChannelEnd, Ordering, State as ChannelState, UpgradeState,
+};
+use ibc_relayer_types::core::ics04_channel::events as channel_events;
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_confirm::{
+    self, MsgChannelCloseConfirm,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_close_init::{self, MsgChannelCloseInit};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_ack::{self, MsgChannelOpenAck};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::{
+    self, MsgChannelOpenConfirm,
+};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_init::{self, MsgChannelOpenInit};
+use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::{self, MsgChannelOpenTry};
+use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::{self, MsgRecvPacket};
+use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc_relayer_types::core::ics24_host::path::{
+    AcksPath, ChannelEndsPath, ClientConnectionsPath, ClientConsensusStatePath, ClientStatePath,
+    ConnectionsPath, Path as IbcPath, ReceiptsPath, SeqAcksPath, SeqRecvsPath, SeqSendsPath,
+};
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height;
+
+use crate::chain::solomachine::error::Error;
+use crate::client_state::AnyClientState;
+use crate::consensus_state::AnyConsensusState;
+
+/// The acknowledgement written for every packet received by the solo machine,
+/// which is the successful acknowledgement of ICS-20.
+pub const SUCCESS_ACKNOWLEDGEMENT: &[u8] = br#"{"result":"AQ=="}"#;
+
+/// The IBC state of a solo machine, as persisted in its store file.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IbcStore {
+    /// The time at which the store was created, from which the height of the solo machine derives.
+    genesis_time: Timestamp,
+    next_client_sequence: u64,
+    next_connection_sequence: u64,
+    next_channel_sequence: u64,
+    /// The value stored at every ICS-24 path.
+    values: BTreeMap<String, Value>,
+    /// The acknowledgements written by the solo machine, by path of their commitment,
+    /// which stand in for the transactions a chain would be queried for.
+    acknowledgements: BTreeMap<String, channel_events::WriteAcknowledgement>,
+    /// The height at which every consensus state of the clients hosted by the solo machine
+    /// was added, by client identifier and height of the consensus state.
+    update_heights: BTreeMap<String, Height>,
+}
+
+/// A value of the store, hex-encoded in the store file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Value(Vec<u8>);
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Hex::upper_case()
+            .encode_to_string(&self.0)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+
+        Hex::upper_case()
+            .decode(hex.to_uppercase())
+            .map(Value)
+            .map_err(D::Error::custom)
+    }
+}
+
+impl IbcStore {
+    pub fn new(genesis_time: Timestamp) -> Self {
+        Self {
+            genesis_time,
+            next_client_sequence: 0,
+            next_connection_sequence: 0,
+            next_channel_sequence: 0,
+            values: BTreeMap::new(),
+            acknowledgements: BTreeMap::new(),
+            update_heights: BTreeMap::new(),
+        }
+    }
+
+    /// Load the store from `file`, or create an empty store if the file does not exist yet.
+    pub fn load_or_init(file: &Path) -> Result<Self, Error> {
+        match read_state_file(file)? {
+            Some(store) => Ok(store),
+            None => {
+                let store = Self::new(Timestamp::now());
+                store.persist(file)?;
+                Ok(store)
+            }
+        }
+    }
+
+    pub fn persist(&self, file: &Path) -> Result<(), Error> {
+        write_state_file(file, self)
+    }
+
+    /// The height of the solo machine, which increases by one every second since
+    /// the creation of its store, so that it advances like that of a chain.
+    pub fn height(&self) -> Height {
+        let elapsed = Timestamp::now()
+            .duration_since(&self.genesis_time)
+            .unwrap_or_default();
+
+        Height::new(0, elapsed.as_secs() + 1).expect("the height of the solo machine is never 0")
+    }
+
+    /// The value stored at `path`, which is empty if there is none, as on ibc-go chains.
+    pub fn get(&self, path: impl Into<IbcPath>) -> Vec<u8> {
+        self.values
+            .get(&path.into().to_string())
+            .map(|value| value.0.clone())
+            .unwrap_or_default()
+    }
+
+    fn set(&mut self, path: impl Into<IbcPath>, value: Vec<u8>) {
+        self.values.insert(path.into().to_string(), Value(value));
+    }
+
+    fn contains(&self, path: impl Into<IbcPath>) -> bool {
+        self.values.contains_key(&path.into().to_string())
+    }
+
+    fn decode<T, R>(&self, path: impl Into<IbcPath>) -> Result<Option<T>, Error>
+    where
+        T: Protobuf<R>,
+        R: Message + Default + From<T>,
+        T: TryFrom<R>,
+        <T as TryFrom<R>>::Error: core::fmt::Display,
+    {
+        let path = path.into();
+        let value = self.get(path.clone());
+
+        if value.is_empty() {
+            return Ok(None);
+        }
+
+        T::decode_vec(&value)
+            .map(Some)
+            .map_err(|e| Error::corrupt_store(path.to_string(), e))
+    }
+
+    pub fn client_state(&self, client_id: &ClientId) -> Result<Option<AnyClientState>, Error> {
+        self.decode::<_, Any>(ClientStatePath(client_id.clone()))
+    }
+
+    pub fn consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<Option<AnyConsensusState>, Error> {
+        self.decode::<_, Any>(consensus_state_path(client_id, height))
+    }
+
+    pub fn connection(&self, connection_id: &ConnectionId) -> Result<Option<ConnectionEnd>, Error> {
+        self.decode(ConnectionsPath(connection_id.clone()))
+    }
+
+    /// The channel end with the given identifiers, which is uninitialized if there is none.
+    pub fn channel(&self, port_id: &PortId, channel_id: &ChannelId) -> Result<ChannelEnd, Error> {
+        Ok(self
+            .decode(ChannelEndsPath(port_id.clone(), channel_id.clone()))?
+            .unwrap_or_default())
+    }
+
+    pub fn next_sequence_recv(&self, port_id: &PortId, channel_id: &ChannelId) -> Sequence {
+        decode_sequence(&self.get(SeqRecvsPath(port_id.clone(), channel_id.clone())))
+    }
+
+    pub fn clients(&self) -> Result<Vec<(ClientId, AnyClientState)>, Error> {
+        self.paths()
+            .filter_map(|path| match path {
+                IbcPath::ClientState(ClientStatePath(client_id)) => Some(client_id),
+                _ => None,
+            })
+            .map(|client_id| {
+                let client_state = self
+                    .client_state(&client_id)?
+                    .expect("client states are listed from the store");
+                Ok((client_id, client_state))
+            })
+            .collect()
+    }
+
+    pub fn consensus_state_heights(&self, client_id: &ClientId) -> Vec<Height> {
+        self.paths()
+            .filter_map(|path| match path {
+                IbcPath::ClientConsensusState(path) if &path.client_id == client_id => {
+                    Height::new(path.epoch, path.height).ok()
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn connections(&self) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.paths()
+            .filter_map(|path| match path {
+                IbcPath::Connections(ConnectionsPath(connection_id)) => Some(connection_id),
+                _ => None,
+            })
+            .map(|connection_id| {
+                let connection_end = self
+                    .connection(&connection_id)?
+                    .expect("connections are listed from the store");
+                Ok(IdentifiedConnectionEnd::new(connection_id, connection_end))
+            })
+            .collect()
+    }
+
+    pub fn client_connections(&self, client_id: &ClientId) -> Result<Vec<ConnectionId>, Error> {
+        Ok(self
+            .connections()?
+            .into_iter()
+            .filter(|connection| connection.end().client_id() == client_id)
+            .map(|connection| connection.connection_id)
+            .collect())
+    }
+
+    pub fn channels(&self) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.paths()
+            .filter_map(|path| match path {
+                IbcPath::ChannelEnds(ChannelEndsPath(port_id, channel_id)) => {
+                    Some((port_id, channel_id))
+                }
+                _ => None,
+            })
+            .map(|(port_id, channel_id)| {
+                let channel_end = self.channel(&port_id, &channel_id)?;
+                Ok(IdentifiedChannelEnd::new(port_id, channel_id, channel_end))
+            })
+            .collect()
+    }
+
+    /// The sequences of the packets received on the given channel, whose
+    /// acknowledgement is stored by the solo machine.
+    pub fn acknowledgement_sequences(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Vec<Sequence> {
+        self.paths()
+            .filter_map(|path| match path {
+                IbcPath::Acks(path)
+                    if &path.port_id == port_id && &path.channel_id == channel_id =>
+                {
+                    Some(path.sequence)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Whether the solo machine received the packet with the given sequence,
+    /// on a channel of either ordering.
+    pub fn packet_received(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<bool, Error> {
+        let channel = self.channel(port_id, channel_id)?;
+
+        Ok(match channel.ordering() {
+            Ordering::Ordered => sequence < self.next_sequence_recv(port_id, channel_id),
+            _ => self.contains(ReceiptsPath {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+                sequence,
+            }),
+        })
+    }
+
+    /// The acknowledgement written by the solo machine for the packet with the given sequence.
+    pub fn acknowledgement(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Option<&channel_events::WriteAcknowledgement> {
+        let path = IbcPath::from(AcksPath {
+            port_id: port_id.clone(),
+            channel_id: channel_id.clone(),
+            sequence,
+        });
+
+        self.acknowledgements.get(&path.to_string())
+    }
+
+    /// The height at which the consensus state at `consensus_height` was added to the client.
+    pub fn update_height(&self, client_id: &ClientId, consensus_height: Height) -> Option<Height> {
+        self.update_heights
+            .get(&consensus_state_path(client_id, consensus_height).to_string())
+            .copied()
+    }
+
+    fn paths(&self) -> impl Iterator<Item = IbcPath> + '_ {
+        self.values.keys().filter_map(|path| path.parse().ok())
+    }
+
+    /// Apply a message to the store, returning the events it emits.
+    pub fn deliver(&mut self, msg: &Any) -> Result<Vec<IbcEvent>, Error> {
+        match msg.type_url.as_str() {
+            create_client::TYPE_URL => self.create_client(decode_msg(msg)?),
+            update_client::TYPE_URL => self.update_client(decode_msg(msg)?),
+            conn_open_init::TYPE_URL => self.conn_open_init(decode_msg(msg)?),
+            conn_open_try::TYPE_URL => self.conn_open_try(decode_msg(msg)?),
+            conn_open_ack::TYPE_URL => self.conn_open_ack(decode_msg(msg)?),
+            conn_open_confirm::TYPE_URL => self.conn_open_confirm(decode_msg(msg)?),
+            chan_open_init::TYPE_URL => self.chan_open_init(decode_msg(msg)?),
+            chan_open_try::TYPE_URL => self.chan_open_try(decode_msg(msg)?),
+            chan_open_ack::TYPE_URL => self.chan_open_ack(decode_msg(msg)?),
+            chan_open_confirm::TYPE_URL => self.chan_open_confirm(decode_msg(msg)?),
+            chan_close_init::TYPE_URL => self.chan_close_init(decode_msg(msg)?),
+            chan_close_confirm::TYPE_URL => self.chan_close_confirm(decode_msg(msg)?),
+            recv_packet::TYPE_URL => self.recv_packet(decode_msg(msg)?),
+            type_url => Err(Error::unsupported_message(type_url.to_string())),
+        }
+    }
+
+    fn create_client(&mut self, msg: MsgCreateClient) -> Result<Vec<IbcEvent>, Error> {
+        let client_state =
+            AnyClientState::try_from(msg.client_state.clone()).map_err(Error::client)?;
+        AnyConsensusState::try_from(msg.consensus_state.clone()).map_err(Error::client)?;
+
+        let client_type = client_state.client_type();
+        let height = client_state.latest_height();
+        let client_id = ClientId::new(client_type, self.next_client_sequence).map_err(|e| {
+            Error::invalid_message(create_client::TYPE_URL.to_string(), e.to_string())
+        })?;
+        self.next_client_sequence += 1;
+
+        self.set(
+            ClientStatePath(client_id.clone()),
+            msg.client_state.encode_to_vec(),
+        );
+        self.set(
+            consensus_state_path(&client_id, height),
+            msg.consensus_state.encode_to_vec(),
+        );
+
+        Ok(vec![IbcEvent::CreateClient(CreateClient(
+            ClientAttributes {
+                client_id,
+                client_type,
+                consensus_height: height,
+            },
+        ))])
+    }
+
+    fn update_client(&mut self, msg: MsgUpdateClient) -> Result<Vec<IbcEvent>, Error> {
+        let invalid = |reason: &str| {
+            Error::invalid_message(update_client::TYPE_URL.to_string(), reason.to_string())
+        };
+
+        let client_state = self
+            .client_state(&msg.client_id)?
+            .ok_or_else(|| invalid("client not found"))?;
+        let header = AnyHeader::try_from(msg.header).map_err(Error::client)?;
+
+        let (AnyClientState::Tendermint(client_state), AnyHeader::Tendermint(tm_header)) =
+            (client_state, header.clone())
+        else {
+            return Err(invalid("only Tendermint clients can be updated"));
+        };
+
+        let height = header.height();
+
+        if height > client_state.latest_height() {
+            let client_state = client_state
+                .with_header(tm_header.clone())
+                .map_err(Error::tendermint_client)?;
+
+            self.set(
+                ClientStatePath(msg.client_id.clone()),
+                AnyClientState::from(client_state).encode_vec(),
+            );
+        }
+
+        let path = consensus_state_path(&msg.client_id, height);
+        let update_height = self.height();
+        self.update_heights
+            .entry(path.to_string())
+            .or_insert(update_height);
+        self.set(
+            path,
+            AnyConsensusState::Tendermint(TmConsensusState::from(tm_header)).encode_vec(),
+        );
+
+        Ok(vec![IbcEvent::UpdateClient(UpdateClient {
+            common: ClientAttributes {
+                client_id: msg.client_id,
+                client_type: header.client_type(),
+                consensus_height: height,
+            },
+            header: Some(header),
+        })])
+    }
+
+    fn conn_open_init(&mut self, msg: MsgConnectionOpenInit) -> Result<Vec<IbcEvent>, Error> {
+        if !self.contains(ClientStatePath(msg.client_id.clone())) {
+            return Err(Error::invalid_message(
+                conn_open_init::TYPE_URL.to_string(),
+                format!("client {} not found", msg.client_id),
+            ));
+        }
+
+        let versions = match msg.version {
+            Some(version) => vec![version],
+            None => get_compatible_versions(),
+        };
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::Init,
+            msg.client_id,
+            msg.counterparty,
+            versions,
+            msg.delay_period,
+        );
+
+        let connection_id = self.add_connection(connection_end.clone());
+
+        Ok(vec![IbcEvent::OpenInitConnection(
+            connection_events::OpenInit(connection_attributes(connection_id, &connection_end)),
+        )])
+    }
+
+    fn conn_open_try(&mut self, msg: MsgConnectionOpenTry) -> Result<Vec<IbcEvent>, Error> {
+        if !self.contains(ClientStatePath(msg.client_id.clone())) {
+            return Err(Error::invalid_message(
+                conn_open_try::TYPE_URL.to_string(),
+                format!("client {} not found", msg.client_id),
+            ));
+        }
+
+        let version = pick_version(get_compatible_versions(), msg.counterparty_versions)
+            .map_err(Error::connection)?;
+
+        let connection_end = ConnectionEnd::new(
+            ConnectionState::TryOpen,
+            msg.client_id,
+            msg.counterparty,
+            vec![version],
+            msg.delay_period,
+        );
+
+        let connection_id = self.add_connection(connection_end.clone());
+
+        Ok(vec![IbcEvent::OpenTryConnection(
+            connection_events::OpenTry(connection_attributes(connection_id, &connection_end)),
+        )])
+    }
+
+    fn conn_open_ack(&mut self, msg: MsgConnectionOpenAck) -> Result<Vec<IbcEvent>, Error> {
+        let mut connection_end = self.connection_in_state(
+            conn_open_ack::TYPE_URL,
+            &msg.connection_id,
+            ConnectionState::Init,
+        )?;
+
+        let counterparty = ConnectionCounterparty::new(
+            connection_end.counterparty().client_id().clone(),
+            Some(msg.counterparty_connection_id),
+            connection_end.counterparty().prefix().clone(),
+        );
+
+        connection_end.set_state(ConnectionState::Open);
+        connection_end.set_counterparty(counterparty);
+        connection_end.set_version(msg.version);
+
+        self.set(
+            ConnectionsPath(msg.connection_id.clone()),
+            connection_end.clone().encode_vec(),
+        );
+
+        Ok(vec![IbcEvent::OpenAckConnection(
+            connection_events::OpenAck(connection_attributes(msg.connection_id, &connection_end)),
+        )])
+    }
+
+    fn conn_open_confirm(&mut self, msg: MsgConnectionOpenConfirm) -> Result<Vec<IbcEvent>, Error> {
+        let mut connection_end = self.connection_in_state(
+            conn_open_confirm::TYPE_URL,
+            &msg.connection_id,
+            ConnectionState::TryOpen,
+        )?;
+
+        connection_end.set_state(ConnectionState::Open);
+
+        self.set(
+            ConnectionsPath(msg.connection_id.clone()),
+            connection_end.clone().encode_vec(),
+        );
+
+        Ok(vec![IbcEvent::OpenConfirmConnection(
+            connection_events::OpenConfirm(connection_attributes(
+                msg.connection_id,
+                &connection_end,
+            )),
+        )])
+    }
+
+    fn add_connection(&mut self, connection_end: ConnectionEnd) -> ConnectionId {
+        let connection_id = ConnectionId::new(self.next_connection_sequence);
+        self.next_connection_sequence += 1;
+
+        let client_connections_path = ClientConnectionsPath(connection_end.client_id().clone());
+        let mut client_connections =
+            ClientPaths::decode(self.get(client_connections_path.clone()).as_slice())
+                .unwrap_or_default();
+        client_connections.paths.push(connection_id.to_string());
+
+        self.set(client_connections_path, client_connections.encode_to_vec());
+        self.set(
+            ConnectionsPath(connection_id.clone()),
+            connection_end.encode_vec(),
+        );
+
+        connection_id
+    }
+
+    fn connection_in_state(
+        &self,
+        type_url: &str,
+        connection_id: &ConnectionId,
+        state: ConnectionState,
+    ) -> Result<ConnectionEnd, Error> {
+        match self.connection(connection_id)? {
+            Some(connection_end) if connection_end.state_matches(&state) => Ok(connection_end),
+            Some(connection_end) => Err(Error::invalid_message(
+                type_url.to_string(),
+                format!(
+                    "connection {} is in state {}, expected {}",
+                    connection_id,
+                    connection_end.state(),
+                    state
+                ),
+            )),
+            None => Err(Error::invalid_message(
+                type_url.to_string(),
+                format!("connection {connection_id} not found"),
+            )),
+        }
+    }
+
+    fn chan_open_init(&mut self, msg: MsgChannelOpenInit) -> Result<Vec<IbcEvent>, Error> {
+        let connection_id = self.channel_connection(chan_open_init::TYPE_URL, &msg.channel)?;
+        let channel_id = self.add_channel(&msg.port_id, msg.channel.clone());
+
+        Ok(vec![IbcEvent::OpenInitChannel(channel_events::OpenInit {
+            port_id: msg.port_id,
+            channel_id: Some(channel_id),
+            connection_id,
+            counterparty_port_id: msg.channel.counterparty().port_id().clone(),
+            counterparty_channel_id: None,
+        })])
+    }
+
+    fn chan_open_try(&mut self, msg: MsgChannelOpenTry) -> Result<Vec<IbcEvent>, Error> {
+        let connection_id = self.channel_connection(chan_open_try::TYPE_URL, &msg.channel)?;
+
+        // Like ICS-20, accept the version proposed by the counterparty
+        let mut channel_end = msg.channel;
+        channel_end.set_state(ChannelState::TryOpen);
+        channel_end.set_version(msg.counterparty_version);
+
+        let channel_id = self.add_channel(&msg.port_id, channel_end.clone());
+
+        Ok(vec![IbcEvent::OpenTryChannel(channel_events::OpenTry {
+            port_id: msg.port_id,
+            channel_id: Some(channel_id),
+            connection_id,
+            counterparty_port_id: channel_end.counterparty().port_id().clone(),
+            counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+        })])
+    }
+
+    fn chan_open_ack(&mut self, msg: MsgChannelOpenAck) -> Result<Vec<IbcEvent>, Error> {
+        let mut channel_end = self.channel_in_state(
+            chan_open_ack::TYPE_URL,
+            &msg.port_id,
+            &msg.channel_id,
+            ChannelState::Init,
+        )?;
+
+        channel_end.set_state(ChannelState::Open(UpgradeState::NotUpgrading));
+        channel_end.set_counterparty_channel_id(msg.counterparty_channel_id.clone());
+        channel_end.set_version(msg.counterparty_version);
+
+        self.set(
+            ChannelEndsPath(msg.port_id.clone(), msg.channel_id.clone()),
+            channel_end.clone().encode_vec(),
+        );
+
+        Ok(vec![IbcEvent::OpenAckChannel(channel_events::OpenAck {
+            port_id: msg.port_id,
+            channel_id: Some(msg.channel_id),
+            counterparty_channel_id: Some(msg.counterparty_channel_id),
+            connection_id: channel_end.connection_hops()[0].clone(),
+            counterparty_port_id: channel_end.counterparty().port_id().clone(),
+        })])
+    }
+
+    fn chan_open_confirm(&mut self, msg: MsgChannelOpenConfirm) -> Result<Vec<IbcEvent>, Error> {
+        let mut channel_end = self.channel_in_state(
+            chan_open_confirm::TYPE_URL,
+            &msg.port_id,
+            &msg.channel_id,
+            ChannelState::TryOpen,
+        )?;
+
+        channel_end.set_state(ChannelState::Open(UpgradeState::NotUpgrading));
+
+        self.set(
+            ChannelEndsPath(msg.port_id.clone(), msg.channel_id.clone()),
+            channel_end.clone().encode_vec(),
+        );
+
+        Ok(vec![IbcEvent::OpenConfirmChannel(
+            channel_events::OpenConfirm {
+                port_id: msg.port_id,
+                channel_id: Some(msg.channel_id),
+                connection_id: channel_end.connection_hops()[0].clone(),
+                counterparty_port_id: channel_end.counterparty().port_id().clone(),
+                counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+            },
+        )])
+    }
+
+    fn chan_close_init(&mut self, msg: MsgChannelCloseInit) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end =
+            self.close_channel(chan_close_init::TYPE_URL, &msg.port_id, &msg.channel_id)?;
+
+        Ok(vec![IbcEvent::CloseInitChannel(
+            channel_events::CloseInit {
+                port_id: msg.port_id,
+                channel_id: msg.channel_id,
+                connection_id: channel_end.connection_hops()[0].clone(),
+                counterparty_port_id: channel_end.counterparty().port_id().clone(),
+                counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+            },
+        )])
+    }
+
+    fn chan_close_confirm(&mut self, msg: MsgChannelCloseConfirm) -> Result<Vec<IbcEvent>, Error> {
+        let channel_end =
+            self.close_channel(chan_close_confirm::TYPE_URL, &msg.port_id, &msg.channel_id)?;
+
+        Ok(vec![IbcEvent::CloseConfirmChannel(
+            channel_events::CloseConfirm {
+                channel_id: Some(msg.channel_id),
+                port_id: msg.port_id,
+                connection_id: channel_end.connection_hops()[0].clone(),
+                counterparty_port_id: channel_end.counterparty().port_id().clone(),
+                counterparty_channel_id: channel_end.counterparty().channel_id().cloned(),
+            },
+        )])
+    }
+
+    fn recv_packet(&mut self, msg: MsgRecvPacket) -> Result<Vec<IbcEvent>, Error> {
+        let invalid =
+            |reason: String| Error::invalid_message(recv_packet::TYPE_URL.to_string(), reason);
+
+        let packet = msg.packet;
+        let (port_id, channel_id) = (&packet.destination_port, &packet.destination_channel);
+
+        let channel_end = self.channel(port_id, channel_id)?;
+
+        if !channel_end.is_open() {
+            return Err(invalid(format!("channel {channel_id} is not open")));
+        }
+
+        if channel_end.counterparty().port_id() != &packet.source_port
+            || channel_end.counterparty().channel_id() != Some(&packet.source_channel)
+        {
+            return Err(invalid(format!(
+                "packet was not sent on the counterparty of channel {channel_id}"
+            )));
+        }
+
+        if packet.timed_out(&Timestamp::now(), self.height()) {
+            return Err(invalid(format!("packet {} has timed out", packet.sequence)));
+        }
+
+        // Like ibc-go, receiving a packet twice is a no-op
+        if self.packet_received(port_id, channel_id, packet.sequence)? {
+            return Ok(vec![]);
+        }
+
+        if channel_end.order_matches(&Ordering::Ordered) {
+            let next_sequence_recv = self.next_sequence_recv(port_id, channel_id);
+
+            if packet.sequence != next_sequence_recv {
+                return Err(invalid(format!(
+                    "packet {} received out of order, expected {}",
+                    packet.sequence, next_sequence_recv
+                )));
+            }
+
+            self.set(
+                SeqRecvsPath(port_id.clone(), channel_id.clone()),
+                encode_sequence(next_sequence_recv.increment()),
+            );
+        } else {
+            self.set(
+                ReceiptsPath {
+                    port_id: port_id.clone(),
+                    channel_id: channel_id.clone(),
+                    sequence: packet.sequence,
+                },
+                vec![1],
+            );
+        }
+
+        self.write_acknowledgement(&packet, SUCCESS_ACKNOWLEDGEMENT.to_vec());
+
+        Ok(vec![
+            IbcEvent::ReceivePacket(channel_events::ReceivePacket {
+                packet: packet.clone(),
+            }),
+            IbcEvent::WriteAcknowledgement(channel_events::WriteAcknowledgement {
+                packet,
+                ack: SUCCESS_ACKNOWLEDGEMENT.to_vec(),
+            }),
+        ])
+    }
+
+    fn write_acknowledgement(&mut self, packet: &Packet, ack: Vec<u8>) {
+        let path = AcksPath {
+            port_id: packet.destination_port.clone(),
+            channel_id: packet.destination_channel.clone(),
+            sequence: packet.sequence,
+        };
+
+        self.set(path.clone(), Sha256::digest(&ack).to_vec());
+        self.acknowledgements.insert(
+            IbcPath::from(path).to_string(),
+            channel_events::WriteAcknowledgement {
+                packet: packet.clone(),
+                ack,
+            },
+        );
+    }
+
+    /// The connection of a channel to be opened, which must exist.
+    fn channel_connection(
+        &self,
+        type_url: &str,
+        channel_end: &ChannelEnd,
+    ) -> Result<ConnectionId, Error> {
+        let connection_id = match channel_end.connection_hops().as_slice() {
+            [connection_id] => connection_id.clone(),
+            _ => {
+                return Err(Error::invalid_message(
+                    type_url.to_string(),
+                    "channels must have exactly one connection hop".to_string(),
+                ))
+            }
+        };
+
+        if self.connection(&connection_id)?.is_none() {
+            return Err(Error::invalid_message(
+                type_url.to_string(),
+                format!("connection {connection_id} not found"),
+            ));
+        }
+
+        Ok(connection_id)
+    }
+
+    fn add_channel(&mut self, port_id: &PortId, channel_end: ChannelEnd) -> ChannelId {
+        let channel_id = ChannelId::new(self.next_channel_sequence);
+        self.next_channel_sequence += 1;
+
+        let first_sequence = encode_sequence(Sequence::from(1));
+        self.set(
+            SeqSendsPath(port_id.clone(), channel_id.clone()),
+            first_sequence.clone(),
+        );
+        self.set(
+            SeqRecvsPath(port_id.clone(), channel_id.clone()),
+            first_sequence.clone(),
+        );
+        self.set(
+            SeqAcksPath(port_id.clone(), channel_id.clone()),
+            first_sequence,
+        );
+        self.set(
+            ChannelEndsPath(port_id.clone(), channel_id.clone()),
+            channel_end.encode_vec(),
+        );
+
+        channel_id
+    }
+
+    fn channel_in_state(
+        &self,
+        type_url: &str,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        state: ChannelState,
+    ) -> Result<ChannelEnd, Error> {
+        let channel_end = self.channel(port_id, channel_id)?;
+
+        if !channel_end.state_matches(&state) {
+            return Err(Error::invalid_message(
+                type_url.to_string(),
+                format!(
+                    "channel {} is in state {}, expected {}",
+                    channel_id,
+                    channel_end.state(),
+                    state
+                ),
+            ));
+        }
+
+        Ok(channel_end)
+    }
+
+    fn close_channel(
+        &mut self,
+        type_url: &str,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ChannelEnd, Error> {
+        let mut channel_end = self.channel(port_id, channel_id)?;
+
+        if channel_end.state_matches(&ChannelState::Uninitialized)
+            || channel_end.state_matches(&ChannelState::Closed)
+        {
+            return Err(Error::invalid_message(
+                type_url.to_string(),
+                format!(
+                    "channel {} is in state {} and cannot be closed",
+                    channel_id,
+                    channel_end.state()
+                ),
+            ));
+        }
+
+        channel_end.set_state(ChannelState::Closed);
+
+        self.set(
+            ChannelEndsPath(port_id.clone(), channel_id.clone()),
+            channel_end.clone().encode_vec(),
+        );
+
+        Ok(channel_end)
+    }
+}
+
+pub fn consensus_state_path(client_id: &ClientId, height: Height) -> ClientConsensusStatePath {
+    ClientConsensusStatePath {
+        client_id: client_id.clone(),
+        epoch: height.revision_number(),
+        height: height.revision_height(),
+    }
+}
+
+/// Sequences are stored as 8-byte big-endian integers, as in ibc-go.
+pub fn encode_sequence(sequence: Sequence) -> Vec<u8> {
+    u64::from(sequence).to_be_bytes().to_vec()
+}
+
+fn decode_sequence(value: &[u8]) -> Sequence {
+    let mut bytes = [0; 8];
+    let len = value.len().min(8);
+    bytes[8 - len..].copy_from_slice(&value[value.len() - len..]);
+    Sequence::from(u64::from_be_bytes(bytes))
+}
+
+fn connection_attributes(
+    connection_id: ConnectionId,
+    connection_end: &ConnectionEnd,
+) -> ConnectionAttributes {
+    ConnectionAttributes {
+        connection_id: Some(connection_id),
+        client_id: connection_end.client_id().clone(),
+        counterparty_connection_id: connection_end.counterparty().connection_id().cloned(),
+        counterparty_client_id: connection_end.counterparty().client_id().clone(),
+    }
+}
+
+fn decode_msg<T, R>(msg: &Any) -> Result<T, Error>
+where
+    T: Protobuf<R>,
+    R: Message + Default + From<T>,
+    T: TryFrom<R>,
+    <T as TryFrom<R>>::Error: core::fmt::Display,
+{
+    T::decode_vec(&msg.value).map_err(|e| Error::decode_message(msg.type_url.clone(), e))
+}
+
+/// Read the JSON state file at `file`, if it exists.
+pub(crate) fn read_state_file<T: DeserializeOwned>(file: &Path) -> Result<Option<T>, Error> {
+    if !file.exists() {
+        return Ok(None);
+    }
+
+    let contents = fs::read_to_string(file).map_err(|e| Error::io(file.to_path_buf(), e))?;
+    let state =
+        serde_json::from_str(&contents).map_err(|e| Error::encode(file.to_path_buf(), e))?;
+
+    Ok(Some(state))
+}
+
+/// Write the JSON state file at `file`, going through a temporary file so that
+/// the state is never left half-written.
+pub(crate) fn write_state_file<T: Serialize>(file: &Path, state: &T) -> Result<(), Error> {
+    let contents =
+        serde_json::to_string_pretty(state).map_err(|e| Error::encode(file.to_path_buf(), e))?;
+
+    let tmp_file = file.with_extension("json.tmp");
+    fs::write(&tmp_file, contents).map_err(|e| Error::io(tmp_file.clone(), e))?;
+    fs::rename(&tmp_file, file).map_err(|e| Error::io(file.to_path_buf(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use ibc_proto::google::protobuf::Any;
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+        AllowUpdate, ClientState as TmClientState,
+    };
+    use ibc_relayer_types::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+    use ibc_relayer_types::core::ics02_client::msgs::create_client::MsgCreateClient;
+    use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+    use ibc_relayer_types::core::ics03_connection::connection::Counterparty as ConnectionCounterparty;
+    use ibc_relayer_types::core::ics03_connection::msgs::conn_open_init::MsgConnectionOpenInit;
+    use ibc_relayer_types::core::ics04_channel::channel::{
+        ChannelEnd, Counterparty, Ordering, State as ChannelState,
+    };
+    use ibc_relayer_types::core::ics04_channel::msgs::chan_open_confirm::MsgChannelOpenConfirm;
+    use ibc_relayer_types::core::ics04_channel::msgs::chan_open_try::MsgChannelOpenTry;
+    use ibc_relayer_types::core::ics04_channel::msgs::recv_packet::MsgRecvPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+    use ibc_relayer_types::core::ics24_host::identifier::{
+        ChainId, ChannelId, ClientId, ConnectionId, PortId,
+    };
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::proofs::Proofs;
+    use ibc_relayer_types::signer::Signer;
+    use ibc_relayer_types::timestamp::Timestamp;
+    use ibc_relayer_types::tx_msg::Msg;
+    use ibc_relayer_types::Height;
+    use tendermint_testgen::{Generator, LightBlock};
+    use test_log::test;
+
+    use super::{IbcStore, SUCCESS_ACKNOWLEDGEMENT};
+    use crate::client_state::AnyClientState;
+    use crate::consensus_state::AnyConsensusState;
+
+    fn proofs() -> Proofs {
+        Proofs::new(
+            vec![1].try_into().unwrap(),
+            None,
+            None,
+            None,
+            None,
+            Height::new(1, 10).unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn create_client() -> Any {
+        let block = LightBlock::new_default(10).generate().unwrap();
+
+        let client_state = TmClientState::new(
+            ChainId::from_string("chain-a-1"),
+            TrustThreshold::ONE_THIRD,
+            Duration::from_secs(3600),
+            Duration::from_secs(3 * 24 * 3600),
+            Duration::from_secs(40),
+            Height::new(1, 10).unwrap(),
+            Default::default(),
+            vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+        )
+        .unwrap();
+        let consensus_state = TmConsensusState::from(block.signed_header.header);
+
+        MsgCreateClient::new(
+            AnyClientState::from(client_state).into(),
+            AnyConsensusState::from(consensus_state).into(),
+            Signer::dummy(),
+        )
+        .unwrap()
+        .to_any()
+    }
+
+    fn conn_open_init(client_id: &ClientId) -> Any {
+        MsgConnectionOpenInit {
+            client_id: client_id.clone(),
+            counterparty: ConnectionCounterparty::new(
+                ClientId::default(),
+                None,
+                b"ibc".to_vec().try_into().unwrap(),
+            ),
+            version: None,
+            delay_period: Duration::ZERO,
+            signer: Signer::dummy(),
+        }
+        .to_any()
+    }
+
+    fn chan_open_try(connection_id: &ConnectionId, ordering: Ordering) -> Any {
+        MsgChannelOpenTry {
+            port_id: PortId::transfer(),
+            previous_channel_id: None,
+            channel: ChannelEnd::new(
+                ChannelState::TryOpen,
+                ordering,
+                Counterparty::new(PortId::transfer(), Some(ChannelId::new(7))),
+                vec![connection_id.clone()],
+                Version::ics20(),
+                Sequence::from(0),
+            ),
+            counterparty_version: Version::ics20(),
+            proofs: proofs(),
+            signer: Signer::dummy(),
+        }
+        .to_any()
+    }
+
+    fn recv_packet(channel_id: &ChannelId, sequence: u64) -> Any {
+        let packet = Packet {
+            sequence: sequence.into(),
+            source_port: PortId::transfer(),
+            source_channel: ChannelId::new(7),
+            destination_port: PortId::transfer(),
+            destination_channel: channel_id.clone(),
+            data: b"data".to_vec(),
+            timeout_height: TimeoutHeight::no_timeout(),
+            timeout_timestamp: Timestamp::none(),
+        };
+
+        MsgRecvPacket::new(packet, proofs(), Signer::dummy()).to_any()
+    }
+
+    /// Open a channel of the given ordering on a fresh store.
+    fn open_channel(ordering: Ordering) -> (IbcStore, ChannelId) {
+        let mut store = IbcStore::new(Timestamp::now());
+
+        let client_id = match store.deliver(&create_client()).unwrap().as_slice() {
+            [IbcEvent::CreateClient(event)] => event.client_id().clone(),
+            events => panic!("unexpected events {events:?}"),
+        };
+        assert!(store.client_state(&client_id).unwrap().is_some());
+
+        let connection_id = match store
+            .deliver(&conn_open_init(&client_id))
+            .unwrap()
+            .as_slice()
+        {
+            [IbcEvent::OpenInitConnection(event)] => event.connection_id().unwrap().clone(),
+            events => panic!("unexpected events {events:?}"),
+        };
+        assert_eq!(
+            store.client_connections(&client_id).unwrap(),
+            vec![connection_id.clone()]
+        );
+
+        let channel_id = ChannelId::new(0);
+        store
+            .deliver(&chan_open_try(&connection_id, ordering))
+            .unwrap();
+        store
+            .deliver(
+                &MsgChannelOpenConfirm::new(
+                    PortId::transfer(),
+                    channel_id.clone(),
+                    proofs(),
+                    Signer::dummy(),
+                )
+                .to_any(),
+            )
+            .unwrap();
+
+        let channel_end = store.channel(&PortId::transfer(), &channel_id).unwrap();
+        assert!(channel_end.is_open());
+
+        (store, channel_id)
+    }
+
+    #[test]
+    fn receive_unordered_packets() {
+        let (mut store, channel_id) = open_channel(Ordering::Unordered);
+        let port_id = PortId::transfer();
+
+        let events = store.deliver(&recv_packet(&channel_id, 2)).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                IbcEvent::ReceivePacket(_),
+                IbcEvent::WriteAcknowledgement(_)
+            ]
+        ));
+
+        // Receiving a packet twice is a no-op
+        assert!(store
+            .deliver(&recv_packet(&channel_id, 2))
+            .unwrap()
+            .is_empty());
+
+        assert!(store
+            .packet_received(&port_id, &channel_id, 2.into())
+            .unwrap());
+        assert!(!store
+            .packet_received(&port_id, &channel_id, 1.into())
+            .unwrap());
+        assert_eq!(
+            store.acknowledgement_sequences(&port_id, &channel_id),
+            vec![Sequence::from(2)]
+        );
+        assert_eq!(
+            store
+                .acknowledgement(&port_id, &channel_id, 2.into())
+                .unwrap()
+                .ack,
+            SUCCESS_ACKNOWLEDGEMENT
+        );
+    }
+
+    #[test]
+    fn receive_ordered_packets() {
+        let (mut store, channel_id) = open_channel(Ordering::Ordered);
+        let port_id = PortId::transfer();
+
+        assert!(store.deliver(&recv_packet(&channel_id, 2)).is_err());
+        store.deliver(&recv_packet(&channel_id, 1)).unwrap();
+
+        assert_eq!(
+            store.next_sequence_recv(&port_id, &channel_id),
+            Sequence::from(2)
+        );
+        assert!(store
+            .packet_received(&port_id, &channel_id, 1.into())
+            .unwrap());
+    }
+
+    #[test]
+    fn store_persists_state() {
+        let (store, channel_id) = open_channel(Ordering::Unordered);
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("store.json");
+        store.persist(&file).unwrap();
+
+        let reloaded = IbcStore::load_or_init(&file).unwrap();
+        assert_eq!(reloaded, store);
+        assert!(reloaded
+            .channel(&PortId::transfer(), &channel_id)
+            .unwrap()
+            .is_open());
+    }
+}
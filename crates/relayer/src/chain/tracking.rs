@@ -70,17 +70,25 @@ impl Display for TrackingId {
 pub struct TrackedMsgs {
     pub msgs: Vec<Any>,
     pub tracking_id: TrackingId,
+    /// Number of times these messages were resubmitted because the
+    /// previous transaction was not confirmed in time, `0` on first submission.
+    pub fee_bump_attempt: u32,
 }
 
 impl TrackedMsgs {
     pub fn new(msgs: Vec<Any>, tracking_id: TrackingId) -> Self {
-        Self { msgs, tracking_id }
+        Self {
+            msgs,
+            tracking_id,
+            fee_bump_attempt: 0,
+        }
     }
 
     pub fn new_static(msgs: Vec<Any>, tracking_id: &'static str) -> Self {
         Self {
             msgs,
             tracking_id: TrackingId::Static(tracking_id),
+            fee_bump_attempt: 0,
         }
     }
 
@@ -88,6 +96,7 @@ impl TrackedMsgs {
         Self {
             msgs,
             tracking_id: TrackingId::Uuid(tracking_id),
+            fee_bump_attempt: 0,
         }
     }
 
@@ -95,6 +104,7 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Static(tracking_id),
+            fee_bump_attempt: 0,
         }
     }
 
@@ -102,9 +112,15 @@ impl TrackedMsgs {
         Self {
             msgs: vec![msg],
             tracking_id: TrackingId::Uuid(tracking_id),
+            fee_bump_attempt: 0,
         }
     }
 
+    pub fn with_fee_bump_attempt(mut self, fee_bump_attempt: u32) -> Self {
+        self.fee_bump_attempt = fee_bump_attempt;
+        self
+    }
+
     pub fn messages(&self) -> &Vec<Any> {
         &self.msgs
     }
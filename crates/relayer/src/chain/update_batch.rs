@@ -0,0 +1,460 @@
+//! Merging of the client updates submitted concurrently to a chain runtime.
+//!
+//! Workers relaying on different paths towards the same chain each build and submit
+//! their own `MsgUpdateClient`s. When several such submissions are waiting in the queue
+//! of the chain runtime, the runtime merges them: updates of the same client to the same
+//! height are only submitted once, and updates of different clients are bundled in as
+//! few transactions as `max_msg_num` allows.
+
+use core::str::FromStr;
+use std::collections::{BTreeSet, HashSet};
+
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::client::v1::MsgUpdateClient as RawMsgUpdateClient;
+use prost::Message;
+
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+use ibc_relayer_types::core::ics02_client::events::{Attributes, UpdateClient};
+use ibc_relayer_types::core::ics02_client::header::{AnyHeader, Header};
+use ibc_relayer_types::core::ics02_client::msgs::update_client::TYPE_URL as UPDATE_CLIENT_TYPE_URL;
+use ibc_relayer_types::core::ics24_host::identifier::ClientId;
+use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::Height;
+
+use crate::chain::tracking::TrackedMsgs;
+use crate::event::IbcEventWithHeight;
+
+/// What a client update brings the client to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum UpdateTarget {
+    Height(Height),
+    /// The header could not be decoded, only exact duplicates are merged
+    Raw(Vec<u8>),
+}
+
+/// Whether the given messages only consist of client updates, and can thus be
+/// merged with the client updates submitted by other workers.
+pub fn is_client_update_only(tracked_msgs: &TrackedMsgs) -> bool {
+    // Resubmissions with a bumped fee are left alone, so as not to bump the fee
+    // of the updates they would be merged with.
+    tracked_msgs.fee_bump_attempt == 0
+        && !tracked_msgs.msgs.is_empty()
+        && tracked_msgs
+            .msgs
+            .iter()
+            .all(|msg| msg.type_url == UPDATE_CLIENT_TYPE_URL)
+}
+
+/// Merge the client updates of the given submissions, keeping the first of the updates
+/// of a client to a given height and preserving the order of the updates otherwise.
+pub fn merge_client_updates<'a>(batch: impl IntoIterator<Item = &'a TrackedMsgs>) -> Vec<Any> {
+    let mut seen = HashSet::new();
+
+    batch
+        .into_iter()
+        .flat_map(|tracked_msgs| tracked_msgs.msgs.iter())
+        .filter(|msg| match update_key(msg) {
+            Some(key) => seen.insert(key),
+            None => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// The clients updated by the given messages, along with the height each of them is
+/// updated to, or `None` if the header could not be decoded.
+pub fn update_targets(tracked_msgs: &TrackedMsgs) -> Vec<(ClientId, Option<Height>)> {
+    tracked_msgs
+        .msgs
+        .iter()
+        .filter_map(update_key)
+        .filter_map(|(client_id, target)| {
+            let client_id = ClientId::from_str(&client_id).ok()?;
+
+            let height = match target {
+                UpdateTarget::Height(height) => Some(height),
+                UpdateTarget::Raw(_) => None,
+            };
+
+            Some((client_id, height))
+        })
+        .collect()
+}
+
+/// Whether the events of a merged submission show that all the client updates
+/// of `tracked_msgs` were committed.
+///
+/// The updates whose header could not be decoded are deemed committed
+/// if any update of their client was.
+pub fn is_committed(tracked_msgs: &TrackedMsgs, events: &[IbcEventWithHeight]) -> bool {
+    update_targets(tracked_msgs)
+        .iter()
+        .all(|(client_id, height)| {
+            events.iter().any(|event| match &event.event {
+                IbcEvent::UpdateClient(update) => {
+                    update.client_id() == client_id
+                        && height.map_or(true, |height| update.consensus_height() == height)
+                }
+                _ => false,
+            })
+        })
+}
+
+/// The events of a merged submission which concern the clients updated by `tracked_msgs`.
+pub fn events_for_request(
+    tracked_msgs: &TrackedMsgs,
+    events: &[IbcEventWithHeight],
+) -> Vec<IbcEventWithHeight> {
+    let client_ids: BTreeSet<String> = tracked_msgs
+        .msgs
+        .iter()
+        .filter_map(|msg| RawMsgUpdateClient::decode(msg.value.as_slice()).ok())
+        .map(|msg| msg.client_id)
+        .collect();
+
+    events
+        .iter()
+        .filter(|event| match &event.event {
+            IbcEvent::UpdateClient(update) => client_ids.contains(update.client_id().as_str()),
+            IbcEvent::ClientMisbehaviour(misbehaviour) => {
+                client_ids.contains(misbehaviour.client_id().as_str())
+            }
+            // Errors of the transactions carrying the updates of other clients
+            IbcEvent::ChainError(_) => false,
+            _ => true,
+        })
+        .cloned()
+        .collect()
+}
+
+/// Rebuild the `UpdateClient` events of the given client updates from the consensus
+/// states they added, as found by `client_type_at`, which returns the type of the client
+/// if it holds a consensus state at the given height.
+///
+/// Returns `None` if any of the updates was not committed, or if its header could not be
+/// decoded, since its consensus height is then unknown.
+pub fn rebuild_update_events(
+    tracked_msgs: &TrackedMsgs,
+    height: Height,
+    client_type_at: impl Fn(&ClientId, Height) -> Option<ClientType>,
+) -> Option<Vec<IbcEventWithHeight>> {
+    update_targets(tracked_msgs)
+        .into_iter()
+        .map(|(client_id, consensus_height)| {
+            let consensus_height = consensus_height?;
+            let client_type = client_type_at(&client_id, consensus_height)?;
+
+            let event = UpdateClient {
+                common: Attributes {
+                    client_id,
+                    client_type,
+                    consensus_height,
+                },
+                header: None,
+            };
+
+            Some(IbcEventWithHeight::new(
+                IbcEvent::UpdateClient(event),
+                height,
+            ))
+        })
+        .collect()
+}
+
+fn update_key(msg: &Any) -> Option<(String, UpdateTarget)> {
+    let msg = RawMsgUpdateClient::decode(msg.value.as_slice()).ok()?;
+    let client_message = msg.client_message?;
+
+    let target = match AnyHeader::try_from(client_message.clone()) {
+        Ok(header) => UpdateTarget::Height(header.height()),
+        Err(_) => UpdateTarget::Raw(client_message.encode_to_vec()),
+    };
+
+    Some((msg.client_id, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_proto::google::protobuf::Any;
+    use ibc_proto::ibc::core::client::v1::MsgUpdateClient as RawMsgUpdateClient;
+    use prost::Message;
+
+    use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
+    use ibc_relayer_types::core::ics02_client::client_type::ClientType;
+    use ibc_relayer_types::core::ics02_client::events::{Attributes, UpdateClient};
+    use ibc_relayer_types::core::ics02_client::msgs::update_client::TYPE_URL;
+    use ibc_relayer_types::core::ics24_host::identifier::ClientId;
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::Height;
+    use tendermint_testgen::{Generator, LightBlock};
+
+    use super::{
+        events_for_request, is_client_update_only, is_committed, merge_client_updates,
+        rebuild_update_events, update_targets,
+    };
+    use crate::chain::tracking::{TrackedMsgs, TrackingId};
+    use crate::event::IbcEventWithHeight;
+
+    fn update(client_id: &str, header: &[u8]) -> Any {
+        let msg = RawMsgUpdateClient {
+            client_id: client_id.to_string(),
+            client_message: Some(Any {
+                type_url: "/unknown.Header".to_string(),
+                value: header.to_vec(),
+            }),
+            signer: "signer".to_string(),
+        };
+
+        Any {
+            type_url: TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn tm_update(client_id: &str, height: u64) -> Any {
+        let block = LightBlock::new_default(height).generate().unwrap();
+
+        let header = TmHeader {
+            signed_header: block.signed_header,
+            validator_set: block.validators.clone(),
+            trusted_height: Height::new(0, height - 1).unwrap(),
+            trusted_validator_set: block.validators,
+        };
+
+        let msg = RawMsgUpdateClient {
+            client_id: client_id.to_string(),
+            client_message: Some(header.into()),
+            signer: "signer".to_string(),
+        };
+
+        Any {
+            type_url: TYPE_URL.to_string(),
+            value: msg.encode_to_vec(),
+        }
+    }
+
+    fn update_event(client_id: &str, height: u64) -> IbcEventWithHeight {
+        let event = UpdateClient {
+            common: Attributes {
+                client_id: client_id.parse().unwrap(),
+                client_type: ClientType::Tendermint,
+                consensus_height: Height::new(0, height).unwrap(),
+            },
+            header: None,
+        };
+
+        IbcEventWithHeight::new(IbcEvent::UpdateClient(event), Height::new(0, 100).unwrap())
+    }
+
+    fn chain_error() -> IbcEventWithHeight {
+        IbcEventWithHeight::new(
+            IbcEvent::ChainError("out of gas".to_string()),
+            Height::new(0, 1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn update_targets_of_decoded_and_raw_headers() {
+        let tracked_msgs = TrackedMsgs::new(
+            vec![
+                tm_update("07-tendermint-0", 12),
+                update("07-tendermint-1", b"h1"),
+            ],
+            TrackingId::new_uuid(),
+        );
+
+        assert_eq!(
+            update_targets(&tracked_msgs),
+            vec![
+                (
+                    ClientId::new(ClientType::Tendermint, 0).unwrap(),
+                    Some(Height::new(0, 12).unwrap())
+                ),
+                (ClientId::new(ClientType::Tendermint, 1).unwrap(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_updates_to_the_same_height() {
+        let worker_a = TrackedMsgs::new(
+            vec![tm_update("07-tendermint-0", 12)],
+            TrackingId::new_uuid(),
+        );
+        let worker_b = TrackedMsgs::new(
+            vec![
+                tm_update("07-tendermint-0", 12),
+                tm_update("07-tendermint-0", 13),
+            ],
+            TrackingId::new_uuid(),
+        );
+
+        let merged = merge_client_updates([&worker_a, &worker_b]);
+
+        assert_eq!(
+            merged,
+            vec![
+                tm_update("07-tendermint-0", 12),
+                tm_update("07-tendermint-0", 13)
+            ]
+        );
+    }
+
+    #[test]
+    fn events_are_dispatched_to_their_request() {
+        let worker_a = TrackedMsgs::new(
+            vec![tm_update("07-tendermint-0", 12)],
+            TrackingId::new_uuid(),
+        );
+        let worker_b = TrackedMsgs::new(
+            vec![tm_update("07-tendermint-1", 20)],
+            TrackingId::new_uuid(),
+        );
+
+        let events = vec![
+            update_event("07-tendermint-0", 12),
+            update_event("07-tendermint-1", 20),
+        ];
+
+        assert_eq!(
+            events_for_request(&worker_a, &events),
+            vec![update_event("07-tendermint-0", 12)]
+        );
+        assert_eq!(
+            events_for_request(&worker_b, &events),
+            vec![update_event("07-tendermint-1", 20)]
+        );
+    }
+
+    #[test]
+    fn only_requests_of_failed_txs_are_not_committed() {
+        // The updates of both workers were split in two transactions,
+        // and the one carrying the update of worker B failed
+        let worker_a = TrackedMsgs::new(
+            vec![tm_update("07-tendermint-0", 12)],
+            TrackingId::new_uuid(),
+        );
+        let worker_b = TrackedMsgs::new(
+            vec![tm_update("07-tendermint-1", 20)],
+            TrackingId::new_uuid(),
+        );
+
+        let events = vec![update_event("07-tendermint-0", 12), chain_error()];
+
+        assert!(is_committed(&worker_a, &events));
+        assert!(!is_committed(&worker_b, &events));
+
+        // The error of worker B's transaction is not reported to worker A
+        assert_eq!(
+            events_for_request(&worker_a, &events),
+            vec![update_event("07-tendermint-0", 12)]
+        );
+    }
+
+    #[test]
+    fn update_to_another_height_is_not_committed() {
+        let tracked_msgs = TrackedMsgs::new(
+            vec![tm_update("07-tendermint-0", 12)],
+            TrackingId::new_uuid(),
+        );
+
+        assert!(!is_committed(
+            &tracked_msgs,
+            &[update_event("07-tendermint-0", 11)]
+        ));
+
+        // Raw headers are matched on the client only
+        let raw = TrackedMsgs::new(
+            vec![update("07-tendermint-0", b"h1")],
+            TrackingId::new_uuid(),
+        );
+
+        assert!(is_committed(&raw, &[update_event("07-tendermint-0", 11)]));
+    }
+
+    #[test]
+    fn rebuilds_events_of_applied_updates() {
+        let tracked_msgs = TrackedMsgs::new(
+            vec![
+                tm_update("07-tendermint-0", 12),
+                tm_update("07-tendermint-1", 20),
+            ],
+            TrackingId::new_uuid(),
+        );
+        let height = Height::new(0, 100).unwrap();
+
+        let events =
+            rebuild_update_events(&tracked_msgs, height, |_, _| Some(ClientType::Tendermint));
+        assert_eq!(
+            events,
+            Some(vec![
+                update_event("07-tendermint-0", 12),
+                update_event("07-tendermint-1", 20)
+            ])
+        );
+
+        // The consensus state of the second client is missing
+        let events = rebuild_update_events(&tracked_msgs, height, |client_id, _| {
+            (client_id.as_str() == "07-tendermint-0").then_some(ClientType::Tendermint)
+        });
+        assert_eq!(events, None);
+
+        // The consensus height of raw headers is unknown
+        let raw = TrackedMsgs::new(
+            vec![update("07-tendermint-0", b"h1")],
+            TrackingId::new_uuid(),
+        );
+        let events = rebuild_update_events(&raw, height, |_, _| Some(ClientType::Tendermint));
+        assert_eq!(events, None);
+    }
+
+    #[test]
+    fn merges_duplicate_client_updates() {
+        let worker_a = TrackedMsgs::new(
+            vec![
+                update("07-tendermint-0", b"h1"),
+                update("07-tendermint-1", b"h1"),
+            ],
+            TrackingId::new_uuid(),
+        );
+        let worker_b = TrackedMsgs::new(
+            vec![
+                update("07-tendermint-0", b"h1"),
+                update("07-tendermint-0", b"h2"),
+            ],
+            TrackingId::new_uuid(),
+        );
+
+        let merged = merge_client_updates([&worker_a, &worker_b]);
+
+        assert_eq!(
+            merged,
+            vec![
+                update("07-tendermint-0", b"h1"),
+                update("07-tendermint-1", b"h1"),
+                update("07-tendermint-0", b"h2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_client_updates_are_merged() {
+        let updates = TrackedMsgs::new(
+            vec![update("07-tendermint-0", b"h1")],
+            TrackingId::new_uuid(),
+        );
+        assert!(is_client_update_only(&updates));
+
+        let mixed = TrackedMsgs::new(
+            vec![
+                update("07-tendermint-0", b"h1"),
+                Any {
+                    type_url: "/ibc.core.channel.v1.MsgRecvPacket".to_string(),
+                    value: vec![],
+                },
+            ],
+            TrackingId::new_uuid(),
+        );
+        assert!(!is_client_update_only(&mixed));
+    }
+}
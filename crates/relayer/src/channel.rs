@@ -28,6 +28,7 @@ use ibc_relayer_types::core::ics24_host::identifier::{
     ChainId, ChannelId, ClientId, ConnectionId, PortId,
 };
 use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::proofs::Proofs;
 use ibc_relayer_types::tx_msg::Msg;
 use ibc_relayer_types::Height;
 
@@ -203,6 +204,40 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         a_port: PortId,
         b_port: PortId,
         version: Option<Version>,
+    ) -> Result<Self, ChannelError> {
+        let mut channel = Self::uninitialized(connection, ordering, a_port, b_port, version)?;
+
+        channel.handshake()?;
+
+        Ok(channel)
+    }
+
+    /// Creates a new channel on top of the existing connection by only sending the
+    /// `ChanOpenInit` message to chain `a`.
+    ///
+    /// The rest of the handshake is left to the caller, who can resume it from the
+    /// chain state with [`Channel::restore_from_state`] and the handshake step functions.
+    pub fn init(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Ordering,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
+    ) -> Result<Self, ChannelError> {
+        let mut channel = Self::uninitialized(connection, ordering, a_port, b_port, version)?;
+
+        let event = channel.flipped().build_chan_open_init_and_send()?;
+        channel.a_side.channel_id = Some(extract_channel_id(&event)?.clone());
+
+        Ok(channel)
+    }
+
+    fn uninitialized(
+        connection: Connection<ChainA, ChainB>,
+        ordering: Ordering,
+        a_port: PortId,
+        b_port: PortId,
+        version: Option<Version>,
     ) -> Result<Self, ChannelError> {
         let src_connection_id = connection
             .src_connection_id()
@@ -211,7 +246,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .dst_connection_id()
             .ok_or_else(|| ChannelError::missing_local_connection(connection.dst_chain().id()))?;
 
-        let mut channel = Self {
+        Ok(Self {
             ordering,
             a_side: ChannelSide::new(
                 connection.src_chain(),
@@ -230,11 +265,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
                 version,
             ),
             connection_delay: connection.delay_period,
-        };
-
-        channel.handshake()?;
-
-        Ok(channel)
+        })
     }
 
     pub fn restore_from_event(
@@ -879,6 +910,29 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         self.step_state(state, index)
     }
 
+    /// Builds the proofs of the channel end on the source chain. If the channel is on
+    /// the localhost connection, no proof is queried and the sentinel proof expected by
+    /// the `09-localhost` client is used at the latest height of the chain instead.
+    fn build_src_channel_proofs(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        height: Height,
+    ) -> Result<Proofs, ChannelError> {
+        if self.dst_connection_id().is_localhost() {
+            let latest_height = self
+                .src_chain()
+                .query_latest_height()
+                .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
+
+            return Ok(Proofs::localhost(false, latest_height));
+        }
+
+        self.src_chain()
+            .build_channel_proofs(port_id, channel_id, height)
+            .map_err(ChannelError::channel_proof)
+    }
+
     pub fn build_update_client_on_dst(&self, height: Height) -> Result<Vec<Any>, ChannelError> {
         let client = ForeignClient::restore(
             self.dst_client_id().clone(),
@@ -1075,10 +1129,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
-            .map_err(ChannelError::channel_proof)?;
+        let proofs =
+            self.build_src_channel_proofs(self.src_port_id(), src_channel_id, query_height)?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -1196,10 +1248,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
-            .map_err(ChannelError::channel_proof)?;
+        let proofs =
+            self.build_src_channel_proofs(self.src_port_id(), src_channel_id, query_height)?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -1304,10 +1354,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
-            .map_err(ChannelError::channel_proof)?;
+        let proofs =
+            self.build_src_channel_proofs(self.src_port_id(), src_channel_id, query_height)?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -1476,10 +1524,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .query_latest_height()
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(self.src_port_id(), src_channel_id, query_height)
-            .map_err(ChannelError::channel_proof)?;
+        let proofs =
+            self.build_src_channel_proofs(self.src_port_id(), src_channel_id, query_height)?;
 
         let counterparty_upgrade_sequence = src_channel_end.upgrade_sequence;
 
@@ -1566,14 +1612,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
         // Building the channel proof at the queried height
-        let src_proof = self
-            .src_chain()
-            .build_channel_proofs(
-                &src_port_id.clone(),
-                &src_channel_id.clone(),
-                src_latest_height,
-            )
-            .map_err(ChannelError::channel_proof)?;
+        let src_proof = self.build_src_channel_proofs(
+            &src_port_id.clone(),
+            &src_channel_id.clone(),
+            src_latest_height,
+        )?;
 
         let (dst_channel_end, _) = self
             .dst_chain()
@@ -1715,14 +1758,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             CommitmentProofBytes::try_from(upgrade_proof).map_err(ChannelError::malformed_proof)?;
 
         // Building the channel proof at the queried height
-        let proof = self
-            .src_chain()
-            .build_channel_proofs(
-                &src_port_id.clone(),
-                &src_channel_id.clone(),
-                src_latest_height,
-            )
-            .map_err(ChannelError::channel_proof)?;
+        let proof = self.build_src_channel_proofs(
+            &src_port_id.clone(),
+            &src_channel_id.clone(),
+            src_latest_height,
+        )?;
 
         let signer = self
             .dst_chain()
@@ -1840,14 +1880,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             CommitmentProofBytes::try_from(upgrade_proof).map_err(ChannelError::malformed_proof)?;
 
         // Building the channel proof at the queried height
-        let proof = self
-            .src_chain()
-            .build_channel_proofs(
-                &src_port_id.clone(),
-                &src_channel_id.clone(),
-                src_latest_height,
-            )
-            .map_err(ChannelError::channel_proof)?;
+        let proof = self.build_src_channel_proofs(
+            &src_port_id.clone(),
+            &src_channel_id.clone(),
+            src_latest_height,
+        )?;
 
         let signer = self
             .dst_chain()
@@ -1948,14 +1985,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
         let counterparty_upgrade_sequence = src_channel_end.upgrade_sequence;
 
         // Building the channel proof at the queried height
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(
-                &src_port_id.clone(),
-                &src_channel_id.clone(),
-                src_latest_height,
-            )
-            .map_err(ChannelError::channel_proof)?;
+        let proofs = self.build_src_channel_proofs(
+            &src_port_id.clone(),
+            &src_channel_id.clone(),
+            src_latest_height,
+        )?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -2050,14 +2084,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .map_err(ChannelError::malformed_proof)?;
 
         // Building the channel proof at the queried height
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(
-                &src_port_id.clone(),
-                &src_channel_id.clone(),
-                src_latest_height,
-            )
-            .map_err(ChannelError::channel_proof)?;
+        let proofs = self.build_src_channel_proofs(
+            &src_port_id.clone(),
+            &src_channel_id.clone(),
+            src_latest_height,
+        )?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
@@ -2146,14 +2177,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Channel<ChainA, ChainB> {
             .map_err(|e| ChannelError::query(self.src_chain().id(), e))?;
 
         // Building the channel proof at the queried height
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(
-                &src_port_id.clone(),
-                &src_channel_id.clone(),
-                src_latest_height,
-            )
-            .map_err(ChannelError::channel_proof)?;
+        let proofs = self.build_src_channel_proofs(
+            &src_port_id.clone(),
+            &src_channel_id.clone(),
+            src_latest_height,
+        )?;
 
         // Build message(s) to update client on destination
         let mut msgs = self.build_update_client_on_dst(proofs.height())?;
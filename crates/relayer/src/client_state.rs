@@ -6,9 +6,16 @@ use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::core::client::v1::IdentifiedClientState;
 use ibc_proto::ibc::lightclients::tendermint::v1::ClientState as RawTmClientState;
 use ibc_proto::Protobuf;
+use ibc_relayer_types::clients::ics06_solomachine::client_state::{
+    ClientState as SmClientState, SOLOMACHINE_CLIENT_STATE_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     ClientState as TmClientState, TENDERMINT_CLIENT_STATE_TYPE_URL,
 };
+use ibc_relayer_types::clients::ics08_wasm::client_state::{
+    ClientState as RawWasmClientState, WASM_CLIENT_STATE_TYPE_URL,
+};
+use ibc_relayer_types::clients::ics09_localhost::client_state::ClientState as RawLocalhostClientState;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::error::Error;
@@ -21,56 +28,129 @@ use ibc_relayer_types::Height;
 #[serde(tag = "type")]
 pub enum AnyClientState {
     Tendermint(TmClientState),
+    SoloMachine(SmClientState),
+    Wasm(WasmClientState),
+    Localhost(LocalhostClientState),
+}
+
+/// The client state of a `08-wasm` client, along with the decoded client state
+/// of the light client implemented by the contract with the given checksum.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WasmClientState {
+    pub checksum: Vec<u8>,
+    pub latest_height: Height,
+    pub inner: Box<AnyClientState>,
+}
+
+/// The client state of the `09-localhost` client, which tracks the chain hosting it.
+///
+/// The identifier of the host chain is not part of the encoded client state,
+/// hence this client state is built by the host chain endpoint instead of being decoded.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LocalhostClientState {
+    pub chain_id: ChainId,
+    pub latest_height: Height,
 }
 
 impl AnyClientState {
     pub fn chain_id(&self) -> ChainId {
         match self {
             AnyClientState::Tendermint(tm_state) => tm_state.chain_id(),
+            AnyClientState::SoloMachine(state) => state.chain_id(),
+            AnyClientState::Wasm(state) => state.inner.chain_id(),
+            AnyClientState::Localhost(state) => state.chain_id.clone(),
         }
     }
 
     pub fn latest_height(&self) -> Height {
         match self {
             Self::Tendermint(tm_state) => tm_state.latest_height(),
+            Self::SoloMachine(state) => state.latest_height(),
+            Self::Wasm(state) => state.latest_height,
+            Self::Localhost(state) => state.latest_height,
         }
     }
 
     pub fn frozen_height(&self) -> Option<Height> {
         match self {
             Self::Tendermint(tm_state) => tm_state.frozen_height(),
+            Self::SoloMachine(state) => state.frozen_height(),
+            Self::Wasm(state) => state.inner.frozen_height(),
+            Self::Localhost(_) => None,
         }
     }
 
     pub fn trust_threshold(&self) -> Option<TrustThreshold> {
         match self {
             AnyClientState::Tendermint(state) => Some(state.trust_threshold),
+            AnyClientState::SoloMachine(_) => None,
+            AnyClientState::Wasm(state) => state.inner.trust_threshold(),
+            AnyClientState::Localhost(_) => None,
         }
     }
 
     pub fn trusting_period(&self) -> Duration {
         match self {
             AnyClientState::Tendermint(state) => state.trusting_period,
+            // A solo machine client only advances with signatures and never expires
+            AnyClientState::SoloMachine(_) => Duration::MAX,
+            AnyClientState::Wasm(state) => state.inner.trusting_period(),
+            // The localhost client is updated by its host at every block and never expires
+            AnyClientState::Localhost(_) => Duration::MAX,
         }
     }
 
     pub fn max_clock_drift(&self) -> Duration {
         match self {
             AnyClientState::Tendermint(state) => state.max_clock_drift,
+            AnyClientState::SoloMachine(_) => Duration::ZERO,
+            AnyClientState::Wasm(state) => state.inner.max_clock_drift(),
+            AnyClientState::Localhost(_) => Duration::ZERO,
         }
     }
 
     pub fn client_type(&self) -> ClientType {
         match self {
             Self::Tendermint(state) => state.client_type(),
+            Self::SoloMachine(state) => state.client_type(),
+            Self::Wasm(_) => ClientType::Wasm,
+            Self::Localhost(_) => ClientType::Localhost,
         }
     }
 
     pub fn expired(&self, elapsed: Duration) -> bool {
         match self {
             Self::Tendermint(state) => state.expired(elapsed),
+            Self::SoloMachine(state) => state.expired(elapsed),
+            Self::Wasm(state) => state.inner.expired(elapsed),
+            Self::Localhost(_) => false,
+        }
+    }
+
+    /// The client state of the light client doing the verification,
+    /// unwrapping any `08-wasm` client state.
+    pub fn inner(&self) -> &AnyClientState {
+        match self {
+            Self::Wasm(state) => state.inner.inner(),
+            _ => self,
         }
     }
+
+    /// Wrap this client state in a `08-wasm` client state, for the light client
+    /// implemented by the contract with the given checksum.
+    pub fn wrap_wasm(self, checksum: Vec<u8>) -> Self {
+        Self::Wasm(WasmClientState {
+            checksum,
+            latest_height: self.latest_height(),
+            inner: Box::new(self),
+        })
+    }
+
+    /// Whether the headers and misbehaviour submitted to this client
+    /// must be wrapped in a `08-wasm` client message.
+    pub fn is_wasm(&self) -> bool {
+        matches!(self, Self::Wasm(_))
+    }
 }
 
 impl Protobuf<Any> for AnyClientState {}
@@ -87,6 +167,21 @@ impl TryFrom<Any> for AnyClientState {
                     .map_err(Error::decode_raw_client_state)?,
             )),
 
+            SOLOMACHINE_CLIENT_STATE_TYPE_URL => {
+                Ok(AnyClientState::SoloMachine(SmClientState::try_from(raw)?))
+            }
+
+            WASM_CLIENT_STATE_TYPE_URL => {
+                let raw_state = RawWasmClientState::try_from(raw)?;
+                let inner = AnyClientState::try_from(raw_state.inner()?)?;
+
+                Ok(AnyClientState::Wasm(WasmClientState {
+                    checksum: raw_state.checksum,
+                    latest_height: raw_state.latest_height,
+                    inner: Box::new(inner),
+                }))
+            }
+
             _ => Err(Error::unknown_client_state_type(raw.type_url)),
         }
     }
@@ -99,6 +194,14 @@ impl From<AnyClientState> for Any {
                 type_url: TENDERMINT_CLIENT_STATE_TYPE_URL.to_string(),
                 value: Protobuf::<RawTmClientState>::encode_vec(value),
             },
+            AnyClientState::SoloMachine(value) => value.into(),
+            AnyClientState::Wasm(value) => {
+                RawWasmClientState::new((*value.inner).into(), value.checksum, value.latest_height)
+                    .into()
+            }
+            AnyClientState::Localhost(value) => {
+                RawLocalhostClientState::new(value.latest_height).into()
+            }
         }
     }
 }
@@ -131,6 +234,12 @@ impl From<TmClientState> for AnyClientState {
     }
 }
 
+impl From<SmClientState> for AnyClientState {
+    fn from(cs: SmClientState) -> Self {
+        Self::SoloMachine(cs)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub struct IdentifiedAnyClientState {
@@ -3,12 +3,16 @@
 pub mod compat_mode;
 pub mod dynamic_gas;
 pub mod error;
+pub mod fee_bump;
 pub mod filter;
 pub mod gas_multiplier;
 pub mod proof_specs;
 pub mod refresh_rate;
+pub mod schema;
 pub mod types;
 
+mod resolve;
+
 use alloc::collections::BTreeMap;
 use core::cmp::Ordering;
 use core::fmt::{Display, Error as FmtError, Formatter};
@@ -16,9 +20,12 @@ use core::str::FromStr;
 use core::time::Duration;
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use std::borrow::Cow;
-use std::{fs, fs::File, io::Write, ops::Range, path::Path};
+use std::{fs, fs::File, io::Write, ops::Range, path::Path, path::PathBuf};
 
 use byte_unit::Byte;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tendermint::block::Height as BlockHeight;
 use tendermint_rpc::Url;
@@ -29,7 +36,9 @@ use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId
 use ibc_relayer_types::timestamp::ZERO_DURATION;
 
 use crate::chain::cosmos::config::CosmosSdkConfig;
+use crate::chain::cosmos::generate::GenerateOnly;
 use crate::chain::penumbra::config::PenumbraConfig;
+use crate::chain::solomachine::config::SoloMachineConfig;
 use crate::config::types::ics20_field_size_limit::Ics20FieldSizeLimit;
 use crate::config::types::TrustThreshold;
 use crate::error::Error as RelayerError;
@@ -44,9 +53,11 @@ pub use error::Error;
 pub use filter::PacketFilter;
 pub use refresh_rate::RefreshRate;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct GasPrice {
+    /// The price per unit of gas
     pub price: f64,
+    /// The denomination of the fees
     pub denom: String,
 }
 
@@ -112,7 +123,7 @@ pub fn parse_gas_prices(prices: String) -> Vec<GasPrice> {
         .collect()
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(
     rename_all = "snake_case",
     tag = "type",
@@ -187,6 +198,10 @@ pub mod default {
         Duration::from_millis(500)
     }
 
+    pub fn replay_speed() -> f64 {
+        1.0
+    }
+
     pub fn clock_drift() -> Duration {
         Duration::from_secs(5)
     }
@@ -220,6 +235,15 @@ pub mod default {
         RefreshRate::new(1, 3)
     }
 
+    pub fn client_upgrade() -> bool {
+        false
+    }
+
+    pub fn urgent_refresh_threshold() -> RefreshRate {
+        // Refresh urgently when less than a tenth of the trusting period is left
+        RefreshRate::new(1, 10)
+    }
+
     pub fn latency_submitted() -> HistogramConfig {
         HistogramConfig {
             range: Range {
@@ -252,24 +276,38 @@ pub mod default {
         true
     }
 
+    pub fn max_in_flight_txs() -> usize {
+        8
+    }
+
     pub fn clear_limit() -> usize {
         50
     }
+
+    pub fn connection_client_proofs() -> bool {
+        true
+    }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
+    /// Parameters which apply globally to the relayer operation
     #[serde(default)]
     pub global: GlobalConfig,
+    /// The operation modes of the relayer
     #[serde(default)]
     pub mode: ModeConfig,
+    /// Parameters of the built-in REST API
     #[serde(default)]
     pub rest: RestConfig,
+    /// Parameters of the built-in telemetry service
     #[serde(default)]
     pub telemetry: TelemetryConfig,
-    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    /// The chains to relay between
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
+    /// Parameters of the server allowing updates to the tracing directives at runtime
     #[serde(default)]
     pub tracing_server: TracingServerConfig,
 }
@@ -329,22 +367,68 @@ impl Config {
                         .map_err(Into::<Diagnostic<Error>>::into)?;
                 }
                 ChainConfig::Penumbra { .. } => { /* no-op for now (erwan) */ }
+                ChainConfig::SoloMachine(_) => { /* no-op */ }
             }
         }
 
         // Check for invalid mode config
         self.mode.validate()?;
 
+        self.validate_urgent_refresh_threshold()?;
+
+        Ok(())
+    }
+
+    /// The urgent refresh threshold must be a fraction in (0, 1), lower than the
+    /// refresh rate of every chain, so that clients are refreshed as usual before
+    /// their refresh becomes urgent.
+    fn validate_urgent_refresh_threshold(&self) -> Result<(), Diagnostic<Error>> {
+        let threshold = self.mode.clients.urgent_refresh_threshold;
+
+        let invalid = |reason: String| {
+            Diagnostic::Error(Error::invalid_urgent_refresh_threshold(
+                threshold.to_string(),
+                reason,
+            ))
+        };
+
+        if !(threshold.as_f64() > 0.0 && threshold.as_f64() < 1.0) {
+            return Err(invalid("it must be strictly between 0 and 1".to_string()));
+        }
+
+        for chain_config in self.chains.iter() {
+            let refresh_rate = match chain_config {
+                ChainConfig::CosmosSdk(config) | ChainConfig::Namada(config) => {
+                    config.client_refresh_rate
+                }
+                ChainConfig::Penumbra(config) => config.client_refresh_rate,
+                // The solo machine uses the default refresh rate
+                ChainConfig::SoloMachine(_) => default::client_refresh_rate(),
+            };
+
+            if threshold.as_f64() >= refresh_rate.as_f64() {
+                return Err(invalid(format!(
+                    "it must be lower than the `client_refresh_rate` '{}' of chain '{}'",
+                    refresh_rate,
+                    chain_config.id()
+                )));
+            }
+        }
+
         Ok(())
     }
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct ModeConfig {
+    /// The client mode
     pub clients: Clients,
+    /// The connections mode
     pub connections: Connections,
+    /// The channels mode
     pub channels: Channels,
+    /// The packets mode
     pub packets: Packets,
 }
 
@@ -381,6 +465,8 @@ impl Default for ModeConfig {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                upgrade: default::client_upgrade(),
+                urgent_refresh_threshold: default::urgent_refresh_threshold(),
             },
             connections: Connections { enabled: false },
             channels: Channels { enabled: false },
@@ -392,44 +478,77 @@ impl Default for ModeConfig {
     }
 }
 
-#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Clients {
+    /// Whether to enable the client workers
     pub enabled: bool,
+    /// Whether to periodically refresh the clients which underlie an open channel
     #[serde(default)]
     pub refresh: bool,
+    /// Whether to enable misbehaviour detection for clients
     #[serde(default)]
     pub misbehaviour: bool,
+    /// Whether to upgrade clients once the chain they track went through a scheduled upgrade.
+    #[serde(default = "default::client_upgrade")]
+    pub upgrade: bool,
+    /// Fraction of the trusting period below which the time left before a client
+    /// expires makes its refresh urgent.
+    #[serde(default = "default::urgent_refresh_threshold")]
+    pub urgent_refresh_threshold: RefreshRate,
 }
 
-#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+impl Default for Clients {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            refresh: false,
+            misbehaviour: false,
+            upgrade: default::client_upgrade(),
+            urgent_refresh_threshold: default::urgent_refresh_threshold(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Connections {
+    /// Whether to enable the connection workers for handshake completion
     pub enabled: bool,
 }
 
-#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Channels {
+    /// Whether to enable the channel workers for handshake completion
     pub enabled: bool,
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct Packets {
+    /// Whether to enable the packet workers
     pub enabled: bool,
+    /// Interval, in number of blocks, at which pending packets are cleared.
+    /// A value of `0` disables periodic packet clearing.
     #[serde(default = "default::clear_packets_interval")]
     pub clear_interval: u64,
+    /// Whether to clear pending packets on start
     #[serde(default = "default::clear_on_start")]
     pub clear_on_start: bool,
+    /// Whether to check that the submitted transactions have been delivered
     #[serde(default = "default::tx_confirmation")]
     pub tx_confirmation: bool,
+    /// Whether to register the relayer as the counterparty payee on fee-enabled channels
     #[serde(default = "default::auto_register_counterparty_payee")]
     pub auto_register_counterparty_payee: bool,
+    /// Maximum size of the memo field of the ICS20 packets to relay
     #[serde(default = "default::ics20_max_memo_size")]
     pub ics20_max_memo_size: Ics20FieldSizeLimit,
+    /// Maximum size of the receiver field of the ICS20 packets to relay
     #[serde(default = "default::ics20_max_receiver_size")]
     pub ics20_max_receiver_size: Ics20FieldSizeLimit,
+    /// Maximum number of packets to clear each time packet clearing is triggered
     #[serde(default = "default::clear_limit")]
     pub clear_limit: usize,
 
@@ -456,7 +575,7 @@ impl Default for Packets {
 /// Log levels are wrappers over [`tracing_core::Level`].
 ///
 /// [`tracing_core::Level`]: https://docs.rs/tracing-core/0.1.17/tracing_core/struct.Level.html
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
     Trace,
@@ -484,26 +603,36 @@ impl Display for LogLevel {
     }
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(default, deny_unknown_fields)]
 pub struct GlobalConfig {
+    /// The verbosity of the relayer logging output
     pub log_level: LogLevel,
+    /// The directory in which detected misbehaviour evidence is archived,
+    /// `~/.hermes/evidence` if unset
+    pub evidence_dir: Option<PathBuf>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct TelemetryConfig {
+    /// Whether to enable the telemetry service
     pub enabled: bool,
+    /// The host over which the metrics are served
     pub host: String,
+    /// The port over which the metrics are served
     pub port: u16,
+    /// The histogram buckets of the latency metrics
     #[serde(default = "HistogramBuckets::default")]
     pub buckets: HistogramBuckets,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct HistogramBuckets {
+    /// The range, in milliseconds, of the buckets of the `tx_latency_submitted` metric
     #[serde(default = "default::latency_submitted")]
     pub latency_submitted: HistogramConfig,
+    /// The range, in milliseconds, of the buckets of the `tx_latency_confirmed` metric
     #[serde(default = "default::latency_confirmed")]
     pub latency_confirmed: HistogramConfig,
 }
@@ -517,7 +646,7 @@ impl Default for HistogramBuckets {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(try_from = "HistogramRangeUnchecked")]
 pub struct HistogramConfig {
     #[serde(flatten)]
@@ -566,11 +695,14 @@ impl Default for TelemetryConfig {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct RestConfig {
+    /// Whether to enable the REST service
     pub enabled: bool,
+    /// The host over which the REST API is served
     pub host: String,
+    /// The port over which the REST API is served
     pub port: u16,
 }
 
@@ -588,7 +720,7 @@ impl Default for RestConfig {
 /// TODO: Ethermint `pk_type` to be restricted
 /// after the Cosmos SDK release with ethsecp256k1
 /// <https://github.com/cosmos/cosmos-sdk/pull/9981>
-#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
 #[serde(
     rename_all = "lowercase",
     tag = "derivation",
@@ -613,23 +745,27 @@ impl Display for AddressType {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct GenesisRestart {
+    #[schemars(schema_with = "schema::block_height")]
     pub restart_height: BlockHeight,
+    #[schemars(schema_with = "schema::url")]
     pub archive_addr: Url,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(tag = "mode", rename_all = "lowercase")]
 pub enum EventSourceMode {
     /// Push-based event source, via WebSocket
     Push {
         /// The WebSocket URL to connect to
+        #[schemars(schema_with = "schema::websocket_url")]
         url: WebSocketClientUrl,
 
         /// Maximum amount of time to wait for a NewBlock event before emitting the event batch
         #[serde(default = "default::batch_delay", with = "humantime_serde")]
+        #[schemars(schema_with = "schema::duration")]
         batch_delay: Duration,
     },
 
@@ -638,6 +774,7 @@ pub enum EventSourceMode {
     Pull {
         /// The polling interval
         #[serde(default = "default::poll_interval", with = "humantime_serde")]
+        #[schemars(schema_with = "schema::duration")]
         interval: Duration,
 
         /// The maximum retries to collect the block results
@@ -645,6 +782,19 @@ pub enum EventSourceMode {
         #[serde(default = "default::max_retries")]
         max_retries: u32,
     },
+
+    /// Replay of an event recording made with `hermes start --record-events`,
+    /// useful to reproduce the exact sequence of events seen by a relayer
+    Replay {
+        /// Path to the recording to replay
+        path: PathBuf,
+
+        /// Replay speed relative to the recorded timing, eg. `2.0` replays twice
+        /// as fast as the events were recorded. A speed of `0` replays the
+        /// recording as fast as possible.
+        #[serde(default = "default::replay_speed")]
+        speed: f64,
+    },
 }
 
 // NOTE: To work around a limitation of serde, which does not allow
@@ -661,6 +811,7 @@ pub enum ChainConfig {
     // Reuse CosmosSdkConfig for tendermint light clients
     Namada(CosmosSdkConfig),
     Penumbra(PenumbraConfig),
+    SoloMachine(SoloMachineConfig),
 }
 
 impl ChainConfig {
@@ -669,6 +820,7 @@ impl ChainConfig {
             Self::CosmosSdk(config) => &config.id,
             Self::Namada(config) => &config.id,
             Self::Penumbra(config) => &config.id,
+            Self::SoloMachine(config) => &config.id,
         }
     }
 
@@ -677,6 +829,7 @@ impl ChainConfig {
             Self::CosmosSdk(config) => &config.packet_filter,
             Self::Namada(config) => &config.packet_filter,
             Self::Penumbra(config) => &config.packet_filter,
+            Self::SoloMachine(config) => &config.packet_filter,
         }
     }
 
@@ -685,6 +838,7 @@ impl ChainConfig {
             Self::CosmosSdk(config) => config.max_block_time,
             Self::Namada(config) => config.max_block_time,
             Self::Penumbra(config) => config.max_block_time,
+            Self::SoloMachine(config) => config.max_block_time,
         }
     }
 
@@ -693,6 +847,7 @@ impl ChainConfig {
             Self::CosmosSdk(config) => &config.key_name,
             Self::Namada(config) => &config.key_name,
             Self::Penumbra(config) => &config.stub_key_name,
+            Self::SoloMachine(config) => &config.key_name,
         }
     }
 
@@ -701,6 +856,7 @@ impl ChainConfig {
             Self::CosmosSdk(config) => config.key_name = key_name,
             Self::Namada(config) => config.key_name = key_name,
             Self::Penumbra(_) => { /* no-op */ }
+            Self::SoloMachine(config) => config.key_name = key_name,
         }
     }
 
@@ -729,6 +885,19 @@ impl ChainConfig {
                     .collect()
             }
             ChainConfig::Penumbra(_) => vec![],
+            ChainConfig::SoloMachine(config) => {
+                let keyring = KeyRing::new_secp256k1(
+                    Store::Test,
+                    &config.account_prefix,
+                    &config.id,
+                    &config.key_store_folder,
+                )?;
+                keyring
+                    .keys()?
+                    .into_iter()
+                    .map(|(key_name, keys)| (key_name, keys.into()))
+                    .collect()
+            }
         };
 
         Ok(keys)
@@ -739,6 +908,7 @@ impl ChainConfig {
             Self::CosmosSdk(config) => config.trust_threshold,
             Self::Namada(config) => config.trust_threshold,
             Self::Penumbra(config) => config.trust_threshold,
+            Self::SoloMachine(_) => TrustThreshold::default(),
         }
     }
 
@@ -746,6 +916,7 @@ impl ChainConfig {
         match self {
             Self::CosmosSdk(config) | Self::Namada(config) => config.clear_interval,
             Self::Penumbra(config) => config.clear_interval,
+            Self::SoloMachine(config) => config.clear_interval,
         }
     }
 
@@ -753,6 +924,56 @@ impl ChainConfig {
         match self {
             Self::CosmosSdk(config) | Self::Namada(config) => config.query_packets_chunk_size,
             Self::Penumbra(config) => config.query_packets_chunk_size,
+            Self::SoloMachine(config) => config.query_packets_chunk_size,
+        }
+    }
+
+    /// Enable dry-run mode for this chain, returning `false` if
+    /// the chain does not support it, in which case the chain
+    /// refuses to submit any transaction instead.
+    pub fn set_dry_run(&mut self, output: Option<PathBuf>) -> bool {
+        match self {
+            Self::CosmosSdk(config) => {
+                config.dry_run = true;
+                config.dry_run_output = output;
+                true
+            }
+            Self::Namada(config) => {
+                config.dry_run = true;
+                false
+            }
+            Self::Penumbra(config) => {
+                config.dry_run = true;
+                false
+            }
+            Self::SoloMachine(config) => {
+                config.dry_run = true;
+                false
+            }
+        }
+    }
+
+    /// Enable generate-only mode for this chain, returning `false` if
+    /// the chain does not support it, in which case the chain
+    /// refuses to submit any transaction instead.
+    pub fn set_generate_only(&mut self, generate_only: GenerateOnly) -> bool {
+        match self {
+            Self::CosmosSdk(config) => {
+                config.generate_only = Some(generate_only);
+                true
+            }
+            Self::Namada(config) => {
+                config.generate_only = Some(generate_only);
+                false
+            }
+            Self::Penumbra(config) => {
+                config.generate_only = true;
+                false
+            }
+            Self::SoloMachine(config) => {
+                config.generate_only = true;
+                false
+            }
         }
     }
 
@@ -762,6 +983,7 @@ impl ChainConfig {
                 config.query_packets_chunk_size = query_packets_chunk_size
             }
             Self::Penumbra(config) => config.query_packets_chunk_size = query_packets_chunk_size,
+            Self::SoloMachine(config) => config.query_packets_chunk_size = query_packets_chunk_size,
         }
     }
 
@@ -773,14 +995,14 @@ impl ChainConfig {
                 .get(channel_id)
                 .map(|seqs| Cow::Borrowed(seqs.as_slice()))
                 .unwrap_or_else(|| Cow::Owned(Vec::new())),
-            Self::Penumbra(_config) => Cow::Owned(Vec::new()),
+            Self::Penumbra(_) | Self::SoloMachine(_) => Cow::Owned(Vec::new()),
         }
     }
 
     pub fn allow_ccq(&self) -> bool {
         match self {
             Self::CosmosSdk(config) | Self::Namada(config) => config.allow_ccq,
-            Self::Penumbra(_config) => false,
+            Self::Penumbra(_) | Self::SoloMachine(_) => false,
         }
     }
 
@@ -788,12 +1010,13 @@ impl ChainConfig {
         match self {
             Self::CosmosSdk(config) | Self::Namada(config) => config.clock_drift,
             Self::Penumbra(config) => config.clock_drift,
+            Self::SoloMachine(config) => config.clock_drift,
         }
     }
 
     pub fn keyring_support(&self) -> bool {
         match self {
-            Self::Namada(_) | Self::CosmosSdk(_) => true,
+            Self::Namada(_) | Self::CosmosSdk(_) | Self::SoloMachine(_) => true,
             Self::Penumbra(_) => false,
         }
     }
@@ -831,6 +1054,9 @@ impl<'de> Deserialize<'de> for ChainConfig {
             "Penumbra" => PenumbraConfig::deserialize(value)
                 .map(Self::Penumbra)
                 .map_err(|e| serde::de::Error::custom(format!("invalid Penumbra config: {e}"))),
+            "SoloMachine" => SoloMachineConfig::deserialize(value)
+                .map(Self::SoloMachine)
+                .map_err(|e| serde::de::Error::custom(format!("invalid SoloMachine config: {e}"))),
             //
             chain_type => Err(serde::de::Error::custom(format!(
                 "unknown chain type: {chain_type}",
@@ -839,16 +1065,42 @@ impl<'de> Deserialize<'de> for ChainConfig {
     }
 }
 
-/// Attempt to load and parse the TOML config file as a `Config`.
+// /!\ Update me when adding a new chain type!
+impl JsonSchema for ChainConfig {
+    fn schema_name() -> String {
+        "ChainConfig".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        // The `type` key defaults to `CosmosSdk`, see the `Deserialize` impl above
+        schema::one_of(vec![
+            schema::with_chain_type(CosmosSdkConfig::json_schema(gen), "CosmosSdk", false),
+            schema::with_chain_type(CosmosSdkConfig::json_schema(gen), "Namada", true),
+            schema::with_chain_type(PenumbraConfig::json_schema(gen), "Penumbra", true),
+            schema::with_chain_type(SoloMachineConfig::json_schema(gen), "SoloMachine", true),
+        ])
+    }
+}
+
+/// Attempt to load and parse the TOML config file as a `Config`,
+/// after resolving its environment variables and includes.
 pub fn load(path: impl AsRef<Path>) -> Result<Config, Error> {
     let config_toml = fs::read_to_string(&path).map_err(Error::io)?;
 
-    let config = toml::from_str::<Config>(&config_toml[..]).map_err(Error::decode)?;
+    let config_table = resolve::resolve(path.as_ref(), &config_toml)?;
+
+    let config = toml::Value::Table(config_table)
+        .try_into::<Config>()
+        .map_err(Error::decode)?;
 
     Ok(config)
 }
 
 /// Serialize the given `Config` as TOML to the given config file.
+///
+/// The whole file is written from the `Config`, so a `Config` obtained with [`load`] must not
+/// be stored back into its file: its environment variables and includes are resolved, and
+/// would be written in plain text. Edit the TOML document of the file instead.
 pub fn store(config: &Config, path: impl AsRef<Path>) -> Result<(), Error> {
     let mut file = if path.as_ref().exists() {
         fs::OpenOptions::new().write(true).truncate(true).open(path)
@@ -861,7 +1113,7 @@ pub fn store(config: &Config, path: impl AsRef<Path>) -> Result<(), Error> {
 }
 
 /// Serialize the given `Config` as TOML to the given writer.
-pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<(), Error> {
+pub fn store_writer(config: &Config, mut writer: impl Write) -> Result<(), Error> {
     let toml_config = toml::to_string_pretty(&config).map_err(Error::encode)?;
 
     writeln!(writer, "{toml_config}").map_err(Error::io)?;
@@ -869,9 +1121,11 @@ pub(crate) fn store_writer(config: &Config, mut writer: impl Write) -> Result<()
     Ok(())
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema)]
 pub struct TracingServerConfig {
+    /// Whether to enable the tracing server
     pub enabled: bool,
+    /// The port over which the tracing directives are served
     pub port: u16,
 }
 
@@ -912,8 +1166,8 @@ impl From<CosmosConfigError> for Error {
 mod tests {
     use core::str::FromStr;
 
-    use super::{load, parse_gas_prices, store_writer, ChainConfig};
-    use crate::config::GasPrice;
+    use super::{load, parse_gas_prices, store_writer, ChainConfig, Diagnostic};
+    use crate::config::{GasPrice, RefreshRate};
     use test_log::test;
 
     #[test]
@@ -1027,7 +1281,9 @@ mod tests {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
                 chain_config.excluded_sequences.clone()
             }
-            ChainConfig::Penumbra(_) => panic!("expected cosmos chain config"),
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => {
+                panic!("expected cosmos chain config")
+            }
         };
 
         assert_eq!(excluded_sequences1, excluded_sequences2);
@@ -1104,4 +1360,56 @@ mod tests {
             assert_eq!(expected, parsed);
         }
     }
+
+    fn example_config() -> super::Config {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        load(path).expect("could not parse config")
+    }
+
+    #[test]
+    fn urgent_refresh_threshold_below_refresh_rate_is_valid() {
+        let mut config = example_config();
+        config.mode.clients.urgent_refresh_threshold = RefreshRate::new(1, 10);
+
+        assert!(config.validate_config().is_ok());
+    }
+
+    #[test]
+    fn urgent_refresh_threshold_out_of_range_is_invalid() {
+        for threshold in [
+            RefreshRate::new(0, 10),
+            RefreshRate::new(1, 1),
+            RefreshRate::new(3, 2),
+            RefreshRate::new(1, 0),
+        ] {
+            let mut config = example_config();
+            config.mode.clients.urgent_refresh_threshold = threshold;
+
+            assert!(
+                matches!(config.validate_config(), Err(Diagnostic::Error(_))),
+                "threshold {threshold} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn urgent_refresh_threshold_above_refresh_rate_is_invalid() {
+        let mut config = example_config();
+        config.mode.clients.urgent_refresh_threshold = RefreshRate::new(1, 2);
+
+        for chain_config in config.chains.iter_mut() {
+            if let ChainConfig::CosmosSdk(cosmos_config) = chain_config {
+                cosmos_config.client_refresh_rate = RefreshRate::new(1, 3);
+            }
+        }
+
+        assert!(matches!(
+            config.validate_config(),
+            Err(Diagnostic::Error(_))
+        ));
+    }
 }
@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::de::Error as DeserializeError;
 use serde::de::Unexpected;
 use serde::Deserialize;
@@ -15,10 +16,14 @@ flex_error::define_error! {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize, JsonSchema)]
 pub struct DynamicGasPrice {
+    /// Whether to query the gas price of the chain instead of using the configured one
     pub enabled: bool,
+    /// The multiplier applied to the queried gas price
+    #[schemars(range(min = 1.0))]
     pub multiplier: f64,
+    /// The maximum gas price
     pub max: f64,
 }
 
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use flex_error::{define_error, TraceError};
@@ -55,6 +57,44 @@ define_error! {
             format!("invalid gas price: {}", e.price)
         },
 
+        UndefinedVariable
+            { name: String }
+            |e| {
+                format!("environment variable '{}' referenced in the config file is not set", e.name)
+            },
+
+        UnterminatedVariable
+            { value: String }
+            |e| {
+                format!("unterminated environment variable reference in '{}', expected '${{VAR}}'", e.value)
+            },
+
+        InvalidInclude
+            { reason: String }
+            |e| {
+                format!("invalid include directive: {}", e.reason)
+            },
+
+        IncludeIo
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            |e| {
+                format!("I/O error when reading included config file '{}'", e.path.display())
+            },
+
+        IncludeDecode
+            { path: PathBuf }
+            [ TraceError<toml::de::Error> ]
+            |e| {
+                format!("invalid chain configuration in included config file '{}'", e.path.display())
+            },
+
+        InvalidUrgentRefreshThreshold
+            { threshold: String, reason: String }
+            |e| {
+                format!("invalid `urgent_refresh_threshold` '{}': {}", e.threshold, e.reason)
+            },
+
         CosmosConfigError { reason: String }
         |e| {
             format!("invalid cosmos config: {}", e.reason)
@@ -0,0 +1,214 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::de::Error as DeserializeError;
+use serde::de::Unexpected;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde_derive::Serialize;
+
+use crate::config::schema;
+
+flex_error::define_error! {
+    Error {
+        EmptyMultipliers
+            |_| { "`multipliers` in fee_bump configuration must not be empty" },
+
+        MultiplierTooSmall
+            { value: f64 }
+            |e| {
+                format_args!("`multipliers` in fee_bump configuration must be greater than {}, found {}",
+                FeeBump::MIN_MULTIPLIER, e.value)
+            },
+    }
+}
+
+/// Configuration of the fee bumping of transactions which stay unconfirmed
+/// for too long, and are therefore resubmitted.
+///
+/// The gas price of the n-th resubmission of a transaction is the configured
+/// gas price multiplied by the n-th multiplier of the schedule, or by the last one
+/// once the schedule is exhausted. The bumped gas price is capped by `dynamic_gas_price.max`,
+/// whether the dynamic gas price is enabled or not.
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+pub struct FeeBump {
+    /// Whether to resubmit unconfirmed transactions with an increasing fee
+    pub enabled: bool,
+    /// The gas price multipliers of the successive resubmissions
+    #[schemars(default = "default_multipliers", schema_with = "multipliers_schema")]
+    pub multipliers: Vec<f64>,
+    /// Whether to sign the resubmitted transactions with the account sequence
+    /// of the unconfirmed ones, to replace them in the mempool
+    #[schemars(default)]
+    pub reuse_sequence: bool,
+}
+
+fn default_multipliers() -> Vec<f64> {
+    FeeBump::DEFAULT_MULTIPLIERS.to_vec()
+}
+
+fn multipliers_schema(_gen: &mut SchemaGenerator) -> Schema {
+    schema::from_json(serde_json::json!({
+        "type": "array",
+        "items": { "type": "number", "exclusiveMinimum": FeeBump::MIN_MULTIPLIER },
+        "minItems": 1,
+    }))
+}
+
+impl FeeBump {
+    const DEFAULT_MULTIPLIERS: [f64; 3] = [1.2, 1.5, 2.0];
+    const MIN_MULTIPLIER: f64 = 1.0;
+
+    pub fn enabled(multipliers: Vec<f64>, reuse_sequence: bool) -> Result<Self, Error> {
+        Self::new(true, multipliers, reuse_sequence)
+    }
+
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            multipliers: Self::DEFAULT_MULTIPLIERS.to_vec(),
+            reuse_sequence: false,
+        }
+    }
+
+    pub fn new(enabled: bool, multipliers: Vec<f64>, reuse_sequence: bool) -> Result<Self, Error> {
+        if multipliers.is_empty() {
+            return Err(Error::empty_multipliers());
+        }
+
+        if let Some(value) = multipliers.iter().find(|m| **m <= Self::MIN_MULTIPLIER) {
+            return Err(Error::multiplier_too_small(*value));
+        }
+
+        Ok(Self {
+            enabled,
+            multipliers,
+            reuse_sequence,
+        })
+    }
+
+    /// The gas price multiplier to apply on the given resubmission attempt,
+    /// starting from 1 for the first resubmission.
+    /// Returns `None` if fee bumping is disabled or for the initial submission.
+    pub fn multiplier(&self, attempt: u32) -> Option<f64> {
+        if !self.enabled || attempt == 0 {
+            return None;
+        }
+
+        let index = (attempt as usize - 1).min(self.multipliers.len() - 1);
+        self.multipliers.get(index).copied()
+    }
+}
+
+impl Default for FeeBump {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl<'de> Deserialize<'de> for FeeBump {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct RawFeeBump {
+            enabled: bool,
+            #[serde(default = "default_multipliers")]
+            multipliers: Vec<f64>,
+            #[serde(default)]
+            reuse_sequence: bool,
+        }
+
+        let RawFeeBump {
+            enabled,
+            multipliers,
+            reuse_sequence,
+        } = RawFeeBump::deserialize(deserializer)?;
+
+        FeeBump::new(enabled, multipliers, reuse_sequence).map_err(|e| match e.detail() {
+            ErrorDetail::EmptyMultipliers(_) => {
+                D::Error::invalid_length(0, &"a non-empty list of multipliers")
+            }
+            ErrorDetail::MultiplierTooSmall(e) => D::Error::invalid_value(
+                Unexpected::Float(e.value),
+                &format!(
+                    "a floating-point value greater than {}",
+                    Self::MIN_MULTIPLIER
+                )
+                .as_str(),
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(dead_code)] // the field of the struct `DummyConfig` defined below is never accessed
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+    use test_log::test;
+
+    #[derive(Debug, Deserialize)]
+    struct DummyConfig {
+        fee_bump: FeeBump,
+    }
+
+    #[test]
+    fn parse_fee_bump() {
+        let config = toml::from_str::<DummyConfig>(
+            "fee_bump = { enabled = true, multipliers = [1.1, 1.3], reuse_sequence = true }",
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.fee_bump,
+            FeeBump::enabled(vec![1.1, 1.3], true).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_fee_bump_defaults() {
+        let config = toml::from_str::<DummyConfig>("fee_bump = { enabled = true }").unwrap();
+
+        assert_eq!(
+            config.fee_bump,
+            FeeBump::enabled(FeeBump::DEFAULT_MULTIPLIERS.to_vec(), false).unwrap()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_fee_bump_multiplier() {
+        let err = toml::from_str::<DummyConfig>(
+            "fee_bump = { enabled = true, multipliers = [1.5, 0.9] }",
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.contains("expected a floating-point value greater than"));
+    }
+
+    #[test]
+    fn parse_empty_fee_bump_multipliers() {
+        let err = toml::from_str::<DummyConfig>("fee_bump = { enabled = true, multipliers = [] }")
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains("non-empty list of multipliers"));
+    }
+
+    #[test]
+    fn multiplier_schedule() {
+        let fee_bump = FeeBump::enabled(vec![1.2, 1.5], false).unwrap();
+
+        assert_eq!(fee_bump.multiplier(0), None);
+        assert_eq!(fee_bump.multiplier(1), Some(1.2));
+        assert_eq!(fee_bump.multiplier(2), Some(1.5));
+        assert_eq!(fee_bump.multiplier(5), Some(1.5));
+
+        assert_eq!(FeeBump::disabled().multiplier(1), None);
+    }
+}
@@ -5,7 +5,11 @@
 use core::fmt;
 use core::str::FromStr;
 use itertools::Itertools;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::json;
 use std::collections::HashMap;
 use std::hash::Hash;
 
@@ -14,6 +18,8 @@ use ibc_relayer_types::bigint::U256;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, PortId};
 use ibc_relayer_types::events::IbcEventType;
 
+use crate::config::schema;
+
 /// Represents all the filtering policies for packets.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PacketFilter {
@@ -33,6 +39,47 @@ impl Default for PacketFilter {
     }
 }
 
+// The channel policy is flattened in the packet filter, so that its `policy` and `list`
+// keys sit next to `min_fees`.
+impl JsonSchema for PacketFilter {
+    fn schema_name() -> String {
+        "PacketFilter".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let list = schema::to_json(gen.subschema_for::<ChannelFilters>());
+        let min_fees = schema::to_json(gen.subschema_for::<HashMap<String, FeePolicy>>());
+
+        let policy = |policy: &str, description: &str, with_list: bool| {
+            let mut properties = json!({
+                "policy": { "const": policy },
+                "min_fees": min_fees,
+            });
+
+            let mut required = vec!["policy"];
+
+            if with_list {
+                properties["list"] = list.clone();
+                required.push("list");
+            }
+
+            schema::from_json(json!({
+                "description": description,
+                "type": "object",
+                "properties": properties,
+                "required": required,
+                "additionalProperties": false,
+            }))
+        };
+
+        schema::one_of(vec![
+            policy("allow", "Allow packets from the specified channels", true),
+            policy("deny", "Deny packets from the specified channels", true),
+            policy("allowall", "Allow any & all packets", false),
+        ])
+    }
+}
+
 impl PacketFilter {
     pub fn new(
         channel_policy: ChannelPolicy,
@@ -71,7 +118,7 @@ pub enum ChannelPolicy {
 
 /// Represents the policy used to filter incentivized packets.
 /// Currently only filtering on `recv_fee` is authorized.
-#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct FeePolicy {
     recv: Vec<MinFee>,
 }
@@ -93,7 +140,7 @@ impl FeePolicy {
 
 /// Represents the minimum fee authorized when filtering.
 /// If no denom is specified, any denom is allowed.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct MinFee {
     amount: u64,
     denom: Option<String>,
@@ -169,6 +216,30 @@ impl ChannelFilters {
         })
     }
 
+    /// Add an exact filter for every given [`PortId`]-[`ChannelId`] pair which is
+    /// not already matched by this filter policy, keeping the existing filters as is.
+    /// Returns the pairs for which a filter was added.
+    pub fn extend_exact(
+        &mut self,
+        channels: impl IntoIterator<Item = (PortId, ChannelId)>,
+    ) -> Vec<(PortId, ChannelId)> {
+        let mut added = Vec::new();
+
+        for (port_id, channel_id) in channels {
+            if self.matches((&port_id, &channel_id)) {
+                continue;
+            }
+
+            self.0.push((
+                FilterPattern::Exact(port_id.clone()),
+                FilterPattern::Exact(channel_id.clone()),
+            ));
+            added.push((port_id, channel_id));
+        }
+
+        added
+    }
+
     /// An iterator over the [`PortId`]-[`ChannelId`] pairs that don't contain wildcards.
     pub fn iter_exact(&self) -> impl Iterator<Item = (&PortId, &ChannelId)> {
         self.0.iter().filter_map(|port_chan_filter| {
@@ -233,6 +304,25 @@ impl Serialize for ChannelFilters {
     }
 }
 
+impl JsonSchema for ChannelFilters {
+    fn schema_name() -> String {
+        "ChannelFilters".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        schema::from_json(json!({
+            "description": "Pairs of port and channel identifiers, which may contain '*' wildcards",
+            "type": "array",
+            "items": {
+                "type": "array",
+                "items": [{ "type": "string" }, { "type": "string" }],
+                "minItems": 2,
+                "maxItems": 2,
+            },
+        }))
+    }
+}
+
 /// Newtype wrapper for expressing wildcard patterns compiled to a [`regex::Regex`].
 #[derive(Clone, Debug)]
 pub struct Wildcard {
@@ -451,6 +541,33 @@ mod tests {
         dbg!(filter_policy);
     }
 
+    #[test]
+    fn extend_exact_keeps_existing_filters() {
+        let transfer = PortId::transfer();
+
+        let mut filters = ChannelFilters(vec![
+            (
+                FilterPattern::Exact(transfer.clone()),
+                FilterPattern::Exact(ChannelId::new(0)),
+            ),
+            (
+                FilterPattern::Wildcard("ica*".parse().unwrap()),
+                FilterPattern::Wildcard("*".parse().unwrap()),
+            ),
+        ]);
+
+        let added = filters.extend_exact([
+            (transfer.clone(), ChannelId::new(0)),
+            (PortId::from_str("icahost").unwrap(), ChannelId::new(4)),
+            (transfer.clone(), ChannelId::new(1)),
+            (transfer.clone(), ChannelId::new(1)),
+        ]);
+
+        assert_eq!(added, vec![(transfer.clone(), ChannelId::new(1))]);
+        assert_eq!(filters.len(), 3);
+        assert!(filters.matches((&transfer, &ChannelId::new(1))));
+    }
+
     #[test]
     fn serialize_packet_filter_policy() {
         use std::str::FromStr;
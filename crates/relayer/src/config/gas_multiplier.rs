@@ -1,6 +1,11 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::de::Unexpected;
 use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::config::schema;
+
 flex_error::define_error! {
     Error {
         TooSmall
@@ -73,6 +78,23 @@ impl From<GasMultiplier> for f64 {
     }
 }
 
+impl JsonSchema for GasMultiplier {
+    fn schema_name() -> String {
+        "GasMultiplier".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        schema::from_json(serde_json::json!({
+            "type": "number",
+            "minimum": Self::MIN_BOUND,
+        }))
+    }
+}
+
 #[cfg(test)]
 #[allow(dead_code)] // the field of the struct `DummyConfig` defined below is never accessed
 mod tests {
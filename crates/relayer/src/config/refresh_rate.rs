@@ -1,7 +1,13 @@
+use core::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+use crate::config::schema;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct RefreshRate {
     numerator: u64,
@@ -21,6 +27,12 @@ impl RefreshRate {
     }
 }
 
+impl Display for RefreshRate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
 impl FromStr for RefreshRate {
     type Err = String;
 
@@ -53,3 +65,21 @@ impl<'de> Deserialize<'de> for RefreshRate {
         RefreshRate::from_str(&s).map_err(serde::de::Error::custom)
     }
 }
+
+impl JsonSchema for RefreshRate {
+    fn schema_name() -> String {
+        "RefreshRate".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        schema::from_json(serde_json::json!({
+            "description": "A fraction, eg. '1/3'",
+            "type": "string",
+            "pattern": "^[0-9]+/[0-9]+$",
+        }))
+    }
+}
@@ -0,0 +1,240 @@
+//! Resolution of the environment variables and the includes of a config file.
+//!
+//! String values of the config file can reference environment variables as `${VAR}`,
+//! and `$${` stands for a literal `${`. The top-level `include` list holds paths or
+//! glob patterns of files, relative to the config file, which each hold the
+//! configuration of a single chain, appended to the `chains` of the config file.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml::{Table, Value};
+
+use crate::config::Error;
+
+/// Resolve the environment variables and the includes of the given config file.
+pub(crate) fn resolve(path: &Path, contents: &str) -> Result<Table, Error> {
+    resolve_with(path, contents, &|name| std::env::var(name).ok())
+}
+
+fn resolve_with(
+    path: &Path,
+    contents: &str,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<Table, Error> {
+    let mut table: Table = toml::from_str(contents).map_err(Error::decode)?;
+    interpolate_table(&mut table, lookup)?;
+
+    let Some(include) = table.remove("include") else {
+        return Ok(table);
+    };
+
+    let base = path.parent().unwrap_or_else(|| Path::new("."));
+
+    let mut included = vec![];
+    for pattern in include_patterns(include)? {
+        for path in expand(base, &pattern)? {
+            let contents =
+                fs::read_to_string(&path).map_err(|e| Error::include_io(path.clone(), e))?;
+
+            let mut chain: Table =
+                toml::from_str(&contents).map_err(|e| Error::include_decode(path.clone(), e))?;
+            interpolate_table(&mut chain, lookup)?;
+
+            included.push(Value::Table(chain));
+        }
+    }
+
+    match table
+        .entry("chains")
+        .or_insert_with(|| Value::Array(vec![]))
+    {
+        Value::Array(chains) => chains.extend(included),
+        _ => {
+            return Err(Error::invalid_include(
+                "'chains' must be a list of chains to include chains from other files".to_string(),
+            ))
+        }
+    }
+
+    Ok(table)
+}
+
+fn include_patterns(include: Value) -> Result<Vec<String>, Error> {
+    let Value::Array(values) = include else {
+        return Err(Error::invalid_include(format!(
+            "expected a list of paths, found a {}",
+            include.type_str()
+        )));
+    };
+
+    values
+        .into_iter()
+        .map(|value| match value {
+            Value::String(pattern) => Ok(pattern),
+            value => Err(Error::invalid_include(format!(
+                "expected a path, found a {}",
+                value.type_str()
+            ))),
+        })
+        .collect()
+}
+
+/// The files matched by the given pattern, relative to the given directory.
+fn expand(base: &Path, pattern: &str) -> Result<Vec<PathBuf>, Error> {
+    let full_pattern = base.join(pattern);
+    let full_pattern = full_pattern.to_str().ok_or_else(|| {
+        Error::invalid_include(format!(
+            "path '{}' is not valid UTF-8",
+            full_pattern.display()
+        ))
+    })?;
+
+    let paths = glob::glob(full_pattern)
+        .map_err(|e| Error::invalid_include(format!("invalid pattern '{pattern}': {e}")))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| Error::include_io(e.path().to_path_buf(), e.into_error()))?;
+
+    if paths.is_empty() {
+        return Err(Error::invalid_include(format!(
+            "'{pattern}' does not match any file"
+        )));
+    }
+
+    Ok(paths)
+}
+
+fn interpolate_table(
+    table: &mut Table,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), Error> {
+    table
+        .iter_mut()
+        .try_for_each(|(_, value)| interpolate_value(value, lookup))
+}
+
+fn interpolate_value(
+    value: &mut Value,
+    lookup: &impl Fn(&str) -> Option<String>,
+) -> Result<(), Error> {
+    match value {
+        Value::String(s) => *s = interpolate(s, lookup)?,
+        Value::Array(values) => values
+            .iter_mut()
+            .try_for_each(|value| interpolate_value(value, lookup))?,
+        Value::Table(table) => interpolate_table(table, lookup)?,
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Replace the `${VAR}` references of the given string by the value of the variables.
+fn interpolate(value: &str, lookup: &impl Fn(&str) -> Option<String>) -> Result<String, Error> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        // `$${` is an escaped `${`
+        if rest[..start].ends_with('$') {
+            result.push_str(&rest[..start - 1]);
+            result.push_str("${");
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        result.push_str(&rest[..start]);
+
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| Error::unterminated_variable(value.to_string()))?;
+
+        let name = &rest[start + 2..start + end];
+        let variable = lookup(name).ok_or_else(|| Error::undefined_variable(name.to_string()))?;
+
+        result.push_str(&variable);
+        rest = &rest[start + end + 1..];
+    }
+
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "RPC_HOST" => Some("rpc.example.com".to_string()),
+            "KEY" => Some("relayer".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn interpolate_variables() {
+        assert_eq!(
+            interpolate("https://${RPC_HOST}:443", &lookup).unwrap(),
+            "https://rpc.example.com:443"
+        );
+        assert_eq!(
+            interpolate("${KEY}-${KEY}", &lookup).unwrap(),
+            "relayer-relayer"
+        );
+        assert_eq!(
+            interpolate("$${KEY} costs $5", &lookup).unwrap(),
+            "${KEY} costs $5"
+        );
+        assert!(interpolate("${UNSET}", &lookup).is_err());
+        assert!(interpolate("${KEY", &lookup).is_err());
+    }
+
+    #[test]
+    fn resolve_includes() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let dir = tempdir.path();
+        fs::create_dir_all(dir.join("chains")).unwrap();
+        fs::write(
+            dir.join("chains").join("ibc-1.toml"),
+            "id = 'ibc-1'\nkey_name = '${KEY}'\n",
+        )
+        .unwrap();
+        fs::write(dir.join("chains").join("ibc-2.toml"), "id = 'ibc-2'\n").unwrap();
+        fs::write(dir.join("chains").join("README.md"), "not a chain").unwrap();
+
+        let contents = r#"
+            include = ["chains/*.toml"]
+
+            [[chains]]
+            id = 'ibc-0'
+            rpc_addr = 'https://${RPC_HOST}'
+        "#;
+
+        let table = resolve_with(&dir.join("config.toml"), contents, &lookup).unwrap();
+
+        assert!(!table.contains_key("include"));
+
+        let chains = table["chains"].as_array().unwrap();
+        let ids = chains
+            .iter()
+            .map(|chain| chain["id"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["ibc-0", "ibc-1", "ibc-2"]);
+        assert_eq!(
+            chains[0]["rpc_addr"].as_str(),
+            Some("https://rpc.example.com")
+        );
+        assert_eq!(chains[1]["key_name"].as_str(), Some("relayer"));
+    }
+
+    #[test]
+    fn resolve_invalid_includes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+
+        assert!(resolve_with(&path, "include = 'chains/*.toml'", &lookup).is_err());
+        assert!(resolve_with(&path, "include = ['chains/*.toml']", &lookup).is_err());
+    }
+}
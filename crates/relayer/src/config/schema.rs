@@ -0,0 +1,400 @@
+//! JSON Schema of the configuration file, generated from the `serde` types of the configuration.
+//!
+//! Most types derive [`schemars::JsonSchema`], which picks up their `serde` attributes, their doc comments
+//! as descriptions, and the values of their `default` functions as defaults. The schemas of the
+//! foreign types of the configuration, which are serialized differently than their own
+//! `JsonSchema` impl would describe, if any, are defined here and referenced with
+//! `#[schemars(schema_with = "...")]`.
+
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{RootSchema, Schema, SchemaObject, SubschemaValidation};
+use serde_json::{json, Value};
+
+use crate::config::Config;
+
+/// The JSON Schema of the configuration file.
+pub fn config_schema() -> RootSchema {
+    let mut root = generator().into_root_schema_for::<Config>();
+
+    let metadata = root.schema.metadata();
+    metadata.title = Some("Hermes configuration".to_string());
+    metadata.description = Some("Configuration file of the Hermes IBC relayer".to_string());
+
+    // The includes are resolved before the configuration is deserialized, see `config::resolve`
+    root.schema.object().properties.insert(
+        "include".to_string(),
+        from_json(json!({
+            "description": "Paths or glob patterns, relative to the configuration file, of files which each hold the configuration of a single chain",
+            "type": "array",
+            "items": { "type": "string" },
+            "default": [],
+        })),
+    );
+
+    root
+}
+
+fn generator() -> SchemaGenerator {
+    // TOML has no null value, optional fields are simply left out
+    SchemaSettings::draft07()
+        .with(|settings| settings.option_add_null_type = false)
+        .into_generator()
+}
+
+/// The schema described by the given JSON value.
+pub(crate) fn from_json(value: Value) -> Schema {
+    serde_json::from_value(value).expect("invalid JSON Schema")
+}
+
+/// The given schema, as a JSON value.
+pub(crate) fn to_json(schema: Schema) -> Value {
+    serde_json::to_value(schema).expect("invalid JSON Schema")
+}
+
+/// A schema which only validates against exactly one of the given schemas.
+pub(crate) fn one_of(schemas: Vec<Schema>) -> Schema {
+    SchemaObject {
+        subschemas: Some(Box::new(SubschemaValidation {
+            one_of: Some(schemas),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// Add the `type` key of the given chain type to the given schema of a chain configuration.
+pub(crate) fn with_chain_type(chain: Schema, chain_type: &str, required: bool) -> Schema {
+    let mut chain = chain.into_object();
+    let object = chain.object();
+
+    object.properties.insert(
+        "type".to_string(),
+        from_json(json!({
+            "description": "The type of the chain",
+            "const": chain_type,
+        })),
+    );
+
+    if required {
+        object.required.insert("type".to_string());
+    }
+
+    chain.into()
+}
+
+/// A duration in the format of the `humantime` crate, eg. `10s` or `1h 30m`.
+pub(crate) fn duration(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "description": "A duration, eg. '500ms', '10s' or '1h 30m'",
+        "type": "string",
+    }))
+}
+
+pub(crate) fn url(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "string",
+        "format": "uri",
+    }))
+}
+
+pub(crate) fn urls(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "array",
+        "items": { "type": "string", "format": "uri" },
+    }))
+}
+
+pub(crate) fn websocket_url(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "string",
+        "format": "uri",
+        "pattern": "^wss?://",
+    }))
+}
+
+pub(crate) fn chain_id(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "description": "A chain identifier, eg. 'cosmoshub-4'",
+        "type": "string",
+        "minLength": 1,
+    }))
+}
+
+pub(crate) fn block_height(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "string",
+        "pattern": "^[0-9]+$",
+    }))
+}
+
+/// A size in bytes, either as a number of bytes or with a unit, eg. `32MiB`.
+pub(crate) fn byte_size(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "description": "A size, as a number of bytes or with a unit, eg. '32MiB'",
+        "anyOf": [
+            { "type": "integer", "minimum": 0 },
+            { "type": "string" },
+        ],
+    }))
+}
+
+/// A fraction, eg. `2/3`, or a table with a `numerator` and a `denominator`.
+pub(crate) fn trust_threshold(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "description": "A fraction, eg. '2/3', or a table with a numerator and a denominator",
+        "anyOf": [
+            { "type": "string", "pattern": "^[0-9]+/[0-9]+$" },
+            {
+                "type": "object",
+                "properties": {
+                    "numerator": { "type": ["integer", "string"] },
+                    "denominator": { "type": ["integer", "string"] },
+                },
+                "required": ["numerator", "denominator"],
+            },
+        ],
+    }))
+}
+
+pub(crate) fn compat_mode(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "description": "The CometBFT version to assume compatibility with",
+        "type": "string",
+        "enum": ["0.34", "0.37", "0.38"],
+    }))
+}
+
+pub(crate) fn proof_specs(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "description": "ICS23 proof specs, serialized as a JSON array",
+        "type": "string",
+    }))
+}
+
+pub(crate) fn kms_config(_: &mut SchemaGenerator) -> Schema {
+    from_json(json!({
+        "type": "object",
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::Path;
+
+    use schemars::JsonSchema;
+
+    use crate::chain::cosmos::config::CosmosSdkConfig;
+    use crate::config::{default, EventSourceMode, Packets, TelemetryConfig};
+
+    /// The schemas of the given type, as JSON.
+    fn schema_json<T: JsonSchema>() -> Value {
+        serde_json::to_value(generator().into_root_schema_for::<T>()).unwrap()
+    }
+
+    fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+        match schema.get("$ref").and_then(Value::as_str) {
+            Some(reference) => {
+                let name = reference.trim_start_matches("#/definitions/");
+                resolve(root, &root["definitions"][name])
+            }
+            None => schema,
+        }
+    }
+
+    /// The schema of the given property, looked up through the references
+    /// and the subschemas of the given schema.
+    fn property<'a>(root: &'a Value, schema: &'a Value, name: &str) -> Option<&'a Value> {
+        let schema = resolve(root, schema);
+
+        if let Some(property) = schema.get("properties").and_then(|p| p.get(name)) {
+            return Some(property);
+        }
+
+        if let Some(additional) = schema.get("additionalProperties").filter(|a| a.is_object()) {
+            return Some(additional);
+        }
+
+        ["allOf", "anyOf", "oneOf"]
+            .iter()
+            .filter_map(|key| schema.get(key).and_then(Value::as_array))
+            .flatten()
+            .find_map(|subschema| property(root, subschema, name))
+    }
+
+    fn default_of(root: &Value, path: &[&str]) -> Value {
+        let schema = path.iter().fold(root, |schema, name| {
+            property(root, schema, name).unwrap_or_else(|| panic!("no property `{name}`"))
+        });
+
+        schema
+            .get("default")
+            .cloned()
+            .unwrap_or_else(|| panic!("no default for `{}`", path.join(".")))
+    }
+
+    fn duration(duration: core::time::Duration) -> Value {
+        json!(humantime::format_duration(duration).to_string())
+    }
+
+    /// Check that every key of the given value is described by the given schema.
+    fn assert_covered(root: &Value, schema: &Value, value: &Value, path: &str) {
+        match value {
+            Value::Object(map) => {
+                for (name, value) in map {
+                    let path = format!("{path}.{name}");
+                    let schema = property(root, schema, name)
+                        .unwrap_or_else(|| panic!("`{path}` is not described by the schema"));
+
+                    assert_covered(root, schema, value, &path);
+                }
+            }
+            Value::Array(values) => {
+                let items = resolve(root, schema).get("items").filter(|i| i.is_object());
+
+                if let Some(items) = items {
+                    for value in values {
+                        assert_covered(root, items, value, path);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn chain_defaults_match_default_values() {
+        let root = schema_json::<CosmosSdkConfig>();
+
+        let cases = [
+            ("rpc_timeout", duration(default::rpc_timeout())),
+            ("trusted_node", json!(default::trusted_node())),
+            (
+                "query_packets_chunk_size",
+                json!(default::query_packets_chunk_size()),
+            ),
+            ("clock_drift", duration(default::clock_drift())),
+            ("max_block_time", duration(default::max_block_time())),
+            (
+                "max_grpc_decoding_size",
+                json!(default::max_grpc_decoding_size()),
+            ),
+            ("client_refresh_rate", json!(default::client_refresh_rate())),
+            ("ccv_consumer_chain", json!(default::ccv_consumer_chain())),
+            ("max_in_flight_txs", json!(default::max_in_flight_txs())),
+            ("trust_threshold", json!(default::trust_threshold())),
+            ("allow_ccq", json!(default::allow_ccq())),
+        ];
+
+        for (field, expected) in cases {
+            assert_eq!(
+                default_of(&root, &[field]),
+                expected,
+                "default of `{field}`"
+            );
+        }
+    }
+
+    #[test]
+    fn event_source_defaults_match_default_values() {
+        let root = schema_json::<EventSourceMode>();
+
+        assert_eq!(
+            default_of(&root, &["batch_delay"]),
+            duration(default::batch_delay())
+        );
+        assert_eq!(
+            default_of(&root, &["interval"]),
+            duration(default::poll_interval())
+        );
+        assert_eq!(
+            default_of(&root, &["max_retries"]),
+            json!(default::max_retries())
+        );
+        assert_eq!(
+            default_of(&root, &["speed"]),
+            json!(default::replay_speed())
+        );
+    }
+
+    #[test]
+    fn mode_defaults_match_default_values() {
+        let root = schema_json::<Packets>();
+
+        let cases = [
+            ("clear_interval", json!(default::clear_packets_interval())),
+            ("clear_on_start", json!(default::clear_on_start())),
+            ("tx_confirmation", json!(default::tx_confirmation())),
+            (
+                "auto_register_counterparty_payee",
+                json!(default::auto_register_counterparty_payee()),
+            ),
+            ("ics20_max_memo_size", json!(default::ics20_max_memo_size())),
+            (
+                "ics20_max_receiver_size",
+                json!(default::ics20_max_receiver_size()),
+            ),
+            ("clear_limit", json!(default::clear_limit())),
+        ];
+
+        for (field, expected) in cases {
+            assert_eq!(
+                default_of(&root, &[field]),
+                expected,
+                "default of `{field}`"
+            );
+        }
+
+        let root = schema_json::<Config>();
+
+        assert_eq!(
+            default_of(&root, &["mode", "clients", "upgrade"]),
+            json!(default::client_upgrade())
+        );
+        assert_eq!(
+            default_of(&root, &["mode", "clients", "urgent_refresh_threshold"]),
+            json!(default::urgent_refresh_threshold())
+        );
+    }
+
+    #[test]
+    fn telemetry_defaults_match_default_values() {
+        let root = schema_json::<TelemetryConfig>();
+
+        assert_eq!(
+            default_of(&root, &["buckets", "latency_submitted"]),
+            serde_json::to_value(default::latency_submitted()).unwrap()
+        );
+        assert_eq!(
+            default_of(&root, &["buckets", "latency_confirmed"]),
+            serde_json::to_value(default::latency_confirmed()).unwrap()
+        );
+    }
+
+    #[test]
+    fn schema_describes_example_configs() {
+        let root = serde_json::to_value(config_schema()).unwrap();
+
+        let configs = [
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../config.toml"),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/config/fixtures/relayer_conf_example.toml"
+            ),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/config/fixtures/relayer_conf_example_fee_filter.toml"
+            ),
+        ];
+
+        for path in configs {
+            let contents = std::fs::read_to_string(Path::new(path)).unwrap();
+            let config: toml::Table = toml::from_str(&contents).unwrap();
+            let config = serde_json::to_value(config).unwrap();
+
+            assert_covered(&root, &root, &config, "config");
+        }
+    }
+}
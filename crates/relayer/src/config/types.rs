@@ -57,9 +57,14 @@ pub mod max_msg_num {
         }
     }
 
+    use schemars::gen::SchemaGenerator;
+    use schemars::schema::Schema;
+    use schemars::JsonSchema;
     use serde::de::Unexpected;
     use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
+    use crate::config::schema;
+
     impl<'de> Deserialize<'de> for MaxMsgNum {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
@@ -94,6 +99,24 @@ pub mod max_msg_num {
             m.0
         }
     }
+
+    impl JsonSchema for MaxMsgNum {
+        fn schema_name() -> String {
+            "MaxMsgNum".to_string()
+        }
+
+        fn is_referenceable() -> bool {
+            false
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            schema::from_json(serde_json::json!({
+                "type": "integer",
+                "minimum": Self::MIN_BOUND,
+                "maximum": Self::MAX_BOUND,
+            }))
+        }
+    }
 }
 
 pub use max_tx_size::MaxTxSize;
@@ -144,9 +167,14 @@ pub mod max_tx_size {
         }
     }
 
+    use schemars::gen::SchemaGenerator;
+    use schemars::schema::Schema;
+    use schemars::JsonSchema;
     use serde::de::Unexpected;
     use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
+    use crate::config::schema;
+
     impl<'de> Deserialize<'de> for MaxTxSize {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
@@ -177,6 +205,24 @@ pub mod max_tx_size {
             m.0
         }
     }
+
+    impl JsonSchema for MaxTxSize {
+        fn schema_name() -> String {
+            "MaxTxSize".to_string()
+        }
+
+        fn is_referenceable() -> bool {
+            false
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            schema::from_json(serde_json::json!({
+                "type": "integer",
+                "minimum": 0,
+                "maximum": Self::MAX_BOUND,
+            }))
+        }
+    }
 }
 
 pub use memo::Memo;
@@ -229,8 +275,13 @@ pub mod memo {
         }
     }
 
+    use schemars::gen::SchemaGenerator;
+    use schemars::schema::Schema;
+    use schemars::JsonSchema;
     use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
+    use crate::config::schema;
+
     impl<'de> Deserialize<'de> for Memo {
         fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
@@ -256,6 +307,23 @@ pub mod memo {
         }
     }
 
+    impl JsonSchema for Memo {
+        fn schema_name() -> String {
+            "Memo".to_string()
+        }
+
+        fn is_referenceable() -> bool {
+            false
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            schema::from_json(serde_json::json!({
+                "type": "string",
+                "maxLength": Self::MAX_LEN,
+            }))
+        }
+    }
+
     use core::fmt::{Display, Error as FmtError, Formatter};
 
     impl Display for Memo {
@@ -267,9 +335,12 @@ pub mod memo {
 
 pub mod ics20_field_size_limit {
     use byte_unit::Byte;
+    use schemars::JsonSchema;
     use serde_derive::{Deserialize, Serialize};
     use std::fmt::Display;
 
+    use crate::config::schema;
+
     pub enum ValidationResult {
         Valid,
         Invalid { size: usize, max: usize },
@@ -287,9 +358,12 @@ pub mod ics20_field_size_limit {
         }
     }
 
-    #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+    #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize, JsonSchema)]
     pub struct Ics20FieldSizeLimit {
+        /// Whether to check the size of the field
         enabled: bool,
+        /// The maximum size of the field
+        #[schemars(schema_with = "schema::byte_size")]
         size: Byte,
     }
 
@@ -167,7 +167,45 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
             ));
         }
 
-        let mut c = Self {
+        let mut c = Self::uninitialized(b_to_a_client, a_to_b_client, delay_period);
+
+        c.handshake()?;
+
+        Ok(c)
+    }
+
+    /// Create a new connection by only sending the `ConnOpenInit` message to chain `a`.
+    ///
+    /// The rest of the handshake is left to the caller, who can resume it from the
+    /// chain state with [`Connection::restore_from_state`] and the handshake step functions.
+    pub fn init(
+        b_to_a_client: ForeignClient<ChainA, ChainB>,
+        a_to_b_client: ForeignClient<ChainB, ChainA>,
+        delay_period: Duration,
+    ) -> Result<Self, ConnectionError> {
+        Self::validate_clients(&b_to_a_client, &a_to_b_client)?;
+
+        if delay_period > MAX_PACKET_DELAY {
+            return Err(ConnectionError::max_delay_period(
+                delay_period,
+                MAX_PACKET_DELAY,
+            ));
+        }
+
+        let mut c = Self::uninitialized(b_to_a_client, a_to_b_client, delay_period);
+
+        let event = c.flipped().build_conn_init_and_send()?;
+        c.a_side.connection_id = Some(extract_connection_id(&event)?.clone());
+
+        Ok(c)
+    }
+
+    fn uninitialized(
+        b_to_a_client: ForeignClient<ChainA, ChainB>,
+        a_to_b_client: ForeignClient<ChainB, ChainA>,
+        delay_period: Duration,
+    ) -> Self {
+        Self {
             delay_period,
             a_side: ConnectionSide::new(
                 b_to_a_client.dst_chain(),
@@ -179,11 +217,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
                 a_to_b_client.id().clone(),
                 Default::default(),
             ),
-        };
-
-        c.handshake()?;
-
-        Ok(c)
+        }
     }
 
     pub fn restore_from_event(
@@ -4,9 +4,15 @@ use ibc_proto::google::protobuf::Any;
 use ibc_proto::ibc::core::client::v1::ConsensusStateWithHeight;
 use ibc_proto::ibc::lightclients::tendermint::v1::ConsensusState as RawConsensusState;
 use ibc_proto::Protobuf;
+use ibc_relayer_types::clients::ics06_solomachine::consensus_state::{
+    ConsensusState as SmConsensusState, SOLOMACHINE_CONSENSUS_STATE_TYPE_URL,
+};
 use ibc_relayer_types::clients::ics07_tendermint::consensus_state::{
     ConsensusState as TmConsensusState, TENDERMINT_CONSENSUS_STATE_TYPE_URL,
 };
+use ibc_relayer_types::clients::ics08_wasm::consensus_state::{
+    ConsensusState as RawWasmConsensusState, WASM_CONSENSUS_STATE_TYPE_URL,
+};
 use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::consensus_state::ConsensusState;
 use ibc_relayer_types::core::ics02_client::error::Error;
@@ -18,20 +24,49 @@ use ibc_relayer_types::Height;
 #[serde(tag = "type")]
 pub enum AnyConsensusState {
     Tendermint(TmConsensusState),
+    SoloMachine(SmConsensusState),
+    Wasm(WasmConsensusState),
+}
+
+/// The consensus state of a `08-wasm` client, along with the decoded consensus state
+/// of the light client implemented by the contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasmConsensusState {
+    pub inner: Box<AnyConsensusState>,
 }
 
 impl AnyConsensusState {
     pub fn timestamp(&self) -> Timestamp {
         match self {
             Self::Tendermint(cs_state) => cs_state.timestamp.into(),
+            Self::SoloMachine(cs_state) => cs_state.timestamp,
+            Self::Wasm(cs_state) => cs_state.inner.timestamp(),
         }
     }
 
     pub fn client_type(&self) -> ClientType {
         match self {
             AnyConsensusState::Tendermint(_cs) => ClientType::Tendermint,
+            AnyConsensusState::SoloMachine(_cs) => ClientType::SoloMachine,
+            AnyConsensusState::Wasm(_cs) => ClientType::Wasm,
         }
     }
+
+    /// The consensus state of the light client doing the verification,
+    /// unwrapping any `08-wasm` consensus state.
+    pub fn inner(&self) -> &AnyConsensusState {
+        match self {
+            Self::Wasm(cs_state) => cs_state.inner.inner(),
+            _ => self,
+        }
+    }
+
+    /// Wrap this consensus state in a `08-wasm` consensus state.
+    pub fn wrap_wasm(self) -> Self {
+        Self::Wasm(WasmConsensusState {
+            inner: Box::new(self),
+        })
+    }
 }
 
 impl Protobuf<Any> for AnyConsensusState {}
@@ -48,6 +83,19 @@ impl TryFrom<Any> for AnyConsensusState {
                     .map_err(Error::decode_raw_client_state)?,
             )),
 
+            SOLOMACHINE_CONSENSUS_STATE_TYPE_URL => Ok(AnyConsensusState::SoloMachine(
+                SmConsensusState::try_from(value)?,
+            )),
+
+            WASM_CONSENSUS_STATE_TYPE_URL => {
+                let raw_state = RawWasmConsensusState::try_from(value)?;
+                let inner = AnyConsensusState::try_from(raw_state.inner()?)?;
+
+                Ok(AnyConsensusState::Wasm(WasmConsensusState {
+                    inner: Box::new(inner),
+                }))
+            }
+
             _ => Err(Error::unknown_consensus_state_type(value.type_url)),
         }
     }
@@ -60,6 +108,10 @@ impl From<AnyConsensusState> for Any {
                 type_url: TENDERMINT_CONSENSUS_STATE_TYPE_URL.to_string(),
                 value: Protobuf::<RawConsensusState>::encode_vec(value),
             },
+            AnyConsensusState::SoloMachine(value) => value.into(),
+            AnyConsensusState::Wasm(value) => {
+                RawWasmConsensusState::new((*value.inner).into()).into()
+            }
         }
     }
 }
@@ -70,6 +122,12 @@ impl From<TmConsensusState> for AnyConsensusState {
     }
 }
 
+impl From<SmConsensusState> for AnyConsensusState {
+    fn from(cs: SmConsensusState) -> Self {
+        Self::SoloMachine(cs)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct AnyConsensusStateWithHeight {
     pub height: Height,
@@ -115,6 +173,8 @@ impl ConsensusState for AnyConsensusState {
     fn root(&self) -> &CommitmentRoot {
         match self {
             Self::Tendermint(cs_state) => cs_state.root(),
+            Self::SoloMachine(cs_state) => cs_state.root(),
+            Self::Wasm(cs_state) => cs_state.inner.root(),
         }
     }
 
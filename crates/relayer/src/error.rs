@@ -37,6 +37,7 @@ use ibc_relayer_types::proofs::ProofError;
 use crate::chain::cosmos::version;
 use crate::chain::cosmos::BLOCK_MAX_BYTES_MAX_FRACTION;
 use crate::chain::namada::error::Error as NamadaError;
+use crate::chain::solomachine::error::Error as SoloMachineError;
 use crate::config::Error as ConfigError;
 use crate::event::source;
 use crate::keyring::{errors::Error as KeyringError, KeyType};
@@ -577,6 +578,41 @@ define_error! {
                 format_args!("message with length {} is too large for a transaction", e.len)
             },
 
+        DryRunUnsupported
+            { chain_id: ChainId }
+            |e| {
+                format_args!(
+                    "dry-run mode is not supported for chain '{}', refusing to submit transactions to it",
+                    e.chain_id
+                )
+            },
+
+        GenerateOnlyUnsupported
+            { chain_id: ChainId }
+            |e| {
+                format_args!(
+                    "generate-only mode is not supported for chain '{}', refusing to submit transactions to it",
+                    e.chain_id
+                )
+            },
+
+        QueryUnsupported
+            { chain_id: ChainId, query: String }
+            |e| {
+                format_args!(
+                    "query '{}' is not supported for chain '{}'",
+                    e.query, e.chain_id
+                )
+            },
+
+        InvalidSignedTx
+            { reason: String }
+            |e| { format!("invalid signed transaction: {}", e.reason) },
+
+        TxJson
+            { reason: String }
+            |e| { format!("failed to encode transaction to JSON: {}", e.reason) },
+
         InvalidKeyType
             { key_type: KeyType }
             |e| {
@@ -655,6 +691,10 @@ define_error! {
         Namada
             [ NamadaError ]
             |_| { "Namada error" },
+
+        SoloMachine
+            [ SoloMachineError ]
+            |_| { "solo machine error" },
     }
 }
 
@@ -1,8 +1,9 @@
 use core::fmt::{Display, Error as FmtError, Formatter};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use subtle_encoding::hex;
 use tendermint::abci::Event as AbciEvent;
+use tendermint::Hash as TxHash;
 
 use ibc_relayer_types::{
     applications::{
@@ -37,9 +38,10 @@ use ibc_relayer_types::{
 
 pub mod bus;
 pub mod error;
+pub mod recording;
 pub mod source;
 
-#[derive(Clone, Debug, Serialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct IbcEventWithHeight {
     pub event: IbcEvent,
     pub height: Height,
@@ -64,6 +66,24 @@ impl Display for IbcEventWithHeight {
     }
 }
 
+/// An IBC event along with the height and the hash of the transaction which emitted it.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct IbcEventWithTxHash {
+    pub event: IbcEvent,
+    pub height: Height,
+    pub tx_hash: TxHash,
+}
+
+impl Display for IbcEventWithTxHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(
+            f,
+            "{} at height {} in tx {}",
+            self.event, self.height, self.tx_hash
+        )
+    }
+}
+
 /// Note: This function, as well as other helpers, are needed as a workaround to
 /// Rust's orphan rule. That is, we want the AbciEvent -> IbcEvent to be defined
 /// in the relayer crate, but can't because neither AbciEvent nor IbcEvent are
@@ -390,6 +410,18 @@ pub fn write_acknowledgement_try_from_abci_event(
         .map_err(|_| ChannelError::abci_conversion_failed(abci_event.kind.clone()))
 }
 
+pub fn receive_packet_try_from_abci_event(
+    abci_event: &AbciEvent,
+) -> Result<channel_events::ReceivePacket, ChannelError> {
+    extract_packet_and_write_ack_from_tx(abci_event)
+        .map(|(packet, write_ack)| {
+            // This event should not have a write ack.
+            debug_assert_eq!(write_ack.len(), 0);
+            channel_events::ReceivePacket { packet }
+        })
+        .map_err(|_| ChannelError::abci_conversion_failed(abci_event.kind.clone()))
+}
+
 pub fn acknowledge_packet_try_from_abci_event(
     abci_event: &AbciEvent,
 ) -> Result<channel_events::AcknowledgePacket, ChannelError> {
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use flex_error::{define_error, TraceError};
 
 use tendermint_rpc::{Error as RpcError, WebSocketClientUrl};
@@ -48,6 +50,14 @@ define_error! {
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
+
+        Recording
+            { path: PathBuf, reason: String }
+            |e| { format!("failed to access event recording `{}`: {}", e.path.display(), e.reason) },
+
+        MalformedRecording
+            { path: PathBuf, line: usize, reason: String }
+            |e| { format!("malformed entry at line {} of event recording `{}`: {}", e.line, e.path.display(), e.reason) },
     }
 }
 
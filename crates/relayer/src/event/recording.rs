@@ -0,0 +1,220 @@
+//! Recording of the event batches emitted by a chain's event source,
+//! stored as gzip-compressed newline-delimited JSON (NDJSON), one
+//! [`RecordedBatch`] per line.
+//!
+//! Recordings are written by the supervisor when started with
+//! `hermes start --record-events <DIR>`, and can be fed back into Hermes
+//! by configuring a chain with the `replay` event source mode.
+
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
+
+use crate::chain::tracking::TrackingId;
+use crate::event::source::{Error, EventBatch, Result};
+use crate::event::IbcEventWithHeight;
+
+/// File extension used for event recordings.
+pub const RECORDING_EXTENSION: &str = "ndjson.gz";
+
+/// A single entry of an event recording.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedBatch {
+    /// Time at which the batch was received, in milliseconds since the UNIX epoch
+    pub recorded_at: u64,
+    pub chain_id: ChainId,
+    pub height: Height,
+    pub events: Vec<IbcEventWithHeight>,
+}
+
+impl RecordedBatch {
+    pub fn new(batch: &EventBatch) -> Self {
+        Self {
+            recorded_at: unix_millis(SystemTime::now()),
+            chain_id: batch.chain_id.clone(),
+            height: batch.height,
+            events: batch.events.clone(),
+        }
+    }
+
+    /// Turn this entry back into an [`EventBatch`] for the given chain,
+    /// with a fresh tracking identifier.
+    pub fn into_batch(self, chain_id: ChainId) -> EventBatch {
+        EventBatch {
+            chain_id,
+            tracking_id: TrackingId::new_uuid(),
+            height: self.height,
+            events: self.events,
+        }
+    }
+}
+
+/// Writes the event batches of a single chain to a compressed NDJSON file.
+pub struct Recorder {
+    path: PathBuf,
+    writer: GzEncoder<BufWriter<File>>,
+}
+
+impl Recorder {
+    /// Create a new recording for the given chain in the given directory.
+    ///
+    /// The file is named `<chain_id>.<unix_timestamp>.ndjson.gz`, so that
+    /// restarting Hermes never overwrites a previous recording.
+    pub fn create(dir: &Path, chain_id: &ChainId) -> Result<Self> {
+        fs::create_dir_all(dir).map_err(|e| Error::recording(dir.to_path_buf(), e.to_string()))?;
+
+        let timestamp = unix_millis(SystemTime::now()) / 1000;
+        let path = dir.join(format!("{chain_id}.{timestamp}.{RECORDING_EXTENSION}"));
+
+        let file =
+            File::create(&path).map_err(|e| Error::recording(path.clone(), e.to_string()))?;
+        let writer = GzEncoder::new(BufWriter::new(file), Compression::default());
+
+        Ok(Self { path, writer })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append the given batch to the recording.
+    ///
+    /// The compressed stream is flushed after every batch, so that the
+    /// recording stays readable up to the last batch if Hermes is killed.
+    pub fn record(&mut self, batch: &EventBatch) -> Result<()> {
+        let entry = RecordedBatch::new(batch);
+
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| Error::recording(self.path.clone(), e.to_string()))?;
+
+        writeln!(self.writer, "{line}")
+            .and_then(|_| self.writer.flush())
+            .map_err(|e| Error::recording(self.path.clone(), e.to_string()))
+    }
+}
+
+/// Reads back the entries of a recording written by a [`Recorder`].
+pub struct RecordingReader {
+    path: PathBuf,
+    lines: std::io::Lines<BufReader<MultiGzDecoder<File>>>,
+    line: usize,
+}
+
+impl RecordingReader {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file =
+            File::open(path).map_err(|e| Error::recording(path.to_path_buf(), e.to_string()))?;
+        let lines = BufReader::new(MultiGzDecoder::new(file)).lines();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            lines,
+            line: 0,
+        })
+    }
+}
+
+impl Iterator for RecordingReader {
+    type Item = Result<RecordedBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line += 1;
+
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                // A recording interrupted mid-write ends with a truncated
+                // compressed block, we treat it as the end of the recording.
+                Err(e) if e.kind() == ErrorKind::UnexpectedEof => return None,
+                Err(e) => return Some(Err(Error::recording(self.path.clone(), e.to_string()))),
+            };
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            return Some(serde_json::from_str(&line).map_err(|e| {
+                Error::malformed_recording(self.path.clone(), self.line, e.to_string())
+            }));
+        }
+    }
+}
+
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// A batch of a new block and a packet sent at the given height, for testing
+/// the recording and the replay of events.
+#[cfg(test)]
+pub(crate) fn test_batch(chain_id: &ChainId, height: u64) -> EventBatch {
+    use ibc_relayer_types::core::ics02_client::events::NewBlock;
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::{Packet, Sequence};
+    use ibc_relayer_types::events::IbcEvent;
+
+    let height = Height::new(chain_id.version(), height).unwrap();
+
+    let packet = Packet {
+        sequence: Sequence::from(height.revision_height()),
+        data: b"packet data".to_vec(),
+        ..Default::default()
+    };
+
+    EventBatch {
+        chain_id: chain_id.clone(),
+        tracking_id: TrackingId::new_uuid(),
+        height,
+        events: vec![
+            IbcEventWithHeight::new(IbcEvent::NewBlock(NewBlock::new(height)), height),
+            IbcEventWithHeight::new(IbcEvent::SendPacket(SendPacket { packet }), height),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_and_read_back() {
+        let dir = std::env::temp_dir().join(format!("hermes-recording-{}", uuid::Uuid::new_v4()));
+        let chain_id = ChainId::new("ibc".to_string(), 0);
+
+        let batches = vec![test_batch(&chain_id, 1), test_batch(&chain_id, 2)];
+
+        let path = {
+            let mut recorder = Recorder::create(&dir, &chain_id).unwrap();
+            for batch in &batches {
+                recorder.record(batch).unwrap();
+            }
+            recorder.path().to_path_buf()
+        };
+
+        let entries = RecordingReader::open(&path)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(entries.len(), batches.len());
+
+        for (entry, batch) in entries.into_iter().zip(batches) {
+            assert_eq!(entry.chain_id, batch.chain_id);
+            assert_eq!(entry.height, batch.height);
+            assert_eq!(entry.events, batch.events);
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,7 +1,8 @@
+pub mod replay;
 pub mod rpc;
 pub mod websocket;
 
-use std::{sync::Arc, time::Duration};
+use std::{path::PathBuf, sync::Arc, time::Duration};
 
 use crossbeam_channel as channel;
 
@@ -25,6 +26,7 @@ pub type Result<T> = core::result::Result<T, Error>;
 pub enum EventSource {
     WebSocket(websocket::EventSource),
     Rpc(rpc::EventSource),
+    Replay(replay::EventSource),
 }
 
 impl EventSource {
@@ -55,10 +57,20 @@ impl EventSource {
         Ok((Self::Rpc(source), tx))
     }
 
+    pub fn replay(
+        chain_id: ChainId,
+        path: PathBuf,
+        speed: f64,
+    ) -> Result<(Self, TxEventSourceCmd)> {
+        let (source, tx) = replay::EventSource::new(chain_id, path, speed)?;
+        Ok((Self::Replay(source), tx))
+    }
+
     pub fn run(self) {
         match self {
             Self::WebSocket(source) => source.run(),
             Self::Rpc(source) => source.run(),
+            Self::Replay(source) => source.run(),
         }
     }
 }
@@ -0,0 +1,239 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossbeam_channel as channel;
+use tracing::{debug, error, error_span, info, trace, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::{
+    event::{
+        bus::EventBus,
+        recording::{RecordedBatch, RecordingReader},
+    },
+    telemetry,
+};
+
+use super::{EventBatch, EventSourceCmd, Result, TxEventSourceCmd};
+
+/// An event source which replays a recording made with
+/// `hermes start --record-events`, instead of listening to a live chain.
+///
+/// The replay only starts once the first subscriber has subscribed to the
+/// event source, so that no batch of the recording is lost.
+/// Once the recording is exhausted, the event source stays idle until it is shut down.
+pub struct EventSource {
+    /// Chain identifier, which replaces the one of the recorded batches
+    chain_id: ChainId,
+
+    /// Path to the recording
+    path: PathBuf,
+
+    /// Replay speed, relative to the time elapsed between the recorded batches.
+    /// A speed of `0` replays the recording as fast as possible.
+    speed: f64,
+
+    /// Event bus for broadcasting events
+    event_bus: EventBus<Arc<Result<EventBatch>>>,
+
+    /// Channel where to receive commands
+    rx_cmd: channel::Receiver<EventSourceCmd>,
+}
+
+impl EventSource {
+    pub fn new(chain_id: ChainId, path: PathBuf, speed: f64) -> Result<(Self, TxEventSourceCmd)> {
+        let event_bus = EventBus::new();
+        let (tx_cmd, rx_cmd) = channel::unbounded();
+
+        let source = Self {
+            chain_id,
+            path,
+            speed,
+            event_bus,
+            rx_cmd,
+        };
+
+        Ok((source, TxEventSourceCmd(tx_cmd)))
+    }
+
+    pub fn run(mut self) {
+        let _span = error_span!("event_source.replay", chain.id = %self.chain_id).entered();
+
+        // Wait for the first subscriber before replaying anything
+        match self.rx_cmd.recv() {
+            Ok(cmd) => {
+                if let Next::Abort = self.process_cmd(cmd) {
+                    debug!("shutting down event source");
+                    return;
+                }
+            }
+            Err(_) => return,
+        }
+
+        info!("replaying events from `{}`", self.path.display());
+
+        if let Next::Continue = self.replay() {
+            info!("finished replaying events from `{}`", self.path.display());
+
+            // Keep serving subscriptions until we are told to shut down
+            while let Ok(cmd) = self.rx_cmd.recv() {
+                if let Next::Abort = self.process_cmd(cmd) {
+                    break;
+                }
+            }
+        }
+
+        debug!("shutting down event source");
+    }
+
+    fn replay(&mut self) -> Next {
+        let reader = match RecordingReader::open(&self.path) {
+            Ok(reader) => reader,
+            Err(e) => {
+                error!("failed to open recording: {e}");
+                return Next::Continue;
+            }
+        };
+
+        let mut previous: Option<u64> = None;
+
+        for entry in reader {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!("skipping recorded batch: {e}");
+                    continue;
+                }
+            };
+
+            let delay = previous
+                .map(|previous| self.delay(entry.recorded_at.saturating_sub(previous)))
+                .unwrap_or_default();
+
+            previous = Some(entry.recorded_at);
+
+            if let Next::Abort = self.wait(delay) {
+                return Next::Abort;
+            }
+
+            self.broadcast_batch(entry);
+        }
+
+        Next::Continue
+    }
+
+    /// Wait for the given amount of time, while processing any incoming command.
+    fn wait(&mut self, delay: Duration) -> Next {
+        let deadline = std::time::Instant::now() + delay;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+
+            match self.rx_cmd.recv_timeout(remaining) {
+                Ok(cmd) => {
+                    if let Next::Abort = self.process_cmd(cmd) {
+                        return Next::Abort;
+                    }
+                }
+                Err(channel::RecvTimeoutError::Timeout) => return Next::Continue,
+                Err(channel::RecvTimeoutError::Disconnected) => return Next::Abort,
+            }
+        }
+    }
+
+    fn delay(&self, elapsed_ms: u64) -> Duration {
+        if self.speed > 0.0 {
+            Duration::from_millis(elapsed_ms).div_f64(self.speed)
+        } else {
+            Duration::ZERO
+        }
+    }
+
+    fn process_cmd(&mut self, cmd: EventSourceCmd) -> Next {
+        match cmd {
+            EventSourceCmd::Shutdown => Next::Abort,
+
+            EventSourceCmd::Subscribe(tx) => {
+                if let Err(e) = tx.send(self.event_bus.subscribe()) {
+                    error!("failed to send back subscription: {e}");
+                }
+
+                Next::Continue
+            }
+        }
+    }
+
+    fn broadcast_batch(&mut self, entry: RecordedBatch) {
+        let batch = entry.into_batch(self.chain_id.clone());
+
+        telemetry!(ws_events, &batch.chain_id, batch.events.len() as u64);
+
+        trace!(
+            chain = %batch.chain_id,
+            count = %batch.events.len(),
+            height = %batch.height,
+            "replaying batch of {} events",
+            batch.events.len()
+        );
+
+        self.event_bus.broadcast(Arc::new(Ok(batch)));
+    }
+}
+
+pub enum Next {
+    Abort,
+    Continue,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+
+    use crate::event::recording::{test_batch, Recorder};
+
+    #[test]
+    fn record_and_replay() {
+        let dir = std::env::temp_dir().join(format!("hermes-replay-{}", uuid::Uuid::new_v4()));
+        let recorded_chain = ChainId::new("recorded".to_string(), 0);
+        let replay_chain = ChainId::new("replay".to_string(), 0);
+
+        let batches = (1..=3)
+            .map(|height| test_batch(&recorded_chain, height))
+            .collect::<Vec<_>>();
+
+        let path = {
+            let mut recorder = Recorder::create(&dir, &recorded_chain).unwrap();
+            for batch in &batches {
+                recorder.record(batch).unwrap();
+            }
+            recorder.path().to_path_buf()
+        };
+
+        let (source, tx_cmd) = EventSource::new(replay_chain.clone(), path, 0.0).unwrap();
+        let handle = std::thread::spawn(move || source.run());
+
+        let subscription = tx_cmd.subscribe().unwrap();
+
+        for expected in &batches {
+            let replayed = subscription.recv_timeout(Duration::from_secs(10)).unwrap();
+            let replayed = replayed.as_ref().as_ref().unwrap();
+
+            assert_eq!(replayed.chain_id, replay_chain);
+            assert_eq!(replayed.height, expected.height);
+            assert_eq!(replayed.events, expected.events);
+        }
+
+        // The recording is exhausted, but the source keeps running until shut down
+        assert!(subscription
+            .recv_timeout(Duration::from_millis(100))
+            .is_err());
+
+        tx_cmd.shutdown().unwrap();
+        handle.join().unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -16,6 +16,7 @@ use tracing::{debug, error, info, instrument, trace, warn};
 use flex_error::define_error;
 use ibc_relayer_types::applications::ics28_ccv::msgs::ccv_misbehaviour::MsgSubmitIcsConsumerMisbehaviour;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics02_client::header::{AnyHeader, Header};
@@ -36,10 +37,11 @@ use crate::chain::handle::ChainHandle;
 use crate::chain::requests::*;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::AnyClientState;
-use crate::config::ChainConfig;
+use crate::config::{default, ChainConfig, RefreshRate};
 use crate::consensus_state::AnyConsensusState;
 use crate::error::Error as RelayerError;
 use crate::event::IbcEventWithHeight;
+use crate::misbehaviour::archive::{EvidenceArchive, EvidenceRecord, SubmissionOutcome};
 use crate::misbehaviour::{AnyMisbehaviour, MisbehaviourEvidence};
 use crate::telemetry;
 use crate::util::collate::CollatedIterExt;
@@ -357,6 +359,10 @@ pub struct CreateOptions {
     pub max_clock_drift: Option<Duration>,
     pub trusting_period: Option<Duration>,
     pub trust_threshold: Option<TrustThreshold>,
+    /// Checksum of the Wasm light client contract stored on the host chain.
+    /// If set, the client is created as a `08-wasm` client wrapping the client
+    /// which would otherwise be created.
+    pub wasm_checksum: Option<Vec<u8>>,
 }
 
 /// Captures the diagnostic of verifying whether a certain
@@ -373,6 +379,54 @@ pub enum ConsensusStateTrusted {
     },
 }
 
+/// When a client is due for a refresh, based on the time of its latest update on chain,
+/// whichever relayer submitted that update.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RefreshSchedule {
+    /// Time elapsed since the latest update of the client
+    pub elapsed: Duration,
+    /// Time after its latest update at which the client should be refreshed
+    pub refresh_period: Duration,
+    pub trusting_period: Duration,
+}
+
+impl RefreshSchedule {
+    pub fn is_due(&self) -> bool {
+        self.elapsed > self.refresh_period
+    }
+
+    /// Time left until the client is due for a refresh.
+    pub fn refresh_in(&self) -> Duration {
+        self.refresh_period.saturating_sub(self.elapsed)
+    }
+
+    /// Time left until the client expires if it is not updated.
+    pub fn expires_in(&self) -> Duration {
+        self.trusting_period.saturating_sub(self.elapsed)
+    }
+
+    /// The time before expiry below which the refresh of the client becomes urgent,
+    /// ie. the given fraction of its trusting period.
+    pub fn urgent_window(&self, urgent_refresh_threshold: RefreshRate) -> Duration {
+        self.trusting_period
+            .mul_f64(urgent_refresh_threshold.as_f64())
+    }
+
+    /// Whether the client is close enough to expiry for its refresh to be urgent.
+    pub fn is_urgent(&self, urgent_refresh_threshold: RefreshRate) -> bool {
+        self.expires_in() <= self.urgent_window(urgent_refresh_threshold)
+    }
+
+    /// Time left until the client is due for a refresh or its refresh becomes urgent,
+    /// whichever comes first.
+    pub fn next_check_in(&self, urgent_refresh_threshold: RefreshRate) -> Duration {
+        self.refresh_in().min(
+            self.expires_in()
+                .saturating_sub(self.urgent_window(urgent_refresh_threshold)),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ForeignClient<DstChain: ChainHandle, SrcChain: ChainHandle> {
     /// The identifier of this client. The host chain determines this id upon client creation,
@@ -599,6 +653,20 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         &self.id
     }
 
+    /// Whether this is the `09-localhost` client, which is updated by the host chain
+    /// itself and therefore never needs to be updated, refreshed or checked for misbehaviour.
+    pub fn is_localhost(&self) -> bool {
+        self.id.is_localhost()
+    }
+
+    /// Whether this is a `06-solomachine` client, whose state only changes when it verifies
+    /// a proof signed by the solo machine and which therefore is never updated or refreshed.
+    pub fn is_solo_machine(&self) -> bool {
+        self.id
+            .as_str()
+            .starts_with(ClientType::SoloMachine.as_str())
+    }
+
     /// Lower-level interface for preparing a message to create a client.
     pub fn build_create_client(
         &self,
@@ -643,6 +711,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             )
         })?;
 
+        let wasm_checksum = options.wasm_checksum.clone();
         let settings = ClientSettings::for_create_command(options, &src_config, &dst_config);
 
         let client_state: AnyClientState = self
@@ -671,6 +740,14 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 )
             })?;
 
+        let (client_state, consensus_state) = match wasm_checksum {
+            Some(checksum) => (
+                client_state.wrap_wasm(checksum),
+                consensus_state.wrap_wasm(),
+            ),
+            None => (client_state, consensus_state),
+        };
+
         //TODO Get acct_prefix
         let msg = MsgCreateClient::new(client_state.into(), consensus_state.into(), signer)
             .map_err(ForeignClientError::client)?;
@@ -762,6 +839,12 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             ));
         }
 
+        // The localhost client has no consensus states, and neither
+        // the localhost client nor solo machine clients ever expire
+        if self.is_localhost() || self.is_solo_machine() {
+            return Ok((client_state, None));
+        }
+
         match self
             .check_consensus_state_trusting_period(&client_state, &client_state.latest_height())?
         {
@@ -865,6 +948,17 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         fields(client = %self)
     )]
     pub fn refresh(&mut self) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
+        self.refresh_when(RefreshSchedule::is_due)
+    }
+
+    /// Refresh the client if `needs_refresh` holds for its current refresh schedule.
+    ///
+    /// Returns `None` if the client did not need to be refreshed,
+    /// or if it never expires.
+    pub fn refresh_when(
+        &mut self,
+        needs_refresh: impl Fn(&RefreshSchedule) -> bool,
+    ) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
         fn check_no_errors(
             ibc_events: &[IbcEvent],
             dst_chain_id: ChainId,
@@ -884,19 +978,30 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             }
         }
 
-        // If elapsed < refresh_window for the client, `try_refresh()` will
-        // be successful with an empty vector.
-        if let Some(events) = self.try_refresh()? {
-            check_no_errors(&events, self.dst_chain().id())?;
-            Ok(Some(events))
-        } else {
-            Ok(None)
+        match self.refresh_schedule()? {
+            Some(schedule) if needs_refresh(&schedule) => {
+                info!(
+                    elapsed = ?schedule.elapsed,
+                    refresh_period = ?schedule.refresh_period,
+                    "client needs to be refreshed"
+                );
+
+                let events = self.build_latest_update_client_and_send()?;
+                check_no_errors(&events, self.dst_chain().id())?;
+                Ok(Some(events))
+            }
+            _ => Ok(None),
         }
     }
 
-    fn try_refresh(&mut self) -> Result<Option<Vec<IbcEvent>>, ForeignClientError> {
+    /// The refresh schedule of the client, or `None` if the client never expires.
+    pub fn refresh_schedule(&self) -> Result<Option<RefreshSchedule>, ForeignClientError> {
         let (client_state, elapsed) = self.validated_client_state()?;
 
+        let Some(elapsed) = elapsed else {
+            return Ok(None);
+        };
+
         let src_config = self.src_chain.config().map_err(|e| {
             ForeignClientError::client_create(
                 self.src_chain.id(),
@@ -910,25 +1015,16 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 config.client_refresh_rate
             }
             ChainConfig::Penumbra(config) => config.client_refresh_rate,
+            ChainConfig::SoloMachine(_) => default::client_refresh_rate(),
         };
 
-        let refresh_period = client_state
-            .trusting_period()
-            .mul_f64(refresh_rate.as_f64());
+        let trusting_period = client_state.trusting_period();
 
-        match (elapsed, refresh_period) {
-            (None, _) => Ok(None),
-            (Some(elapsed), refresh_period) => {
-                if elapsed > refresh_period {
-                    info!(?elapsed, ?refresh_period, "client needs to be refreshed");
-
-                    self.build_latest_update_client_and_send()
-                        .map_or_else(Err, |ev| Ok(Some(ev)))
-                } else {
-                    Ok(None)
-                }
-            }
-        }
+        Ok(Some(RefreshSchedule {
+            elapsed,
+            refresh_period: trusting_period.mul_f64(refresh_rate.as_f64()),
+            trusting_period,
+        }))
     }
 
     /// Wrapper for build_update_client_with_trusted.
@@ -1149,6 +1245,16 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             }
         );
 
+        if self.is_localhost() {
+            debug!("localhost client is updated by its host chain, skipping update");
+            return Ok(vec![]);
+        }
+
+        if self.is_solo_machine() {
+            debug!("solo machine client is never updated, skipping update");
+            return Ok(vec![]);
+        }
+
         let consensus_state = self.dst_chain().query_consensus_state(
             QueryConsensusStateRequest {
                 client_id: self.id().clone(),
@@ -1224,6 +1330,10 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 "dst_chain": self.dst_chain().id(),
             }
         );
+
+        if self.is_localhost() || self.is_solo_machine() {
+            return Ok(vec![]);
+        }
         // Get the latest client state on destination.
         let (client_state, _) = self.validated_client_state()?;
 
@@ -1289,9 +1399,18 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
 
         self.wait_for_header_validation_delay(&client_state, &header)?;
 
+        // Headers submitted to a `08-wasm` client are wrapped in a Wasm client message
+        let wrap_header = |header: AnyHeader| {
+            if client_state.is_wasm() {
+                header.wrap_wasm()
+            } else {
+                header
+            }
+        };
+
         let mut msgs = vec![];
 
-        for header in support {
+        for header in support.into_iter().map(wrap_header) {
             debug!(
                 "building a MsgUpdateAnyClient for intermediate height {}",
                 header.height(),
@@ -1311,7 +1430,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         );
 
         msgs.push(MsgUpdateClient {
-            header: header.into(),
+            header: wrap_header(header).into(),
             signer,
             client_id: self.id.clone(),
         });
@@ -1586,6 +1705,12 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         &self,
         mut update: Option<&UpdateClient>,
     ) -> Result<Option<MisbehaviourEvidence>, ForeignClientError> {
+        // The localhost client verifies the state of its own host chain,
+        // and solo machine clients are never updated
+        if self.is_localhost() || self.is_solo_machine() {
+            return Ok(None);
+        }
+
         // FIXME(romac): Why do we need this, and shouldn't we wait somewhere else up the call stack?
         thread::sleep(Duration::from_millis(200));
 
@@ -1739,7 +1864,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         skip_all,
         fields(client = %self)
     )]
-    fn submit_evidence(
+    pub fn submit_evidence(
         &self,
         evidence: MisbehaviourEvidence,
     ) -> Result<Vec<IbcEvent>, ForeignClientError> {
@@ -1764,12 +1889,28 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             ChainConfig::CosmosSdk(config) | ChainConfig::Namada(config) => {
                 config.ccv_consumer_chain
             }
-            ChainConfig::Penumbra(_) => false,
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => false,
+        };
+
+        let (client_state, _) = self.validated_client_state()?;
+
+        // Misbehaviour submitted to a `08-wasm` client is wrapped in a Wasm client message
+        let (misbehaviour, supporting_headers) = if client_state.is_wasm() {
+            (
+                evidence.misbehaviour.wrap_wasm(),
+                evidence
+                    .supporting_headers
+                    .into_iter()
+                    .map(AnyHeader::wrap_wasm)
+                    .collect(),
+            )
+        } else {
+            (evidence.misbehaviour, evidence.supporting_headers)
         };
 
         let mut msgs = vec![];
 
-        for header in evidence.supporting_headers {
+        for header in supporting_headers {
             msgs.push(
                 MsgUpdateClient {
                     header: header.into(),
@@ -1780,13 +1921,13 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             );
         }
 
-        let tm_misbehaviour = match &evidence.misbehaviour {
+        let tm_misbehaviour = match misbehaviour.inner() {
             AnyMisbehaviour::Tendermint(tm_misbehaviour) => Some(tm_misbehaviour.clone()),
+            AnyMisbehaviour::Wasm(_) => None,
         }
         .ok_or_else(|| {
             ForeignClientError::misbehaviour_desc(format!(
-                "underlying evidence is not a Tendermint misbehaviour: {:?}",
-                evidence.misbehaviour
+                "underlying evidence is not a Tendermint misbehaviour: {misbehaviour:?}"
             ))
         })?;
 
@@ -1815,7 +1956,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
 
         msgs.push(
             MsgSubmitMisbehaviour {
-                misbehaviour: evidence.misbehaviour.into(),
+                misbehaviour: misbehaviour.into(),
                 client_id: self.id.clone(),
                 signer,
             }
@@ -1849,6 +1990,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
     pub fn detect_misbehaviour_and_submit_evidence(
         &self,
         update_event: Option<UpdateClient>,
+        archive: Option<&EvidenceArchive>,
     ) -> MisbehaviourResults {
         // check evidence of misbehaviour for all updates or one
         let result = match self.detect_misbehaviour(update_event.as_ref()) {
@@ -1868,7 +2010,13 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                     1
                 );
 
-                self.submit_evidence(detected)
+                let result = self.submit_evidence(detected.clone());
+
+                if let Some(archive) = archive {
+                    self.archive_evidence(archive, detected, &result);
+                }
+
+                result
             }
         };
 
@@ -1919,6 +2067,36 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
         }
     }
 
+    /// Write the given evidence and the result of its submission to the evidence archive.
+    fn archive_evidence(
+        &self,
+        archive: &EvidenceArchive,
+        evidence: MisbehaviourEvidence,
+        result: &Result<Vec<IbcEvent>, ForeignClientError>,
+    ) {
+        let submission = match result {
+            Ok(events) => SubmissionOutcome::Submitted {
+                details: events.iter().map(ToString::to_string).collect(),
+            },
+            Err(e) => SubmissionOutcome::Failed {
+                error: e.to_string(),
+            },
+        };
+
+        let record = EvidenceRecord::new(
+            self.src_chain.id(),
+            self.dst_chain.id(),
+            self.id.clone(),
+            evidence,
+            submission,
+        );
+
+        match archive.store(&record) {
+            Ok(path) => info!("misbehaviour evidence archived in {}", path.display()),
+            Err(e) => warn!("failed to archive misbehaviour evidence: {e}"),
+        }
+    }
+
     pub fn map_chain<DstChain2: ChainHandle, SrcChain2: ChainHandle>(
         self,
         map_dst: impl Fn(DstChain) -> DstChain2,
@@ -1967,3 +2145,63 @@ pub fn fetch_ccv_consumer_id(
 
     Ok(consumer_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    fn schedule(elapsed_hours: u64) -> RefreshSchedule {
+        RefreshSchedule {
+            elapsed: HOUR * elapsed_hours as u32,
+            refresh_period: HOUR * 4,
+            trusting_period: HOUR * 12,
+        }
+    }
+
+    fn threshold() -> RefreshRate {
+        RefreshRate::new(1, 4)
+    }
+
+    #[test]
+    fn urgent_window_is_fraction_of_trusting_period() {
+        assert_eq!(schedule(0).urgent_window(threshold()), HOUR * 3);
+    }
+
+    #[test]
+    fn refresh_becomes_urgent_close_to_expiry() {
+        assert!(!schedule(5).is_urgent(threshold()));
+        assert!(schedule(9).is_urgent(threshold()));
+        assert!(schedule(10).is_urgent(threshold()));
+
+        // An expired client is still reported as urgent
+        assert!(schedule(13).is_urgent(threshold()));
+    }
+
+    #[test]
+    fn next_check_when_refresh_is_due_first() {
+        let schedule = schedule(1);
+
+        assert!(!schedule.is_due());
+        assert_eq!(schedule.next_check_in(threshold()), HOUR * 3);
+    }
+
+    #[test]
+    fn next_check_when_refresh_becomes_urgent_first() {
+        // With a refresh rate past the urgent threshold, the refresh becomes
+        // urgent 9 hours after the latest update, before it is due.
+        let schedule = RefreshSchedule {
+            refresh_period: HOUR * 10,
+            ..schedule(1)
+        };
+
+        assert_eq!(schedule.next_check_in(threshold()), HOUR * 8);
+    }
+
+    #[test]
+    fn next_check_is_immediate_once_due_or_urgent() {
+        assert_eq!(schedule(5).next_check_in(threshold()), Duration::ZERO);
+        assert_eq!(schedule(13).next_check_in(threshold()), Duration::ZERO);
+    }
+}
@@ -21,6 +21,7 @@ use std::fs::{self, File};
 use std::path::PathBuf;
 
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use errors::Error;
@@ -182,7 +183,7 @@ impl<S: SigningKeyPairSized> KeyStore<S> for Test {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum Store {
     Memory,
     Test,
@@ -1,3 +1,4 @@
+mod cache;
 mod detector;
 
 use std::time::Duration;
@@ -22,8 +23,9 @@ use tendermint_rpc as rpc;
 
 use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
 use ibc_relayer_types::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+use ibc_relayer_types::core::ics02_client::client_type::ClientType;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
-use ibc_relayer_types::core::ics02_client::header::AnyHeader;
+use ibc_relayer_types::core::ics02_client::header::{AnyHeader, Header};
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::Height as ICSHeight;
 
@@ -41,11 +43,14 @@ use super::{
     Verified,
 };
 
+use self::cache::{CachedIo, LightBlockCache, VerificationKey};
+
 pub struct LightClient {
     chain_id: ChainId,
     peer_id: PeerId,
     io: AnyIo,
     enable_verification: bool,
+    cache: LightBlockCache,
 }
 
 impl super::LightClient<CosmosSdkChain> for LightClient {
@@ -105,26 +110,47 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
             });
         }
 
-        let client = self.prepare_client(client_state, now)?;
-        let mut state = self.prepare_state(trusted_height)?;
+        let tm_client_state = match client_state.inner() {
+            AnyClientState::Tendermint(client_state) => Ok::<_, Error>(client_state),
+            client_state => Err(Error::client_type_mismatch(
+                ClientType::Tendermint,
+                client_state.client_type(),
+            )),
+        }?;
 
-        // Verify the target header
-        let target = client
-            .verify_to_target(target_height.into(), &mut state)
-            .map_err(|e| Error::light_client_verification(self.chain_id.to_string(), e))?;
+        let key = VerificationKey {
+            trusted_height,
+            target_height,
+            trust_threshold: tm_client_state.trust_threshold,
+            trusting_period: tm_client_state.trusting_period,
+            max_clock_drift: tm_client_state.max_clock_drift,
+        };
 
-        // Collect the verification trace for the target block
-        let target_trace = state.get_trace(target.height());
+        let trusted_block = self.fetch_light_block(AtHeight::At(trusted_height.into()))?;
+        let trusted_time = trusted_block.signed_header.header.time;
 
-        // Compute the supporting set, sorted by ascending height, omitting the target header
-        let supporting = target_trace
-            .into_iter()
-            .unique_by(LightBlock::height)
-            .sorted_by_key(LightBlock::height)
-            .filter(|lb| lb.height() != target.height())
-            .collect_vec();
+        self.cache.get_or_verify(key, trusted_time, now, || {
+            let client = self.prepare_client(client_state, now)?;
+            let mut state = Self::prepare_state(trusted_block);
 
-        Ok(Verified { target, supporting })
+            // Verify the target header
+            let target = client
+                .verify_to_target(target_height.into(), &mut state)
+                .map_err(|e| Error::light_client_verification(self.chain_id.to_string(), e))?;
+
+            // Collect the verification trace for the target block
+            let target_trace = state.get_trace(target.height());
+
+            // Compute the supporting set, sorted by ascending height, omitting the target header
+            let supporting = target_trace
+                .into_iter()
+                .unique_by(LightBlock::height)
+                .sorted_by_key(LightBlock::height)
+                .filter(|lb| lb.height() != target.height())
+                .collect_vec();
+
+            Ok(Verified { target, supporting })
+        })
     }
 
     fn fetch(&mut self, height: ICSHeight) -> Result<LightBlock, Error> {
@@ -154,12 +180,20 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
             ))
         })?;
 
-        let update_header = match any_header {
+        let update_header = match any_header.inner() {
             AnyHeader::Tendermint(header) => Ok::<_, Error>(header),
+            header => Err(Error::client_type_mismatch(
+                ClientType::Tendermint,
+                header.client_type(),
+            )),
         }?;
 
-        let client_state = match client_state {
+        let client_state = match client_state.inner() {
             AnyClientState::Tendermint(client_state) => Ok::<_, Error>(client_state),
+            client_state => Err(Error::client_type_mismatch(
+                ClientType::Tendermint,
+                client_state.client_type(),
+            )),
         }?;
 
         let next_validators = self
@@ -307,6 +341,7 @@ impl LightClient {
             io,
 
             enable_verification,
+            cache: LightBlockCache::new(config.id.clone()),
         })
     }
 
@@ -344,6 +379,7 @@ impl LightClient {
             io,
 
             enable_verification,
+            cache: LightBlockCache::new(config.id.clone()),
         })
     }
 
@@ -356,8 +392,12 @@ impl LightClient {
         let verifier = ProdVerifier::default();
         let scheduler = components::scheduler::basic_bisecting_schedule;
 
-        let client_state = match client_state {
+        let client_state = match client_state.inner() {
             AnyClientState::Tendermint(client_state) => Ok::<_, Error>(client_state),
+            client_state => Err(Error::client_type_mismatch(
+                ClientType::Tendermint,
+                client_state.client_type(),
+            )),
         }?;
 
         Ok(TmLightClient::new(
@@ -366,21 +406,19 @@ impl LightClient {
             clock,
             scheduler,
             verifier,
-            self.io.clone(),
+            CachedIo::new(self.io.clone(), self.cache.clone()),
         ))
     }
 
-    fn prepare_state(&self, trusted_height: ICSHeight) -> Result<LightClientState, Error> {
-        let trusted_block = self.fetch_light_block(AtHeight::At(trusted_height.into()))?;
-
+    fn prepare_state(trusted_block: LightBlock) -> LightClientState {
         let mut store = MemoryStore::new();
         store.insert(trusted_block, Status::Trusted);
 
-        Ok(LightClientState::new(store))
+        LightClientState::new(store)
     }
 
     fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, Error> {
-        self.io
+        CachedIo::new(self.io.clone(), self.cache.clone())
             .fetch_light_block(height)
             .map_err(|e| Error::light_client_io(self.chain_id.to_string(), e))
     }
@@ -0,0 +1,336 @@
+//! Cache of the light blocks fetched and verified by the light client of a chain.
+//!
+//! There is a single light client per chain runtime, hence this cache is shared by
+//! all the clients of that chain hosted on counterparty chains: when a hub has many
+//! counterparties, the headers at a given height are fetched and verified only once
+//! instead of once per client.
+
+use std::time::Duration;
+
+use moka::sync::Cache as MokaCache;
+use tendermint::Time;
+use tendermint_light_client::components::io::{AtHeight, Io, IoError};
+use tendermint_light_client::verifier::types::{Height as TMHeight, LightBlock};
+
+use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height as ICSHeight;
+
+use crate::light_client::io::AnyIo;
+use crate::light_client::Verified;
+
+const LIGHT_BLOCK_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const VERIFIED_CACHE_TTL: Duration = Duration::from_secs(60);
+
+const LIGHT_BLOCK_CACHE_CAPACITY: u64 = 500;
+const VERIFIED_CACHE_CAPACITY: u64 = 100;
+
+/// The parameters a verification outcome depends on, besides the current time.
+///
+/// Two clients with a different trust threshold or trusting period do not
+/// accept the same verification traces, so they never share a cache entry.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VerificationKey {
+    pub trusted_height: ICSHeight,
+    pub target_height: ICSHeight,
+    pub trust_threshold: TrustThreshold,
+    pub trusting_period: Duration,
+    pub max_clock_drift: Duration,
+}
+
+#[derive(Clone)]
+struct VerifiedEntry {
+    /// Time of the trusted block the verification started from
+    trusted_time: Time,
+    verified: Verified<LightBlock>,
+}
+
+#[derive(Clone)]
+pub struct LightBlockCache {
+    chain_id: ChainId,
+    /// Light blocks fetched from the chain, keyed by height.
+    blocks: MokaCache<TMHeight, LightBlock>,
+    /// Outcomes of successful verifications, keyed by the parameters of the verification.
+    verified: MokaCache<VerificationKey, VerifiedEntry>,
+}
+
+impl LightBlockCache {
+    pub fn new(chain_id: ChainId) -> Self {
+        Self::with_capacity(
+            chain_id,
+            LIGHT_BLOCK_CACHE_CAPACITY,
+            VERIFIED_CACHE_CAPACITY,
+        )
+    }
+
+    fn with_capacity(chain_id: ChainId, blocks_capacity: u64, verified_capacity: u64) -> Self {
+        Self {
+            chain_id,
+            blocks: MokaCache::builder()
+                .time_to_live(LIGHT_BLOCK_CACHE_TTL)
+                .max_capacity(blocks_capacity)
+                .build(),
+            verified: MokaCache::builder()
+                .time_to_live(VERIFIED_CACHE_TTL)
+                .max_capacity(verified_capacity)
+                .build(),
+        }
+    }
+
+    /// Return the light block at the given height, fetching it with `fetch` on a cache miss.
+    pub fn get_or_fetch_block<E>(
+        &self,
+        height: TMHeight,
+        fetch: impl FnOnce() -> Result<LightBlock, E>,
+    ) -> Result<LightBlock, E> {
+        if let Some(block) = self.blocks.get(&height) {
+            crate::telemetry!(light_block_cache_hits, &self.chain_id, "light_block");
+            return Ok(block);
+        }
+
+        crate::telemetry!(light_block_cache_misses, &self.chain_id, "light_block");
+
+        let block = fetch()?;
+        self.blocks.insert(height, block.clone());
+
+        Ok(block)
+    }
+
+    /// Return the outcome of the verification with the given parameters, running it
+    /// with `verify` on a cache miss.
+    ///
+    /// A cached outcome is only reused if the trusted block it started from
+    /// is still within the trusting period at time `now`.
+    pub fn get_or_verify<E>(
+        &self,
+        key: VerificationKey,
+        trusted_time: Time,
+        now: Time,
+        verify: impl FnOnce() -> Result<Verified<LightBlock>, E>,
+    ) -> Result<Verified<LightBlock>, E> {
+        if let Some(entry) = self.verified.get(&key) {
+            let within_trusting_period = (entry.trusted_time + key.trusting_period)
+                .map(|expiry| now < expiry)
+                .unwrap_or(false);
+
+            if within_trusting_period {
+                crate::telemetry!(light_block_cache_hits, &self.chain_id, "verification");
+                return Ok(entry.verified);
+            }
+
+            self.verified.invalidate(&key);
+        }
+
+        crate::telemetry!(light_block_cache_misses, &self.chain_id, "verification");
+
+        let verified = verify()?;
+
+        self.verified.insert(
+            key,
+            VerifiedEntry {
+                trusted_time,
+                verified: verified.clone(),
+            },
+        );
+
+        Ok(verified)
+    }
+}
+
+/// An [`Io`] which goes through the light block cache, so that the blocks fetched
+/// while bisecting during verification are shared as well.
+#[derive(Clone)]
+pub struct CachedIo {
+    io: AnyIo,
+    cache: LightBlockCache,
+}
+
+impl CachedIo {
+    pub fn new(io: AnyIo, cache: LightBlockCache) -> Self {
+        Self { io, cache }
+    }
+}
+
+impl Io for CachedIo {
+    fn fetch_light_block(&self, height: AtHeight) -> Result<LightBlock, IoError> {
+        match height {
+            AtHeight::At(height) => self
+                .cache
+                .get_or_fetch_block(height, || self.io.fetch_light_block(AtHeight::At(height))),
+
+            // The latest block changes over time, only cache it under its actual height
+            AtHeight::Highest => {
+                let block = self.io.fetch_light_block(AtHeight::Highest)?;
+                self.cache.blocks.insert(block.height(), block.clone());
+                Ok(block)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::cell::Cell;
+    use core::convert::Infallible;
+
+    use tendermint_testgen::{Generator, LightBlock as TestgenLightBlock};
+
+    fn light_block(height: u64) -> LightBlock {
+        let block = TestgenLightBlock::new_default(height).generate().unwrap();
+
+        LightBlock {
+            signed_header: block.signed_header,
+            validators: block.validators,
+            next_validators: block.next_validators,
+            provider: block.provider,
+        }
+    }
+
+    fn verification_key(trust_threshold: TrustThreshold) -> VerificationKey {
+        VerificationKey {
+            trusted_height: ICSHeight::new(0, 5).unwrap(),
+            target_height: ICSHeight::new(0, 10).unwrap(),
+            trust_threshold,
+            trusting_period: Duration::from_secs(100),
+            max_clock_drift: Duration::from_secs(5),
+        }
+    }
+
+    fn verified(height: u64) -> Verified<LightBlock> {
+        Verified {
+            target: light_block(height),
+            supporting: vec![light_block(height - 1)],
+        }
+    }
+
+    fn time(secs: i64) -> Time {
+        Time::from_unix_timestamp(secs, 0).unwrap()
+    }
+
+    fn cache() -> LightBlockCache {
+        LightBlockCache::new(ChainId::from_string("ibc-0"))
+    }
+
+    #[test]
+    fn block_hit_and_miss() {
+        let cache = cache();
+        let fetches = Cell::new(0);
+
+        let fetch = |height| {
+            fetches.set(fetches.get() + 1);
+            Ok::<_, Infallible>(light_block(height))
+        };
+
+        let height = TMHeight::from(3_u32);
+        let first = cache.get_or_fetch_block(height, || fetch(3)).unwrap();
+        let second = cache.get_or_fetch_block(height, || fetch(3)).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(fetches.get(), 1);
+
+        // Another height misses
+        let other = cache
+            .get_or_fetch_block(TMHeight::from(4_u32), || fetch(4))
+            .unwrap();
+
+        assert_eq!(other.height(), TMHeight::from(4_u32));
+        assert_eq!(fetches.get(), 2);
+    }
+
+    #[test]
+    fn failed_fetch_is_not_cached() {
+        let cache = cache();
+        let height = TMHeight::from(3_u32);
+
+        assert!(cache.get_or_fetch_block(height, || Err(())).is_err());
+
+        let block = cache
+            .get_or_fetch_block(height, || Ok::<_, ()>(light_block(3)))
+            .unwrap();
+        assert_eq!(block.height(), height);
+    }
+
+    #[test]
+    fn verification_hit_and_miss() {
+        let cache = cache();
+        let verifications = Cell::new(0);
+
+        let verify = || {
+            verifications.set(verifications.get() + 1);
+            Ok::<_, Infallible>(verified(10))
+        };
+
+        let key = verification_key(TrustThreshold::ONE_THIRD);
+
+        let first = cache
+            .get_or_verify(key.clone(), time(1000), time(1050), verify)
+            .unwrap();
+        let second = cache
+            .get_or_verify(key, time(1000), time(1060), verify)
+            .unwrap();
+
+        assert_eq!(first.target, second.target);
+        assert_eq!(first.supporting, second.supporting);
+        assert_eq!(verifications.get(), 1);
+
+        // A client with another trust threshold does not share the outcome
+        cache
+            .get_or_verify(
+                verification_key(TrustThreshold::TWO_THIRDS),
+                time(1000),
+                time(1060),
+                verify,
+            )
+            .unwrap();
+
+        assert_eq!(verifications.get(), 2);
+    }
+
+    #[test]
+    fn verification_evicted_outside_trusting_period() {
+        let cache = cache();
+        let verifications = Cell::new(0);
+
+        let verify = || {
+            verifications.set(verifications.get() + 1);
+            Ok::<_, Infallible>(verified(10))
+        };
+
+        let key = verification_key(TrustThreshold::ONE_THIRD);
+
+        cache
+            .get_or_verify(key.clone(), time(1000), time(1050), verify)
+            .unwrap();
+
+        // The trusted block expired at 1100, the verification is run again
+        cache
+            .get_or_verify(key.clone(), time(1090), time(1100), verify)
+            .unwrap();
+        assert_eq!(verifications.get(), 2);
+
+        // The new outcome replaced the expired one
+        cache
+            .get_or_verify(key, time(1090), time(1150), verify)
+            .unwrap();
+        assert_eq!(verifications.get(), 2);
+    }
+
+    #[test]
+    fn blocks_evicted_over_capacity() {
+        let cache = LightBlockCache::with_capacity(ChainId::from_string("ibc-0"), 2, 2);
+
+        for height in 1..=5_u32 {
+            cache
+                .get_or_fetch_block(TMHeight::from(height), || {
+                    Ok::<_, Infallible>(light_block(height.into()))
+                })
+                .unwrap();
+        }
+
+        cache.blocks.run_pending_tasks();
+
+        assert_eq!(cache.blocks.entry_count(), 2);
+    }
+}
@@ -96,6 +96,9 @@ pub struct OperationalData {
     /// A unique ID for tracking this batch of events starting from when they were received
     /// until the transactions corresponding to those events is submitted.
     pub tracking_id: TrackingId,
+    /// Number of times the transactions for this batch were resubmitted because
+    /// they were not confirmed in time, used to bump their fee.
+    pub fee_bump_attempt: u32,
     /// Stores `Some(ConnectionDelay)` if the delay is non-zero and `None` otherwise
     connection_delay: Option<ConnectionDelay>,
 }
@@ -119,6 +122,7 @@ impl OperationalData {
             target,
             connection_delay,
             tracking_id,
+            fee_bump_attempt: 0,
         }
     }
 
@@ -202,7 +206,8 @@ impl OperationalData {
             };
 
             if client_state.is_frozen() {
-                return Ok(TrackedMsgs::new(vec![], self.tracking_id));
+                return Ok(TrackedMsgs::new(vec![], self.tracking_id)
+                    .with_fee_bump_attempt(self.fee_bump_attempt));
             }
 
             vec![]
@@ -213,7 +218,8 @@ impl OperationalData {
             .chain(self.batch.iter().map(|gm| gm.msg.clone()))
             .collect();
 
-        let tm = TrackedMsgs::new(msgs, self.tracking_id);
+        let tm =
+            TrackedMsgs::new(msgs, self.tracking_id).with_fee_bump_attempt(self.fee_bump_attempt);
 
         info!("assembled batch of {} message(s)", tm.messages().len());
 
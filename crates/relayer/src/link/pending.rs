@@ -195,14 +195,29 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                                 // The pending tx needs to be resubmitted. This involves replacing the tx's
                                 // stale operational data with a fresh copy and then applying the `resubmit`
                                 // closure to it.
-                                let new_od = relay_path
-                                    .regenerate_operational_data(pending.original_od.clone());
+                                // The resubmission attempt is recorded so that the chain can bump the fee
+                                // of the replacement transaction, if configured to do so.
+                                let fee_bump_attempt = pending.original_od.fee_bump_attempt + 1;
 
-                                trace!("regenerated operational data for {}", tx_hashes);
+                                let new_od = relay_path
+                                    .regenerate_operational_data(pending.original_od.clone())
+                                    .map(|mut od| {
+                                        od.fee_bump_attempt = fee_bump_attempt;
+                                        od
+                                    });
+
+                                trace!(
+                                    fee_bump_attempt,
+                                    "regenerated operational data for {}",
+                                    tx_hashes
+                                );
 
                                 match new_od.map(f) {
                                     Some(Ok(reply)) => {
-                                        self.insert_new_pending_tx(reply, pending.original_od);
+                                        let mut original_od = pending.original_od;
+                                        original_od.fee_bump_attempt = fee_bump_attempt;
+
+                                        self.insert_new_pending_tx(reply, original_od);
                                         Ok(None)
                                     }
                                     Some(Err(e)) => {
@@ -21,6 +21,7 @@ use ibc_relayer_types::core::ics04_channel::msgs::{
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType};
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use ibc_relayer_types::events::{IbcEvent, IbcEventType, WithBlockDataType};
+use ibc_relayer_types::proofs::Proofs;
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::tx_msg::Msg;
@@ -211,6 +212,36 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self.path_id.channel_id
     }
 
+    /// Builds the packet proofs on the given chain. On the localhost connection the
+    /// `09-localhost` client reads the host state directly, so no proofs are queried
+    /// and the sentinel proof is used at the latest height of the chain instead.
+    fn build_packet_proofs<Chain: ChainHandle>(
+        &self,
+        chain: &Chain,
+        packet_type: PacketMsgType,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        height: Height,
+    ) -> Result<Proofs, LinkError> {
+        if self.dst_connection_id().is_localhost() {
+            let latest_height = chain
+                .query_latest_height()
+                .map_err(|e| LinkError::query(chain.id(), e))?;
+
+            let with_channel_proof = matches!(
+                packet_type,
+                PacketMsgType::TimeoutOnCloseUnordered | PacketMsgType::TimeoutOnCloseOrdered
+            );
+
+            return Ok(Proofs::localhost(with_channel_proof, latest_height));
+        }
+
+        chain
+            .build_packet_proofs(packet_type, port_id, channel_id, sequence, height)
+            .map_err(|e| LinkError::packet_proofs_constructor(chain.id(), e))
+    }
+
     pub fn channel(&self) -> &Channel<ChainA, ChainB> {
         &self.channel
     }
@@ -362,10 +393,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
 
         let src_channel_id = self.src_channel_id();
-        let proofs = self
-            .src_chain()
-            .build_channel_proofs(self.src_port_id(), src_channel_id, event.height)
-            .map_err(|e| LinkError::channel(ChannelError::channel_proof(e)))?;
+        let proofs = if self.dst_connection_id().is_localhost() {
+            let latest_height = self
+                .src_chain()
+                .query_latest_height()
+                .map_err(|e| LinkError::query(self.src_chain().id(), e))?;
+
+            Proofs::localhost(false, latest_height)
+        } else {
+            self.src_chain()
+                .build_channel_proofs(self.src_port_id(), src_channel_id, event.height)
+                .map_err(|e| LinkError::channel(ChannelError::channel_proof(e)))?
+        };
 
         let counterparty_upgrade_sequence = self.src_channel(QueryHeight::Latest)?.upgrade_sequence;
 
@@ -1289,16 +1328,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     }
 
     fn build_recv_packet(&self, packet: &Packet, height: Height) -> Result<Option<Any>, LinkError> {
-        let proofs = self
-            .src_chain()
-            .build_packet_proofs(
-                PacketMsgType::Recv,
-                &packet.source_port,
-                &packet.source_channel,
-                packet.sequence,
-                height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
+        let proofs = self.build_packet_proofs(
+            self.src_chain(),
+            PacketMsgType::Recv,
+            &packet.source_port,
+            &packet.source_channel,
+            packet.sequence,
+            height,
+        )?;
 
         let msg = MsgRecvPacket::new(packet.clone(), proofs.clone(), self.dst_signer()?);
 
@@ -1314,16 +1351,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     ) -> Result<Option<Any>, LinkError> {
         let packet = event.packet.clone();
 
-        let proofs = self
-            .src_chain()
-            .build_packet_proofs(
-                PacketMsgType::Ack,
-                &packet.destination_port,
-                &packet.destination_channel,
-                packet.sequence,
-                height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.src_chain().id(), e))?;
+        let proofs = self.build_packet_proofs(
+            self.src_chain(),
+            PacketMsgType::Ack,
+            &packet.destination_port,
+            &packet.destination_channel,
+            packet.sequence,
+            height,
+        )?;
 
         let msg = MsgAcknowledgement::new(
             packet,
@@ -1364,16 +1399,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             (PacketMsgType::TimeoutUnordered, packet.sequence)
         };
 
-        let proofs = self
-            .dst_chain()
-            .build_packet_proofs(
-                packet_type,
-                &packet.destination_port,
-                &packet.destination_channel,
-                next_sequence_received,
-                height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
+        let proofs = self.build_packet_proofs(
+            self.dst_chain(),
+            packet_type,
+            &packet.destination_port,
+            &packet.destination_channel,
+            next_sequence_received,
+            height,
+        )?;
 
         let msg = MsgTimeout::new(
             packet.clone(),
@@ -1413,16 +1446,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             (PacketMsgType::TimeoutOnCloseUnordered, packet.sequence)
         };
 
-        let proofs = self
-            .dst_chain()
-            .build_packet_proofs(
-                packet_type,
-                &packet.destination_port,
-                &packet.destination_channel,
-                next_sequence_received,
-                height,
-            )
-            .map_err(|e| LinkError::packet_proofs_constructor(self.dst_chain().id(), e))?;
+        let proofs = self.build_packet_proofs(
+            self.dst_chain(),
+            packet_type,
+            &packet.destination_port,
+            &packet.destination_channel,
+            next_sequence_received,
+            height,
+        )?;
 
         let counterparty_upgrade_sequence = self.src_channel(QueryHeight::Latest)?.upgrade_sequence;
 
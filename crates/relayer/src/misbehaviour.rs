@@ -1,9 +1,14 @@
+pub mod archive;
+
 use serde::{Deserialize, Serialize};
 
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::clients::ics07_tendermint::misbehaviour::{
     Misbehaviour as TmMisbehaviour, TENDERMINT_MISBEHAVIOR_TYPE_URL,
 };
+use ibc_relayer_types::clients::ics08_wasm::client_message::{
+    ClientMessage as WasmClientMessage, WASM_CLIENT_MESSAGE_TYPE_URL,
+};
 use ibc_relayer_types::core::ics02_client::error::Error;
 use ibc_relayer_types::core::ics02_client::header::AnyHeader;
 use ibc_relayer_types::core::ics02_client::misbehaviour::Misbehaviour;
@@ -21,18 +26,45 @@ pub struct MisbehaviourEvidence {
 #[allow(clippy::large_enum_variant)]
 pub enum AnyMisbehaviour {
     Tendermint(TmMisbehaviour),
+    /// The misbehaviour of a light client wrapped in a `08-wasm` client message
+    Wasm(Box<AnyMisbehaviour>),
+}
+
+impl AnyMisbehaviour {
+    /// The misbehaviour of the underlying light client, unwrapping any `08-wasm` client message.
+    pub fn inner(&self) -> &AnyMisbehaviour {
+        match self {
+            Self::Wasm(misbehaviour) => misbehaviour.inner(),
+            _ => self,
+        }
+    }
+
+    /// Set the identifier of the client this misbehaviour is submitted to.
+    pub fn set_client_id(&mut self, client_id: ClientId) {
+        match self {
+            Self::Tendermint(misbehaviour) => misbehaviour.client_id = client_id,
+            Self::Wasm(misbehaviour) => misbehaviour.set_client_id(client_id),
+        }
+    }
+
+    /// Wrap this misbehaviour in a `08-wasm` client message.
+    pub fn wrap_wasm(self) -> Self {
+        Self::Wasm(Box::new(self))
+    }
 }
 
 impl Misbehaviour for AnyMisbehaviour {
     fn client_id(&self) -> &ClientId {
         match self {
             Self::Tendermint(misbehaviour) => misbehaviour.client_id(),
+            Self::Wasm(misbehaviour) => misbehaviour.client_id(),
         }
     }
 
     fn height(&self) -> Height {
         match self {
             Self::Tendermint(misbehaviour) => misbehaviour.height(),
+            Self::Wasm(misbehaviour) => misbehaviour.height(),
         }
     }
 }
@@ -48,6 +80,12 @@ impl TryFrom<Any> for AnyMisbehaviour {
                 TmMisbehaviour::decode_vec(&raw.value).map_err(Error::decode_raw_misbehaviour)?,
             )),
 
+            WASM_CLIENT_MESSAGE_TYPE_URL => {
+                let message = WasmClientMessage::try_from(raw)?;
+                let inner = AnyMisbehaviour::try_from(message.inner()?)?;
+                Ok(AnyMisbehaviour::Wasm(Box::new(inner)))
+            }
+
             _ => Err(Error::unknown_misbehaviour_type(raw.type_url)),
         }
     }
@@ -60,6 +98,9 @@ impl From<AnyMisbehaviour> for Any {
                 type_url: TENDERMINT_MISBEHAVIOR_TYPE_URL.to_string(),
                 value: misbehaviour.encode_vec(),
             },
+            AnyMisbehaviour::Wasm(misbehaviour) => {
+                WasmClientMessage::new((*misbehaviour).into()).into()
+            }
         }
     }
 }
@@ -68,6 +109,7 @@ impl core::fmt::Display for AnyMisbehaviour {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         match self {
             AnyMisbehaviour::Tendermint(tm) => write!(f, "{tm}"),
+            AnyMisbehaviour::Wasm(misbehaviour) => write!(f, "Wasm({misbehaviour})"),
         }
     }
 }
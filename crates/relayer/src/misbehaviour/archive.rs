@@ -0,0 +1,409 @@
+//! Archive of the misbehaviour evidence detected by the relayer.
+//!
+//! Every detected misbehaviour is written to the archive directory, both as JSON for
+//! operators to inspect and as protobuf, along with the headers supporting it, the chains
+//! involved and the outcome of its submission. An archived record can later be loaded
+//! again to replay the evidence against the clients of the misbehaving chain hosted
+//! on other chains.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use flex_error::{define_error, TraceError};
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use ibc_relayer_types::core::ics02_client::error::Error as ClientError;
+use ibc_relayer_types::core::ics02_client::header::AnyHeader;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::timestamp::Timestamp;
+
+use crate::config::Config;
+use crate::misbehaviour::{AnyMisbehaviour, MisbehaviourEvidence};
+
+pub const EVIDENCE_ARCHIVE_DEFAULT_FOLDER: &str = ".hermes/evidence/";
+
+define_error! {
+    EvidenceArchiveError {
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+
+        Io
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            |e| { format!("failed to access evidence file {}", e.path.display()) },
+
+        Json
+            { path: PathBuf }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("failed to encode or decode evidence file {} as JSON", e.path.display()) },
+
+        Protobuf
+            { path: PathBuf }
+            [ TraceError<prost::DecodeError> ]
+            |e| { format!("failed to decode evidence file {} as protobuf", e.path.display()) },
+
+        InvalidRecord
+            { path: PathBuf }
+            [ ClientError ]
+            |e| { format!("invalid evidence in file {}", e.path.display()) },
+
+        InvalidTimestamp
+            { path: PathBuf, timestamp: u64 }
+            |e| { format!("invalid detection timestamp {} in evidence file {}", e.timestamp, e.path.display()) },
+
+        MissingMisbehaviour
+            { path: PathBuf }
+            |e| { format!("missing misbehaviour in evidence file {}", e.path.display()) },
+    }
+}
+
+/// The outcome of the submission of a misbehaviour evidence.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SubmissionOutcome {
+    /// The evidence was submitted, the details list the resulting events or transactions
+    Submitted {
+        details: Vec<String>,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+/// A misbehaviour evidence, as stored in the evidence archive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceRecord {
+    pub detected_at: Timestamp,
+    /// The chain which misbehaved
+    pub src_chain_id: ChainId,
+    /// The chain hosting the client of the misbehaving chain
+    pub dst_chain_id: ChainId,
+    pub client_id: ClientId,
+    pub misbehaviour: AnyMisbehaviour,
+    pub supporting_headers: Vec<AnyHeader>,
+    pub submission: SubmissionOutcome,
+}
+
+impl EvidenceRecord {
+    pub fn new(
+        src_chain_id: ChainId,
+        dst_chain_id: ChainId,
+        client_id: ClientId,
+        evidence: MisbehaviourEvidence,
+        submission: SubmissionOutcome,
+    ) -> Self {
+        Self {
+            detected_at: Timestamp::now(),
+            src_chain_id,
+            dst_chain_id,
+            client_id,
+            misbehaviour: evidence.misbehaviour,
+            supporting_headers: evidence.supporting_headers,
+            submission,
+        }
+    }
+
+    /// The evidence of this record, targeting the client `client_id`.
+    pub fn evidence_for(&self, client_id: &ClientId) -> MisbehaviourEvidence {
+        let mut misbehaviour = self.misbehaviour.clone();
+        misbehaviour.set_client_id(client_id.clone());
+
+        MisbehaviourEvidence {
+            misbehaviour,
+            supporting_headers: self.supporting_headers.clone(),
+        }
+    }
+
+    /// The name of the files of this record in the archive, without extension.
+    fn file_stem(&self) -> String {
+        format!(
+            "{}-{}-{}-{}",
+            self.detected_at.nanoseconds(),
+            self.src_chain_id,
+            self.dst_chain_id,
+            self.client_id
+        )
+    }
+}
+
+/// The protobuf encoding of an [`EvidenceRecord`].
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RawEvidenceRecord {
+    /// Nanoseconds since the Unix epoch
+    #[prost(uint64, tag = "1")]
+    pub detected_at: u64,
+    #[prost(string, tag = "2")]
+    pub src_chain_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub dst_chain_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub client_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "5")]
+    pub misbehaviour: ::core::option::Option<Any>,
+    #[prost(message, repeated, tag = "6")]
+    pub supporting_headers: ::prost::alloc::vec::Vec<Any>,
+    /// Either `submitted` or `failed`
+    #[prost(string, tag = "7")]
+    pub submission_status: ::prost::alloc::string::String,
+    /// The submission details, or the error of a failed submission
+    #[prost(string, repeated, tag = "8")]
+    pub submission_details: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+
+impl From<EvidenceRecord> for RawEvidenceRecord {
+    fn from(record: EvidenceRecord) -> Self {
+        let (submission_status, submission_details) = match record.submission {
+            SubmissionOutcome::Submitted { details } => ("submitted", details),
+            SubmissionOutcome::Failed { error } => ("failed", vec![error]),
+        };
+
+        Self {
+            detected_at: record.detected_at.nanoseconds(),
+            src_chain_id: record.src_chain_id.to_string(),
+            dst_chain_id: record.dst_chain_id.to_string(),
+            client_id: record.client_id.to_string(),
+            misbehaviour: Some(record.misbehaviour.into()),
+            supporting_headers: record
+                .supporting_headers
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            submission_status: submission_status.to_string(),
+            submission_details,
+        }
+    }
+}
+
+impl RawEvidenceRecord {
+    fn try_into_record(self, path: &Path) -> Result<EvidenceRecord, EvidenceArchiveError> {
+        let detected_at = Timestamp::from_nanoseconds(self.detected_at).map_err(|_| {
+            EvidenceArchiveError::invalid_timestamp(path.to_path_buf(), self.detected_at)
+        })?;
+
+        let misbehaviour = self
+            .misbehaviour
+            .ok_or_else(|| EvidenceArchiveError::missing_misbehaviour(path.to_path_buf()))?;
+        let misbehaviour = AnyMisbehaviour::try_from(misbehaviour)
+            .map_err(|e| EvidenceArchiveError::invalid_record(path.to_path_buf(), e))?;
+
+        let supporting_headers = self
+            .supporting_headers
+            .into_iter()
+            .map(AnyHeader::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| EvidenceArchiveError::invalid_record(path.to_path_buf(), e))?;
+
+        let client_id = self.client_id.parse().map_err(|e| {
+            EvidenceArchiveError::invalid_record(
+                path.to_path_buf(),
+                ClientError::invalid_client_identifier(e),
+            )
+        })?;
+
+        let submission = match self.submission_status.as_str() {
+            "submitted" => SubmissionOutcome::Submitted {
+                details: self.submission_details,
+            },
+            _ => SubmissionOutcome::Failed {
+                error: self.submission_details.join("\n"),
+            },
+        };
+
+        Ok(EvidenceRecord {
+            detected_at,
+            src_chain_id: ChainId::from_string(&self.src_chain_id),
+            dst_chain_id: ChainId::from_string(&self.dst_chain_id),
+            client_id,
+            misbehaviour,
+            supporting_headers,
+            submission,
+        })
+    }
+}
+
+/// A directory in which misbehaviour evidence is archived.
+#[derive(Clone, Debug)]
+pub struct EvidenceArchive {
+    dir: PathBuf,
+}
+
+impl EvidenceArchive {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The archive in the default folder `~/.hermes/evidence`.
+    pub fn from_home() -> Result<Self, EvidenceArchiveError> {
+        let home =
+            dirs_next::home_dir().ok_or_else(EvidenceArchiveError::home_location_unavailable)?;
+        Ok(Self::new(home.join(EVIDENCE_ARCHIVE_DEFAULT_FOLDER)))
+    }
+
+    /// The archive in the folder set by `global.evidence_dir` in the given configuration,
+    /// or in the default folder if it is not set.
+    pub fn from_config(config: &Config) -> Result<Self, EvidenceArchiveError> {
+        match &config.global.evidence_dir {
+            Some(dir) => Ok(Self::new(dir.clone())),
+            None => Self::from_home(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Store the given record in the archive, as a `.json` and a `.pb` file.
+    ///
+    /// Returns the path of the JSON file.
+    pub fn store(&self, record: &EvidenceRecord) -> Result<PathBuf, EvidenceArchiveError> {
+        fs::create_dir_all(&self.dir).map_err(|e| EvidenceArchiveError::io(self.dir.clone(), e))?;
+
+        let stem = record.file_stem();
+        let json_path = self.dir.join(format!("{stem}.json"));
+        let proto_path = self.dir.join(format!("{stem}.pb"));
+
+        let json = serde_json::to_string_pretty(record)
+            .map_err(|e| EvidenceArchiveError::json(json_path.clone(), e))?;
+        fs::write(&json_path, json).map_err(|e| EvidenceArchiveError::io(json_path.clone(), e))?;
+
+        let proto = RawEvidenceRecord::from(record.clone()).encode_to_vec();
+        fs::write(&proto_path, proto).map_err(|e| EvidenceArchiveError::io(proto_path, e))?;
+
+        Ok(json_path)
+    }
+}
+
+/// Load an evidence record from the given file, decoding it as protobuf
+/// if its extension is `.pb` and as JSON otherwise.
+pub fn load_record(path: &Path) -> Result<EvidenceRecord, EvidenceArchiveError> {
+    let contents = fs::read(path).map_err(|e| EvidenceArchiveError::io(path.to_path_buf(), e))?;
+
+    if path.extension().is_some_and(|ext| ext == "pb") {
+        RawEvidenceRecord::decode(contents.as_slice())
+            .map_err(|e| EvidenceArchiveError::protobuf(path.to_path_buf(), e))?
+            .try_into_record(path)
+    } else {
+        serde_json::from_slice(&contents)
+            .map_err(|e| EvidenceArchiveError::json(path.to_path_buf(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::clients::ics07_tendermint::header::Header as TmHeader;
+    use ibc_relayer_types::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+    use ibc_relayer_types::Height;
+    use tendermint_testgen::{Generator, LightBlock};
+
+    fn tm_header(height: u64, trusted_height: u64) -> TmHeader {
+        let block = LightBlock::new_default(height).generate().unwrap();
+
+        TmHeader {
+            signed_header: block.signed_header,
+            validator_set: block.validators.clone(),
+            trusted_height: Height::new(0, trusted_height).unwrap(),
+            trusted_validator_set: block.validators,
+        }
+    }
+
+    fn record(submission: SubmissionOutcome) -> EvidenceRecord {
+        let client_id: ClientId = "07-tendermint-0".parse().unwrap();
+
+        let misbehaviour = TmMisbehaviour {
+            client_id: client_id.clone(),
+            header1: tm_header(10, 5),
+            header2: tm_header(10, 5),
+        };
+
+        EvidenceRecord::new(
+            ChainId::from_string("ibc-1"),
+            ChainId::from_string("ibc-0"),
+            client_id,
+            MisbehaviourEvidence {
+                misbehaviour: AnyMisbehaviour::Tendermint(misbehaviour),
+                supporting_headers: vec![
+                    AnyHeader::Tendermint(tm_header(3, 2)),
+                    AnyHeader::Tendermint(tm_header(5, 3)),
+                ],
+            },
+            submission,
+        )
+    }
+
+    fn submitted() -> SubmissionOutcome {
+        SubmissionOutcome::Submitted {
+            details: vec!["tx1".to_string(), "tx2".to_string()],
+        }
+    }
+
+    fn failed() -> SubmissionOutcome {
+        SubmissionOutcome::Failed {
+            error: "client is frozen".to_string(),
+        }
+    }
+
+    fn temp_archive() -> EvidenceArchive {
+        EvidenceArchive::new(
+            std::env::temp_dir().join(format!("hermes-evidence-{}", uuid::Uuid::new_v4())),
+        )
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for record in [record(submitted()), record(failed())] {
+            let json = serde_json::to_string(&record).unwrap();
+            let decoded: EvidenceRecord = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(decoded, record);
+        }
+    }
+
+    #[test]
+    fn protobuf_round_trip() {
+        let path = Path::new("evidence.pb");
+
+        for record in [record(submitted()), record(failed())] {
+            let bytes = RawEvidenceRecord::from(record.clone()).encode_to_vec();
+            let decoded = RawEvidenceRecord::decode(bytes.as_slice())
+                .unwrap()
+                .try_into_record(path)
+                .unwrap();
+
+            assert_eq!(decoded, record);
+        }
+    }
+
+    #[test]
+    fn load_stored_record() {
+        let archive = temp_archive();
+        let record = record(submitted());
+
+        let json_path = archive.store(&record).unwrap();
+        let proto_path = json_path.with_extension("pb");
+
+        assert_eq!(load_record(&json_path).unwrap(), record);
+        assert_eq!(load_record(&proto_path).unwrap(), record);
+
+        fs::remove_dir_all(archive.dir()).unwrap();
+    }
+
+    #[test]
+    fn load_missing_record_fails() {
+        let archive = temp_archive();
+
+        assert!(load_record(&archive.dir().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn archive_dir_from_config() {
+        let mut config = Config::default();
+        config.global.evidence_dir = Some(PathBuf::from("/var/lib/hermes/evidence"));
+
+        let archive = EvidenceArchive::from_config(&config).unwrap();
+        assert_eq!(archive.dir(), Path::new("/var/lib/hermes/evidence"));
+    }
+}
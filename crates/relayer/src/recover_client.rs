@@ -0,0 +1,440 @@
+//! Recovery of expired or frozen clients by substituting them with a fresh
+//! client through a governance proposal with a `MsgRecoverClient` message.
+
+use std::fs;
+use std::path::Path;
+
+use flex_error::define_error;
+use serde_json::json;
+use tendermint::Hash as TxHash;
+use tracing::info;
+
+use ibc_proto::cosmos::base::v1beta1::Coin;
+use ibc_proto::cosmos::gov::v1::MsgSubmitProposal;
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::clients::ics07_tendermint::client_state::ClientState as TmClientState;
+use ibc_relayer_types::core::ics02_client::msgs::recover_client::MsgRecoverClient;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::tx_msg::Msg;
+use ibc_relayer_types::{downcast, Height};
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use crate::chain::tracking::TrackedMsgs;
+use crate::client_state::AnyClientState;
+use crate::error::Error;
+use crate::foreign_client::{
+    extract_client_id, CreateOptions, ForeignClient, ForeignClientError, HasExpiredOrFrozenError,
+};
+
+define_error! {
+    RecoverClientError {
+        Query
+            { client_id: ClientId }
+            [ Error ]
+            |e| { format!("failed to query the state of client {}", e.client_id) },
+
+        Key
+            [ Error ]
+            |_| { "key error" },
+
+        ForeignClient
+            [ ForeignClientError ]
+            |_| { "failed to create the substitute client" },
+
+        Submit
+            { chain_id: ChainId }
+            [ Error ]
+            |e| { format!("failed while submitting the recover client proposal to chain {}", e.chain_id) },
+
+        NotSubmitted
+            { chain_id: ChainId }
+            |e| { format!("no transaction was broadcast to chain {}, the recover client proposal was not submitted", e.chain_id) },
+
+        ClientNotExpiredOrFrozen
+            { client_id: ClientId }
+            |e| { format!("client {} is neither expired nor frozen, there is nothing to recover", e.client_id) },
+
+        TendermintOnly
+            |_| { "only Tendermint clients can be recovered" },
+
+        IneligibleSubstitute
+            {
+                subject_client_id: ClientId,
+                reason: String,
+            }
+            |e| { format!("substitute client is not eligible to recover client {}: {}", e.subject_client_id, e.reason) },
+
+        MissingSubstituteClientEvent
+            |_| { "no event was emitted when creating the substitute client" },
+
+        InvalidGovAccount
+            { account: String }
+            |e| { format!("invalid governance account `{}`", e.account) },
+
+        Io
+            [ flex_error::TraceError<std::io::Error> ]
+            |_| { "I/O error while writing the proposal" },
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RecoverClientOptions {
+    pub subject_client_id: ClientId,
+    pub gov_account: String,
+    pub amount: u64,
+    pub denom: String,
+    pub title: String,
+    pub summary: String,
+}
+
+/// Check that the subject client can be recovered, ie. is expired or frozen,
+/// and return its client state.
+pub fn query_subject_client<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+) -> Result<TmClientState, RecoverClientError> {
+    match client.validated_client_state() {
+        Ok(_) => {
+            return Err(RecoverClientError::client_not_expired_or_frozen(
+                client.id().clone(),
+            ))
+        }
+        Err(e) if e.is_expired_or_frozen_error() => {}
+        Err(e) => return Err(RecoverClientError::foreign_client(e)),
+    }
+
+    query_tm_client_state(&client.dst_chain(), client.id())
+}
+
+/// Query the state of the given substitute client and check that it is eligible to recover
+/// the subject client.
+pub fn check_existing_substitute<Chain: ChainHandle>(
+    dst_chain: &Chain,
+    subject_client_id: &ClientId,
+    subject: &TmClientState,
+    substitute_client_id: &ClientId,
+) -> Result<(), RecoverClientError> {
+    let substitute = query_tm_client_state(dst_chain, substitute_client_id)?;
+    check_substitute(subject_client_id, subject, &substitute)
+}
+
+/// Create a substitute client with the same parameters as the subject client,
+/// and return its identifier.
+///
+/// The substitute client state is checked for eligibility before it is submitted.
+pub fn create_substitute_client<DstChain: ChainHandle, SrcChain: ChainHandle>(
+    client: &ForeignClient<DstChain, SrcChain>,
+    subject: &TmClientState,
+) -> Result<ClientId, RecoverClientError> {
+    let options = CreateOptions {
+        max_clock_drift: Some(subject.max_clock_drift),
+        trusting_period: Some(subject.trusting_period),
+        trust_threshold: Some(subject.trust_threshold),
+        wasm_checksum: None,
+    };
+
+    let msg = client
+        .build_create_client(options)
+        .map_err(RecoverClientError::foreign_client)?;
+
+    let substitute = AnyClientState::try_from(msg.client_state.clone()).map_err(|e| {
+        RecoverClientError::ineligible_substitute(client.id().clone(), e.to_string())
+    })?;
+
+    let substitute = downcast!(substitute => AnyClientState::Tendermint)
+        .ok_or_else(RecoverClientError::tendermint_only)?;
+
+    check_substitute(client.id(), subject, &substitute)?;
+
+    let events = client
+        .dst_chain()
+        .send_messages_and_wait_commit(TrackedMsgs::new_single(
+            msg.to_any(),
+            "create substitute client",
+        ))
+        .map_err(|e| RecoverClientError::submit(client.dst_chain().id(), e))?;
+
+    let event = events
+        .first()
+        .ok_or_else(RecoverClientError::missing_substitute_client_event)?;
+
+    let substitute_client_id = extract_client_id(&event.event)
+        .map_err(RecoverClientError::foreign_client)?
+        .clone();
+
+    info!(
+        subject = %client.id(),
+        substitute = %substitute_client_id,
+        "created substitute client"
+    );
+
+    Ok(substitute_client_id)
+}
+
+/// Check that a substitute client state is eligible to recover the subject client state,
+/// following the rules enforced by ibc-go when handling `MsgRecoverClient`:
+///
+/// - the substitute client is not frozen,
+/// - its latest height is greater than the latest height of the subject,
+/// - all its parameters match the ones of the subject, except for the chain identifier,
+///   the trusting period, the latest height and the frozen height.
+pub fn check_substitute(
+    subject_client_id: &ClientId,
+    subject: &TmClientState,
+    substitute: &TmClientState,
+) -> Result<(), RecoverClientError> {
+    let ineligible = |reason: String| {
+        RecoverClientError::ineligible_substitute(subject_client_id.clone(), reason)
+    };
+
+    if substitute.frozen_height.is_some() {
+        return Err(ineligible("the substitute client is frozen".to_string()));
+    }
+
+    if substitute.latest_height <= subject.latest_height {
+        return Err(ineligible(format!(
+            "the latest height of the substitute client ({}) must be greater than the one of the subject client ({})",
+            substitute.latest_height, subject.latest_height
+        )));
+    }
+
+    let normalize = |cs: &TmClientState| TmClientState {
+        chain_id: ChainId::default(),
+        trusting_period: Default::default(),
+        latest_height: Height::new(0, 1).unwrap(),
+        frozen_height: None,
+        ..cs.clone()
+    };
+
+    if normalize(subject) != normalize(substitute) {
+        return Err(ineligible(
+            "the parameters of the substitute client do not match the ones of the subject client"
+                .to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Build a `MsgSubmitProposal` with a `MsgRecoverClient` message, signed by the governance account.
+pub fn build_recover_client_proposal<Chain: ChainHandle>(
+    dst_chain: &Chain,
+    substitute_client_id: &ClientId,
+    opts: &RecoverClientOptions,
+) -> Result<MsgSubmitProposal, RecoverClientError> {
+    let proposer = dst_chain.get_signer().map_err(RecoverClientError::key)?;
+
+    let msg = recover_client_msg(substitute_client_id, opts)?;
+
+    Ok(MsgSubmitProposal {
+        messages: vec![msg.to_any()],
+        initial_deposit: vec![deposit(opts)],
+        proposer: proposer.to_string(),
+        metadata: "".to_string(),
+        title: opts.title.clone(),
+        summary: opts.summary.clone(),
+        expedited: false,
+    })
+}
+
+/// Submit the recover client proposal to the chain hosting the subject client.
+pub fn send_recover_client_proposal<Chain: ChainHandle>(
+    dst_chain: &Chain,
+    substitute_client_id: &ClientId,
+    opts: &RecoverClientOptions,
+) -> Result<TxHash, RecoverClientError> {
+    let proposal = build_recover_client_proposal(dst_chain, substitute_client_id, opts)?;
+
+    let any_msg = Any {
+        type_url: "/cosmos.gov.v1.MsgSubmitProposal".to_string(),
+        value: prost::Message::encode_to_vec(&proposal),
+    };
+
+    // As for upgrade proposals, no IBC event is emitted by the submission
+    // of the proposal, so there is nothing to wait for beyond `CheckTx`.
+    let responses = dst_chain
+        .send_messages_and_wait_check_tx(TrackedMsgs::new_single(any_msg, "recover client"))
+        .map_err(|e| RecoverClientError::submit(dst_chain.id(), e))?;
+
+    // No transaction is broadcast in generate-only or dry-run mode
+    responses
+        .first()
+        .map(|response| response.hash)
+        .ok_or_else(|| RecoverClientError::not_submitted(dst_chain.id()))
+}
+
+/// Write the recover client proposal to the given file, in the JSON format expected by
+/// `<chain binary> tx gov submit-proposal`.
+pub fn write_recover_client_proposal(
+    path: &Path,
+    substitute_client_id: &ClientId,
+    opts: &RecoverClientOptions,
+) -> Result<serde_json::Value, RecoverClientError> {
+    let proposal = recover_client_proposal_json(substitute_client_id, opts)?;
+
+    let contents = serde_json::to_string_pretty(&proposal).expect("JSON value is serializable");
+    fs::write(path, contents).map_err(RecoverClientError::io)?;
+
+    Ok(proposal)
+}
+
+pub fn recover_client_proposal_json(
+    substitute_client_id: &ClientId,
+    opts: &RecoverClientOptions,
+) -> Result<serde_json::Value, RecoverClientError> {
+    let msg = recover_client_msg(substitute_client_id, opts)?;
+
+    Ok(json!({
+        "messages": [{
+            "@type": msg.type_url(),
+            "subject_client_id": msg.subject_client_id.to_string(),
+            "substitute_client_id": msg.substitute_client_id.to_string(),
+            "signer": msg.signer.to_string(),
+        }],
+        "metadata": "",
+        "deposit": format!("{}{}", opts.amount, opts.denom),
+        "title": opts.title,
+        "summary": opts.summary,
+        "expedited": false,
+    }))
+}
+
+fn recover_client_msg(
+    substitute_client_id: &ClientId,
+    opts: &RecoverClientOptions,
+) -> Result<MsgRecoverClient, RecoverClientError> {
+    let signer: Signer = opts
+        .gov_account
+        .parse()
+        .map_err(|_| RecoverClientError::invalid_gov_account(opts.gov_account.clone()))?;
+
+    Ok(MsgRecoverClient::new(
+        opts.subject_client_id.clone(),
+        substitute_client_id.clone(),
+        signer,
+    ))
+}
+
+fn deposit(opts: &RecoverClientOptions) -> Coin {
+    Coin {
+        denom: opts.denom.clone(),
+        amount: opts.amount.to_string(),
+    }
+}
+
+fn query_tm_client_state<Chain: ChainHandle>(
+    chain: &Chain,
+    client_id: &ClientId,
+) -> Result<TmClientState, RecoverClientError> {
+    let (client_state, _) = chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| RecoverClientError::query(client_id.clone(), e))?;
+
+    downcast!(client_state => AnyClientState::Tendermint)
+        .ok_or_else(RecoverClientError::tendermint_only)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+    use core::time::Duration;
+
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::AllowUpdate;
+    use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+
+    fn client_state(chain_id: &str, trusting_period: u64, latest_height: u64) -> TmClientState {
+        TmClientState::new(
+            ChainId::from_string(chain_id),
+            TrustThreshold::ONE_THIRD,
+            Duration::from_secs(trusting_period),
+            Duration::from_secs(3 * 24 * 3600),
+            Duration::from_secs(40),
+            Height::new(1, latest_height).unwrap(),
+            Default::default(),
+            vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+        )
+        .unwrap()
+    }
+
+    fn subject_id() -> ClientId {
+        ClientId::from_str("07-tendermint-0").unwrap()
+    }
+
+    fn opts() -> RecoverClientOptions {
+        RecoverClientOptions {
+            subject_client_id: subject_id(),
+            gov_account: "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn".to_string(),
+            amount: 10000000,
+            denom: "stake".to_string(),
+            title: "Recover client".to_string(),
+            summary: "Recover expired client".to_string(),
+        }
+    }
+
+    #[test]
+    fn eligible_substitute() {
+        let subject = client_state("chain-a", 3600, 10);
+        let substitute = client_state("chain-a-1", 7200, 100);
+
+        assert!(check_substitute(&subject_id(), &subject, &substitute).is_ok());
+    }
+
+    #[test]
+    fn substitute_behind_subject() {
+        let subject = client_state("chain-a", 3600, 100);
+        let substitute = client_state("chain-a", 3600, 10);
+
+        assert!(check_substitute(&subject_id(), &subject, &substitute).is_err());
+    }
+
+    #[test]
+    fn frozen_substitute() {
+        let subject = client_state("chain-a", 3600, 10);
+        let mut substitute = client_state("chain-a", 3600, 100);
+        substitute.frozen_height = Some(Height::new(1, 50).unwrap());
+
+        assert!(check_substitute(&subject_id(), &subject, &substitute).is_err());
+    }
+
+    #[test]
+    fn substitute_with_different_parameters() {
+        let subject = client_state("chain-a", 3600, 10);
+        let mut substitute = client_state("chain-a", 3600, 100);
+        substitute.max_clock_drift = Duration::from_secs(10);
+
+        assert!(check_substitute(&subject_id(), &subject, &substitute).is_err());
+    }
+
+    #[test]
+    fn proposal_json() {
+        let substitute = ClientId::from_str("07-tendermint-1").unwrap();
+        let proposal = recover_client_proposal_json(&substitute, &opts()).unwrap();
+
+        assert_eq!(
+            proposal["messages"][0]["@type"],
+            "/ibc.core.client.v1.MsgRecoverClient"
+        );
+        assert_eq!(
+            proposal["messages"][0]["subject_client_id"],
+            "07-tendermint-0"
+        );
+        assert_eq!(
+            proposal["messages"][0]["substitute_client_id"],
+            "07-tendermint-1"
+        );
+        assert_eq!(proposal["deposit"], "10000000stake");
+    }
+}
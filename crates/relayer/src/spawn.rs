@@ -8,7 +8,7 @@ use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use crate::{
     chain::{
         cosmos::CosmosSdkChain, handle::ChainHandle, namada::NamadaChain, penumbra::PenumbraChain,
-        runtime::ChainRuntime,
+        runtime::ChainRuntime, solomachine::SoloMachineChain,
     },
     config::{ChainConfig, Config},
     error::Error as RelayerError,
@@ -87,6 +87,7 @@ pub fn spawn_chain_runtime_with_config<Handle: ChainHandle>(
         ChainConfig::CosmosSdk(_) => ChainRuntime::<CosmosSdkChain>::spawn(config, rt),
         ChainConfig::Namada(_) => ChainRuntime::<NamadaChain>::spawn(config, rt),
         ChainConfig::Penumbra(_) => ChainRuntime::<PenumbraChain>::spawn(config, rt),
+        ChainConfig::SoloMachine(_) => ChainRuntime::<SoloMachineChain>::spawn(config, rt),
     }
     .map_err(SpawnError::relayer)?;
 
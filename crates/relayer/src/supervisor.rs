@@ -3,9 +3,10 @@ use alloc::sync::Arc;
 use core::convert::Infallible;
 use core::ops::Deref;
 use core::time::Duration;
+use std::path::{Path, PathBuf};
 use std::sync::RwLock;
 
-use crossbeam_channel::{unbounded, Receiver, Sender};
+use crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender};
 use itertools::Itertools;
 use tracing::{debug, error, error_span, info, instrument, trace, warn};
 
@@ -19,6 +20,7 @@ use crate::{
     chain::{endpoint::HealthCheck, handle::ChainHandle, tracking::TrackingId},
     config::Config,
     event::{
+        recording::Recorder,
         source::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
         IbcEventWithHeight,
     },
@@ -75,6 +77,10 @@ pub struct SupervisorOptions {
     /// even when an allow list is configured for a chain and the full scan could
     /// be omitted.
     pub force_full_scan: bool,
+
+    /// Record all the event batches received from each chain into
+    /// a compressed NDJSON file in the given directory.
+    pub record_events: Option<PathBuf>,
 }
 
 /**
@@ -196,6 +202,11 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
+    let record_tasks = match &options.record_events {
+        Some(dir) => spawn_record_workers(dir, &subscriptions),
+        None => Vec::new(),
+    };
+
     let batch_tasks = spawn_batch_workers(
         &config,
         registry.clone(),
@@ -208,6 +219,7 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
 
     let mut tasks = vec![cmd_task];
     tasks.extend(batch_tasks);
+    tasks.extend(record_tasks);
 
     if let Some(rest_rx) = rest_rx {
         let rest_task = spawn_rest_worker(config, registry, workers.clone(), rest_rx);
@@ -260,6 +272,68 @@ fn spawn_batch_workers<Chain: ChainHandle>(
     handles
 }
 
+/// Spawn a background task per chain which records every event batch
+/// received from that chain, using a dedicated subscription.
+fn spawn_record_workers<Chain: ChainHandle>(
+    dir: &Path,
+    subscriptions: &[(Chain, Subscription)],
+) -> Vec<TaskHandle> {
+    let mut handles = Vec::with_capacity(subscriptions.len());
+
+    for (chain, _) in subscriptions {
+        let mut recorder = match Recorder::create(dir, &chain.id()) {
+            Ok(recorder) => recorder,
+            Err(e) => {
+                error!("failed to create event recording for {}: {e}", chain.id());
+                continue;
+            }
+        };
+
+        let subscription = match chain.subscribe() {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                error!("failed to subscribe to events of {}: {e}", chain.id());
+                continue;
+            }
+        };
+
+        info!(
+            "recording events of {} to `{}`",
+            chain.id(),
+            recorder.path().display()
+        );
+
+        // Block on the subscription rather than polling it, waking up
+        // regularly to let the task be stopped when no event comes in
+        let handle = spawn_background_task(
+            error_span!("worker.record", chain = %chain.id()),
+            None,
+            move || -> Result<Next, TaskError<Infallible>> {
+                match subscription.recv_timeout(Duration::from_millis(500)) {
+                    Ok(batch) => {
+                        if let Ok(batch) = batch.deref() {
+                            if let Err(e) = recorder.record(batch) {
+                                error!("failed to record event batch: {e}");
+                            }
+                        }
+
+                        Ok(Next::Continue)
+                    }
+                    Err(RecvTimeoutError::Timeout) => Ok(Next::Continue),
+                    Err(RecvTimeoutError::Disconnected) => {
+                        warn!("event subscription closed, stopping the recording");
+                        Ok(Next::Abort)
+                    }
+                }
+            },
+        );
+
+        handles.push(handle);
+    }
+
+    handles
+}
+
 pub fn spawn_cmd_worker<Chain: ChainHandle>(
     registry: SharedRegistry<Chain>,
     workers: Arc<RwLock<WorkerMap>>,
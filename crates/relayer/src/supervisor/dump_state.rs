@@ -65,9 +65,13 @@ impl Display for SupervisorState {
                 if let Some(WorkerData::Client {
                     misbehaviour,
                     refresh,
+                    upgrade,
                 }) = desc.data
                 {
-                    writeln!(f, "    | misbehaviour: {misbehaviour}, refresh: {refresh}")?;
+                    writeln!(
+                        f,
+                        "    | misbehaviour: {misbehaviour}, refresh: {refresh}, upgrade: {upgrade}"
+                    )?;
                 }
             }
         }
@@ -60,6 +60,28 @@ define_error! {
     }
 }
 
+/// A software upgrade scheduled on a chain by its upgrade module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpgradePlan {
+    pub name: String,
+    /// The height at which the chain halts for the upgrade
+    pub height: Height,
+}
+
+impl UpgradePlan {
+    pub fn from_raw(chain_id: &ChainId, plan: Plan) -> Result<Self, Error> {
+        let height = u64::try_from(plan.height)
+            .ok()
+            .and_then(|height| Height::new(chain_id.version(), height).ok())
+            .ok_or_else(Error::invalid_height_no_source)?;
+
+        Ok(Self {
+            name: plan.name,
+            height,
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct UpgradePlanOptions {
     pub src_client_id: ClientId,
@@ -348,3 +370,39 @@ impl Proposal {
         .to_owned()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plan(height: i64) -> Plan {
+        Plan {
+            name: "v2".to_string(),
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn upgrade_plan_from_raw() {
+        let chain_id = ChainId::from_string("ibc-1");
+
+        let upgrade_plan = UpgradePlan::from_raw(&chain_id, plan(120)).unwrap();
+
+        assert_eq!(
+            upgrade_plan,
+            UpgradePlan {
+                name: "v2".to_string(),
+                height: Height::new(1, 120).unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn upgrade_plan_from_raw_with_invalid_height() {
+        let chain_id = ChainId::from_string("ibc-1");
+
+        assert!(UpgradePlan::from_raw(&chain_id, plan(0)).is_err());
+        assert!(UpgradePlan::from_raw(&chain_id, plan(-1)).is_err());
+    }
+}
@@ -1,3 +1,6 @@
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
 use serde::de::{Error, MapAccess, Visitor};
 use serde::ser::SerializeMap;
 use serde::Deserializer;
@@ -12,6 +15,7 @@ use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_relayer_types::core::ics24_host::identifier::ChannelId;
 
 use crate::chain::cosmos::config::error::Error as ConfigError;
+use crate::config::schema;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ExcludedSequences {
@@ -29,6 +33,28 @@ impl ExcludedSequences {
     }
 }
 
+impl JsonSchema for ExcludedSequences {
+    fn schema_name() -> String {
+        "ExcludedSequences".to_string()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        schema::from_json(serde_json::json!({
+            "description": "Sequences per channel identifier, as numbers or inclusive ranges, eg. [1, '5-10']",
+            "type": "object",
+            "additionalProperties": {
+                "type": "array",
+                "items": {
+                    "anyOf": [
+                        { "type": "integer", "minimum": 0 },
+                        { "type": "string", "pattern": "^[0-9]+-[0-9]+$" },
+                    ],
+                },
+            },
+        }))
+    }
+}
+
 fn serialize_excluded_sequences<S>(
     map: &BTreeMap<ChannelId, Vec<Sequence>>,
     serializer: S,
@@ -3,10 +3,12 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use ibc_relayer_types::core::ics04_channel::channel::Ordering;
 use serde::{Deserialize, Serialize};
 use std::sync::Mutex;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::foreign_client::ForeignClient;
 use crate::link::{Link, LinkParameters, Resubmit};
+use crate::misbehaviour::archive::EvidenceArchive;
+use crate::worker::client::UpgradePlanWatch;
 use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     config::Config,
@@ -54,11 +56,16 @@ impl Display for WorkerId {
     }
 }
 
+/// Spawn the tasks of the worker for the given object.
+///
+/// The upgrade task of a client worker is only spawned if a watch on
+/// the upgrade plan of the source chain is given.
 pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     chains: ChainHandlePair<ChainA, ChainB>,
     id: WorkerId,
     object: Object,
     config: &Config,
+    upgrade_plan: Option<UpgradePlanWatch>,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
 
@@ -66,17 +73,37 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         Object::Client(client) => {
             let client = ForeignClient::restore(client.dst_client_id.clone(), chains.b, chains.a);
 
-            let (mut refresh, mut misbehaviour) = (false, false);
+            let (mut refresh, mut misbehaviour, mut upgrade) = (false, false, false);
 
-            let refresh_task = client::spawn_refresh_client(client.clone());
+            let refresh_task = client::spawn_refresh_client(
+                client.clone(),
+                config.mode.clients.urgent_refresh_threshold,
+            );
             if let Some(refresh_task) = refresh_task {
                 task_handles.push(refresh_task);
                 refresh = true;
             }
 
+            if let Some(upgrade_plan) = upgrade_plan {
+                if let Some(upgrade_task) =
+                    client::spawn_upgrade_client(client.clone(), upgrade_plan)
+                {
+                    task_handles.push(upgrade_task);
+                    upgrade = true;
+                }
+            }
+
             let cmd_tx = if config.mode.clients.misbehaviour {
                 let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-                let misbehavior_task = client::detect_misbehavior_task(cmd_rx, client);
+                let archive = match EvidenceArchive::from_config(config) {
+                    Ok(archive) => Some(archive),
+                    Err(e) => {
+                        warn!("misbehaviour evidence will not be archived: {e}");
+                        None
+                    }
+                };
+
+                let misbehavior_task = client::detect_misbehavior_task(cmd_rx, client, archive);
                 if let Some(task) = misbehavior_task {
                     task_handles.push(task);
                     misbehaviour = true;
@@ -90,6 +117,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             let data = WorkerData::Client {
                 misbehaviour,
                 refresh,
+                upgrade,
             };
 
             (cmd_tx, Some(data))
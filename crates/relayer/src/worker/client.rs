@@ -1,29 +1,55 @@
 use core::convert::Infallible;
+use core::fmt;
 use core::time::Duration;
 use crossbeam_channel::Receiver;
-use retry::delay::Fibonacci;
+use retry::delay::{Fibonacci, Fixed};
 use retry::retry_with_index;
-use tracing::{debug, debug_span, error_span, trace, warn};
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+use tracing::{debug, debug_span, error, error_span, info, trace, warn};
 
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::events::IbcEvent;
 
+use crate::chain::requests::{
+    IncludeProof, QueryClientStateRequest, QueryHeight, QueryUpgradedClientStateRequest,
+};
+use crate::client_state::AnyClientState;
+use crate::config::RefreshRate;
+use crate::foreign_client::ForeignClientError;
+use crate::misbehaviour::archive::EvidenceArchive;
+use crate::telemetry;
+use crate::upgrade_chain::UpgradePlan;
+use crate::util::lock::LockExt;
+use crate::util::pretty::PrettySlice;
 use crate::util::retry::clamp_total;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::{
     chain::handle::ChainHandle,
-    foreign_client::{ForeignClient, MisbehaviourResults},
+    foreign_client::{ForeignClient, MisbehaviourResults, RefreshSchedule},
 };
 
 use super::WorkerCmd;
 
 const REFRESH_CHECK_INTERVAL: Duration = Duration::from_secs(5); // 5 seconds
+const MAX_REFRESH_CHECK_DELAY: Duration = Duration::from_secs(10 * 60); // 10 minutes
 const INITIAL_BACKOFF: Duration = Duration::from_secs(5); // 5 seconds
 const MAX_REFRESH_DELAY: Duration = Duration::from_secs(60 * 60); // 1 hour
 const MAX_REFRESH_TOTAL_DELAY: Duration = Duration::from_secs(60 * 60 * 24); // 1 day
+const URGENT_REFRESH_DELAY: Duration = Duration::from_secs(30); // 30 seconds
+const UPGRADE_CHECK_INTERVAL: Duration = Duration::from_secs(60); // 1 minute
+const MAX_UPGRADE_ATTEMPTS: u32 = 10;
 
+/// Spawn the task refreshing the given client.
+///
+/// Instead of checking the client at a fixed rate, the task schedules its next check
+/// from the time of the latest update of the client on chain, so that updates submitted
+/// by other relayers postpone the refresh. Once the time left before the client expires
+/// drops below `urgent_refresh_threshold` of its trusting period, the client is refreshed
+/// right away, and failed refreshes are retried often until the client expires.
 pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
     mut client: ForeignClient<ChainA, ChainB>,
+    urgent_refresh_threshold: RefreshRate,
 ) -> Option<TaskHandle> {
     if client.is_expired_or_frozen() {
         warn!(
@@ -34,6 +60,10 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
         return None;
     }
 
+    let is_urgent = move |schedule: &RefreshSchedule| schedule.is_urgent(urgent_refresh_threshold);
+
+    let mut next_check = Instant::now();
+
     Some(spawn_background_task(
         error_span!(
             "worker.client.refresh",
@@ -43,15 +73,58 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
         ),
         Some(REFRESH_CHECK_INTERVAL),
         move || {
-            // Try to refresh the client, but only if the refresh window has expired.
-            // If the refresh fails, retry according to the given strategy.
-            let res = retry_with_index(refresh_strategy(), |_| client.refresh());
+            if Instant::now() < next_check {
+                return Ok(Next::Continue);
+            }
+
+            let schedule = retry_with_index(refresh_strategy(), |_| client.refresh_schedule())
+                .map_err(TaskError::Fatal)?;
+
+            let Some(schedule) = schedule else {
+                // The client never expires, only check that it is still active from time to time
+                next_check = Instant::now() + MAX_REFRESH_CHECK_DELAY;
+                return Ok(Next::Continue);
+            };
+
+            if !schedule.is_due() && !is_urgent(&schedule) {
+                // Check again once the client is due for a refresh or its refresh
+                // becomes urgent, unless it gets updated in the meantime.
+                let delay = schedule
+                    .next_check_in(urgent_refresh_threshold)
+                    .min(MAX_REFRESH_CHECK_DELAY);
+
+                trace!(
+                    elapsed = ?schedule.elapsed,
+                    next_check_in = ?delay,
+                    "client does not need to be refreshed yet"
+                );
+
+                next_check = Instant::now() + delay;
+                return Ok(Next::Continue);
+            }
+
+            // Refresh the client. If the refresh fails, retry according to the strategy
+            // matching its urgency, checking again whether the refresh is still needed.
+            let res = if is_urgent(&schedule) {
+                warn!(
+                    expires_in = ?schedule.expires_in(),
+                    "client is close to expiry, refreshing it urgently"
+                );
+
+                retry_with_index(urgent_refresh_strategy(schedule.expires_in()), |_| {
+                    client.refresh_when(|schedule| schedule.is_due() || is_urgent(schedule))
+                })
+            } else {
+                retry_with_index(refresh_strategy(), |_| {
+                    client.refresh_when(|schedule| schedule.is_due() || is_urgent(schedule))
+                })
+            };
 
             match res {
-                // If `client.refresh()` was successful, continue
+                // If the refresh was successful, check the new schedule of the client right away
                 Ok(_) => Ok(Next::Continue),
 
-                // If `client.refresh()` failed and the retry mechanism
+                // If the refresh failed and the retry mechanism
                 // exceeded the maximum delay, return a fatal error.
                 Err(e) => Err(TaskError::Fatal(e)),
             }
@@ -59,9 +132,236 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
     ))
 }
 
+/// A watch on the upgrade plan of a chain, shared by the upgrade tasks of all the
+/// clients of that chain so that its upgrade plan is queried once per chain.
+///
+/// The watch stops querying the chain once all its clones are dropped.
+#[derive(Clone)]
+pub struct UpgradePlanWatch {
+    plan: Arc<RwLock<Option<UpgradePlan>>>,
+    _task: Arc<TaskHandle>,
+}
+
+impl UpgradePlanWatch {
+    pub fn spawn<Chain: ChainHandle>(chain: Chain) -> Self {
+        let plan = Arc::new(RwLock::new(None));
+
+        let task = spawn_background_task(
+            error_span!("worker.upgrade_plan", chain = %chain.id()),
+            Some(UPGRADE_CHECK_INTERVAL),
+            {
+                let plan = plan.clone();
+
+                move || -> Result<Next, TaskError<Infallible>> {
+                    // The chain does not answer while it is halted for the upgrade,
+                    // keep the last known plan until it resumes.
+                    match chain.query_upgrade_plan() {
+                        Ok(latest) => *plan.acquire_write() = latest,
+                        Err(e) => trace!("failed to query the upgrade plan of the chain: {e}"),
+                    }
+
+                    Ok(Next::Continue)
+                }
+            },
+        );
+
+        Self {
+            plan,
+            _task: Arc::new(task),
+        }
+    }
+
+    /// The upgrade plan of the chain, as of its latest query.
+    pub fn plan(&self) -> Option<UpgradePlan> {
+        self.plan.acquire_read().clone()
+    }
+}
+
+impl fmt::Debug for UpgradePlanWatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UpgradePlanWatch")
+            .field("plan", &self.plan())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Spawn the task upgrading the given client once the chain it tracks has gone
+/// through a software upgrade scheduled by its upgrade module.
+///
+/// The task remembers the upgrade plan of the source chain, as reported by the given
+/// watch, since the chain halts at the plan height and clears the plan once upgraded.
+/// After the chain resumes past that height, the task waits for the upgraded client
+/// and consensus states to be queryable, then upgrades the client, unless another
+/// relayer already did.
+pub fn spawn_upgrade_client<ChainA: ChainHandle, ChainB: ChainHandle>(
+    client: ForeignClient<ChainA, ChainB>,
+    upgrade_plan: UpgradePlanWatch,
+) -> Option<TaskHandle> {
+    if client.is_expired_or_frozen() {
+        warn!(
+            client = %client.id,
+            "skipping upgrade client task on frozen client",
+        );
+
+        return None;
+    }
+
+    let mut scheduled: Option<UpgradePlan> = None;
+    let mut failed_attempts = 0;
+
+    Some(spawn_background_task(
+        error_span!(
+            "worker.client.upgrade",
+            client = %client.id,
+            src_chain = %client.src_chain.id(),
+            dst_chain = %client.dst_chain.id(),
+        ),
+        Some(UPGRADE_CHECK_INTERVAL),
+        move || -> Result<Next, TaskError<ForeignClientError>> {
+            match upgrade_plan.plan() {
+                Some(plan) if scheduled.as_ref() != Some(&plan) => {
+                    info!(
+                        plan = %plan.name,
+                        height = %plan.height,
+                        "chain upgrade scheduled, the client will be upgraded once the chain resumes"
+                    );
+
+                    telemetry!(
+                        client_upgrades,
+                        &client.src_chain.id(),
+                        &client.dst_chain.id(),
+                        &client.id,
+                        "scheduled"
+                    );
+
+                    scheduled = Some(plan);
+                }
+                _ => {}
+            }
+
+            let Some(plan) = scheduled.clone() else {
+                return Ok(Next::Continue);
+            };
+
+            let Ok(src_height) = client.src_chain.query_latest_height() else {
+                return Ok(Next::Continue);
+            };
+
+            if src_height < plan.height {
+                // The plan was cancelled before the chain reached the upgrade height
+                if upgrade_plan.plan().is_none() {
+                    info!(plan = %plan.name, "chain upgrade cancelled");
+                    scheduled = None;
+                }
+
+                return Ok(Next::Continue);
+            }
+
+            // The upgraded client state is only queryable once the upgrade has been applied
+            let Ok((upgraded_client_state, _)) =
+                client
+                    .src_chain
+                    .query_upgraded_client_state(QueryUpgradedClientStateRequest {
+                        upgrade_height: plan.height,
+                    })
+            else {
+                debug!(
+                    height = %plan.height,
+                    "waiting for the upgraded client state to be queryable"
+                );
+                return Ok(Next::Continue);
+            };
+
+            let client_state = client.dst_chain.query_client_state(
+                QueryClientStateRequest {
+                    client_id: client.id.clone(),
+                    height: QueryHeight::Latest,
+                },
+                IncludeProof::No,
+            );
+
+            if let Ok((client_state, _)) = client_state {
+                if is_upgraded(&client_state, &upgraded_client_state) {
+                    info!(plan = %plan.name, "client already upgraded");
+                    scheduled = None;
+                    return Ok(Next::Continue);
+                }
+            }
+
+            match client.upgrade(plan.height) {
+                Ok(events) => {
+                    info!(
+                        plan = %plan.name,
+                        "client upgraded: {}",
+                        PrettySlice(&events)
+                    );
+
+                    telemetry!(
+                        client_upgrades,
+                        &client.src_chain.id(),
+                        &client.dst_chain.id(),
+                        &client.id,
+                        "upgraded"
+                    );
+
+                    scheduled = None;
+                    failed_attempts = 0;
+
+                    Ok(Next::Continue)
+                }
+                Err(e) => {
+                    telemetry!(
+                        client_upgrades,
+                        &client.src_chain.id(),
+                        &client.dst_chain.id(),
+                        &client.id,
+                        "failed"
+                    );
+
+                    failed_attempts += 1;
+
+                    if failed_attempts >= MAX_UPGRADE_ATTEMPTS {
+                        error!(
+                            plan = %plan.name,
+                            "giving up upgrading the client after {failed_attempts} attempts"
+                        );
+
+                        Err(TaskError::Fatal(e))
+                    } else {
+                        Err(TaskError::Ignore(e))
+                    }
+                }
+            }
+        },
+    ))
+}
+
+/// Whether the client already went through the upgrade resulting in the given
+/// upgraded client state, ie. whether it tracks the upgraded chain id and revision,
+/// and has the upgraded fields of its type.
+///
+/// The latest height of the client alone does not tell, since the client may have been
+/// updated past the upgrade height without being upgraded.
+fn is_upgraded(client_state: &AnyClientState, upgraded: &AnyClientState) -> bool {
+    let same_chain = client_state.chain_id() == upgraded.chain_id()
+        && client_state.latest_height().revision_number()
+            == upgraded.latest_height().revision_number();
+
+    match (client_state, upgraded) {
+        (AnyClientState::Tendermint(client_state), AnyClientState::Tendermint(upgraded)) => {
+            same_chain
+                && client_state.unbonding_period == upgraded.unbonding_period
+                && client_state.proof_specs == upgraded.proof_specs
+                && client_state.upgrade_path == upgraded.upgrade_path
+        }
+        _ => same_chain,
+    }
+}
+
 pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
     receiver: Receiver<WorkerCmd>,
     client: ForeignClient<ChainB, ChainA>,
+    archive: Option<EvidenceArchive>,
 ) -> Option<TaskHandle> {
     if client.is_expired_or_frozen() {
         warn!(
@@ -89,7 +389,7 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
                 initial_check_done = true;
 
                 debug!("doing initial misbehavior check");
-                let result = client.detect_misbehaviour_and_submit_evidence(None);
+                let result = client.detect_misbehaviour_and_submit_evidence(None, archive.as_ref());
                 debug!("misbehavior detection result: {:?}", result);
             }
 
@@ -98,7 +398,7 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                 for event_with_height in batch.events {
                     if let IbcEvent::UpdateClient(update) = event_with_height.event {
-                        match on_client_update(&client, update) {
+                        match on_client_update(&client, archive.as_ref(), update) {
                             Next::Continue => continue,
                             Next::Abort => return Ok(Next::Abort),
                         }
@@ -115,6 +415,7 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
 
 fn on_client_update<ChainA: ChainHandle, ChainB: ChainHandle>(
     client: &ForeignClient<ChainB, ChainA>,
+    archive: Option<&EvidenceArchive>,
     update: UpdateClient,
 ) -> Next {
     let _span = debug_span!(
@@ -126,7 +427,7 @@ fn on_client_update<ChainA: ChainHandle, ChainB: ChainHandle>(
 
     debug!("checking misbehavior for updated client");
 
-    let result = client.detect_misbehaviour_and_submit_evidence(Some(update));
+    let result = client.detect_misbehaviour_and_submit_evidence(Some(update), archive);
 
     trace!("misbehavior detection result: {:?}", result);
 
@@ -165,3 +466,104 @@ fn refresh_strategy() -> impl Iterator<Item = Duration> {
         MAX_REFRESH_TOTAL_DELAY,
     )
 }
+
+/// Retry at a fixed short interval, for as long as the client has not expired.
+fn urgent_refresh_strategy(expires_in: Duration) -> impl Iterator<Item = Duration> {
+    clamp_total(
+        Fixed::from(URGENT_REFRESH_DELAY),
+        URGENT_REFRESH_DELAY,
+        expires_in,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::{
+        AllowUpdate, ClientState as TmClientState, UpgradeOptions,
+    };
+    use ibc_relayer_types::core::ics02_client::client_state::UpgradableClientState;
+    use ibc_relayer_types::core::ics02_client::trust_threshold::TrustThreshold;
+    use ibc_relayer_types::core::ics23_commitment::specs::ProofSpecs;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::Height;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn client_state(chain_id: &str, height: Height) -> TmClientState {
+        TmClientState {
+            chain_id: ChainId::from_string(chain_id),
+            trust_threshold: TrustThreshold::ONE_THIRD,
+            trusting_period: DAY * 14,
+            unbonding_period: DAY * 21,
+            max_clock_drift: Duration::from_secs(40),
+            latest_height: height,
+            proof_specs: ProofSpecs::default(),
+            upgrade_path: vec!["upgrade".to_string(), "upgradedIBCState".to_string()],
+            allow_update: AllowUpdate {
+                after_expiry: true,
+                after_misbehaviour: true,
+            },
+            frozen_height: None,
+        }
+    }
+
+    /// The upgraded client state, as committed by the upgraded chain at the upgrade height.
+    fn upgraded(
+        client_state: &TmClientState,
+        chain_id: &str,
+        unbonding_period: Duration,
+    ) -> TmClientState {
+        let chain_id = ChainId::from_string(chain_id);
+        let upgrade_height = Height::new(chain_id.version(), 1).unwrap();
+
+        let mut upgraded = client_state.clone();
+        upgraded.upgrade(
+            upgrade_height,
+            UpgradeOptions { unbonding_period },
+            chain_id,
+        );
+        upgraded
+    }
+
+    #[test]
+    fn client_updated_past_the_upgrade_height_is_not_upgraded() {
+        let client_state = client_state("ibc-1", Height::new(1, 150).unwrap());
+        let upgraded = upgraded(&client_state, "ibc-2", DAY * 21);
+
+        assert!(!is_upgraded(
+            &AnyClientState::Tendermint(client_state),
+            &AnyClientState::Tendermint(upgraded),
+        ));
+    }
+
+    #[test]
+    fn client_of_upgraded_chain_is_upgraded() {
+        let client_state = client_state("ibc-1", Height::new(1, 150).unwrap());
+        let upgraded = upgraded(&client_state, "ibc-2", DAY * 21);
+
+        // The relayer restores the custom fields of the client when upgrading it
+        let upgraded_client = TmClientState {
+            chain_id: upgraded.chain_id.clone(),
+            latest_height: Height::new(2, 10).unwrap(),
+            ..client_state
+        };
+
+        assert!(is_upgraded(
+            &AnyClientState::Tendermint(upgraded_client),
+            &AnyClientState::Tendermint(upgraded),
+        ));
+    }
+
+    #[test]
+    fn client_with_former_unbonding_period_is_not_upgraded() {
+        let client_state = client_state("ibc-1", Height::new(1, 150).unwrap());
+        let upgraded = upgraded(&client_state, "ibc-1", DAY * 14);
+
+        assert!(!is_upgraded(
+            &AnyClientState::Tendermint(client_state),
+            &AnyClientState::Tendermint(upgraded),
+        ));
+    }
+}
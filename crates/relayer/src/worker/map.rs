@@ -13,6 +13,7 @@ use crate::{
     telemetry,
 };
 
+use super::client::UpgradePlanWatch;
 use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 
 /// Manage the lifecycle of [`WorkerHandle`]s associated with [`Object`]s.
@@ -20,6 +21,8 @@ use super::{spawn_worker_tasks, WorkerHandle, WorkerId};
 pub struct WorkerMap {
     workers: HashMap<Object, WorkerHandle>,
     latest_worker_id: WorkerId,
+    /// The watches on the upgrade plans of the source chains of the client workers
+    upgrade_plans: HashMap<ChainId, UpgradePlanWatch>,
 }
 
 impl Default for WorkerMap {
@@ -27,6 +30,7 @@ impl Default for WorkerMap {
         Self {
             workers: HashMap::new(),
             latest_worker_id: WorkerId::new(0),
+            upgrade_plans: HashMap::new(),
         }
     }
 }
@@ -171,11 +175,22 @@ impl WorkerMap {
     ) -> WorkerHandle {
         telemetry!(worker, metric_type(object), 1);
 
+        let upgrade_plan = match object {
+            Object::Client(_) if config.mode.clients.upgrade => Some(
+                self.upgrade_plans
+                    .entry(src.id())
+                    .or_insert_with(|| UpgradePlanWatch::spawn(src.clone()))
+                    .clone(),
+            ),
+            _ => None,
+        };
+
         spawn_worker_tasks(
             ChainHandlePair { a: src, b: dst },
             self.next_worker_id(),
             object.clone(),
             config,
+            upgrade_plan,
         )
     }
 
@@ -221,6 +236,8 @@ impl WorkerMap {
 
     /// Shut down all the workers, asynchronously.
     pub fn shutdown(&mut self) {
+        self.upgrade_plans.clear();
+
         let workers = mem::take(&mut self.workers);
         for worker in workers.values() {
             // Send shutdown signal to all tasks in parallel.
@@ -108,6 +108,9 @@ pub struct TelemetryState {
     /// Number of misbehaviours detected and submitted per client
     client_misbehaviours_submitted: Counter<u64>,
 
+    /// Number of scheduled chain upgrades seen and client upgrades performed, per client and stage
+    client_upgrades: Counter<u64>,
+
     /// Number of confirmed receive packets per channel
     receive_packets_confirmed: Counter<u64>,
 
@@ -123,6 +126,12 @@ pub struct TelemetryState {
     /// Number of cache hits for queries submitted by Hermes, per chain and query type
     queries_cache_hits: Counter<u64>,
 
+    /// Number of hits of the light block cache, per chain and cache
+    light_block_cache_hits: Counter<u64>,
+
+    /// Number of misses of the light block cache, per chain and cache
+    light_block_cache_misses: Counter<u64>,
+
     /// Number of times Hermes reconnected to the websocket endpoint, per chain
     ws_reconnect: Counter<u64>,
 
@@ -204,6 +213,9 @@ pub struct TelemetryState {
     /// Number of errors observed by Hermes when simulating a Tx
     simulate_errors: Counter<u64>,
 
+    /// Number of transactions resubmitted with a bumped fee after timing out
+    tx_fee_bumps: Counter<u64>,
+
     /// The EIP-1559 base fee queried
     dynamic_gas_queried_fees: ObservableGauge<f64>,
 
@@ -274,6 +286,11 @@ impl TelemetryState {
                 .with_description("Number of misbehaviours detected and submitted")
                 .init(),
 
+            client_upgrades: meter
+                .u64_counter("client_upgrades")
+                .with_description("Number of scheduled chain upgrades seen and of client upgrades performed or failed, per stage")
+                .init(),
+
             receive_packets_confirmed: meter
                 .u64_counter("receive_packets_confirmed")
                 .with_description("Number of confirmed receive packets. Available if relayer runs with Tx confirmation enabled")
@@ -301,6 +318,16 @@ impl TelemetryState {
                 .with_description("Number of cache hits for queries submitted by Hermes")
                 .init(),
 
+            light_block_cache_hits: meter
+                .u64_counter("light_block_cache_hits")
+                .with_description("Number of light blocks and verification outcomes served from the light block cache")
+                .init(),
+
+            light_block_cache_misses: meter
+                .u64_counter("light_block_cache_misses")
+                .with_description("Number of light blocks fetched and verifications performed because they were missing from the light block cache")
+                .init(),
+
             ws_reconnect: meter
                 .u64_counter("ws_reconnect")
                 .with_description("Number of times Hermes reconnected to the websocket endpoint")
@@ -413,6 +440,13 @@ impl TelemetryState {
                 )
                 .init(),
 
+            tx_fee_bumps: meter
+                .u64_counter("tx_fee_bumps")
+                .with_description(
+                    "Number of transactions resubmitted with a bumped fee after not being confirmed in time",
+                )
+                .init(),
+
             dynamic_gas_queried_fees: meter
                 .f64_observable_gauge("dynamic_gas_queried_fees")
                 .with_description("The EIP-1559 base fee queried")
@@ -636,6 +670,26 @@ impl TelemetryState {
         self.client_misbehaviours_submitted.add(&cx, count, labels);
     }
 
+    /// Number of client upgrades, per stage (`scheduled`, `upgraded` or `failed`)
+    pub fn client_upgrades(
+        &self,
+        src_chain: &ChainId,
+        dst_chain: &ChainId,
+        client: &ClientId,
+        stage: &'static str,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("src_chain", src_chain.to_string()),
+            KeyValue::new("dst_chain", dst_chain.to_string()),
+            KeyValue::new("client", client.to_string()),
+            KeyValue::new("stage", stage),
+        ];
+
+        self.client_upgrades.add(&cx, 1, labels);
+    }
+
     /// Number of receive packets relayed, per channel
     #[allow(clippy::too_many_arguments)]
     pub fn receive_packets_confirmed(
@@ -745,6 +799,32 @@ impl TelemetryState {
         self.queries_cache_hits.add(&cx, 1, labels);
     }
 
+    /// Number of hits of the light block cache, per chain and cache,
+    /// where the cache is either `light_block` or `verification`
+    pub fn light_block_cache_hits(&self, chain_id: &ChainId, cache: &'static str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("cache", cache),
+        ];
+
+        self.light_block_cache_hits.add(&cx, 1, labels);
+    }
+
+    /// Number of misses of the light block cache, per chain and cache,
+    /// where the cache is either `light_block` or `verification`
+    pub fn light_block_cache_misses(&self, chain_id: &ChainId, cache: &'static str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("cache", cache),
+        ];
+
+        self.light_block_cache_misses.add(&cx, 1, labels);
+    }
+
     /// Number of time the relayer had to reconnect to the WebSocket endpoint, per chain
     pub fn ws_reconnect(&self, chain_id: &ChainId) {
         let cx = Context::current();
@@ -1208,6 +1288,15 @@ impl TelemetryState {
         self.simulate_errors.add(&cx, 1, labels);
     }
 
+    /// Increment the number of transactions resubmitted with a bumped fee
+    pub fn tx_fee_bumps(&self, chain_id: &ChainId) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.tx_fee_bumps.add(&cx, 1, labels);
+    }
+
     pub fn dynamic_gas_queried_fees(&self, chain_id: &ChainId, amount: f64) {
         let cx = Context::current();
 
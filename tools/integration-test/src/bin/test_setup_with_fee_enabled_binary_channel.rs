@@ -50,7 +50,7 @@ impl TestOverrides for Test {
                     // with external relayer commands.
                     chain_config.key_store_type = Store::Test;
                 }
-                ChainConfig::Penumbra(_) => { /* no-op */ }
+                ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
             }
         }
     }
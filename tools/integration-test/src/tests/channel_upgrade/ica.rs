@@ -321,6 +321,9 @@ impl TestOverrides for ChannelUpgradeICAUnordered {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
     }
@@ -100,6 +100,7 @@ impl TestOverrides for ExpirationTestOverrides {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                ..Default::default()
             },
             connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
@@ -117,7 +118,7 @@ impl TestOverrides for ExpirationTestOverrides {
                 ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
                     chain_config.trusting_period = Some(CLIENT_EXPIRY);
                 }
-                ChainConfig::Penumbra(_) => { /* no-op */ }
+                ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
             }
         }
     }
@@ -464,7 +465,7 @@ impl BinaryChainTest for MisbehaviorExpirationTest {
             let misbehavior_result = chains
                 .foreign_clients
                 .client_b_to_a
-                .detect_misbehaviour_and_submit_evidence(None);
+                .detect_misbehaviour_and_submit_evidence(None, None);
 
             info!("misbehavior result: {:?}", misbehavior_result);
         }
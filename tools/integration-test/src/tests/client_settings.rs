@@ -32,7 +32,7 @@ impl TestOverrides for ClientDefaultsTest {
                 chain_config_a.trusting_period = Some(Duration::from_secs(120_000));
                 chain_config_a.trust_threshold = TrustThreshold::new(13, 23).unwrap();
             }
-            ChainConfig::Penumbra(_) => { /* no-op */ }
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
         }
 
         match &mut config.chains[1] {
@@ -42,7 +42,7 @@ impl TestOverrides for ClientDefaultsTest {
                 chain_config_b.trusting_period = Some(Duration::from_secs(340_000));
                 chain_config_b.trust_threshold = TrustThreshold::TWO_THIRDS;
             }
-            ChainConfig::Penumbra(_) => { /* no-op */ }
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
         }
     }
 }
@@ -75,6 +75,7 @@ impl TestOverrides for ClientOptionsTest {
             max_clock_drift: Some(Duration::from_secs(3)),
             trusting_period: Some(Duration::from_secs(120_000)),
             trust_threshold: Some(TrustThreshold::new(13, 23).unwrap()),
+            wasm_checksum: None,
         }
     }
 
@@ -83,6 +84,7 @@ impl TestOverrides for ClientOptionsTest {
             max_clock_drift: Some(Duration::from_secs(6)),
             trusting_period: Some(Duration::from_secs(340_000)),
             trust_threshold: Some(TrustThreshold::TWO_THIRDS),
+            wasm_checksum: None,
         }
     }
 }
@@ -18,7 +18,6 @@ use std::str::FromStr;
 use ibc_relayer::chain::requests::IncludeProof;
 use ibc_relayer::chain::requests::QueryClientStateRequest;
 use ibc_relayer::chain::requests::QueryHeight;
-use ibc_relayer::client_state::AnyClientState;
 use ibc_relayer::upgrade_chain::{build_and_send_ibc_upgrade_proposal, UpgradePlanOptions};
 use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_test_framework::chain::config::cosmos::{
@@ -168,12 +167,9 @@ impl BinaryChainTest for ClientUpgradeTest {
             IncludeProof::No,
         )?;
 
-        match state {
-            AnyClientState::Tendermint(client_state) => {
-                assert_eq!(client_state.chain_id, upgraded_chain_id);
-                Ok(())
-            }
-        }
+        assert_eq!(state.chain_id(), upgraded_chain_id);
+
+        Ok(())
     }
 }
 
@@ -221,12 +217,9 @@ impl BinaryChainTest for InvalidClientUpgradeTest {
             IncludeProof::No,
         )?;
 
-        match state {
-            AnyClientState::Tendermint(client_state) => {
-                assert_eq!(client_state.chain_id, chains.handle_a().id());
-                Ok(())
-            }
-        }
+        assert_eq!(state.chain_id(), chains.handle_a().id());
+
+        Ok(())
     }
 }
 
@@ -321,12 +314,9 @@ impl BinaryChainTest for HeightTooHighClientUpgradeTest {
             IncludeProof::No,
         )?;
 
-        match state {
-            AnyClientState::Tendermint(client_state) => {
-                assert_eq!(client_state.chain_id, chains.handle_a().id());
-                Ok(())
-            }
-        }
+        assert_eq!(state.chain_id(), chains.handle_a().id());
+
+        Ok(())
     }
 }
 
@@ -424,12 +414,9 @@ impl BinaryChainTest for HeightTooLowClientUpgradeTest {
             IncludeProof::No,
         )?;
 
-        match state {
-            AnyClientState::Tendermint(client_state) => {
-                assert_eq!(client_state.chain_id, chains.handle_a().id());
-                Ok(())
-            }
-        }
+        assert_eq!(state.chain_id(), chains.handle_a().id());
+
+        Ok(())
     }
 }
 
@@ -54,6 +54,9 @@ impl TestOverrides for DynamicGasTest {
             }
             ChainConfig::Namada(_) => {}
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         }
 
         match &mut config.chains[1] {
@@ -65,6 +68,9 @@ impl TestOverrides for DynamicGasTest {
             }
             ChainConfig::Namada(_) => {}
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         }
     }
 
@@ -104,6 +110,9 @@ impl BinaryChannelTest for DynamicGasTest {
                 chain_config.gas_price.denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         };
 
         let gas_denom_str_b: String = match relayer
@@ -116,6 +125,9 @@ impl BinaryChannelTest for DynamicGasTest {
                 chain_config.gas_price.denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         };
 
         let gas_denom_a: MonoTagged<ChainA, Denom> =
@@ -38,6 +38,9 @@ impl TestOverrides for FilterIncentivizedFeesRelayerTest {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
     }
@@ -193,6 +196,9 @@ impl TestOverrides for FilterByChannelIncentivizedFeesRelayerTest {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
     }
@@ -89,6 +89,9 @@ impl BinaryChannelTest for FeeGrantTest {
                 chain_config.gas_price.denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         };
 
         let gas_denom: MonoTagged<ChainA, Denom> =
@@ -118,6 +121,9 @@ impl BinaryChannelTest for FeeGrantTest {
                         ChainConfig::Penumbra(_) => {
                             panic!("running tests with Penumbra chain not supported")
                         }
+                        ChainConfig::SoloMachine(_) => {
+                            panic!("running tests with a solo machine not supported")
+                        }
                     }
                 }
             });
@@ -243,6 +249,9 @@ impl BinaryChannelTest for NoFeeGrantTest {
                 chain_config.gas_price.denom.clone()
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         };
 
         let gas_denom: MonoTagged<ChainA, Denom> =
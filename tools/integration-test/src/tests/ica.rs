@@ -77,6 +77,9 @@ impl TestOverrides for IcaFilterTestAllow {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
     }
@@ -201,6 +204,9 @@ impl TestOverrides for IcaFilterTestDeny {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
     }
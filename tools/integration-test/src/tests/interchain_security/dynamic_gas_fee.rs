@@ -87,6 +87,9 @@ impl TestOverrides for DynamicGasTest {
                 chain_config_a.dynamic_gas_price = DynamicGasPrice::unsafe_new(false, 1.1, 0.6);
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         }
 
         match &mut config.chains[1] {
@@ -100,6 +103,9 @@ impl TestOverrides for DynamicGasTest {
                     DynamicGasPrice::unsafe_new(self.dynamic_gas_enabled, 1.1, 0.6);
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         }
     }
 
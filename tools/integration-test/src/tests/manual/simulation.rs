@@ -35,6 +35,9 @@ impl TestOverrides for SimulationTest {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
     }
@@ -57,6 +57,9 @@ impl TestOverrides for OrderedChannelClearTest {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
 
@@ -66,6 +69,9 @@ impl TestOverrides for OrderedChannelClearTest {
                 chain_config.sequential_batch_tx = self.sequential_batch_tx;
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         }
     }
 
@@ -208,6 +214,9 @@ impl TestOverrides for OrderedChannelClearEqualCLITest {
                 ChainConfig::Penumbra(_) => {
                     panic!("running tests with Penumbra chain not supported")
                 }
+                ChainConfig::SoloMachine(_) => {
+                    panic!("running tests with a solo machine not supported")
+                }
             }
         }
 
@@ -218,6 +227,9 @@ impl TestOverrides for OrderedChannelClearEqualCLITest {
                 chain_config.max_msg_num = MaxMsgNum::new(3).unwrap();
             }
             ChainConfig::Penumbra(_) => panic!("running tests with Penumbra chain not supported"),
+            ChainConfig::SoloMachine(_) => {
+                panic!("running tests with a solo machine not supported")
+            }
         }
     }
 
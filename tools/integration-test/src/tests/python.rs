@@ -16,7 +16,7 @@ impl TestOverrides for PythonTest {
                     // with external relayer commands.
                     chain_config.key_store_type = Store::Test;
                 }
-                ChainConfig::Penumbra(_) => { /* no-op */ }
+                ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
             }
         }
     }
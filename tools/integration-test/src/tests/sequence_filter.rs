@@ -55,7 +55,7 @@ impl TestOverrides for FilterClearOnStartTest {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
                 chain_config.excluded_sequences = ExcludedSequences::new(excluded_sequences);
             }
-            ChainConfig::Penumbra(_) => { /* no-op */ }
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
         }
         config.mode.channels.enabled = true;
 
@@ -93,7 +93,7 @@ impl TestOverrides for FilterClearIntervalTest {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
                 chain_config.excluded_sequences = ExcludedSequences::new(excluded_sequences);
             }
-            ChainConfig::Penumbra(_) => { /* no-op */ }
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
         }
         config.mode.channels.enabled = true;
 
@@ -253,7 +253,7 @@ impl TestOverrides for StandardRelayingNoFilterTest {
             ChainConfig::CosmosSdk(chain_config) | ChainConfig::Namada(chain_config) => {
                 chain_config.excluded_sequences = ExcludedSequences::new(excluded_sequences);
             }
-            ChainConfig::Penumbra(_) => { /* no-op */ }
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => { /* no-op */ }
         }
         config.mode.packets.clear_on_start = true;
         config.mode.packets.clear_interval = 0;
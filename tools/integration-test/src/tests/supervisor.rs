@@ -34,6 +34,7 @@ impl TestOverrides for SupervisorTest {
                 enabled: true,
                 refresh: true,
                 misbehaviour: true,
+                ..Default::default()
             },
             connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
@@ -179,6 +180,7 @@ impl TestOverrides for SupervisorScanTest {
                 enabled: false, // disable client workers, otherwise we have to scan
                 refresh: true,
                 misbehaviour: true,
+                ..Default::default()
             },
             connections: config::Connections { enabled: true },
             channels: config::Channels { enabled: true },
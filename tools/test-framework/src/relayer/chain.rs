@@ -42,9 +42,10 @@ use ibc_relayer::connection::ConnectionMsgType;
 use ibc_relayer::consensus_state::AnyConsensusState;
 use ibc_relayer::denom::DenomTrace;
 use ibc_relayer::error::Error;
-use ibc_relayer::event::IbcEventWithHeight;
+use ibc_relayer::event::{IbcEventWithHeight, IbcEventWithTxHash};
 use ibc_relayer::keyring::AnySigningKeyPair;
 use ibc_relayer::misbehaviour::MisbehaviourEvidence;
+use ibc_relayer::upgrade_chain::UpgradePlan;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics02_client::header::AnyHeader;
@@ -388,6 +389,13 @@ where
         self.value().query_packet_events(request)
     }
 
+    fn query_packet_txs(
+        &self,
+        request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithTxHash>, Error> {
+        self.value().query_packet_txs(request)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -439,6 +447,10 @@ where
         self.value().query_consumer_chains()
     }
 
+    fn query_upgrade_plan(&self) -> Result<Option<UpgradePlan>, Error> {
+        self.value().query_upgrade_plan()
+    }
+
     fn query_upgrade(
         &self,
         request: QueryUpgradeRequest,
@@ -62,6 +62,7 @@ impl RelayerDriver {
             SupervisorOptions {
                 health_check: false,
                 force_full_scan: false,
+                record_events: None,
             },
         )
         .map_err(Error::supervisor)
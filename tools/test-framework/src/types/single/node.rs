@@ -172,6 +172,8 @@ impl FullNode {
                 id: self.chain_driver.chain_id.clone(),
                 rpc_addr: Url::from_str(&self.chain_driver.rpc_address())?,
                 grpc_addr: Url::from_str(&self.chain_driver.grpc_address())?,
+                backup_rpc_addrs: Vec::new(),
+                backup_grpc_addrs: Vec::new(),
                 event_source: config::EventSourceMode::Push {
                     url: WebSocketClientUrl::from_str(&self.chain_driver.websocket_address())?,
                     batch_delay: config::default::batch_delay(),
@@ -189,6 +191,7 @@ impl FullNode {
                 gas_adjustment: None,
                 gas_multiplier: Some(GasMultiplier::unsafe_new(1.5)),
                 dynamic_gas_price,
+                fee_bump: Default::default(),
                 fee_granter: None,
                 max_msg_num: Default::default(),
                 max_tx_size: Default::default(),
@@ -208,6 +211,11 @@ impl FullNode {
                 proof_specs: Default::default(),
                 extension_options: Default::default(),
                 sequential_batch_tx: false,
+                pipeline_txs: false,
+                max_in_flight_txs: config::default::max_in_flight_txs(),
+                dry_run: false,
+                dry_run_output: None,
+                generate_only: None,
                 compat_mode,
                 clear_interval: None,
                 excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
@@ -217,6 +225,8 @@ impl FullNode {
                 id: self.chain_driver.chain_id.clone(),
                 rpc_addr: Url::from_str(&self.chain_driver.rpc_address())?,
                 grpc_addr: Url::from_str(&self.chain_driver.grpc_address())?,
+                backup_rpc_addrs: Vec::new(),
+                backup_grpc_addrs: Vec::new(),
                 event_source: config::EventSourceMode::Push {
                     url: WebSocketClientUrl::from_str(&self.chain_driver.websocket_address())?,
                     batch_delay: config::default::batch_delay(),
@@ -234,6 +244,7 @@ impl FullNode {
                 gas_adjustment: None,
                 gas_multiplier: Some(GasMultiplier::unsafe_new(1.2)),
                 dynamic_gas_price: DynamicGasPrice::default(),
+                fee_bump: Default::default(),
                 fee_granter: None,
                 max_msg_num: Default::default(),
                 max_tx_size: Default::default(),
@@ -253,6 +264,11 @@ impl FullNode {
                 proof_specs: Default::default(),
                 extension_options: Default::default(),
                 sequential_batch_tx: false,
+                pipeline_txs: false,
+                max_in_flight_txs: config::default::max_in_flight_txs(),
+                dry_run: false,
+                dry_run_output: None,
+                generate_only: None,
                 compat_mode,
                 clear_interval: None,
                 excluded_sequences: ExcludedSequences::new(BTreeMap::new()),
@@ -36,7 +36,9 @@ pub fn update_relayer_config_for_consumer_chain(config: &mut Config) {
                 chain_config.trusting_period = Some(Duration::from_secs(99));
             }
             ChainConfig::CosmosSdk(_) | ChainConfig::Namada(_) => {}
-            ChainConfig::Penumbra(_) => { /* no-op Penumbra does not support CCV */ }
+            ChainConfig::Penumbra(_) | ChainConfig::SoloMachine(_) => {
+                /* no-op Penumbra and solo machines do not support CCV */
+            }
         }
     }
 }